@@ -1,9 +1,10 @@
-use najm_course_apis::{apps, axum_init};
+use najm_course_apis::{apps, axum_init, spawn_attempt_sweeper};
 
 #[tokio::main]
 async fn main() {
 	env_logger::init();
 	axum_init(|surrealdb_ws, surrealdb_mem| async {
+		spawn_attempt_sweeper(surrealdb_ws.clone(), surrealdb_mem.clone());
 		apps(surrealdb_ws, surrealdb_mem).await
 	})
 	.await;
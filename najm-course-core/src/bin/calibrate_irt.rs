@@ -0,0 +1,55 @@
+use najm_course_apis::{
+	surrealdb_init_mem, surrealdb_init_ws, AppState, IrtCalibrateRequestDto,
+	IrtRepository,
+};
+use serde::Deserialize;
+use std::error::Error;
+use surrealdb::sql::Thing;
+
+#[derive(Debug, Deserialize)]
+struct TestId {
+	id: Thing,
+}
+
+#[tokio::main]
+async fn main() -> Result<(), Box<dyn Error>> {
+	let mut args = std::env::args().skip(1);
+	let test_id = args.next();
+	let model = args.next().unwrap_or("2pl".into());
+	let min_responses = args.next().and_then(|v| v.parse().ok());
+
+	let state = AppState {
+		surrealdb_ws: surrealdb_init_ws().await?,
+		surrealdb_mem: surrealdb_init_mem().await?,
+	};
+	let test_ids = match test_id {
+		Some(id) => vec![id],
+		None => {
+			let tests: Vec<TestId> = state
+				.surrealdb_ws
+				.query("SELECT id FROM app_tests WHERE is_deleted = false")
+				.await?
+				.take(0)?;
+			tests.into_iter().map(|t| t.id.id.to_raw()).collect()
+		}
+	};
+	let repo = IrtRepository::new(&state);
+	for test_id in test_ids {
+		let result = repo
+			.query_calibrate_test(IrtCalibrateRequestDto {
+				test_id: test_id.clone(),
+				model: model.clone(),
+				min_responses,
+			})
+			.await?;
+		println!(
+			"✅ Calibrated {}: {} items, {} skipped, {} respondents",
+			test_id,
+			result.items.len(),
+			result.skipped,
+			result.respondents
+		);
+	}
+	println!("✅ IRT calibration finished");
+	Ok(())
+}
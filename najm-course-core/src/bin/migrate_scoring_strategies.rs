@@ -0,0 +1,53 @@
+use najm_course_apis::{get_iso_date, Env, ScoringConfigSchema, SessionsSchema};
+use serde::Deserialize;
+use std::error::Error;
+use surrealdb::{engine::remote::ws::Ws, opt::auth::Root, Surreal};
+
+#[derive(Debug, Deserialize)]
+struct TestName {
+	name: String,
+}
+
+#[tokio::main]
+async fn main() -> Result<(), Box<dyn Error>> {
+	let env = Env::new();
+	let db = Surreal::new::<Ws>(env.surrealdb_url).await?;
+	db.signin(Root {
+		username: &env.surrealdb_username,
+		password: &env.surrealdb_password,
+	})
+	.await?;
+	db.use_ns(env.surrealdb_namespace)
+		.use_db(env.surrealdb_dbname)
+		.await?;
+
+	let sessions: Vec<SessionsSchema> = db
+		.query("SELECT * FROM app_sessions WHERE is_deleted = false")
+		.await?
+		.take(0)?;
+	for mut session in sessions {
+		let mut changed = false;
+		for test in session.tests.iter_mut() {
+			if test.scoring.is_some() {
+				continue;
+			}
+			let record: Option<TestName> = db
+				.select((test.test.tb.clone(), test.test.id.to_raw()))
+				.await?;
+			let name = record.map(|t| t.name).unwrap_or_default();
+			let scoring = ScoringConfigSchema::infer(&session.category, &name);
+			println!("➡️  {} / {}: {}", session.name, name, scoring.strategy);
+			test.scoring = Some(scoring);
+			changed = true;
+		}
+		if !changed {
+			continue;
+		}
+		let id = session.id.id.to_raw();
+		session.updated_at = get_iso_date();
+		let _: Option<SessionsSchema> =
+			db.update(("app_sessions", id)).content(session).await?;
+	}
+	println!("✅ All session scoring strategies successfully migrated");
+	Ok(())
+}
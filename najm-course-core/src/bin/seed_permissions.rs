@@ -286,6 +286,120 @@ async fn main() -> Result<(), Box<dyn Error>> {
 			Some("2025-05-13T00:00:00+00"),
 			Some("2025-05-13T00:00:00+00"),
 		),
+		(
+			"8d31eefb-4f85-4281-b661-e2953483d269",
+			"Read Detail Attempts",
+			Some("2026-10-18T00:00:00+00"),
+			Some("2026-10-18T00:00:00+00"),
+		),
+		(
+			"28f800ec-0351-4a20-9b05-d7a0a5c60807",
+			"Create Attempts",
+			Some("2026-10-18T00:00:00+00"),
+			Some("2026-10-18T00:00:00+00"),
+		),
+		(
+			"49988202-4deb-41b0-b723-8f496d85bfbc",
+			"Update Attempts",
+			Some("2026-10-18T00:00:00+00"),
+			Some("2026-10-18T00:00:00+00"),
+		),
+		(
+			"976112b4-a877-41cf-af4d-36e46eab52b8",
+			"Calibrate Questions",
+			Some("2026-10-18T00:00:00+00"),
+			Some("2026-10-18T00:00:00+00"),
+		),
+		(
+			"513331ac-7f5a-4fbf-b969-cd0527b2698c",
+			"Read List Session Results",
+			Some("2026-10-18T00:00:00+00"),
+			Some("2026-10-18T00:00:00+00"),
+		),
+		(
+			"caa787b5-741c-4fe6-bb2b-58fa8fa4718a",
+			"Read Detail Session Results",
+			Some("2026-10-18T00:00:00+00"),
+			Some("2026-10-18T00:00:00+00"),
+		),
+		(
+			"793dee38-2ad3-496c-aca7-e7ba67b99462",
+			"Create Session Results",
+			Some("2026-10-18T00:00:00+00"),
+			Some("2026-10-18T00:00:00+00"),
+		),
+		(
+			"568bd361-7fc6-4d9e-8d4e-f2357f9e58dd",
+			"Analyze Questions",
+			Some("2026-10-18T00:00:00+00"),
+			Some("2026-10-18T00:00:00+00"),
+		),
+		(
+			"20e4b550-9c4e-4853-bbb6-abce64b32c7e",
+			"Grade Answers",
+			Some("2026-10-18T00:00:00+00"),
+			Some("2026-10-18T00:00:00+00"),
+		),
+		(
+			"73cf48f8-9f2f-44c3-9b73-82fc7f187513",
+			"Manage Enrollments",
+			Some("2026-10-18T00:00:00+00"),
+			Some("2026-10-18T00:00:00+00"),
+		),
+		(
+			"09a793da-5341-4fce-bab2-046c84acff6d",
+			"Create Enrollments",
+			Some("2026-10-18T00:00:00+00"),
+			Some("2026-10-18T00:00:00+00"),
+		),
+		(
+			"094f7a6e-2541-4641-b90f-c981e6b5dcdf",
+			"Read List Attempts",
+			Some("2026-10-18T00:00:00+00"),
+			Some("2026-10-18T00:00:00+00"),
+		),
+		(
+			"1cc06254-ddb3-49eb-a11b-5feb3f55b928",
+			"Review Answer Keys",
+			Some("2026-10-18T00:00:00+00"),
+			Some("2026-10-18T00:00:00+00"),
+		),
+		(
+			"b9d4910e-b76e-4bc7-8b38-b1ca5cdbf1c5",
+			"Create Proctoring Events",
+			Some("2026-10-18T00:00:00+00"),
+			Some("2026-10-18T00:00:00+00"),
+		),
+		(
+			"85c035af-b175-4135-a9ce-607e68434cd9",
+			"Read List Proctoring Events",
+			Some("2026-10-18T00:00:00+00"),
+			Some("2026-10-18T00:00:00+00"),
+		),
+		(
+			"fc86a678-620c-4c17-bed2-5eb82f01ff1a",
+			"Broadcast Sessions",
+			Some("2026-10-18T00:00:00+00"),
+			Some("2026-10-18T00:00:00+00"),
+		),
+		(
+			"62c59e5a-dea1-44ac-80da-f3a15a8dfa3d",
+			"Monitor Sessions",
+			Some("2026-10-18T00:00:00+00"),
+			Some("2026-10-18T00:00:00+00"),
+		),
+		(
+			"af4a3b60-9a0a-476a-aa1b-0309d34545fa",
+			"Transfer Sessions",
+			Some("2026-10-18T00:00:00+00"),
+			Some("2026-10-18T00:00:00+00"),
+		),
+		(
+			"64afdbdf-5360-47b6-902c-278ec2f3e9ff",
+			"Export Session Results",
+			Some("2026-10-18T00:00:00+00"),
+			Some("2026-10-18T00:00:00+00"),
+		),
 	];
 	for (id, name, _created_at, _updated_at) in permissions {
 		db.query("CREATE type::thing('app_permissions', $id) CONTENT $data")
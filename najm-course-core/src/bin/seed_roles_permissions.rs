@@ -62,6 +62,25 @@ async fn main() -> Result<(), Box<dyn Error>> {
 		"8367cc33-07b4-43c5-9992-2e00202c55df",
 		"a401b265-b775-4a6c-9ed1-1806fdde4060",
 		"d08f2e9c-49b4-4c09-b9d5-6a2746fbd50e",
+		"8d31eefb-4f85-4281-b661-e2953483d269",
+		"28f800ec-0351-4a20-9b05-d7a0a5c60807",
+		"49988202-4deb-41b0-b723-8f496d85bfbc",
+		"976112b4-a877-41cf-af4d-36e46eab52b8",
+		"513331ac-7f5a-4fbf-b969-cd0527b2698c",
+		"caa787b5-741c-4fe6-bb2b-58fa8fa4718a",
+		"793dee38-2ad3-496c-aca7-e7ba67b99462",
+		"568bd361-7fc6-4d9e-8d4e-f2357f9e58dd",
+		"20e4b550-9c4e-4853-bbb6-abce64b32c7e",
+		"73cf48f8-9f2f-44c3-9b73-82fc7f187513",
+		"09a793da-5341-4fce-bab2-046c84acff6d",
+		"094f7a6e-2541-4641-b90f-c981e6b5dcdf",
+		"1cc06254-ddb3-49eb-a11b-5feb3f55b928",
+		"b9d4910e-b76e-4bc7-8b38-b1ca5cdbf1c5",
+		"85c035af-b175-4135-a9ce-607e68434cd9",
+		"fc86a678-620c-4c17-bed2-5eb82f01ff1a",
+		"62c59e5a-dea1-44ac-80da-f3a15a8dfa3d",
+		"af4a3b60-9a0a-476a-aa1b-0309d34545fa",
+		"64afdbdf-5360-47b6-902c-278ec2f3e9ff",
 	];
 
 	let student_permissions = vec![
@@ -70,6 +89,14 @@ async fn main() -> Result<(), Box<dyn Error>> {
 		"76046fc3-ea45-43de-9e32-7dff9622019e",
 		"7d4b1379-4960-416a-b045-98cd82c0cac9",
 		"05940747-2c2f-4ee2-a280-72557c508686",
+		"8d31eefb-4f85-4281-b661-e2953483d269",
+		"28f800ec-0351-4a20-9b05-d7a0a5c60807",
+		"49988202-4deb-41b0-b723-8f496d85bfbc",
+		"caa787b5-741c-4fe6-bb2b-58fa8fa4718a",
+		"793dee38-2ad3-496c-aca7-e7ba67b99462",
+		"09a793da-5341-4fce-bab2-046c84acff6d",
+		"094f7a6e-2541-4641-b90f-c981e6b5dcdf",
+		"b9d4910e-b76e-4bc7-8b38-b1ca5cdbf1c5",
 	];
 
 	let admin_role_id = "f6b03f25-e416-4893-ac88-caaa690afb07";
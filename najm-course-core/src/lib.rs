@@ -16,6 +16,27 @@ pub mod v2;
 
 pub use v1::*;
 
+/// Expires overdue attempts every minute. Started once by the server binary.
+pub fn spawn_attempt_sweeper(
+	surrealdb_ws: SurrealWsClient,
+	surrealdb_mem: SurrealMemClient,
+) {
+	let state = AppState {
+		surrealdb_ws,
+		surrealdb_mem,
+	};
+	tokio::spawn(async move {
+		let mut interval = tokio::time::interval(std::time::Duration::from_secs(60));
+		loop {
+			interval.tick().await;
+			let repo = AttemptsRepository::new(&state);
+			if let Err(e) = repo.query_expire_overdue().await {
+				log::error!("Failed to expire overdue attempts: {}", e);
+			}
+		}
+	});
+}
+
 pub async fn apps(
 	surrealdb_ws: SurrealWsClient,
 	surrealdb_mem: SurrealMemClient,
@@ -1,6 +1,6 @@
 use env_logger::{init, Builder, Env};
 use log::LevelFilter;
-use najm_course_apis::{apps, axum_init};
+use najm_course_apis::{apps, axum_init, spawn_attempt_sweeper};
 use std::fs::File;
 use std::io::Write;
 
@@ -22,6 +22,7 @@ async fn main() {
 		.filter(None, LevelFilter::Info)
 		.init();
 	axum_init(|surrealdb_ws, surrealdb_mem| async {
+		spawn_attempt_sweeper(surrealdb_ws.clone(), surrealdb_mem.clone());
 		apps(surrealdb_ws, surrealdb_mem).await
 	})
 	.await;
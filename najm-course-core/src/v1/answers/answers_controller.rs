@@ -1,9 +1,18 @@
-use super::{AnswersCreateRequestDto, AnswersService};
+use super::{
+	AnswersCreateRequestDto, AnswersGradeRequestDto, AnswersGradingItemDto,
+	AnswersGradingQueryDto, AnswersSaveRequestDto, AnswersSaveResponseDto,
+	AnswersService,
+};
 use crate::{
-	answers::TestsItemAnswersDto, permissions_guard, AppState, MessageResponseDto,
-	PermissionsEnum, ResponseSuccessDto,
+	answers::TestsItemAnswersDto, eligibility_guard, extract_email, permissions_guard,
+	AppState, MessageResponseDto, MetaRequestDto, PermissionsEnum,
+	ResponseListSuccessDto, ResponseSuccessDto, SessionsViewerDto, UsersItemDtoRaw,
+};
+use axum::{
+	extract::{Path, Query},
+	response::IntoResponse,
+	Extension, Json,
 };
-use axum::{extract::Path, response::IntoResponse, Extension, Json};
 
 #[utoipa::path(
 	get,
@@ -20,6 +29,7 @@ use axum::{extract::Path, response::IntoResponse, Extension, Json};
 pub async fn get_answer_by_id(
 	headers: axum::http::HeaderMap,
 	Extension(state): Extension<AppState>,
+	extensions: axum::http::Extensions,
 	Path(id): Path<String>,
 ) -> impl IntoResponse {
 	match permissions_guard(
@@ -29,7 +39,10 @@ pub async fn get_answer_by_id(
 	)
 	.await
 	{
-		Ok(_) => AnswersService::get_answer_by_id(&state, id).await,
+		Ok(_) => {
+			let viewer = SessionsViewerDto::from(extensions.get::<UsersItemDtoRaw>());
+			AnswersService::get_answer_by_id(&state, id, viewer).await
+		}
 		Err(response) => response,
 	}
 }
@@ -49,6 +62,7 @@ pub async fn get_answer_by_id(
 pub async fn post_create_answer(
 	headers: axum::http::HeaderMap,
 	Extension(state): Extension<AppState>,
+	extensions: axum::http::Extensions,
 	Json(payload): Json<AnswersCreateRequestDto>,
 ) -> impl IntoResponse {
 	match permissions_guard(
@@ -58,7 +72,54 @@ pub async fn post_create_answer(
 	)
 	.await
 	{
-		Ok(_) => AnswersService::create_answer(&state, payload).await,
+		Ok(_) => {
+			let user = extensions.get::<UsersItemDtoRaw>();
+			if let Err(response) =
+				eligibility_guard(&state, user, &payload.session_id).await
+			{
+				return response;
+			}
+			AnswersService::create_answer(&state, payload, SessionsViewerDto::from(user))
+				.await
+		}
+		Err(response) => response,
+	}
+}
+
+#[utoipa::path(
+	put,
+	security(
+		("Bearer" = [])
+	),
+	path = "/v1/answers/save",
+	request_body = AnswersSaveRequestDto,
+	responses(
+		(status = 200, description = "Save or clear a single answer", body = ResponseSuccessDto<AnswersSaveResponseDto>),
+	),
+	tag = "Answers"
+)]
+pub async fn put_save_answer(
+	headers: axum::http::HeaderMap,
+	Extension(state): Extension<AppState>,
+	extensions: axum::http::Extensions,
+	Json(payload): Json<AnswersSaveRequestDto>,
+) -> impl IntoResponse {
+	match permissions_guard(
+		&headers,
+		state.clone(),
+		vec![PermissionsEnum::CreateAnswers],
+	)
+	.await
+	{
+		Ok(_) => {
+			let user = extensions.get::<UsersItemDtoRaw>();
+			if let Err(response) =
+				eligibility_guard(&state, user, &payload.session_id).await
+			{
+				return response;
+			}
+			AnswersService::save_answer(&state, payload).await
+		}
 		Err(response) => response,
 	}
 }
@@ -90,3 +151,103 @@ pub async fn delete_answer(
 		Err(response) => response,
 	}
 }
+
+#[utoipa::path(
+	get,
+	security(
+		("Bearer" = [])
+	),
+	path = "/v1/answers/grading",
+	params(
+		("page" = Option<i64>, Query, description = "Page number"),
+		("per_page" = Option<i64>, Query, description = "Items per page"),
+		("sort_by" = Option<String>, Query, description = "Sort by field"),
+		("order" = Option<String>, Query, description = "Order ASC or DESC"),
+		("session_id" = Option<String>, Query, description = "Filter by session ID"),
+		("test_id" = Option<String>, Query, description = "Filter by test ID"),
+	),
+	responses(
+		(status = 200, description = "Get essay answers waiting for grading", body = ResponseListSuccessDto<Vec<AnswersGradingItemDto>>)
+	),
+	tag = "Answers"
+)]
+pub async fn get_grading_queue(
+	headers: axum::http::HeaderMap,
+	Extension(state): Extension<AppState>,
+	Query(meta): Query<MetaRequestDto>,
+	Query(query): Query<AnswersGradingQueryDto>,
+) -> impl IntoResponse {
+	match permissions_guard(
+		&headers,
+		state.clone(),
+		vec![PermissionsEnum::GradeAnswers],
+	)
+	.await
+	{
+		Ok(_) => AnswersService::get_grading_queue(&state, query, meta).await,
+		Err(response) => response,
+	}
+}
+
+#[utoipa::path(
+	put,
+	security(
+		("Bearer" = [])
+	),
+	path = "/v1/answers/grading/{id}",
+	params(("id" = String, Path, description = "Answer ID")),
+	request_body = AnswersGradeRequestDto,
+	responses(
+		(status = 200, description = "Save a draft grade for an essay answer", body = ResponseSuccessDto<AnswersGradingItemDto>)
+	),
+	tag = "Answers"
+)]
+pub async fn put_grade_answer(
+	headers: axum::http::HeaderMap,
+	Extension(state): Extension<AppState>,
+	Path(id): Path<String>,
+	Json(payload): Json<AnswersGradeRequestDto>,
+) -> impl IntoResponse {
+	match permissions_guard(
+		&headers,
+		state.clone(),
+		vec![PermissionsEnum::GradeAnswers],
+	)
+	.await
+	{
+		Ok(_) => {
+			let graded_by = extract_email(&headers);
+			AnswersService::grade_answer(&state, id, payload, graded_by).await
+		}
+		Err(response) => response,
+	}
+}
+
+#[utoipa::path(
+	post,
+	security(
+		("Bearer" = [])
+	),
+	path = "/v1/answers/grading/{id}/finalize",
+	params(("id" = String, Path, description = "Answer ID")),
+	responses(
+		(status = 200, description = "Finalise the grade and recompute the session result", body = ResponseSuccessDto<AnswersGradingItemDto>)
+	),
+	tag = "Answers"
+)]
+pub async fn post_finalize_grading(
+	headers: axum::http::HeaderMap,
+	Extension(state): Extension<AppState>,
+	Path(id): Path<String>,
+) -> impl IntoResponse {
+	match permissions_guard(
+		&headers,
+		state.clone(),
+		vec![PermissionsEnum::GradeAnswers],
+	)
+	.await
+	{
+		Ok(_) => AnswersService::finalize_grading(&state, id).await,
+		Err(response) => response,
+	}
+}
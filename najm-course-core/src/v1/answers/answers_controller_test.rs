@@ -1,16 +1,22 @@
 use super::{answers_router, AnswerEntryDto};
 use crate::{
-	create_mock_app_state, v1::answers::AnswersCreateRequestDto, AppState,
-	PermissionsEnum,
+	attempts::attempts_repository_test::build_user, create_mock_app_state,
+	v1::answers::AnswersCreateRequestDto, AppState, PermissionsEnum,
 };
 use axum::{Extension, Router};
 use axum_test::TestServer;
 use najm_course_utils::authorized;
+use surrealdb::Uuid;
 
 fn create_test_app(state: AppState) -> TestServer {
+	let user = build_user(
+		&Uuid::new_v4().to_string(),
+		vec![PermissionsEnum::ManageEnrollments],
+	);
 	let app = Router::new()
 		.nest("/v1/answers", answers_router())
-		.layer(Extension(state));
+		.layer(Extension(state))
+		.layer(Extension(user));
 	TestServer::new(app).unwrap()
 }
 
@@ -56,6 +62,14 @@ async fn test_post_create_answer_should_return_200() {
 			session_id, test_id, now, now
 		))
 		.await;
+	super::answers_repository_test::seed_in_progress_attempt(
+		db,
+		&user_id,
+		&session_id,
+		&test_id,
+	)
+	.await
+	.unwrap();
 	let server = create_test_app(state);
 	let payload = AnswersCreateRequestDto {
 		user_id: user_id.clone(),
@@ -63,7 +77,12 @@ async fn test_post_create_answer_should_return_200() {
 		session_id: session_id.clone(),
 		answers: vec![AnswerEntryDto {
 			question_id,
-			option_id,
+			option_id: Some(option_id),
+			option_ids: None,
+			statements: None,
+			numeric_value: None,
+			text: None,
+			answered_at: None,
 		}],
 	};
 	let res = authorized(
@@ -119,6 +138,14 @@ async fn test_delete_answer_should_return_200() {
 			session_id, test_id, now, now
 		))
 		.await;
+	super::answers_repository_test::seed_in_progress_attempt(
+		db,
+		&user_id,
+		&session_id,
+		&test_id,
+	)
+	.await
+	.unwrap();
 	let server = create_test_app(state.clone());
 	let payload = AnswersCreateRequestDto {
 		user_id: user_id.clone(),
@@ -126,7 +153,12 @@ async fn test_delete_answer_should_return_200() {
 		session_id: session_id.clone(),
 		answers: vec![AnswerEntryDto {
 			question_id: question_id.clone(),
-			option_id: option_id.clone(),
+			option_id: Some(option_id.clone()),
+			option_ids: None,
+			statements: None,
+			numeric_value: None,
+			text: None,
+			answered_at: None,
 		}],
 	};
 	let _ = authorized(
@@ -160,3 +192,94 @@ async fn test_delete_answer_should_return_200() {
 	.await;
 	assert_eq!(res.status_code(), 200);
 }
+
+#[tokio::test]
+async fn test_put_save_answer_should_return_200() {
+	use super::answers_repository_test::seed_answer_dependencies;
+	use crate::v1::answers::AnswersSaveRequestDto;
+	use surrealdb::Uuid;
+	let state = create_mock_app_state().await;
+	let (test_id, session_id, question_id, option_id) =
+		seed_answer_dependencies(&state.surrealdb_ws).await.unwrap();
+	let user_id = Uuid::new_v4().to_string();
+	super::answers_repository_test::seed_in_progress_attempt(
+		&state.surrealdb_ws,
+		&user_id,
+		&session_id,
+		&test_id,
+	)
+	.await
+	.unwrap();
+	let server = create_test_app(state);
+	let payload = AnswersSaveRequestDto {
+		user_id,
+		test_id,
+		session_id,
+		question_id,
+		option_id: Some(option_id),
+		option_ids: None,
+		statements: None,
+		numeric_value: None,
+		text: None,
+		answered_at: None,
+	};
+	let res = authorized(
+		&server,
+		"PUT",
+		"/v1/answers/save",
+		vec![&PermissionsEnum::CreateAnswers.to_string()],
+		Some(&payload),
+	)
+	.await;
+	assert_eq!(res.status_code(), 200);
+}
+
+#[tokio::test]
+async fn test_get_grading_queue_should_return_200() {
+	let state = create_mock_app_state().await;
+	let server = create_test_app(state);
+	let res = authorized::<()>(
+		&server,
+		"GET",
+		"/v1/answers/grading",
+		vec![&PermissionsEnum::GradeAnswers.to_string()],
+		None,
+	)
+	.await;
+	assert_eq!(res.status_code(), 200);
+}
+
+#[tokio::test]
+async fn test_get_grading_queue_without_permission_should_return_403() {
+	let state = create_mock_app_state().await;
+	let server = create_test_app(state);
+	let res = authorized::<()>(
+		&server,
+		"GET",
+		"/v1/answers/grading",
+		vec![&PermissionsEnum::CreateAnswers.to_string()],
+		None,
+	)
+	.await;
+	assert_eq!(res.status_code(), 403);
+}
+
+#[tokio::test]
+async fn test_put_grade_answer_should_return_404_if_not_found() {
+	use super::AnswersGradeRequestDto;
+	let state = create_mock_app_state().await;
+	let server = create_test_app(state);
+	let res = authorized(
+		&server,
+		"PUT",
+		"/v1/answers/grading/non-existent-id",
+		vec![&PermissionsEnum::GradeAnswers.to_string()],
+		Some(&AnswersGradeRequestDto {
+			points: Some(5),
+			rubric: vec![],
+			comment: None,
+		}),
+	)
+	.await;
+	assert_eq!(res.status_code(), 404);
+}
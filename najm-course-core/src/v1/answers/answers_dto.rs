@@ -1,3 +1,5 @@
+use super::{AnswersGradingSchema, AnswersRubricScoreSchema, AnswersSchema};
+use crate::{KecermatanResultDto, QuestionsTagsDto, ScoringRuleDto};
 use serde::{Deserialize, Serialize};
 use utoipa::ToSchema;
 use validator::Validate;
@@ -26,18 +28,133 @@ pub struct AnswerEntryDto {
 	#[schema(example = "uuid")]
 	pub question_id: String,
 
-	#[validate(length(min = 1))]
+	#[schema(example = "uuid")]
+	pub option_id: Option<String>,
+
+	pub option_ids: Option<Vec<String>>,
+
+	pub statements: Option<Vec<AnswerStatementDto>>,
+
+	#[schema(example = 12.5)]
+	pub numeric_value: Option<f64>,
+
+	pub text: Option<String>,
+
+	#[schema(example = "2025-05-01T08:00:42Z")]
+	pub answered_at: Option<String>,
+}
+
+impl AnswerEntryDto {
+	pub fn is_empty(&self) -> bool {
+		self.option_id.is_none()
+			&& self.option_ids.is_none()
+			&& self.statements.is_none()
+			&& self.numeric_value.is_none()
+			&& self.text.is_none()
+	}
+}
+
+impl From<&AnswersSchema> for AnswerEntryDto {
+	fn from(value: &AnswersSchema) -> Self {
+		let option_ids: Vec<String> =
+			value.options.iter().map(|o| o.id.to_raw()).collect();
+		let statements: Vec<AnswerStatementDto> = value
+			.statements
+			.iter()
+			.map(|s| AnswerStatementDto {
+				option_id: s.option.id.to_raw(),
+				value: s.value,
+			})
+			.collect();
+		AnswerEntryDto {
+			question_id: value.question.id.to_raw(),
+			option_id: value.option.as_ref().map(|o| o.id.to_raw()),
+			option_ids: (!option_ids.is_empty()).then_some(option_ids),
+			statements: (!statements.is_empty()).then_some(statements),
+			numeric_value: value.numeric_value,
+			text: value.text.clone(),
+			answered_at: value.answered_at.clone(),
+		}
+	}
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, ToSchema)]
+pub struct AnswerStatementDto {
 	#[schema(example = "uuid")]
 	pub option_id: String,
+	#[schema(example = true)]
+	pub value: bool,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, Validate, ToSchema)]
+pub struct AnswersSaveRequestDto {
+	#[validate(length(min = 1))]
+	#[schema(example = "uuid")]
+	pub user_id: String,
+
+	#[validate(length(min = 1))]
+	#[schema(example = "uuid")]
+	pub test_id: String,
+
+	#[validate(length(min = 1))]
+	#[schema(example = "uuid")]
+	pub session_id: String,
+
+	#[validate(length(min = 1))]
+	#[schema(example = "uuid")]
+	pub question_id: String,
+
+	#[schema(example = "uuid")]
+	pub option_id: Option<String>,
+
+	pub option_ids: Option<Vec<String>>,
+
+	pub statements: Option<Vec<AnswerStatementDto>>,
+
+	#[schema(example = 12.5)]
+	pub numeric_value: Option<f64>,
+
+	pub text: Option<String>,
+
+	#[schema(example = "2025-05-01T08:00:42Z")]
+	pub answered_at: Option<String>,
+}
+
+impl AnswersSaveRequestDto {
+	pub fn entry(&self) -> AnswerEntryDto {
+		AnswerEntryDto {
+			question_id: self.question_id.clone(),
+			option_id: self.option_id.clone(),
+			option_ids: self.option_ids.clone(),
+			statements: self.statements.clone(),
+			numeric_value: self.numeric_value,
+			text: self.text.clone(),
+			answered_at: self.answered_at.clone(),
+		}
+	}
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, ToSchema)]
+pub struct AnswersSaveResponseDto {
+	pub question_id: String,
+	pub option_id: Option<String>,
+	pub option_ids: Option<Vec<String>>,
+	pub statements: Option<Vec<AnswerStatementDto>>,
+	pub numeric_value: Option<f64>,
+	pub text: Option<String>,
+	pub answered_at: Option<String>,
+	pub updated_at: String,
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize, ToSchema)]
 pub struct OptionsItemAnswersDto {
 	pub id: String,
 	pub label: String,
+	pub display_label: Option<String>,
 	pub is_correct: bool,
 	pub points: Option<i32>,
 	pub is_user_selected: bool,
+	pub user_value: Option<bool>,
 	pub image_url: Option<String>,
 	pub created_at: String,
 	pub updated_at: String,
@@ -51,16 +168,159 @@ pub struct QuestionsItemAnswersDto {
 	pub question_image_url: Option<String>,
 	pub discussion_image_url: Option<String>,
 	pub options: Vec<OptionsItemAnswersDto>,
+	pub question_type: String,
+	pub numeric_value: Option<f64>,
+	pub text: Option<String>,
+	pub is_correct: bool,
+	pub points: Option<i32>,
+	pub credit: f64,
+	pub scoring_rule: Option<ScoringRuleDto>,
+	pub grading: Option<AnswersGradingDto>,
+	pub tags: Option<QuestionsTagsDto>,
+	pub answered_at: Option<String>,
 	pub created_at: String,
 	pub updated_at: String,
 }
 
+impl QuestionsItemAnswersDto {
+	pub fn is_answered(&self) -> bool {
+		self.options.iter().any(|o| o.is_user_selected)
+			|| self.numeric_value.is_some()
+			|| self.text.is_some()
+	}
+}
+
 #[derive(Clone, Debug, Serialize, Deserialize, ToSchema)]
 pub struct TestsItemAnswersDto {
 	pub id: String,
 	pub name: String,
 	pub score: i32,
+	pub theta: Option<f64>,
+	pub kecermatan: Option<KecermatanResultDto>,
+	pub pending_grading: u32,
+	pub is_revealed: bool,
 	pub questions: Vec<QuestionsItemAnswersDto>,
 	pub created_at: String,
 	pub updated_at: String,
 }
+
+impl TestsItemAnswersDto {
+	/// Keeps the student's own responses but hides scores, correctness and
+	/// discussions until the session visibility policy reveals them.
+	pub fn concealed(self) -> Self {
+		let questions = self
+			.questions
+			.into_iter()
+			.map(|q| QuestionsItemAnswersDto {
+				discussion: String::new(),
+				discussion_image_url: None,
+				options: q
+					.options
+					.into_iter()
+					.map(|o| OptionsItemAnswersDto {
+						is_correct: false,
+						points: None,
+						..o
+					})
+					.collect(),
+				is_correct: false,
+				points: None,
+				credit: 0.0,
+				scoring_rule: None,
+				grading: None,
+				..q
+			})
+			.collect();
+		Self {
+			score: 0,
+			theta: None,
+			kecermatan: None,
+			is_revealed: false,
+			questions,
+			..self
+		}
+	}
+}
+
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct AnswersGradingQueryDto {
+	pub session_id: Option<String>,
+	pub test_id: Option<String>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, Validate, ToSchema)]
+pub struct AnswersRubricScoreDto {
+	#[validate(length(min = 1))]
+	#[schema(example = "Argument structure")]
+	pub criterion: String,
+
+	#[validate(range(min = 0))]
+	#[schema(example = 3)]
+	pub points: i32,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, Validate, ToSchema)]
+pub struct AnswersGradeRequestDto {
+	#[schema(example = 8)]
+	pub points: Option<i32>,
+
+	#[serde(default)]
+	#[validate]
+	pub rubric: Vec<AnswersRubricScoreDto>,
+
+	#[schema(example = "Good reasoning, missing a conclusion")]
+	pub comment: Option<String>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, ToSchema)]
+pub struct AnswersGradingDto {
+	pub points: i32,
+	pub rubric: Vec<AnswersRubricScoreDto>,
+	pub comment: Option<String>,
+	pub graded_by: Option<String>,
+	pub is_final: bool,
+	pub graded_at: String,
+}
+
+impl From<AnswersGradingSchema> for AnswersGradingDto {
+	fn from(value: AnswersGradingSchema) -> Self {
+		AnswersGradingDto {
+			points: value.points,
+			rubric: value
+				.rubric
+				.into_iter()
+				.map(|r| AnswersRubricScoreDto {
+					criterion: r.criterion,
+					points: r.points,
+				})
+				.collect(),
+			comment: value.comment,
+			graded_by: value.graded_by,
+			is_final: value.is_final,
+			graded_at: value.graded_at,
+		}
+	}
+}
+
+impl From<AnswersRubricScoreDto> for AnswersRubricScoreSchema {
+	fn from(value: AnswersRubricScoreDto) -> Self {
+		AnswersRubricScoreSchema {
+			criterion: value.criterion,
+			points: value.points,
+		}
+	}
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, ToSchema)]
+pub struct AnswersGradingItemDto {
+	pub id: String,
+	pub user_id: String,
+	pub session_id: String,
+	pub test_id: String,
+	pub question_id: String,
+	pub question: String,
+	pub max_points: Option<i32>,
+	pub text: Option<String>,
+	pub answered_at: Option<String>,
+	pub grading: Option<AnswersGradingDto>,
+}
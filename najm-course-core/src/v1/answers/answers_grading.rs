@@ -0,0 +1,178 @@
+use super::AnswerEntryDto;
+use crate::{QuestionsItemDto, QuestionsTypeEnum};
+use anyhow::{bail, Result};
+use std::collections::HashSet;
+
+pub const NUMERIC_EPSILON: f64 = 1e-9;
+
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct AnswerGrade {
+	pub is_correct: bool,
+	pub points: Option<i32>,
+	pub credit: f64,
+}
+
+impl AnswerGrade {
+	/// Grade for a manually scored essay, with credit as the share of the
+	/// question's maximum points awarded.
+	pub fn essay(
+		question: &QuestionsItemDto,
+		is_correct: bool,
+		points: Option<i32>,
+	) -> Self {
+		let max_points = question.answer_key.as_ref().and_then(|key| key.points);
+		let credit = match (points, max_points) {
+			_ if is_correct => 1.0,
+			(Some(points), Some(max)) if max > 0 => {
+				(points as f64 / max as f64).clamp(0.0, 1.0)
+			}
+			_ => 0.0,
+		};
+		Self {
+			is_correct,
+			points,
+			credit,
+		}
+	}
+}
+
+fn question_type(question: &QuestionsItemDto) -> QuestionsTypeEnum {
+	question.question_type.parse().unwrap_or_default()
+}
+
+/// Rejects answers whose shape does not fit the question type or that reference
+/// options outside the question.
+pub fn validate_answer(
+	question: &QuestionsItemDto,
+	entry: &AnswerEntryDto,
+) -> Result<QuestionsTypeEnum> {
+	let question_type = question_type(question);
+	let selected: Vec<&String> = match question_type {
+		QuestionsTypeEnum::SingleChoice => entry.option_id.iter().collect(),
+		QuestionsTypeEnum::MultipleCorrect => {
+			entry.option_ids.iter().flatten().collect()
+		}
+		QuestionsTypeEnum::TrueFalse => entry
+			.statements
+			.iter()
+			.flatten()
+			.map(|s| &s.option_id)
+			.collect(),
+		QuestionsTypeEnum::Numeric | QuestionsTypeEnum::Essay => vec![],
+	};
+	let matches = match question_type {
+		QuestionsTypeEnum::SingleChoice => entry.option_id.is_some(),
+		QuestionsTypeEnum::MultipleCorrect => entry.option_ids.is_some(),
+		QuestionsTypeEnum::TrueFalse => entry.statements.is_some(),
+		QuestionsTypeEnum::Numeric => entry.numeric_value.is_some_and(f64::is_finite),
+		QuestionsTypeEnum::Essay => entry.text.is_some(),
+	};
+	if !matches {
+		bail!("Answer does not match question type");
+	}
+	let known: HashSet<&String> = question.options.iter().map(|o| &o.id).collect();
+	if selected.iter().any(|id| !known.contains(id)) {
+		bail!("Option not found");
+	}
+	Ok(question_type)
+}
+
+/// Grades an answer against the question's key. Essays stay ungraded until
+/// scored manually.
+pub fn grade_answer(
+	question: &QuestionsItemDto,
+	entry: &AnswerEntryDto,
+) -> AnswerGrade {
+	let key_points = question.answer_key.as_ref().and_then(|key| key.points);
+	let sum_points = |ids: &HashSet<&str>| -> i32 {
+		question
+			.options
+			.iter()
+			.filter(|o| ids.contains(o.id.as_str()))
+			.map(|o| o.points.unwrap_or(0))
+			.sum()
+	};
+	let graded = |is_correct: bool, points: Option<i32>, credit: f64| AnswerGrade {
+		is_correct,
+		points: if is_correct { points } else { Some(0) },
+		credit: if is_correct { 1.0 } else { credit.max(0.0) },
+	};
+	match question_type(question) {
+		QuestionsTypeEnum::SingleChoice => {
+			let selected = question
+				.options
+				.iter()
+				.find(|o| entry.option_id.as_deref() == Some(o.id.as_str()));
+			let is_correct = selected.is_some_and(|o| o.is_correct.unwrap_or(false));
+			AnswerGrade {
+				is_correct,
+				points: selected.and_then(|o| o.points),
+				credit: if is_correct { 1.0 } else { 0.0 },
+			}
+		}
+		QuestionsTypeEnum::MultipleCorrect => {
+			let selected: HashSet<&str> = entry
+				.option_ids
+				.iter()
+				.flatten()
+				.map(String::as_str)
+				.collect();
+			let correct: HashSet<&str> = question
+				.options
+				.iter()
+				.filter(|o| o.is_correct.unwrap_or(false))
+				.map(|o| o.id.as_str())
+				.collect();
+			let is_correct = entry.option_ids.is_some() && selected == correct;
+			let hits = selected.intersection(&correct).count() as f64;
+			let misses = selected.difference(&correct).count() as f64;
+			let credit = if is_correct {
+				1.0
+			} else if correct.is_empty() {
+				0.0
+			} else {
+				((hits - misses) / correct.len() as f64).max(0.0)
+			};
+			let max_points = key_points.unwrap_or_else(|| sum_points(&correct));
+			AnswerGrade {
+				is_correct,
+				points: Some((max_points as f64 * credit).round() as i32),
+				credit,
+			}
+		}
+		QuestionsTypeEnum::TrueFalse => {
+			let statements = entry.statements.as_deref().unwrap_or_default();
+			let judged = question
+				.options
+				.iter()
+				.filter(|o| {
+					statements
+						.iter()
+						.any(|s| s.option_id == o.id && s.value == o.is_correct.unwrap_or(false))
+				})
+				.count();
+			let is_correct =
+				!question.options.is_empty() && judged == question.options.len();
+			let credit = if question.options.is_empty() {
+				0.0
+			} else {
+				judged as f64 / question.options.len() as f64
+			};
+			let all: HashSet<&str> =
+				question.options.iter().map(|o| o.id.as_str()).collect();
+			graded(is_correct, key_points.or(Some(sum_points(&all))), credit)
+		}
+		QuestionsTypeEnum::Numeric => {
+			let key = question.answer_key.clone().unwrap_or_default();
+			let tolerance = key.numeric_tolerance.unwrap_or(0.0).max(0.0);
+			let is_correct = match (entry.numeric_value, key.numeric_answer) {
+				(Some(value), Some(answer)) => {
+					(value - answer).abs() <= tolerance + NUMERIC_EPSILON
+				}
+				_ => false,
+			};
+			graded(is_correct, key_points, 0.0)
+		}
+		QuestionsTypeEnum::Essay => AnswerGrade::default(),
+	}
+}
@@ -0,0 +1,284 @@
+use super::*;
+use crate::{OptionsItemDto, QuestionsAnswerKeyDto, QuestionsItemDto};
+
+fn option(id: &str, is_correct: bool, points: i32) -> OptionsItemDto {
+	OptionsItemDto {
+		id: id.into(),
+		label: id.to_uppercase(),
+		display_label: None,
+		image_url: None,
+		is_correct: Some(is_correct),
+		points: Some(points),
+		created_at: "2025-01-01T00:00:00Z".into(),
+		updated_at: "2025-01-01T00:00:00Z".into(),
+	}
+}
+
+fn question(
+	question_type: &str,
+	options: Vec<OptionsItemDto>,
+	answer_key: Option<QuestionsAnswerKeyDto>,
+) -> QuestionsItemDto {
+	QuestionsItemDto {
+		id: "question".into(),
+		question: "Question".into(),
+		discussion: "Discussion".into(),
+		question_image_url: None,
+		discussion_image_url: None,
+		options,
+		question_type: question_type.into(),
+		answer_key,
+		tags: None,
+		created_at: "2025-01-01T00:00:00Z".into(),
+		updated_at: "2025-01-01T00:00:00Z".into(),
+	}
+}
+
+fn entry() -> AnswerEntryDto {
+	AnswerEntryDto {
+		question_id: "question".into(),
+		option_id: None,
+		option_ids: None,
+		statements: None,
+		numeric_value: None,
+		text: None,
+		answered_at: None,
+	}
+}
+
+fn statement(option_id: &str, value: bool) -> AnswerStatementDto {
+	AnswerStatementDto {
+		option_id: option_id.into(),
+		value,
+	}
+}
+
+#[test]
+fn test_single_choice_should_use_selected_option() {
+	let q = question(
+		"single_choice",
+		vec![option("a", false, 1), option("b", true, 5)],
+		None,
+	);
+	let answer = AnswerEntryDto {
+		option_id: Some("b".into()),
+		..entry()
+	};
+	assert_eq!(
+		grade_answer(&q, &answer),
+		AnswerGrade {
+			is_correct: true,
+			points: Some(5),
+			credit: 1.0
+		}
+	);
+	let answer = AnswerEntryDto {
+		option_id: Some("a".into()),
+		..entry()
+	};
+	assert_eq!(
+		grade_answer(&q, &answer),
+		AnswerGrade {
+			is_correct: false,
+			points: Some(1),
+			credit: 0.0
+		}
+	);
+}
+
+#[test]
+fn test_multiple_correct_should_require_exact_set() {
+	let q = question(
+		"multiple_correct",
+		vec![
+			option("a", true, 2),
+			option("b", true, 3),
+			option("c", false, 0),
+		],
+		None,
+	);
+	let exact = AnswerEntryDto {
+		option_ids: Some(vec!["b".into(), "a".into()]),
+		..entry()
+	};
+	assert_eq!(
+		grade_answer(&q, &exact),
+		AnswerGrade {
+			is_correct: true,
+			points: Some(5),
+			credit: 1.0
+		}
+	);
+	let partial = AnswerEntryDto {
+		option_ids: Some(vec!["a".into()]),
+		..entry()
+	};
+	assert_eq!(
+		grade_answer(&q, &partial),
+		AnswerGrade {
+			is_correct: false,
+			points: Some(3),
+			credit: 0.5
+		}
+	);
+	let extra = AnswerEntryDto {
+		option_ids: Some(vec!["a".into(), "b".into(), "c".into()]),
+		..entry()
+	};
+	assert_eq!(grade_answer(&q, &extra).points, Some(3));
+	let wrong = AnswerEntryDto {
+		option_ids: Some(vec!["c".into()]),
+		..entry()
+	};
+	assert_eq!(grade_answer(&q, &wrong).points, Some(0));
+}
+
+#[test]
+fn test_true_false_should_require_every_statement() {
+	let key = QuestionsAnswerKeyDto {
+		points: Some(4),
+		..Default::default()
+	};
+	let q = question(
+		"true_false",
+		vec![option("a", true, 0), option("b", false, 0)],
+		Some(key),
+	);
+	let all = AnswerEntryDto {
+		statements: Some(vec![statement("a", true), statement("b", false)]),
+		..entry()
+	};
+	assert_eq!(
+		grade_answer(&q, &all),
+		AnswerGrade {
+			is_correct: true,
+			points: Some(4),
+			credit: 1.0
+		}
+	);
+	let missing = AnswerEntryDto {
+		statements: Some(vec![statement("a", true)]),
+		..entry()
+	};
+	assert!(!grade_answer(&q, &missing).is_correct);
+	let wrong = AnswerEntryDto {
+		statements: Some(vec![statement("a", true), statement("b", true)]),
+		..entry()
+	};
+	assert_eq!(grade_answer(&q, &wrong).points, Some(0));
+}
+
+#[test]
+fn test_numeric_should_accept_within_tolerance() {
+	let key = QuestionsAnswerKeyDto {
+		numeric_answer: Some(9.81),
+		numeric_tolerance: Some(0.05),
+		points: Some(3),
+		scoring_rule: None,
+	};
+	let q = question("numeric", vec![], Some(key));
+	let close = AnswerEntryDto {
+		numeric_value: Some(9.85),
+		..entry()
+	};
+	assert_eq!(
+		grade_answer(&q, &close),
+		AnswerGrade {
+			is_correct: true,
+			points: Some(3),
+			credit: 1.0
+		}
+	);
+	let far = AnswerEntryDto {
+		numeric_value: Some(9.9),
+		..entry()
+	};
+	assert!(!grade_answer(&q, &far).is_correct);
+}
+
+#[test]
+fn test_essay_should_stay_ungraded() {
+	let q = question("essay", vec![], None);
+	let answer = AnswerEntryDto {
+		text: Some("Because of gravity".into()),
+		..entry()
+	};
+	assert_eq!(
+		grade_answer(&q, &answer),
+		AnswerGrade {
+			is_correct: false,
+			points: None,
+			credit: 0.0
+		}
+	);
+}
+
+#[test]
+fn test_validate_answer_should_reject_mismatched_shape() {
+	let q = question("numeric", vec![], None);
+	let answer = AnswerEntryDto {
+		option_id: Some("a".into()),
+		..entry()
+	};
+	let err = validate_answer(&q, &answer).unwrap_err();
+	assert_eq!(err.to_string(), "Answer does not match question type");
+}
+
+#[test]
+fn test_validate_answer_should_reject_foreign_option() {
+	let q = question(
+		"multiple_correct",
+		vec![option("a", true, 1), option("b", false, 0)],
+		None,
+	);
+	let answer = AnswerEntryDto {
+		option_ids: Some(vec!["a".into(), "z".into()]),
+		..entry()
+	};
+	let err = validate_answer(&q, &answer).unwrap_err();
+	assert_eq!(err.to_string(), "Option not found");
+}
+
+#[test]
+fn test_partial_answers_should_report_credit() {
+	let q = question(
+		"multiple_correct",
+		vec![
+			option("a", true, 1),
+			option("b", true, 1),
+			option("c", false, 0),
+			option("d", false, 0),
+		],
+		None,
+	);
+	let half = AnswerEntryDto {
+		option_ids: Some(vec!["a".into()]),
+		..entry()
+	};
+	assert_eq!(grade_answer(&q, &half).credit, 0.5);
+	let cancelled = AnswerEntryDto {
+		option_ids: Some(vec!["a".into(), "c".into()]),
+		..entry()
+	};
+	assert_eq!(grade_answer(&q, &cancelled).credit, 0.0);
+	let q = question(
+		"true_false",
+		vec![
+			option("a", true, 0),
+			option("b", false, 0),
+			option("c", true, 0),
+		],
+		None,
+	);
+	let answer = AnswerEntryDto {
+		statements: Some(vec![
+			statement("a", true),
+			statement("b", true),
+			statement("c", true),
+		]),
+		..entry()
+	};
+	let grade = grade_answer(&q, &answer);
+	assert!(!grade.is_correct);
+	assert!((grade.credit - 2.0 / 3.0).abs() < NUMERIC_EPSILON);
+}
@@ -1,17 +1,112 @@
-use std::vec;
+use std::{collections::HashMap, vec};
 
 use super::{
-	AnswersCreateRequestDto, AnswersSchema, OptionsItemAnswersDto,
-	QuestionsItemAnswersDto, TestsItemAnswersDto,
+	grade_answer, validate_answer, AnswerEntryDto, AnswerGrade,
+	AnswersCreateRequestDto, AnswersGradeRequestDto, AnswersGradingDto,
+	AnswersGradingItemDto, AnswersGradingQueryDto, AnswersGradingSchema,
+	AnswersSaveRequestDto, AnswersSaveResponseDto, AnswersSchema,
+	AnswersStatementSchema, OptionsItemAnswersDto, QuestionsItemAnswersDto,
+	TestsItemAnswersDto,
 };
 use crate::{
-	AppState, OptionsSchema, QuestionsRepository, SessionsRepository, TestsRepository,
+	AppState, AttemptsRepository, AttemptsSchema, AttemptsStatusEnum, IrtRepository,
+	MetaRequestDto, QuestionsItemDto, QuestionsRepository, QuestionsTypeEnum,
+	ResponseListSuccessDto, ScoringConfigSchema, ScoringInput, ScoringRuleSchema,
+	ScoringStrategyEnum, SessionResultsComputeRequestDto, SessionResultsRepository,
+	SessionsRepository, SessionsViewerDto, TestsRepository, SCORING_REGISTRY,
 };
 use anyhow::{bail, Error, Result};
+use chrono::{DateTime, Utc};
 use najm_course_libs::ResourceEnum;
-use najm_course_utils::{get_id, get_iso_date, make_thing};
+use najm_course_utils::{
+	display_label, get_id, get_iso_date, make_thing, query_list_with_meta,
+};
+use sha2::{Digest, Sha256};
+use surrealdb::sql::Thing;
 use validator::Validate;
 
+/// Scopes answer queries to one attempt. Answers stored before attempts
+/// existed have no attempt and are only read when the user has none.
+fn attempt_condition(attempt: Option<&Thing>) -> String {
+	match attempt {
+		Some(attempt) => format!(
+			"attempt = {}:⟨{}⟩",
+			ResourceEnum::Attempts,
+			attempt.id.to_raw()
+		),
+		None => "attempt IS NONE".into(),
+	}
+}
+
+/// Client timestamps let offline answers keep their order, but they are
+/// kept inside the attempt so late answers cannot pass as early ones.
+pub fn clamp_answered_at(
+	value: Option<&str>,
+	started_at: Option<&str>,
+	now: DateTime<Utc>,
+) -> Result<String> {
+	let Some(value) = value else {
+		return Ok(now.to_rfc3339());
+	};
+	let Ok(date) = DateTime::parse_from_rfc3339(value) else {
+		bail!("Invalid date format");
+	};
+	let mut date = date.with_timezone(&Utc).min(now);
+	if let Some(start) = started_at.and_then(|s| DateTime::parse_from_rfc3339(s).ok())
+	{
+		date = date.max(start.with_timezone(&Utc));
+	}
+	Ok(date.to_rfc3339())
+}
+
+/// One record per question and attempt, so concurrent saves of the same
+/// answer write to the same id instead of racing to create two.
+fn answer_record_id(
+	user_id: &str,
+	session_id: &str,
+	test_id: &str,
+	attempt: Option<&Thing>,
+	question_id: &str,
+) -> String {
+	let attempt_id = attempt.map(|a| a.id.to_raw()).unwrap_or_default();
+	let key = format!("{user_id}|{session_id}|{test_id}|{attempt_id}|{question_id}");
+	Sha256::digest(key.as_bytes())[..16]
+		.iter()
+		.map(|byte| format!("{:02x}", byte))
+		.collect()
+}
+
+pub fn pending_grading(questions: &[QuestionsItemAnswersDto]) -> u32 {
+	questions
+		.iter()
+		.filter(|q| {
+			q.question_type == QuestionsTypeEnum::Essay.to_string()
+				&& q.is_answered()
+				&& q.points.is_none()
+		})
+		.count() as u32
+}
+
+fn grading_item(
+	answer: AnswersSchema,
+	question: Option<&QuestionsItemDto>,
+) -> AnswersGradingItemDto {
+	AnswersGradingItemDto {
+		id: answer.id.id.to_raw(),
+		user_id: answer.user.id.to_raw(),
+		session_id: answer.session.id.to_raw(),
+		test_id: answer.test.id.to_raw(),
+		question_id: answer.question.id.to_raw(),
+		question: question.map(|q| q.question.clone()).unwrap_or_default(),
+		max_points: question
+			.and_then(|q| q.answer_key.as_ref())
+			.and_then(|key| key.points),
+		text: answer.text,
+		answered_at: answer.answered_at,
+		grading: answer.grading.map(AnswersGradingDto::from),
+	}
+}
+
 pub struct AnswersRepository<'a> {
 	pub state: &'a AppState,
 }
@@ -36,6 +131,21 @@ impl<'a> AnswersRepository<'a> {
 		session_id: &str,
 		test_id: &str,
 		user_id: &str,
+	) -> Result<TestsItemAnswersDto> {
+		let attempt = AttemptsRepository::new(self.state)
+			.query_attempt_by_user(user_id, session_id, test_id)
+			.await?;
+		self
+			.query_test_with_attempt_answers(session_id, test_id, user_id, attempt)
+			.await
+	}
+
+	pub async fn query_test_with_attempt_answers(
+		&self,
+		session_id: &str,
+		test_id: &str,
+		user_id: &str,
+		attempt: Option<AttemptsSchema>,
 	) -> Result<TestsItemAnswersDto> {
 		let db = &self.state.surrealdb_ws;
 		let question_repo = QuestionsRepository::new(&self.state);
@@ -50,8 +160,11 @@ impl<'a> AnswersRepository<'a> {
 
 		let answers: Vec<AnswersSchema> = db
 			.query(&format!(
-				"SELECT * FROM app_answers WHERE test = app_tests:⟨{}⟩ AND user = app_users:⟨{}⟩ AND session = app_sessions:⟨{}⟩ AND is_deleted = false",
-				test_id, user_id, session_id
+				"SELECT * FROM app_answers WHERE test = app_tests:⟨{}⟩ AND user = app_users:⟨{}⟩ AND session = app_sessions:⟨{}⟩ AND {} AND is_deleted = false",
+				test_id,
+				user_id,
+				session_id,
+				attempt_condition(attempt.as_ref().map(|a| &a.id))
 			))
 			.await?
 			.take(0)?;
@@ -68,21 +181,39 @@ impl<'a> AnswersRepository<'a> {
 
 		for answer in &answers {
 			let question_id = answer.question.id.to_raw();
-			let selected_option_id = answer.option.id.to_raw();
+			let entry = AnswerEntryDto::from(answer);
 			let question = question_repo.query_question_by_id(&question_id).await?;
+			let grade = match question.question_type.parse() {
+				Ok(QuestionsTypeEnum::Essay) => {
+					AnswerGrade::essay(&question, answer.is_correct, answer.points)
+				}
+				_ => grade_answer(&question, &entry),
+			};
 
 			let options_dto = question
 				.options
 				.iter()
-				.map(|opt| OptionsItemAnswersDto {
-					id: opt.id.clone(),
-					label: opt.label.clone(),
-					is_user_selected: opt.id == selected_option_id,
-					points: opt.points,
-					is_correct: opt.is_correct.unwrap_or(false),
-					image_url: opt.image_url.clone(),
-					created_at: opt.created_at.clone(),
-					updated_at: opt.updated_at.clone(),
+				.map(|opt| {
+					let user_value = entry
+						.statements
+						.iter()
+						.flatten()
+						.find(|s| s.option_id == opt.id)
+						.map(|s| s.value);
+					OptionsItemAnswersDto {
+						id: opt.id.clone(),
+						label: opt.label.clone(),
+						display_label: None,
+						is_user_selected: entry.option_id.as_ref() == Some(&opt.id)
+							|| entry.option_ids.iter().flatten().any(|id| *id == opt.id)
+							|| user_value.is_some(),
+						user_value,
+						points: opt.points,
+						is_correct: opt.is_correct.unwrap_or(false),
+						image_url: opt.image_url.clone(),
+						created_at: opt.created_at.clone(),
+						updated_at: opt.updated_at.clone(),
+					}
 				})
 				.collect();
 
@@ -93,60 +224,122 @@ impl<'a> AnswersRepository<'a> {
 				question_image_url: question.question_image_url,
 				discussion_image_url: question.discussion_image_url,
 				options: options_dto,
+				question_type: question.question_type,
+				numeric_value: entry.numeric_value,
+				text: entry.text,
+				is_correct: grade.is_correct,
+				points: grade.points,
+				credit: grade.credit,
+				scoring_rule: question
+					.answer_key
+					.as_ref()
+					.and_then(|key| key.scoring_rule.clone()),
+				grading: answer.grading.clone().map(AnswersGradingDto::from),
+				tags: question.tags,
+				answered_at: answer.answered_at.clone(),
 				created_at: question.created_at,
 				updated_at: question.updated_at,
 			});
 		}
 
 		let test_response = test.clone().test;
-		let mut score = 0;
-
-		if session.category == "Akademik" {
-			let correct_count = questions_dto
-				.iter()
-				.filter(|q| q.options.iter().any(|o| o.is_user_selected && o.is_correct))
-				.count();
-			let raw_score = correct_count as f64 * test.multiplier as f64;
-			score = (test.weight as f64 * raw_score).round() as i32;
-		}
-
-		if session.category == "Psikologi" {
-			let test_name = test.clone().test.name.to_lowercase();
-
-			let is_kepribadian = test_name.contains("kepribadian");
-			let is_kecerdasan = test_name.contains("kecerdasan");
-			let is_kecermatan = test_name.contains("kecermatan");
-
-			let total_points: i32 = questions_dto
+		let scoring = match test.scoring.clone() {
+			Some(scoring) => ScoringConfigSchema::from(scoring),
+			None => ScoringConfigSchema::infer(&session.category, &test_response.name),
+		};
+		let presented = attempt
+			.as_ref()
+			.map(|a| a.question_order.clone())
+			.unwrap_or_default();
+		let question_order: Vec<String> = if presented.is_empty() {
+			TestsRepository::new(self.state)
+				.query_raw_test_by_id(test_id)
+				.await?
+				.questions
 				.iter()
-				.flat_map(|q| &q.options)
-				.filter(|o| o.is_user_selected)
-				.map(|o| o.points.unwrap_or(0))
-				.sum();
-
-			if is_kepribadian {
-				score = ((total_points as f64) * 100.0 / 500.0).round() as i32;
-			}
-
-			if is_kecerdasan {
-				score = total_points;
+				.map(|q| q.id.to_raw())
+				.collect()
+		} else {
+			presented.iter().map(|o| o.question.clone()).collect()
+		};
+		if !presented.is_empty() {
+			let position = |ids: &[String], id: &str| {
+				ids.iter().position(|i| i == id).unwrap_or(usize::MAX)
+			};
+			questions_dto.sort_by_key(|q| position(&question_order, &q.id));
+			for question in questions_dto.iter_mut() {
+				if let Some(entry) = presented.iter().find(|o| o.question == question.id) {
+					question
+						.options
+						.sort_by_key(|o| position(&entry.options, &o.id));
+				}
 			}
-
-			if is_kecermatan {
-				// TODO: Implement scoring logic for kecermatan
+		}
+		for question in questions_dto.iter_mut() {
+			for (index, option) in question.options.iter_mut().enumerate() {
+				option.display_label = Some(display_label(index));
 			}
 		}
+		let item_params = if scoring.strategy == ScoringStrategyEnum::Irt.to_string() {
+			IrtRepository::new(self.state)
+				.query_item_params(&question_order)
+				.await?
+		} else {
+			HashMap::new()
+		};
+		let rule = test.scoring_rule.clone().map(ScoringRuleSchema::from);
+		let output = SCORING_REGISTRY.score(
+			&scoring.strategy,
+			&ScoringInput {
+				questions: &questions_dto,
+				question_order: &question_order,
+				started_at: attempt.as_ref().map(|a| a.started_at.as_str()),
+				item_params: &item_params,
+				weight: test.weight,
+				multiplier: test.multiplier,
+				params: &scoring.params,
+				rule: rule.as_ref(),
+			},
+		)?;
 
 		Ok(TestsItemAnswersDto {
 			id: answer_id,
 			name: test_response.name,
-			score,
+			score: output.score,
+			theta: output.theta,
+			kecermatan: output.kecermatan,
+			pending_grading: pending_grading(&questions_dto),
+			is_revealed: true,
 			questions: questions_dto,
 			created_at: test_response.created_at,
 			updated_at: test_response.updated_at,
 		})
 	}
 
+	/// Conceals scores and correctness from a student until the session's
+	/// visibility policy reveals them. Answers without an attempt count as
+	/// submitted.
+	pub async fn query_apply_visibility(
+		&self,
+		data: TestsItemAnswersDto,
+		viewer: &SessionsViewerDto,
+		user_id: &str,
+		session_id: &str,
+		test_id: &str,
+	) -> Result<TestsItemAnswersDto> {
+		if viewer.is_reviewer {
+			return Ok(data);
+		}
+		let is_submitted = AttemptsRepository::new(self.state)
+			.query_attempt_by_user(user_id, session_id, test_id)
+			.await?
+			.is_none_or(|a| a.status != AttemptsStatusEnum::InProgress.to_string());
+		let revealed = SessionsRepository::new(self.state)
+			.query_is_revealed(session_id, is_submitted)
+			.await?;
+		Ok(if revealed { data } else { data.concealed() })
+	}
+
 	pub async fn query_by_id(&self, id: &str) -> Result<TestsItemAnswersDto> {
 		let db = &self.state.surrealdb_ws;
 		let answer: Option<AnswersSchema> =
@@ -161,8 +354,16 @@ impl<'a> AnswersRepository<'a> {
 		let user_id = answer.user.id.to_raw();
 		let test_id = answer.test.id.to_raw();
 		let session_id = answer.session.id.to_raw();
+		let attempt = match &answer.attempt {
+			Some(attempt) => Some(
+				AttemptsRepository::new(self.state)
+					.query_raw_attempt_by_id(&attempt.id.to_raw())
+					.await?,
+			),
+			None => None,
+		};
 		self
-			.query_test_with_answers(&session_id, &test_id, &user_id)
+			.query_test_with_attempt_answers(&session_id, &test_id, &user_id, attempt)
 			.await
 	}
 
@@ -171,44 +372,47 @@ impl<'a> AnswersRepository<'a> {
 		payload: AnswersCreateRequestDto,
 	) -> Result<TestsItemAnswersDto> {
 		payload.clone().validate()?;
+		let attempt = AttemptsRepository::new(self.state)
+			.query_ensure_accepting_answers(
+				&payload.user_id,
+				&payload.session_id,
+				&payload.test_id,
+			)
+			.await?;
+		self
+			.query_create_for_attempt(payload, attempt.as_ref())
+			.await
+	}
+
+	/// Writes the answers under `attempt` without checking that it still
+	/// accepts answers; the caller has already claimed it.
+	pub async fn query_create_for_attempt(
+		&self,
+		payload: AnswersCreateRequestDto,
+		attempt: Option<&AttemptsSchema>,
+	) -> Result<TestsItemAnswersDto> {
 		let db = &self.state.surrealdb_ws;
 		let test_repo = TestsRepository::new(&self.state);
 		let question_repo = QuestionsRepository::new(&self.state);
-		let now = get_iso_date();
 		for entry in &payload.answers {
-			let id = surrealdb::Uuid::new_v4().to_string();
-			let selected_option: Option<OptionsSchema> = db
-				.select((ResourceEnum::Options.to_string(), &entry.option_id))
-				.await?;
-			let is_correct = selected_option.map_or(false, |opt| opt.is_correct);
-			let answer = AnswersSchema {
-				id: make_thing(&ResourceEnum::Answers.to_string(), &id),
-				user: make_thing(&ResourceEnum::Users.to_string(), &payload.user_id),
-				test: make_thing(&ResourceEnum::Tests.to_string(), &payload.test_id),
-				session: make_thing(
-					&ResourceEnum::Sessions.to_string(),
+			self
+				.query_upsert_answer(
+					&payload.user_id,
 					&payload.session_id,
-				),
-				question: make_thing(
-					&ResourceEnum::Questions.to_string(),
-					&entry.question_id,
-				),
-				option: make_thing(&ResourceEnum::Options.to_string(), &entry.option_id),
-				is_correct,
-				is_deleted: false,
-				created_at: now.clone(),
-				updated_at: now.clone(),
-			};
-			let _: Option<AnswersSchema> = db
-				.create((ResourceEnum::Answers.to_string(), &id))
-				.content(answer)
+					&payload.test_id,
+					attempt,
+					entry,
+				)
 				.await?;
 		}
 		let test_data = test_repo.query_test_by_id(&payload.test_id).await?;
 		let answers: Vec<AnswersSchema> = db
 			.query(&format!(
-				"SELECT * FROM app_answers WHERE session = app_sessions:⟨{}⟩ AND test = app_tests:⟨{}⟩ AND user = app_users:⟨{}⟩ AND is_deleted = false",
-				&payload.session_id, &payload.test_id, &payload.user_id
+				"SELECT * FROM app_answers WHERE session = app_sessions:⟨{}⟩ AND test = app_tests:⟨{}⟩ AND user = app_users:⟨{}⟩ AND {} AND is_deleted = false",
+				&payload.session_id,
+				&payload.test_id,
+				&payload.user_id,
+				attempt_condition(attempt.map(|a| &a.id))
 			))
 			.await?
 			.take(0)?;
@@ -222,7 +426,6 @@ impl<'a> AnswersRepository<'a> {
 		let mut questions_dto = vec![];
 		for answer in &answers {
 			let question_id = answer.question.id.to_raw();
-			let _selected_option_id = answer.option.id.to_raw();
 			let question = question_repo.query_question_by_id(&question_id).await?;
 			let _options = question.options.clone();
 			let options_converted = vec![];
@@ -233,6 +436,19 @@ impl<'a> AnswersRepository<'a> {
 				question_image_url: question.question_image_url,
 				discussion_image_url: question.discussion_image_url,
 				options: options_converted,
+				question_type: question.question_type,
+				numeric_value: answer.numeric_value,
+				text: answer.text.clone(),
+				is_correct: answer.is_correct,
+				points: answer.points,
+				credit: if answer.is_correct { 1.0 } else { 0.0 },
+				scoring_rule: question
+					.answer_key
+					.as_ref()
+					.and_then(|key| key.scoring_rule.clone()),
+				grading: answer.grading.clone().map(AnswersGradingDto::from),
+				tags: question.tags,
+				answered_at: answer.answered_at.clone(),
 				created_at: question.created_at,
 				updated_at: question.updated_at,
 			});
@@ -241,12 +457,361 @@ impl<'a> AnswersRepository<'a> {
 			id: answer_id,
 			name: test_data.name,
 			score: 0,
+			theta: None,
+			kecermatan: None,
+			pending_grading: pending_grading(&questions_dto),
+			is_revealed: true,
 			questions: questions_dto,
 			created_at: test_data.created_at,
 			updated_at: test_data.updated_at,
 		})
 	}
 
+	pub async fn query_save(
+		&self,
+		payload: AnswersSaveRequestDto,
+	) -> Result<AnswersSaveResponseDto> {
+		payload.validate()?;
+		let attempt = AttemptsRepository::new(self.state)
+			.query_ensure_accepting_answers(
+				&payload.user_id,
+				&payload.session_id,
+				&payload.test_id,
+			)
+			.await?;
+		let entry = payload.entry();
+		let saved = if entry.is_empty() {
+			self
+				.query_clear_answer(
+					&payload.user_id,
+					&payload.session_id,
+					&payload.test_id,
+					attempt.as_ref().map(|a| &a.id),
+					&payload.question_id,
+				)
+				.await?
+		} else {
+			Some(
+				self
+					.query_upsert_answer(
+						&payload.user_id,
+						&payload.session_id,
+						&payload.test_id,
+						attempt.as_ref(),
+						&entry,
+					)
+					.await?,
+			)
+		};
+		Ok(match saved {
+			Some(answer) if !answer.is_deleted => {
+				let entry = AnswerEntryDto::from(&answer);
+				AnswersSaveResponseDto {
+					question_id: payload.question_id,
+					option_id: entry.option_id,
+					option_ids: entry.option_ids,
+					statements: entry.statements,
+					numeric_value: entry.numeric_value,
+					text: entry.text,
+					answered_at: answer.answered_at,
+					updated_at: answer.updated_at,
+				}
+			}
+			_ => AnswersSaveResponseDto {
+				question_id: payload.question_id,
+				option_id: None,
+				option_ids: None,
+				statements: None,
+				numeric_value: None,
+				text: None,
+				answered_at: None,
+				updated_at: get_iso_date(),
+			},
+		})
+	}
+
+	async fn query_answer_by_question(
+		&self,
+		user_id: &str,
+		session_id: &str,
+		test_id: &str,
+		attempt: Option<&Thing>,
+		question_id: &str,
+	) -> Result<Option<AnswersSchema>> {
+		let db = &self.state.surrealdb_ws;
+		let answers: Vec<AnswersSchema> = db
+			.query(format!(
+				"SELECT * FROM app_answers WHERE user = app_users:⟨{}⟩ AND session = app_sessions:⟨{}⟩ AND test = app_tests:⟨{}⟩ AND question = app_questions:⟨{}⟩ AND {} ORDER BY updated_at DESC LIMIT 1",
+				user_id,
+				session_id,
+				test_id,
+				question_id,
+				attempt_condition(attempt)
+			))
+			.await?
+			.take(0)?;
+		Ok(answers.into_iter().next())
+	}
+
+	async fn query_upsert_answer(
+		&self,
+		user_id: &str,
+		session_id: &str,
+		test_id: &str,
+		attempt: Option<&AttemptsSchema>,
+		entry: &AnswerEntryDto,
+	) -> Result<AnswersSchema> {
+		let db = &self.state.surrealdb_ws;
+		let now = get_iso_date();
+		let question_id = entry.question_id.as_str();
+		let question = QuestionsRepository::new(self.state)
+			.query_question_by_id(question_id)
+			.await?;
+		validate_answer(&question, entry)?;
+		let grade = grade_answer(&question, entry);
+		let option_thing =
+			|id: &String| make_thing(&ResourceEnum::Options.to_string(), id);
+		let answered_at = match attempt {
+			Some(attempt) => clamp_answered_at(
+				entry.answered_at.as_deref(),
+				Some(&attempt.started_at),
+				Utc::now(),
+			)?,
+			None => now.clone(),
+		};
+		let attempt = attempt.map(|a| &a.id);
+		let existing = self
+			.query_answer_by_question(user_id, session_id, test_id, attempt, question_id)
+			.await?;
+		let (id, created_at) = match &existing {
+			Some(a) => (a.id.id.to_raw(), a.created_at.clone()),
+			None => (
+				answer_record_id(user_id, session_id, test_id, attempt, question_id),
+				now.clone(),
+			),
+		};
+		let answer = AnswersSchema {
+			id: make_thing(&ResourceEnum::Answers.to_string(), &id),
+			user: make_thing(&ResourceEnum::Users.to_string(), user_id),
+			test: make_thing(&ResourceEnum::Tests.to_string(), test_id),
+			session: make_thing(&ResourceEnum::Sessions.to_string(), session_id),
+			question: make_thing(&ResourceEnum::Questions.to_string(), question_id),
+			attempt: attempt.cloned(),
+			option: entry.option_id.as_ref().map(option_thing),
+			options: entry
+				.option_ids
+				.iter()
+				.flatten()
+				.map(option_thing)
+				.collect(),
+			statements: entry
+				.statements
+				.iter()
+				.flatten()
+				.map(|s| AnswersStatementSchema {
+					option: option_thing(&s.option_id),
+					value: s.value,
+				})
+				.collect(),
+			numeric_value: entry.numeric_value,
+			text: entry.text.clone(),
+			is_correct: grade.is_correct,
+			points: grade.points,
+			grading: None,
+			answered_at: Some(answered_at),
+			is_deleted: false,
+			created_at,
+			updated_at: now,
+		};
+		let record: Option<AnswersSchema> = db
+			.upsert((ResourceEnum::Answers.to_string(), &id))
+			.content(answer)
+			.await?;
+		match record {
+			Some(a) => Ok(a),
+			None => bail!("Failed to save answer"),
+		}
+	}
+
+	async fn query_clear_answer(
+		&self,
+		user_id: &str,
+		session_id: &str,
+		test_id: &str,
+		attempt: Option<&Thing>,
+		question_id: &str,
+	) -> Result<Option<AnswersSchema>> {
+		let db = &self.state.surrealdb_ws;
+		let Some(existing) = self
+			.query_answer_by_question(user_id, session_id, test_id, attempt, question_id)
+			.await?
+		else {
+			return Ok(None);
+		};
+		let record: Option<AnswersSchema> = db
+			.update(get_id(&existing.id)?)
+			.merge(serde_json::json!({
+				"is_deleted": true,
+				"updated_at": get_iso_date(),
+			}))
+			.await?;
+		Ok(record)
+	}
+
+	pub async fn query_grading_queue(
+		&self,
+		query: AnswersGradingQueryDto,
+		meta: MetaRequestDto,
+	) -> Result<ResponseListSuccessDto<Vec<AnswersGradingItemDto>>> {
+		let mut conditions = vec![
+			"is_deleted = false".to_string(),
+			"text != NONE".to_string(),
+			"points = NONE".to_string(),
+		];
+		if let Some(session_id) = &query.session_id {
+			conditions.push(format!(
+				"session = {}:⟨{}⟩",
+				ResourceEnum::Sessions,
+				session_id
+			));
+		}
+		if let Some(test_id) = &query.test_id {
+			conditions.push(format!("test = {}:⟨{}⟩", ResourceEnum::Tests, test_id));
+		}
+		let raw = query_list_with_meta::<AnswersSchema>(
+			&self.state.surrealdb_ws,
+			&ResourceEnum::Answers.to_string(),
+			&meta,
+			conditions,
+			None,
+		)
+		.await?;
+		let question_repo = QuestionsRepository::new(self.state);
+		let mut questions: HashMap<String, QuestionsItemDto> = HashMap::new();
+		let mut data = Vec::with_capacity(raw.data.len());
+		for answer in raw.data {
+			let question_id = answer.question.id.to_raw();
+			if !questions.contains_key(&question_id) {
+				if let Ok(question) = question_repo.query_question_by_id(&question_id).await
+				{
+					questions.insert(question_id.clone(), question);
+				}
+			}
+			data.push(grading_item(answer, questions.get(&question_id)));
+		}
+		Ok(ResponseListSuccessDto {
+			data,
+			meta: raw.meta,
+		})
+	}
+
+	async fn query_essay_answer(
+		&self,
+		id: &str,
+	) -> Result<(AnswersSchema, QuestionsItemDto)> {
+		let answer = self.query_raw_answer_by_id(id).await?;
+		let question = QuestionsRepository::new(self.state)
+			.query_question_by_id(&answer.question.id.to_raw())
+			.await?;
+		if question.question_type != QuestionsTypeEnum::Essay.to_string() {
+			bail!("Answer is not an essay");
+		}
+		if answer.grading.as_ref().is_some_and(|g| g.is_final) {
+			bail!("Answer already graded");
+		}
+		Ok((answer, question))
+	}
+
+	pub async fn query_grade_answer(
+		&self,
+		id: &str,
+		payload: AnswersGradeRequestDto,
+		graded_by: Option<String>,
+	) -> Result<AnswersGradingItemDto> {
+		payload.validate()?;
+		let (answer, question) = self.query_essay_answer(id).await?;
+		let rubric_total: Option<i32> = (!payload.rubric.is_empty())
+			.then(|| payload.rubric.iter().map(|r| r.points).sum());
+		let points = match (payload.points, rubric_total) {
+			(Some(points), Some(total)) if points != total => {
+				bail!("Points must match rubric total")
+			}
+			(Some(points), _) => points,
+			(None, Some(total)) => total,
+			(None, None) => bail!("Points are required"),
+		};
+		if points < 0 {
+			bail!("Points must not be negative");
+		}
+		let max_points = question.answer_key.as_ref().and_then(|key| key.points);
+		if max_points.is_some_and(|max| points > max) {
+			bail!("Points must not exceed maximum points");
+		}
+		let grading = AnswersGradingSchema {
+			points,
+			rubric: payload.rubric.into_iter().map(Into::into).collect(),
+			comment: payload.comment,
+			graded_by,
+			is_final: false,
+			graded_at: get_iso_date(),
+		};
+		let db = &self.state.surrealdb_ws;
+		let record: Option<AnswersSchema> = db
+			.update(get_id(&answer.id)?)
+			.merge(serde_json::json!({ "grading": grading }))
+			.await?;
+		match record {
+			Some(answer) => Ok(grading_item(answer, Some(&question))),
+			None => bail!("Failed to grade answer"),
+		}
+	}
+
+	pub async fn query_finalize_grading(
+		&self,
+		id: &str,
+	) -> Result<AnswersGradingItemDto> {
+		let (answer, question) = self.query_essay_answer(id).await?;
+		let Some(mut grading) = answer.grading.clone() else {
+			bail!("Answer has not been graded");
+		};
+		grading.is_final = true;
+		grading.graded_at = get_iso_date();
+		let max_points = question.answer_key.as_ref().and_then(|key| key.points);
+		let is_correct = match max_points {
+			Some(max) => grading.points >= max,
+			None => grading.points > 0,
+		};
+		let db = &self.state.surrealdb_ws;
+		let record: Option<AnswersSchema> = db
+			.update(get_id(&answer.id)?)
+			.merge(serde_json::json!({
+				"grading": grading,
+				"points": grading.points,
+				"is_correct": is_correct,
+				"updated_at": get_iso_date(),
+			}))
+			.await?;
+		let Some(answer) = record else {
+			bail!("Failed to finalize grading");
+		};
+		let user_id = answer.user.id.to_raw();
+		let session_id = answer.session.id.to_raw();
+		let results = SessionResultsRepository::new(self.state);
+		if results
+			.query_raw_result_by_user(&user_id, &session_id)
+			.await?
+			.is_some()
+		{
+			results
+				.query_compute_result(SessionResultsComputeRequestDto {
+					user_id,
+					session_id,
+				})
+				.await?;
+		}
+		Ok(grading_item(answer, Some(&question)))
+	}
+
 	pub async fn query_delete(&self, id: String) -> Result<String> {
 		let db = &self.state.surrealdb_ws;
 		let raw: Option<AnswersSchema> =
@@ -58,6 +58,21 @@ pub async fn seed_answer_dependencies(
 	Ok((test_id, session_id, question_id, option_id))
 }
 
+pub async fn seed_in_progress_attempt(
+	db: &SurrealWsClient,
+	user_id: &str,
+	session_id: &str,
+	test_id: &str,
+) -> Result<()> {
+	let now = get_iso_date();
+	db.query(format!(
+		"CREATE app_attempts SET user = app_users:⟨{user_id}⟩, session = app_sessions:⟨{session_id}⟩, test = app_tests:⟨{test_id}⟩, status = 'in_progress', attempt_number = 1, started_at = '{now}', deadline_at = '2099-01-01T00:00:00Z', last_heartbeat_at = '{now}', submitted_at = NONE, is_deleted = false, created_at = '{now}', updated_at = '{now}'"
+	))
+	.await?
+	.check()?;
+	Ok(())
+}
+
 pub fn build_payload(
 	user_id: &str,
 	test_id: &str,
@@ -71,7 +86,12 @@ pub fn build_payload(
 		session_id: session_id.to_string(),
 		answers: vec![AnswerEntryDto {
 			question_id: question_id.to_string(),
-			option_id: option_id.to_string(),
+			option_id: Some(option_id.to_string()),
+			option_ids: None,
+			statements: None,
+			numeric_value: None,
+			text: None,
+			answered_at: None,
 		}],
 	}
 }
@@ -83,6 +103,9 @@ async fn test_query_create_answers_should_succeed() {
 	let (test_id, session_id, question_id, option_id) =
 		seed_answer_dependencies(db).await.unwrap();
 	let user_id = Uuid::new_v4().to_string();
+	seed_in_progress_attempt(&state.surrealdb_ws, &user_id, &session_id, &test_id)
+		.await
+		.unwrap();
 	let payload =
 		build_payload(&user_id, &test_id, &session_id, &question_id, &option_id);
 	let repo = AnswersRepository::new(&state);
@@ -101,6 +124,9 @@ async fn test_query_by_id_should_return_data() {
 	let (test_id, session_id, question_id, option_id) =
 		seed_answer_dependencies(db).await.unwrap();
 	let user_id = Uuid::new_v4().to_string();
+	seed_in_progress_attempt(&state.surrealdb_ws, &user_id, &session_id, &test_id)
+		.await
+		.unwrap();
 	let payload =
 		build_payload(&user_id, &test_id, &session_id, &question_id, &option_id);
 	let repo = AnswersRepository::new(&state);
@@ -139,6 +165,9 @@ async fn test_query_delete_should_soft_delete() {
 	let (test_id, session_id, question_id, option_id) =
 		seed_answer_dependencies(db).await.unwrap();
 	let user_id = Uuid::new_v4().to_string();
+	seed_in_progress_attempt(&state.surrealdb_ws, &user_id, &session_id, &test_id)
+		.await
+		.unwrap();
 	let payload =
 		build_payload(&user_id, &test_id, &session_id, &question_id, &option_id);
 	let repo = AnswersRepository::new(&state);
@@ -173,6 +202,9 @@ async fn test_query_delete_should_fail_if_already_deleted() {
 	let (test_id, session_id, question_id, option_id) =
 		seed_answer_dependencies(db).await.unwrap();
 	let user_id = Uuid::new_v4().to_string();
+	seed_in_progress_attempt(&state.surrealdb_ws, &user_id, &session_id, &test_id)
+		.await
+		.unwrap();
 	let now = chrono::Utc::now().to_rfc3339();
 	let _ = db
 		.query(format!(
@@ -206,3 +238,543 @@ async fn test_query_delete_should_fail_if_already_deleted() {
 	dbg!(&delete_twice);
 	assert!(delete_twice.is_err(), "Expected error on second delete");
 }
+
+fn build_save_payload(
+	user_id: &str,
+	test_id: &str,
+	session_id: &str,
+	question_id: &str,
+	option_id: Option<&str>,
+) -> AnswersSaveRequestDto {
+	AnswersSaveRequestDto {
+		user_id: user_id.to_string(),
+		test_id: test_id.to_string(),
+		session_id: session_id.to_string(),
+		question_id: question_id.to_string(),
+		option_id: option_id.map(|o| o.to_string()),
+		option_ids: None,
+		statements: None,
+		numeric_value: None,
+		text: None,
+		answered_at: None,
+	}
+}
+
+async fn count_answers(
+	db: &SurrealWsClient,
+	user_id: &str,
+	session_id: &str,
+	test_id: &str,
+) -> usize {
+	let answers: Vec<AnswersSchema> = db
+		.query(format!(
+			"SELECT * FROM app_answers WHERE user = app_users:⟨{}⟩ AND session = app_sessions:⟨{}⟩ AND test = app_tests:⟨{}⟩ AND is_deleted = false",
+			user_id, session_id, test_id
+		))
+		.await
+		.unwrap()
+		.take(0)
+		.unwrap();
+	answers.len()
+}
+
+#[tokio::test]
+async fn test_query_create_answers_twice_should_not_duplicate() {
+	let state = create_mock_app_state().await;
+	let db = &state.surrealdb_ws;
+	let (test_id, session_id, question_id, option_id) =
+		seed_answer_dependencies(db).await.unwrap();
+	let user_id = Uuid::new_v4().to_string();
+	seed_in_progress_attempt(&state.surrealdb_ws, &user_id, &session_id, &test_id)
+		.await
+		.unwrap();
+	let payload =
+		build_payload(&user_id, &test_id, &session_id, &question_id, &option_id);
+	let repo = AnswersRepository::new(&state);
+	repo.query_create(payload.clone()).await.unwrap();
+	repo.query_create(payload).await.unwrap();
+	assert_eq!(count_answers(db, &user_id, &session_id, &test_id).await, 1);
+}
+
+#[tokio::test]
+async fn test_query_save_should_upsert_and_clear() {
+	let state = create_mock_app_state().await;
+	let db = &state.surrealdb_ws;
+	let (test_id, session_id, question_id, option_id) =
+		seed_answer_dependencies(db).await.unwrap();
+	let user_id = Uuid::new_v4().to_string();
+	seed_in_progress_attempt(&state.surrealdb_ws, &user_id, &session_id, &test_id)
+		.await
+		.unwrap();
+	let repo = AnswersRepository::new(&state);
+	for _ in 0..3 {
+		let saved = repo
+			.query_save(build_save_payload(
+				&user_id,
+				&test_id,
+				&session_id,
+				&question_id,
+				Some(&option_id),
+			))
+			.await
+			.unwrap();
+		assert_eq!(saved.option_id, Some(option_id.clone()));
+	}
+	assert_eq!(count_answers(db, &user_id, &session_id, &test_id).await, 1);
+	let cleared = repo
+		.query_save(build_save_payload(
+			&user_id,
+			&test_id,
+			&session_id,
+			&question_id,
+			None,
+		))
+		.await
+		.unwrap();
+	assert_eq!(cleared.option_id, None);
+	assert_eq!(count_answers(db, &user_id, &session_id, &test_id).await, 0);
+	repo
+		.query_save(build_save_payload(
+			&user_id,
+			&test_id,
+			&session_id,
+			&question_id,
+			Some(&option_id),
+		))
+		.await
+		.unwrap();
+	assert_eq!(count_answers(db, &user_id, &session_id, &test_id).await, 1);
+}
+
+#[tokio::test]
+async fn test_query_save_should_not_duplicate_concurrent_saves() {
+	let state = create_mock_app_state().await;
+	let db = &state.surrealdb_ws;
+	let (test_id, session_id, question_id, option_id) =
+		seed_answer_dependencies(db).await.unwrap();
+	let user_id = Uuid::new_v4().to_string();
+	seed_in_progress_attempt(db, &user_id, &session_id, &test_id)
+		.await
+		.unwrap();
+	let repo = AnswersRepository::new(&state);
+	let save = || {
+		repo.query_save(build_save_payload(
+			&user_id,
+			&test_id,
+			&session_id,
+			&question_id,
+			Some(&option_id),
+		))
+	};
+	let (first, second) = tokio::join!(save(), save());
+	first.unwrap();
+	second.unwrap();
+	assert_eq!(count_answers(db, &user_id, &session_id, &test_id).await, 1);
+}
+
+#[tokio::test]
+async fn test_query_save_should_fail_after_submit() {
+	use crate::attempts::attempts_repository_test::{
+		build_start_payload, build_submit_payload, seed_attempt_dependencies,
+	};
+	let state = create_mock_app_state().await;
+	let fixture = seed_attempt_dependencies(&state.surrealdb_ws, -10, 120, Some(30))
+		.await
+		.unwrap();
+	let attempt_repo = crate::AttemptsRepository::new(&state);
+	let attempt = attempt_repo
+		.query_start_attempt(&fixture.user_id, build_start_payload(&fixture))
+		.await
+		.unwrap();
+	let repo = AnswersRepository::new(&state);
+	repo
+		.query_save(build_save_payload(
+			&fixture.user_id,
+			&fixture.test_id,
+			&fixture.session_id,
+			&fixture.question_id,
+			Some(&fixture.option_id),
+		))
+		.await
+		.unwrap();
+	attempt_repo
+		.query_submit_attempt(
+			&attempt.id,
+			&fixture.user_id,
+			build_submit_payload(&fixture),
+		)
+		.await
+		.unwrap();
+	assert_eq!(
+		count_answers(
+			&state.surrealdb_ws,
+			&fixture.user_id,
+			&fixture.session_id,
+			&fixture.test_id
+		)
+		.await,
+		1
+	);
+	let result = repo
+		.query_save(build_save_payload(
+			&fixture.user_id,
+			&fixture.test_id,
+			&fixture.session_id,
+			&fixture.question_id,
+			None,
+		))
+		.await;
+	assert_eq!(result.unwrap_err().to_string(), "Attempt already submitted");
+}
+
+async fn seed_typed_question(
+	state: &crate::AppState,
+	payload: crate::QuestionsCreateRequestDto,
+) -> (String, String, crate::QuestionsItemDto) {
+	let db = &state.surrealdb_ws;
+	let now = get_iso_date();
+	let questions = crate::QuestionsRepository::new(state);
+	let question_id = questions.query_create_question(payload).await.unwrap();
+	let question = questions.query_question_by_id(&question_id).await.unwrap();
+	let test_id = Uuid::new_v4().to_string();
+	let session_id = Uuid::new_v4().to_string();
+	db.query(format!(
+		"CREATE app_tests:⟨{test_id}⟩ SET name = 'Typed Test', questions = [app_questions:⟨{question_id}⟩], is_deleted = false, created_at = '{now}', updated_at = '{now}';
+		CREATE app_sessions:⟨{session_id}⟩ SET name = 'Typed Session', category = 'Dummy Category', description = 'Dummy Description', student_type = 'Dummy Type', passing_grade = 5, tests = [{{ test: app_tests:⟨{test_id}⟩, shuffle: false, weight: 1.0, multiplier: 1.0, start_date: '2025-01-01T00:00:00Z', end_date: '2099-01-01T00:00:00Z', scoring: {{ strategy: 'points_sum' }} }}], is_active = true, is_deleted = false, created_at = '{now}', updated_at = '{now}';"
+	))
+	.await
+	.unwrap();
+	(test_id, session_id, question)
+}
+
+async fn fetch_answer(
+	db: &SurrealWsClient,
+	user_id: &str,
+	test_id: &str,
+) -> AnswersSchema {
+	let answers: Vec<AnswersSchema> = db
+		.query(format!(
+			"SELECT * FROM app_answers WHERE user = app_users:⟨{user_id}⟩ AND test = app_tests:⟨{test_id}⟩ AND is_deleted = false"
+		))
+		.await
+		.unwrap()
+		.take(0)
+		.unwrap();
+	answers.into_iter().next().expect("Expected a saved answer")
+}
+
+fn typed_question_payload(
+	question_type: &str,
+	options: Vec<(&str, bool)>,
+	answer_key: Option<crate::QuestionsAnswerKeyDto>,
+) -> crate::QuestionsCreateRequestDto {
+	crate::QuestionsCreateRequestDto {
+		question: Some(format!("Question {}", Uuid::new_v4())),
+		discussion: Some("Discussion".into()),
+		question_image_url: None,
+		discussion_image_url: None,
+		question_type: Some(question_type.into()),
+		answer_key,
+		tags: None,
+		options: options
+			.into_iter()
+			.map(|(label, is_correct)| crate::OptionsCreateRequestDto {
+				label: Some(label.into()),
+				image_url: None,
+				is_correct,
+				points: Some(2),
+			})
+			.collect(),
+	}
+}
+
+#[tokio::test]
+async fn test_query_save_should_grade_multiple_correct() {
+	let state = create_mock_app_state().await;
+	let (test_id, session_id, question) = seed_typed_question(
+		&state,
+		typed_question_payload(
+			"multiple_correct",
+			vec![("A", true), ("B", true), ("C", false)],
+			None,
+		),
+	)
+	.await;
+	let correct: Vec<String> = question
+		.options
+		.iter()
+		.filter(|o| o.is_correct == Some(true))
+		.map(|o| o.id.clone())
+		.collect();
+	let user_id = Uuid::new_v4().to_string();
+	seed_in_progress_attempt(&state.surrealdb_ws, &user_id, &session_id, &test_id)
+		.await
+		.unwrap();
+	let repo = AnswersRepository::new(&state);
+	let mut payload =
+		build_save_payload(&user_id, &test_id, &session_id, &question.id, None);
+	payload.option_ids = Some(correct.clone());
+	let saved = repo.query_save(payload).await.unwrap();
+	assert_eq!(saved.option_ids, Some(correct));
+	let answer = fetch_answer(&state.surrealdb_ws, &user_id, &test_id).await;
+	assert!(answer.is_correct);
+	assert_eq!(answer.points, Some(4));
+	assert_eq!(answer.options.len(), 2);
+	let mut partial =
+		build_save_payload(&user_id, &test_id, &session_id, &question.id, None);
+	partial.option_ids = Some(vec![question.options[0].id.clone()]);
+	repo.query_save(partial).await.unwrap();
+	let answer = fetch_answer(&state.surrealdb_ws, &user_id, &test_id).await;
+	assert!(!answer.is_correct);
+	assert_eq!(answer.points, Some(2));
+}
+
+#[tokio::test]
+async fn test_query_save_should_grade_numeric_and_keep_essay_pending() {
+	let state = create_mock_app_state().await;
+	let repo = AnswersRepository::new(&state);
+	let user_id = Uuid::new_v4().to_string();
+	let key = crate::QuestionsAnswerKeyDto {
+		numeric_answer: Some(42.0),
+		numeric_tolerance: Some(0.5),
+		points: Some(3),
+		scoring_rule: None,
+	};
+	let (test_id, session_id, question) = seed_typed_question(
+		&state,
+		typed_question_payload("numeric", vec![], Some(key)),
+	)
+	.await;
+	seed_in_progress_attempt(&state.surrealdb_ws, &user_id, &session_id, &test_id)
+		.await
+		.unwrap();
+	let mut payload =
+		build_save_payload(&user_id, &test_id, &session_id, &question.id, None);
+	payload.numeric_value = Some(42.3);
+	repo.query_save(payload).await.unwrap();
+	let answer = fetch_answer(&state.surrealdb_ws, &user_id, &test_id).await;
+	assert!(answer.is_correct);
+	assert_eq!(answer.points, Some(3));
+
+	let (test_id, session_id, question) =
+		seed_typed_question(&state, typed_question_payload("essay", vec![], None)).await;
+	seed_in_progress_attempt(&state.surrealdb_ws, &user_id, &session_id, &test_id)
+		.await
+		.unwrap();
+	let mut payload =
+		build_save_payload(&user_id, &test_id, &session_id, &question.id, None);
+	payload.text = Some("Free text answer".into());
+	repo.query_save(payload).await.unwrap();
+	let answer = fetch_answer(&state.surrealdb_ws, &user_id, &test_id).await;
+	assert!(!answer.is_correct);
+	assert_eq!(answer.points, None);
+	assert_eq!(answer.text.as_deref(), Some("Free text answer"));
+}
+
+#[tokio::test]
+async fn test_query_save_should_reject_mismatched_answer_shape() {
+	let state = create_mock_app_state().await;
+	let (test_id, session_id, question) =
+		seed_typed_question(&state, typed_question_payload("essay", vec![], None)).await;
+	let user_id = Uuid::new_v4().to_string();
+	seed_in_progress_attempt(&state.surrealdb_ws, &user_id, &session_id, &test_id)
+		.await
+		.unwrap();
+	let repo = AnswersRepository::new(&state);
+	let mut payload =
+		build_save_payload(&user_id, &test_id, &session_id, &question.id, None);
+	payload.numeric_value = Some(1.0);
+	let res = repo.query_save(payload).await;
+	assert_eq!(
+		res.unwrap_err().to_string(),
+		"Answer does not match question type"
+	);
+}
+
+#[tokio::test]
+async fn test_essay_grading_should_finalize_and_recompute_result() {
+	use crate::{
+		QuestionsAnswerKeyDto, SessionResultsComputeRequestDto, SessionResultsRepository,
+	};
+	let state = create_mock_app_state().await;
+	let key = QuestionsAnswerKeyDto {
+		points: Some(10),
+		..Default::default()
+	};
+	let (test_id, session_id, question) =
+		seed_typed_question(&state, typed_question_payload("essay", vec![], Some(key)))
+			.await;
+	let user_id = Uuid::new_v4().to_string();
+	seed_in_progress_attempt(&state.surrealdb_ws, &user_id, &session_id, &test_id)
+		.await
+		.unwrap();
+	let repo = AnswersRepository::new(&state);
+	let mut payload =
+		build_save_payload(&user_id, &test_id, &session_id, &question.id, None);
+	payload.text = Some("Photosynthesis converts light into energy".into());
+	repo.query_save(payload).await.unwrap();
+	let answer_id = fetch_answer(&state.surrealdb_ws, &user_id, &test_id)
+		.await
+		.id
+		.id
+		.to_raw();
+	let results = SessionResultsRepository::new(&state);
+	let pending = results
+		.query_compute_result(SessionResultsComputeRequestDto {
+			user_id: user_id.clone(),
+			session_id: session_id.clone(),
+		})
+		.await
+		.unwrap();
+	assert!(pending.is_pending_grading);
+	assert_eq!(pending.tests[0].pending_grading, 1);
+	assert_eq!(pending.passed, None);
+
+	let query = AnswersGradingQueryDto {
+		session_id: Some(session_id.clone()),
+		test_id: None,
+	};
+	let queue = repo
+		.query_grading_queue(query.clone(), Default::default())
+		.await
+		.unwrap();
+	assert_eq!(queue.data.len(), 1);
+	assert_eq!(queue.data[0].id, answer_id);
+	assert_eq!(queue.data[0].max_points, Some(10));
+
+	let graded = repo
+		.query_grade_answer(
+			&answer_id,
+			AnswersGradeRequestDto {
+				points: None,
+				rubric: vec![
+					AnswersRubricScoreDto {
+						criterion: "Accuracy".into(),
+						points: 3,
+					},
+					AnswersRubricScoreDto {
+						criterion: "Clarity".into(),
+						points: 4,
+					},
+				],
+				comment: Some("Mostly correct".into()),
+			},
+			Some("grader@example.com".into()),
+		)
+		.await
+		.unwrap();
+	let grading = graded.grading.expect("Expected draft grading");
+	assert_eq!(grading.points, 7);
+	assert!(!grading.is_final);
+
+	let finalized = repo.query_finalize_grading(&answer_id).await.unwrap();
+	assert!(finalized.grading.unwrap().is_final);
+	let result = results
+		.query_result_by_user(&user_id, &session_id)
+		.await
+		.unwrap();
+	assert!(!result.is_pending_grading);
+	assert_eq!(result.weighted_total, 7.0);
+	assert_eq!(result.passed, Some(true));
+	let queue = repo
+		.query_grading_queue(query, Default::default())
+		.await
+		.unwrap();
+	assert!(queue.data.is_empty());
+	let again = repo.query_finalize_grading(&answer_id).await;
+	assert_eq!(again.unwrap_err().to_string(), "Answer already graded");
+}
+
+#[tokio::test]
+async fn test_query_grade_answer_should_validate_points() {
+	let state = create_mock_app_state().await;
+	let key = crate::QuestionsAnswerKeyDto {
+		points: Some(5),
+		..Default::default()
+	};
+	let (test_id, session_id, question) =
+		seed_typed_question(&state, typed_question_payload("essay", vec![], Some(key)))
+			.await;
+	let user_id = Uuid::new_v4().to_string();
+	seed_in_progress_attempt(&state.surrealdb_ws, &user_id, &session_id, &test_id)
+		.await
+		.unwrap();
+	let repo = AnswersRepository::new(&state);
+	let mut payload =
+		build_save_payload(&user_id, &test_id, &session_id, &question.id, None);
+	payload.text = Some("Answer".into());
+	repo.query_save(payload).await.unwrap();
+	let answer_id = fetch_answer(&state.surrealdb_ws, &user_id, &test_id)
+		.await
+		.id
+		.id
+		.to_raw();
+	let grade = |points: Option<i32>| AnswersGradeRequestDto {
+		points,
+		rubric: vec![],
+		comment: None,
+	};
+	let res = repo
+		.query_grade_answer(&answer_id, grade(Some(6)), None)
+		.await;
+	assert_eq!(
+		res.unwrap_err().to_string(),
+		"Points must not exceed maximum points"
+	);
+	let res = repo.query_grade_answer(&answer_id, grade(None), None).await;
+	assert_eq!(res.unwrap_err().to_string(), "Points are required");
+	let res = repo.query_finalize_grading(&answer_id).await;
+	assert_eq!(res.unwrap_err().to_string(), "Answer has not been graded");
+}
+
+#[tokio::test]
+async fn test_query_save_should_reject_answers_without_attempt() {
+	let state = create_mock_app_state().await;
+	let (test_id, session_id, question_id, option_id) =
+		seed_answer_dependencies(&state.surrealdb_ws).await.unwrap();
+	let user_id = Uuid::new_v4().to_string();
+	let repo = AnswersRepository::new(&state);
+	let result = repo
+		.query_save(build_save_payload(
+			&user_id,
+			&test_id,
+			&session_id,
+			&question_id,
+			Some(&option_id),
+		))
+		.await;
+	assert_eq!(result.unwrap_err().to_string(), "No attempt in progress");
+	let result = repo
+		.query_create(build_payload(
+			&user_id,
+			&test_id,
+			&session_id,
+			&question_id,
+			&option_id,
+		))
+		.await;
+	assert_eq!(result.unwrap_err().to_string(), "No attempt in progress");
+}
+
+#[test]
+fn test_clamp_answered_at_should_keep_answers_inside_the_attempt() {
+	let now = chrono::DateTime::parse_from_rfc3339("2025-05-01T09:00:00Z")
+		.unwrap()
+		.with_timezone(&chrono::Utc);
+	let started_at = Some("2025-05-01T08:00:00+00:00");
+	let clamp = |value| clamp_answered_at(value, started_at, now).unwrap();
+	assert_eq!(clamp(None), "2025-05-01T09:00:00+00:00");
+	assert_eq!(
+		clamp(Some("2025-05-01T08:30:00Z")),
+		"2025-05-01T08:30:00+00:00"
+	);
+	assert_eq!(
+		clamp(Some("2020-01-01T00:00:00Z")),
+		"2025-05-01T08:00:00+00:00"
+	);
+	assert_eq!(
+		clamp(Some("2099-01-01T00:00:00Z")),
+		"2025-05-01T09:00:00+00:00"
+	);
+	assert!(clamp_answered_at(Some("yesterday"), started_at, now).is_err());
+}
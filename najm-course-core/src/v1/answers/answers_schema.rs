@@ -8,9 +8,42 @@ pub struct AnswersSchema {
 	pub test: Thing,
 	pub session: Thing,
 	pub question: Thing,
-	pub option: Thing,
+	pub attempt: Option<Thing>,
+	pub option: Option<Thing>,
+	#[serde(default)]
+	pub options: Vec<Thing>,
+	#[serde(default)]
+	pub statements: Vec<AnswersStatementSchema>,
+	pub numeric_value: Option<f64>,
+	pub text: Option<String>,
 	pub is_deleted: bool,
 	pub is_correct: bool,
+	pub points: Option<i32>,
+	pub grading: Option<AnswersGradingSchema>,
+	pub answered_at: Option<String>,
 	pub created_at: String,
 	pub updated_at: String,
 }
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct AnswersStatementSchema {
+	pub option: Thing,
+	pub value: bool,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct AnswersRubricScoreSchema {
+	pub criterion: String,
+	pub points: i32,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct AnswersGradingSchema {
+	pub points: i32,
+	#[serde(default)]
+	pub rubric: Vec<AnswersRubricScoreSchema>,
+	pub comment: Option<String>,
+	pub graded_by: Option<String>,
+	pub is_final: bool,
+	pub graded_at: String,
+}
@@ -1,31 +1,80 @@
-use super::{AnswersCreateRequestDto, AnswersRepository};
-use crate::{common_response, success_response, AppState, ResponseSuccessDto};
+use super::{
+	AnswersCreateRequestDto, AnswersGradeRequestDto, AnswersGradingQueryDto,
+	AnswersRepository, AnswersSaveRequestDto,
+};
+use crate::{
+	common_response, success_list_response, success_response, validate_request,
+	AppState, MetaRequestDto, ResponseSuccessDto, SessionsViewerDto,
+};
 use axum::{http::StatusCode, response::Response};
 
 pub struct AnswersService;
 
 impl AnswersService {
-	pub async fn get_answer_by_id(state: &AppState, id: String) -> Response {
+	pub async fn get_answer_by_id(
+		state: &AppState,
+		id: String,
+		viewer: SessionsViewerDto,
+	) -> Response {
 		let repo = AnswersRepository::new(state);
-		match repo.query_by_id(&id).await {
-			Ok(answer) => success_response(ResponseSuccessDto { data: answer }),
-			Err(e) => common_response(StatusCode::NOT_FOUND, &e.to_string()),
+		let answer = match repo.query_by_id(&id).await {
+			Ok(answer) => answer,
+			Err(e) => return common_response(StatusCode::NOT_FOUND, &e.to_string()),
+		};
+		let raw = match repo.query_raw_answer_by_id(&id).await {
+			Ok(raw) => raw,
+			Err(e) => return common_response(StatusCode::NOT_FOUND, &e.to_string()),
+		};
+		match repo
+			.query_apply_visibility(
+				answer,
+				&viewer,
+				&raw.user.id.to_raw(),
+				&raw.session.id.to_raw(),
+				&raw.test.id.to_raw(),
+			)
+			.await
+		{
+			Ok(data) => success_response(ResponseSuccessDto { data }),
+			Err(e) => common_response(StatusCode::INTERNAL_SERVER_ERROR, &e.to_string()),
 		}
 	}
 
 	pub async fn create_answer(
 		state: &AppState,
 		payload: AnswersCreateRequestDto,
+		viewer: SessionsViewerDto,
 	) -> Response {
 		let repo = AnswersRepository::new(state);
-		match repo.query_create(payload).await {
+		let (user_id, session_id, test_id) = (
+			payload.user_id.clone(),
+			payload.session_id.clone(),
+			payload.test_id.clone(),
+		);
+		let result = match repo.query_create(payload).await {
+			Ok(data) => {
+				repo
+					.query_apply_visibility(data, &viewer, &user_id, &session_id, &test_id)
+					.await
+			}
+			Err(e) => Err(e),
+		};
+		match result {
 			Ok(data) => success_response(ResponseSuccessDto { data }),
 			Err(e) => {
 				let msg = e.to_string();
 				let status = match msg.as_str() {
-					"Test not found" | "Question not found" | "Option not found" => {
-						StatusCode::BAD_REQUEST
-					}
+					"Test not found"
+					| "Question not found"
+					| "Option not found"
+					| "Invalid date format"
+					| "Answer does not match question type" => StatusCode::BAD_REQUEST,
+					"Attempt deadline has passed"
+					| "No attempt in progress"
+					| "Session has not started"
+					| "Session has ended" => StatusCode::FORBIDDEN,
+					msg if msg.starts_with("Prerequisite") => StatusCode::FORBIDDEN,
+					"Attempt already submitted" => StatusCode::CONFLICT,
 					_ => StatusCode::INTERNAL_SERVER_ERROR,
 				};
 				return common_response(status, &msg);
@@ -33,6 +82,36 @@ impl AnswersService {
 		}
 	}
 
+	pub async fn save_answer(
+		state: &AppState,
+		payload: AnswersSaveRequestDto,
+	) -> Response {
+		if let Err((status, message)) = validate_request(&payload) {
+			return common_response(status, &message);
+		}
+		let repo = AnswersRepository::new(state);
+		match repo.query_save(payload).await {
+			Ok(data) => success_response(ResponseSuccessDto { data }),
+			Err(e) => {
+				let msg = e.to_string();
+				let status = match msg.as_str() {
+					"Question not found"
+					| "Option not found"
+					| "Invalid date format"
+					| "Answer does not match question type" => StatusCode::BAD_REQUEST,
+					"Attempt deadline has passed"
+					| "No attempt in progress"
+					| "Session has not started"
+					| "Session has ended" => StatusCode::FORBIDDEN,
+					msg if msg.starts_with("Prerequisite") => StatusCode::FORBIDDEN,
+					"Attempt already submitted" => StatusCode::CONFLICT,
+					_ => StatusCode::INTERNAL_SERVER_ERROR,
+				};
+				common_response(status, &msg)
+			}
+		}
+	}
+
 	pub async fn delete_answer(state: &AppState, id: String) -> Response {
 		let repo = AnswersRepository::new(state);
 		match repo.query_by_id(&id).await {
@@ -49,4 +128,61 @@ impl AnswersService {
 			Err(e) => common_response(StatusCode::BAD_REQUEST, &e.to_string()),
 		}
 	}
+
+	fn grading_error_status(msg: &str) -> StatusCode {
+		match msg {
+			"Answer not found" | "Question not found" => StatusCode::NOT_FOUND,
+			"Answer already graded" => StatusCode::CONFLICT,
+			_ if msg.contains("must")
+				|| msg.contains("required")
+				|| msg == "Answer is not an essay"
+				|| msg == "Answer has not been graded" =>
+			{
+				StatusCode::BAD_REQUEST
+			}
+			_ => StatusCode::INTERNAL_SERVER_ERROR,
+		}
+	}
+
+	pub async fn get_grading_queue(
+		state: &AppState,
+		query: AnswersGradingQueryDto,
+		meta: MetaRequestDto,
+	) -> Response {
+		let repo = AnswersRepository::new(state);
+		match repo.query_grading_queue(query, meta).await {
+			Ok(data) => success_list_response(data),
+			Err(e) => common_response(StatusCode::BAD_REQUEST, &e.to_string()),
+		}
+	}
+
+	pub async fn grade_answer(
+		state: &AppState,
+		id: String,
+		payload: AnswersGradeRequestDto,
+		graded_by: Option<String>,
+	) -> Response {
+		if let Err((status, message)) = validate_request(&payload) {
+			return common_response(status, &message);
+		}
+		let repo = AnswersRepository::new(state);
+		match repo.query_grade_answer(&id, payload, graded_by).await {
+			Ok(data) => success_response(ResponseSuccessDto { data }),
+			Err(e) => {
+				let msg = e.to_string();
+				common_response(Self::grading_error_status(&msg), &msg)
+			}
+		}
+	}
+
+	pub async fn finalize_grading(state: &AppState, id: String) -> Response {
+		let repo = AnswersRepository::new(state);
+		match repo.query_finalize_grading(&id).await {
+			Ok(data) => success_response(ResponseSuccessDto { data }),
+			Err(e) => {
+				let msg = e.to_string();
+				common_response(Self::grading_error_status(&msg), &msg)
+			}
+		}
+	}
 }
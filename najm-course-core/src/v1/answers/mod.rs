@@ -1,10 +1,11 @@
 use axum::{
-	routing::{delete, get, post},
+	routing::{delete, get, post, put},
 	Router,
 };
 
 pub mod answers_controller;
 pub mod answers_dto;
+pub mod answers_grading;
 pub mod answers_repository;
 pub mod answers_schema;
 pub mod answers_service;
@@ -15,8 +16,12 @@ pub mod answers_repository_test;
 #[cfg(test)]
 pub mod answers_controller_test;
 
+#[cfg(test)]
+pub mod answers_grading_test;
+
 pub use answers_controller::*;
 pub use answers_dto::*;
+pub use answers_grading::*;
 pub use answers_repository::*;
 pub use answers_schema::*;
 pub use answers_service::*;
@@ -25,5 +30,9 @@ pub fn answers_router() -> Router {
 	Router::new()
 		.route("/detail/{id}", get(get_answer_by_id))
 		.route("/create", post(post_create_answer))
+		.route("/save", put(put_save_answer))
 		.route("/delete/{id}", delete(delete_answer))
+		.route("/grading", get(get_grading_queue))
+		.route("/grading/{id}", put(put_grade_answer))
+		.route("/grading/{id}/finalize", post(post_finalize_grading))
 }
@@ -0,0 +1,171 @@
+use super::{
+	AttemptsChannelEventDto, AttemptsChannelRequestDto, AttemptsItemDto,
+	AttemptsRepository, AttemptsSchema, AttemptsStatusEnum,
+};
+use crate::{
+	answers::{AnswersRepository, AnswersSaveRequestDto},
+	AppState, EnrollmentsCandidateDto, EnrollmentsRepository, SessionsBroadcastSchema,
+	SessionsRepository,
+};
+use anyhow::Result;
+use axum::extract::ws::{Message, WebSocket};
+use chrono::Utc;
+use futures_util::{SinkExt, StreamExt};
+use std::time::Duration;
+use surrealdb::Action;
+
+pub const CHANNEL_TICK_SECONDS: u64 = 5;
+
+pub fn attempt_event(attempt: AttemptsSchema) -> AttemptsChannelEventDto {
+	if attempt.status == AttemptsStatusEnum::InProgress.to_string() {
+		let item = AttemptsItemDto::from(attempt);
+		return AttemptsChannelEventDto::RemainingTime {
+			remaining_seconds: item.remaining_seconds,
+			deadline_at: item.deadline_at,
+			server_time: Utc::now().to_rfc3339(),
+		};
+	}
+	if attempt.status == AttemptsStatusEnum::Submitted.to_string() {
+		return AttemptsChannelEventDto::Submitted {
+			submitted_at: attempt.submitted_at,
+		};
+	}
+	AttemptsChannelEventDto::ForcedSubmit {
+		status: attempt.status,
+		reason: "Attempt deadline has passed".into(),
+	}
+}
+
+pub fn broadcast_event(
+	broadcast: SessionsBroadcastSchema,
+	test_id: &str,
+) -> Option<AttemptsChannelEventDto> {
+	let target = broadcast.test.map(|t| t.id.to_raw());
+	if target.as_deref().is_some_and(|t| t != test_id) {
+		return None;
+	}
+	Some(AttemptsChannelEventDto::Broadcast {
+		message: broadcast.message,
+		test_id: target,
+		question_id: broadcast.question.map(|q| q.id.to_raw()),
+		created_at: broadcast.created_at,
+	})
+}
+
+async fn handle_request(
+	state: &AppState,
+	attempt: &AttemptsSchema,
+	candidate: &EnrollmentsCandidateDto,
+	text: &str,
+) -> AttemptsChannelEventDto {
+	let request = match serde_json::from_str::<AttemptsChannelRequestDto>(text) {
+		Ok(request) => request,
+		Err(_) => {
+			return AttemptsChannelEventDto::Error {
+				message: "Invalid message".into(),
+			}
+		}
+	};
+	match request {
+		AttemptsChannelRequestDto::Ping => AttemptsChannelEventDto::Pong,
+		AttemptsChannelRequestDto::SaveAnswer(entry) => {
+			let session_id = attempt.session.id.to_raw();
+			if let Err(e) = EnrollmentsRepository::new(state)
+				.query_ensure_eligible(candidate, &session_id)
+				.await
+			{
+				return AttemptsChannelEventDto::Error {
+					message: e.to_string(),
+				};
+			}
+			let payload = AnswersSaveRequestDto {
+				user_id: attempt.user.id.to_raw(),
+				test_id: attempt.test.id.to_raw(),
+				session_id,
+				question_id: entry.question_id,
+				option_id: entry.option_id,
+				option_ids: entry.option_ids,
+				statements: entry.statements,
+				numeric_value: entry.numeric_value,
+				text: entry.text,
+				answered_at: entry.answered_at,
+			};
+			match AnswersRepository::new(state).query_save(payload).await {
+				Ok(data) => AttemptsChannelEventDto::AnswerSaved(data),
+				Err(e) => AttemptsChannelEventDto::Error {
+					message: e.to_string(),
+				},
+			}
+		}
+	}
+}
+
+/// The ticker also closes attempts whose deadline passed.
+pub async fn serve_attempt_channel(
+	state: AppState,
+	socket: WebSocket,
+	attempt: AttemptsSchema,
+	candidate: EnrollmentsCandidateDto,
+) {
+	if let Err(e) = run_attempt_channel(&state, socket, attempt, candidate).await {
+		log::error!("Attempt channel closed: {}", e);
+	}
+}
+
+async fn run_attempt_channel(
+	state: &AppState,
+	socket: WebSocket,
+	attempt: AttemptsSchema,
+	candidate: EnrollmentsCandidateDto,
+) -> Result<()> {
+	let attempt_id = attempt.id.id.to_raw();
+	let test_id = attempt.test.id.to_raw();
+	let repo = AttemptsRepository::new(state);
+	let mut attempt_changes = repo.query_live_attempt(&attempt_id).await?;
+	let mut broadcasts = SessionsRepository::new(state)
+		.query_live_broadcasts(&attempt.session.id.to_raw())
+		.await?;
+	let (mut sender, mut receiver) = socket.split();
+	let mut ticker = tokio::time::interval(Duration::from_secs(CHANNEL_TICK_SECONDS));
+	loop {
+		let event = tokio::select! {
+			_ = ticker.tick() => {
+				let current = repo.query_raw_attempt_by_id(&attempt_id).await?;
+				attempt_event(repo.query_close_if_expired(current).await?)
+			}
+			Some(change) = attempt_changes.next() => {
+				let change = change?;
+				if change.action != Action::Update {
+					continue;
+				}
+				attempt_event(change.data)
+			}
+			Some(notification) = broadcasts.next() => {
+				let notification = notification?;
+				if notification.action != Action::Create {
+					continue;
+				}
+				match broadcast_event(notification.data, &test_id) {
+					Some(event) => event,
+					None => continue,
+				}
+			}
+			message = receiver.next() => match message {
+				Some(Ok(Message::Text(text))) => {
+					handle_request(state, &attempt, &candidate, text.as_str()).await
+				}
+				Some(Ok(Message::Close(_))) | Some(Err(_)) | None => break,
+				Some(Ok(_)) => continue,
+			},
+		};
+		let is_final = event.is_final();
+		sender
+			.send(Message::Text(serde_json::to_string(&event)?.into()))
+			.await?;
+		if is_final {
+			sender.send(Message::Close(None)).await.ok();
+			break;
+		}
+	}
+	Ok(())
+}
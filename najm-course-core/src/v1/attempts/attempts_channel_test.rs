@@ -0,0 +1,95 @@
+use super::*;
+use crate::SessionsBroadcastSchema;
+use chrono::{Duration, Utc};
+use najm_course_utils::make_thing;
+
+fn attempt(status: AttemptsStatusEnum, deadline_at: String) -> AttemptsSchema {
+	AttemptsSchema {
+		id: make_thing("app_attempts", "attempt"),
+		user: make_thing("app_users", "user"),
+		session: make_thing("app_sessions", "session"),
+		test: make_thing("app_tests", "test"),
+		status: status.to_string(),
+		attempt_number: 1,
+		started_at: "2025-01-01T00:00:00Z".into(),
+		deadline_at,
+		last_heartbeat_at: "2025-01-01T00:00:00Z".into(),
+		submitted_at: None,
+		question_order: vec![],
+		is_deleted: false,
+		created_at: "2025-01-01T00:00:00Z".into(),
+		updated_at: "2025-01-01T00:00:00Z".into(),
+	}
+}
+
+fn broadcast(test_id: Option<&str>) -> SessionsBroadcastSchema {
+	SessionsBroadcastSchema {
+		id: make_thing("app_session_broadcasts", "broadcast"),
+		session: make_thing("app_sessions", "session"),
+		test: test_id.map(|t| make_thing("app_tests", t)),
+		question: Some(make_thing("app_questions", "q12")),
+		message: "Question 12 has been corrected".into(),
+		sent_by: None,
+		created_at: "2025-01-01T00:00:00Z".into(),
+	}
+}
+
+#[test]
+fn test_attempt_event_should_push_remaining_time_while_in_progress() {
+	let deadline = (Utc::now() + Duration::minutes(10)).to_rfc3339();
+	let event = attempt_event(attempt(AttemptsStatusEnum::InProgress, deadline));
+	match event {
+		AttemptsChannelEventDto::RemainingTime {
+			remaining_seconds, ..
+		} => assert!(remaining_seconds > 9 * 60 && remaining_seconds <= 10 * 60),
+		other => panic!("unexpected event {:?}", other),
+	}
+}
+
+#[test]
+fn test_attempt_event_should_close_on_expiry_and_submit() {
+	let deadline = "2025-01-01T01:00:00Z".to_string();
+	let expired =
+		attempt_event(attempt(AttemptsStatusEnum::Expired, deadline.clone()));
+	let submitted = attempt_event(attempt(AttemptsStatusEnum::Submitted, deadline));
+	assert!(matches!(
+		expired,
+		AttemptsChannelEventDto::ForcedSubmit { .. }
+	));
+	assert!(matches!(
+		submitted,
+		AttemptsChannelEventDto::Submitted { .. }
+	));
+	assert!(expired.is_final() && submitted.is_final());
+	let json = serde_json::to_value(&expired).unwrap();
+	assert_eq!(json["type"], "forced_submit");
+}
+
+#[test]
+fn test_broadcast_event_should_skip_other_tests() {
+	assert!(broadcast_event(broadcast(Some("other")), "test").is_none());
+	assert!(broadcast_event(broadcast(Some("test")), "test").is_some());
+	let event = broadcast_event(broadcast(None), "test").unwrap();
+	let json = serde_json::to_value(&event).unwrap();
+	assert_eq!(json["type"], "broadcast");
+	assert_eq!(json["question_id"], "q12");
+}
+
+#[test]
+fn test_channel_request_should_parse_save_answer() {
+	let request: AttemptsChannelRequestDto = serde_json::from_str(
+		r#"{"type":"save_answer","question_id":"q1","option_id":"o1"}"#,
+	)
+	.unwrap();
+	match request {
+		AttemptsChannelRequestDto::SaveAnswer(entry) => {
+			assert_eq!(entry.question_id, "q1");
+			assert_eq!(entry.option_id.as_deref(), Some("o1"));
+		}
+		other => panic!("unexpected request {:?}", other),
+	}
+	assert!(matches!(
+		serde_json::from_str::<AttemptsChannelRequestDto>(r#"{"type":"ping"}"#).unwrap(),
+		AttemptsChannelRequestDto::Ping
+	));
+}
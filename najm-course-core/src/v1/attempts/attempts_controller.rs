@@ -0,0 +1,239 @@
+use super::{
+	AttemptsHistoryDto, AttemptsHistoryQueryDto, AttemptsItemDto, AttemptsService,
+	AttemptsStartRequestDto, AttemptsSubmitRequestDto,
+};
+use crate::{
+	answers::TestsItemAnswersDto, common_response, eligibility_guard,
+	permissions_guard, AppState, PermissionsEnum, ResponseSuccessDto,
+	SessionsViewerDto, UsersItemDtoRaw,
+};
+use axum::{
+	extract::{ws::WebSocketUpgrade, Path, Query},
+	http::StatusCode,
+	response::IntoResponse,
+	Extension, Json,
+};
+
+#[utoipa::path(
+	get,
+	security(
+		("Bearer" = [])
+	),
+	path = "/v1/attempts/history",
+	params(
+		("session_id" = String, Query, description = "Session ID"),
+		("test_id" = String, Query, description = "Test ID"),
+		("user_id" = Option<String>, Query, description = "User ID, defaults to the current user")
+	),
+	responses(
+		(status = 200, description = "Get attempt history of a test", body = ResponseSuccessDto<AttemptsHistoryDto>)
+	),
+	tag = "Attempts"
+)]
+pub async fn get_attempt_history(
+	headers: axum::http::HeaderMap,
+	Extension(state): Extension<AppState>,
+	extensions: axum::http::Extensions,
+	Query(query): Query<AttemptsHistoryQueryDto>,
+) -> impl IntoResponse {
+	match permissions_guard(
+		&headers,
+		state.clone(),
+		vec![PermissionsEnum::ReadListAttempts],
+	)
+	.await
+	{
+		Ok(_) => {
+			let viewer = SessionsViewerDto::from(extensions.get::<UsersItemDtoRaw>());
+			AttemptsService::get_attempt_history(&state, viewer, query).await
+		}
+		Err(response) => response,
+	}
+}
+
+#[utoipa::path(
+	get,
+	security(
+		("Bearer" = [])
+	),
+	path = "/v1/attempts/detail/{id}",
+	params(("id" = String, Path, description = "Attempt ID")),
+	responses(
+		(status = 200, description = "Get attempt by ID", body = ResponseSuccessDto<AttemptsItemDto>)
+	),
+	tag = "Attempts"
+)]
+pub async fn get_attempt_by_id(
+	headers: axum::http::HeaderMap,
+	Extension(state): Extension<AppState>,
+	extensions: axum::http::Extensions,
+	Path(id): Path<String>,
+) -> impl IntoResponse {
+	match permissions_guard(
+		&headers,
+		state.clone(),
+		vec![PermissionsEnum::ReadDetailAttempts],
+	)
+	.await
+	{
+		Ok(_) => {
+			let Some(user) = extensions.get::<UsersItemDtoRaw>() else {
+				return common_response(StatusCode::UNAUTHORIZED, "Unauthorized user");
+			};
+			let viewer = SessionsViewerDto::from(Some(user));
+			AttemptsService::get_attempt_by_id(&state, id, viewer).await
+		}
+		Err(response) => response,
+	}
+}
+
+#[utoipa::path(
+	get,
+	security(
+		("Bearer" = [])
+	),
+	path = "/v1/attempts/channel/{id}",
+	params(
+		("id" = String, Path, description = "Attempt ID"),
+		("access_token" = Option<String>, Query, description = "Bearer token for clients that cannot set headers on a WebSocket handshake")
+	),
+	responses(
+		(status = 101, description = "Upgrade to the real-time attempt channel. Pushes AttemptsChannelEventDto messages and accepts AttemptsChannelRequestDto messages")
+	),
+	tag = "Attempts"
+)]
+pub async fn get_attempt_channel(
+	headers: axum::http::HeaderMap,
+	Extension(state): Extension<AppState>,
+	extensions: axum::http::Extensions,
+	Path(id): Path<String>,
+	ws: WebSocketUpgrade,
+) -> impl IntoResponse {
+	match permissions_guard(
+		&headers,
+		state.clone(),
+		vec![PermissionsEnum::ReadDetailAttempts],
+	)
+	.await
+	{
+		Ok(_) => {
+			let Some(user) = extensions.get::<UsersItemDtoRaw>() else {
+				return common_response(StatusCode::UNAUTHORIZED, "Unauthorized user");
+			};
+			AttemptsService::open_channel(state, id, user, ws).await
+		}
+		Err(response) => response,
+	}
+}
+
+#[utoipa::path(
+	post,
+	security(
+		("Bearer" = [])
+	),
+	path = "/v1/attempts/start",
+	request_body = AttemptsStartRequestDto,
+	responses(
+		(status = 200, description = "Start or resume an attempt", body = ResponseSuccessDto<AttemptsItemDto>)
+	),
+	tag = "Attempts"
+)]
+pub async fn post_start_attempt(
+	headers: axum::http::HeaderMap,
+	Extension(state): Extension<AppState>,
+	extensions: axum::http::Extensions,
+	Json(payload): Json<AttemptsStartRequestDto>,
+) -> impl IntoResponse {
+	match permissions_guard(
+		&headers,
+		state.clone(),
+		vec![PermissionsEnum::CreateAttempts],
+	)
+	.await
+	{
+		Ok(_) => {
+			let Some(user) = extensions.get::<UsersItemDtoRaw>() else {
+				return common_response(StatusCode::UNAUTHORIZED, "Unauthorized user");
+			};
+			if let Err(response) =
+				eligibility_guard(&state, Some(user), &payload.session_id).await
+			{
+				return response;
+			}
+			AttemptsService::start_attempt(&state, user.id.id.to_raw(), payload).await
+		}
+		Err(response) => response,
+	}
+}
+
+#[utoipa::path(
+	put,
+	security(
+		("Bearer" = [])
+	),
+	path = "/v1/attempts/heartbeat/{id}",
+	params(("id" = String, Path, description = "Attempt ID")),
+	responses(
+		(status = 200, description = "Record attempt heartbeat", body = ResponseSuccessDto<AttemptsItemDto>)
+	),
+	tag = "Attempts"
+)]
+pub async fn put_heartbeat_attempt(
+	headers: axum::http::HeaderMap,
+	Extension(state): Extension<AppState>,
+	extensions: axum::http::Extensions,
+	Path(id): Path<String>,
+) -> impl IntoResponse {
+	match permissions_guard(
+		&headers,
+		state.clone(),
+		vec![PermissionsEnum::UpdateAttempts],
+	)
+	.await
+	{
+		Ok(_) => {
+			let Some(user) = extensions.get::<UsersItemDtoRaw>() else {
+				return common_response(StatusCode::UNAUTHORIZED, "Unauthorized user");
+			};
+			AttemptsService::heartbeat_attempt(&state, id, user.id.id.to_raw()).await
+		}
+		Err(response) => response,
+	}
+}
+
+#[utoipa::path(
+	post,
+	security(
+		("Bearer" = [])
+	),
+	path = "/v1/attempts/submit/{id}",
+	params(("id" = String, Path, description = "Attempt ID")),
+	request_body = AttemptsSubmitRequestDto,
+	responses(
+		(status = 200, description = "Submit attempt answers", body = ResponseSuccessDto<TestsItemAnswersDto>)
+	),
+	tag = "Attempts"
+)]
+pub async fn post_submit_attempt(
+	headers: axum::http::HeaderMap,
+	Extension(state): Extension<AppState>,
+	extensions: axum::http::Extensions,
+	Path(id): Path<String>,
+	Json(payload): Json<AttemptsSubmitRequestDto>,
+) -> impl IntoResponse {
+	match permissions_guard(
+		&headers,
+		state.clone(),
+		vec![PermissionsEnum::UpdateAttempts],
+	)
+	.await
+	{
+		Ok(_) => {
+			let Some(user) = extensions.get::<UsersItemDtoRaw>() else {
+				return common_response(StatusCode::UNAUTHORIZED, "Unauthorized user");
+			};
+			AttemptsService::submit_attempt(&state, id, user, payload).await
+		}
+		Err(response) => response,
+	}
+}
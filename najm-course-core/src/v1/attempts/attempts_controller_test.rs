@@ -0,0 +1,239 @@
+use super::{
+	attempts_repository_test::{
+		build_start_payload, build_submit_payload, build_user, seed_attempt_dependencies,
+	},
+	attempts_router, AttemptsRepository,
+};
+use crate::{create_mock_app_state, AppState, PermissionsEnum, UsersItemDtoRaw};
+use axum::{Extension, Router};
+use axum_test::TestServer;
+use najm_course_utils::authorized;
+use surrealdb::Uuid;
+
+fn create_test_app(state: AppState, user_id: &str) -> TestServer {
+	create_test_app_as(state, Some(build_user(user_id, vec![])))
+}
+
+fn create_test_app_as(state: AppState, user: Option<UsersItemDtoRaw>) -> TestServer {
+	let app = Router::new()
+		.nest("/v1/attempts", attempts_router())
+		.layer(Extension(state));
+	let app = match user {
+		Some(user) => app.layer(Extension(user)),
+		None => app,
+	};
+	TestServer::new(app).unwrap()
+}
+
+#[tokio::test]
+async fn test_post_start_attempt_should_return_200() {
+	let state = create_mock_app_state().await;
+	let fixture = seed_attempt_dependencies(&state.surrealdb_ws, -10, 120, Some(30))
+		.await
+		.unwrap();
+	let server = create_test_app(state, &fixture.user_id);
+	let res = authorized(
+		&server,
+		"POST",
+		"/v1/attempts/start",
+		vec![&PermissionsEnum::CreateAttempts.to_string()],
+		Some(&build_start_payload(&fixture)),
+	)
+	.await;
+	assert_eq!(res.status_code(), 200);
+}
+
+#[tokio::test]
+async fn test_post_start_attempt_should_return_403_before_window() {
+	let state = create_mock_app_state().await;
+	let fixture = seed_attempt_dependencies(&state.surrealdb_ws, 60, 120, None)
+		.await
+		.unwrap();
+	let server = create_test_app(state, &fixture.user_id);
+	let res = authorized(
+		&server,
+		"POST",
+		"/v1/attempts/start",
+		vec![&PermissionsEnum::CreateAttempts.to_string()],
+		Some(&build_start_payload(&fixture)),
+	)
+	.await;
+	assert_eq!(res.status_code(), 403);
+}
+
+#[tokio::test]
+async fn test_put_heartbeat_attempt_should_return_200() {
+	let state = create_mock_app_state().await;
+	let fixture = seed_attempt_dependencies(&state.surrealdb_ws, -10, 120, Some(30))
+		.await
+		.unwrap();
+	let attempt = AttemptsRepository::new(&state)
+		.query_start_attempt(&fixture.user_id, build_start_payload(&fixture))
+		.await
+		.unwrap();
+	let server = create_test_app(state, &fixture.user_id);
+	let res = authorized::<()>(
+		&server,
+		"PUT",
+		&format!("/v1/attempts/heartbeat/{}", attempt.id),
+		vec![&PermissionsEnum::UpdateAttempts.to_string()],
+		None,
+	)
+	.await;
+	assert_eq!(res.status_code(), 200);
+}
+
+#[tokio::test]
+async fn test_post_submit_attempt_should_return_409_when_submitted_twice() {
+	let state = create_mock_app_state().await;
+	let fixture = seed_attempt_dependencies(&state.surrealdb_ws, -10, 120, Some(30))
+		.await
+		.unwrap();
+	let attempt = AttemptsRepository::new(&state)
+		.query_start_attempt(&fixture.user_id, build_start_payload(&fixture))
+		.await
+		.unwrap();
+	let server = create_test_app(state, &fixture.user_id);
+	let url = format!("/v1/attempts/submit/{}", attempt.id);
+	let first = authorized(
+		&server,
+		"POST",
+		&url,
+		vec![&PermissionsEnum::UpdateAttempts.to_string()],
+		Some(&build_submit_payload(&fixture)),
+	)
+	.await;
+	assert_eq!(first.status_code(), 200);
+	let second = authorized(
+		&server,
+		"POST",
+		&url,
+		vec![&PermissionsEnum::UpdateAttempts.to_string()],
+		Some(&build_submit_payload(&fixture)),
+	)
+	.await;
+	assert_eq!(second.status_code(), 409);
+}
+
+#[tokio::test]
+async fn test_get_attempt_by_id_should_return_404_if_not_found() {
+	let state = create_mock_app_state().await;
+	let server = create_test_app(state, &Uuid::new_v4().to_string());
+	let res = authorized::<()>(
+		&server,
+		"GET",
+		"/v1/attempts/detail/non-existent-id",
+		vec![&PermissionsEnum::ReadDetailAttempts.to_string()],
+		None,
+	)
+	.await;
+	assert_eq!(res.status_code(), 404);
+}
+
+#[tokio::test]
+async fn test_put_heartbeat_attempt_should_return_403_for_another_user() {
+	let state = create_mock_app_state().await;
+	let fixture = seed_attempt_dependencies(&state.surrealdb_ws, -10, 120, Some(30))
+		.await
+		.unwrap();
+	let attempt = AttemptsRepository::new(&state)
+		.query_start_attempt(&fixture.user_id, build_start_payload(&fixture))
+		.await
+		.unwrap();
+	let server = create_test_app(state, &Uuid::new_v4().to_string());
+	let res = authorized::<()>(
+		&server,
+		"PUT",
+		&format!("/v1/attempts/heartbeat/{}", attempt.id),
+		vec![&PermissionsEnum::UpdateAttempts.to_string()],
+		None,
+	)
+	.await;
+	assert_eq!(res.status_code(), 403);
+}
+
+#[tokio::test]
+async fn test_post_start_attempt_should_return_401_without_user() {
+	let state = create_mock_app_state().await;
+	let fixture = seed_attempt_dependencies(&state.surrealdb_ws, -10, 120, Some(30))
+		.await
+		.unwrap();
+	let server = create_test_app_as(state, None);
+	let res = authorized(
+		&server,
+		"POST",
+		"/v1/attempts/start",
+		vec![&PermissionsEnum::CreateAttempts.to_string()],
+		Some(&build_start_payload(&fixture)),
+	)
+	.await;
+	assert_eq!(res.status_code(), 401);
+}
+
+#[tokio::test]
+async fn test_post_submit_attempt_should_return_403_when_not_eligible() {
+	let state = create_mock_app_state().await;
+	let fixture = seed_attempt_dependencies(&state.surrealdb_ws, -10, 120, Some(30))
+		.await
+		.unwrap();
+	let attempt = AttemptsRepository::new(&state)
+		.query_start_attempt(&fixture.user_id, build_start_payload(&fixture))
+		.await
+		.unwrap();
+	let mut user = build_user(&fixture.user_id, vec![]);
+	user.student_type = "TNI".into();
+	let server = create_test_app_as(state, Some(user));
+	let res = authorized(
+		&server,
+		"POST",
+		&format!("/v1/attempts/submit/{}", attempt.id),
+		vec![&PermissionsEnum::UpdateAttempts.to_string()],
+		Some(&build_submit_payload(&fixture)),
+	)
+	.await;
+	assert_eq!(res.status_code(), 403);
+}
+
+#[tokio::test]
+async fn test_get_attempt_history_should_let_reviewers_pick_the_user() {
+	let state = create_mock_app_state().await;
+	let fixture = seed_attempt_dependencies(&state.surrealdb_ws, -10, 120, Some(30))
+		.await
+		.unwrap();
+	AttemptsRepository::new(&state)
+		.query_start_attempt(&fixture.user_id, build_start_payload(&fixture))
+		.await
+		.unwrap();
+	let url = format!(
+		"/v1/attempts/history?session_id={}&test_id={}&user_id={}",
+		fixture.session_id, fixture.test_id, fixture.user_id
+	);
+	let reviewer = build_user(
+		&Uuid::new_v4().to_string(),
+		vec![PermissionsEnum::ReviewAnswerKeys],
+	);
+	let server = create_test_app_as(state.clone(), Some(reviewer));
+	let res = authorized::<()>(
+		&server,
+		"GET",
+		&url,
+		vec![&PermissionsEnum::ReadListAttempts.to_string()],
+		None,
+	)
+	.await;
+	assert_eq!(res.status_code(), 200);
+	let body = res.json::<serde_json::Value>();
+	assert_eq!(body["data"]["attempts"].as_array().unwrap().len(), 1);
+	let server = create_test_app(state, &Uuid::new_v4().to_string());
+	let res = authorized::<()>(
+		&server,
+		"GET",
+		&url,
+		vec![&PermissionsEnum::ReadListAttempts.to_string()],
+		None,
+	)
+	.await;
+	assert_eq!(res.status_code(), 200);
+	let body = res.json::<serde_json::Value>();
+	assert!(body["data"]["attempts"].as_array().unwrap().is_empty());
+}
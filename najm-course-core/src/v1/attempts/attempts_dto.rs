@@ -0,0 +1,196 @@
+use super::{AttemptsQuestionOrderSchema, AttemptsSchema, AttemptsStatusEnum};
+use crate::answers::{AnswerEntryDto, AnswersSaveResponseDto};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+use validator::Validate;
+
+#[derive(Clone, Debug, Serialize, Deserialize, Validate, ToSchema)]
+pub struct AttemptsStartRequestDto {
+	#[validate(length(min = 1))]
+	#[schema(example = "uuid")]
+	pub session_id: String,
+
+	#[validate(length(min = 1))]
+	#[schema(example = "uuid")]
+	pub test_id: String,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, Validate, ToSchema)]
+pub struct AttemptsSubmitRequestDto {
+	#[validate(length(min = 1))]
+	pub answers: Vec<AnswerEntryDto>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, Validate, ToSchema)]
+pub struct AttemptsHistoryQueryDto {
+	#[validate(length(min = 1))]
+	pub session_id: String,
+
+	#[validate(length(min = 1))]
+	pub test_id: String,
+
+	pub user_id: Option<String>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, ToSchema)]
+pub struct AttemptsHistoryItemDto {
+	pub id: String,
+	#[schema(example = 1)]
+	pub attempt_number: u32,
+	#[schema(example = "submitted")]
+	pub status: String,
+	pub started_at: String,
+	pub submitted_at: Option<String>,
+	#[schema(example = 80)]
+	pub score: Option<i32>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, ToSchema)]
+pub struct AttemptsHistoryDto {
+	pub session_id: String,
+	pub test_id: String,
+	#[schema(example = 3)]
+	pub max_attempts: u32,
+	#[schema(example = "best")]
+	pub score_selection: String,
+	#[schema(example = 80)]
+	pub selected_score: Option<i32>,
+	pub next_attempt_at: Option<String>,
+	pub attempts: Vec<AttemptsHistoryItemDto>,
+}
+
+impl AttemptsHistoryDto {
+	pub fn without_scores(self) -> Self {
+		Self {
+			selected_score: None,
+			attempts: self
+				.attempts
+				.into_iter()
+				.map(|a| AttemptsHistoryItemDto { score: None, ..a })
+				.collect(),
+			..self
+		}
+	}
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, ToSchema)]
+pub struct AttemptsQuestionOrderDto {
+	#[schema(example = "uuid")]
+	pub question_id: String,
+	pub option_ids: Vec<String>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, ToSchema)]
+pub struct AttemptsItemDto {
+	pub id: String,
+	pub user_id: String,
+	pub session_id: String,
+	pub test_id: String,
+	#[schema(example = "in_progress")]
+	pub status: String,
+	#[schema(example = 1)]
+	pub attempt_number: u32,
+	pub started_at: String,
+	pub deadline_at: String,
+	pub last_heartbeat_at: String,
+	pub submitted_at: Option<String>,
+	pub remaining_seconds: i64,
+	pub question_order: Vec<AttemptsQuestionOrderDto>,
+	pub created_at: String,
+	pub updated_at: String,
+}
+
+impl From<AttemptsSchema> for AttemptsItemDto {
+	fn from(value: AttemptsSchema) -> Self {
+		let remaining_seconds =
+			if value.status == AttemptsStatusEnum::InProgress.to_string() {
+				DateTime::parse_from_rfc3339(&value.deadline_at)
+					.map(|deadline| {
+						(deadline.with_timezone(&Utc) - Utc::now())
+							.num_seconds()
+							.max(0)
+					})
+					.unwrap_or(0)
+			} else {
+				0
+			};
+		Self {
+			id: value.id.id.to_raw(),
+			user_id: value.user.id.to_raw(),
+			session_id: value.session.id.to_raw(),
+			test_id: value.test.id.to_raw(),
+			status: value.status,
+			attempt_number: value.attempt_number,
+			started_at: value.started_at,
+			deadline_at: value.deadline_at,
+			last_heartbeat_at: value.last_heartbeat_at,
+			submitted_at: value.submitted_at,
+			remaining_seconds,
+			question_order: value
+				.question_order
+				.into_iter()
+				.map(AttemptsQuestionOrderDto::from)
+				.collect(),
+			created_at: value.created_at,
+			updated_at: value.updated_at,
+		}
+	}
+}
+
+impl From<AttemptsQuestionOrderSchema> for AttemptsQuestionOrderDto {
+	fn from(value: AttemptsQuestionOrderSchema) -> Self {
+		Self {
+			question_id: value.question,
+			option_ids: value.options,
+		}
+	}
+}
+
+/// Messages a client sends over the attempt channel.
+#[derive(Clone, Debug, Serialize, Deserialize, ToSchema)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum AttemptsChannelRequestDto {
+	SaveAnswer(AnswerEntryDto),
+	Ping,
+}
+
+/// Messages the server pushes over the attempt channel.
+#[derive(Clone, Debug, Serialize, Deserialize, ToSchema)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum AttemptsChannelEventDto {
+	RemainingTime {
+		remaining_seconds: i64,
+		deadline_at: String,
+		server_time: String,
+	},
+	ForcedSubmit {
+		status: String,
+		reason: String,
+	},
+	Submitted {
+		submitted_at: Option<String>,
+	},
+	Broadcast {
+		message: String,
+		test_id: Option<String>,
+		question_id: Option<String>,
+		created_at: String,
+	},
+	AnswerSaved(AnswersSaveResponseDto),
+	Pong,
+	Error {
+		message: String,
+	},
+}
+
+impl AttemptsChannelEventDto {
+	/// Whether the channel closes after sending this event.
+	pub fn is_final(&self) -> bool {
+		matches!(
+			self,
+			AttemptsChannelEventDto::ForcedSubmit { .. }
+				| AttemptsChannelEventDto::Submitted { .. }
+		)
+	}
+}
@@ -0,0 +1,19 @@
+use std::fmt;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum AttemptsStatusEnum {
+	InProgress,
+	Submitted,
+	Expired,
+}
+
+impl fmt::Display for AttemptsStatusEnum {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		let status_str = match self {
+			AttemptsStatusEnum::InProgress => "in_progress",
+			AttemptsStatusEnum::Submitted => "submitted",
+			AttemptsStatusEnum::Expired => "expired",
+		};
+		write!(f, "{}", status_str)
+	}
+}
@@ -0,0 +1,529 @@
+use super::{
+	ensure_retake_allowed, next_attempt_at, select_attempt_score, AttemptsHistoryDto,
+	AttemptsHistoryItemDto, AttemptsHistoryQueryDto, AttemptsItemDto, AttemptsSchema,
+	AttemptsStartRequestDto, AttemptsStatusEnum, AttemptsSubmitRequestDto,
+};
+use crate::{
+	answers::{AnswersCreateRequestDto, AnswersRepository, TestsItemAnswersDto},
+	test_lock, AppState, SessionResultsComputeRequestDto, SessionResultsRepository,
+	SessionsRepository, SessionsScoreSelectionEnum, TestSessionsLockStatusEnum,
+	TestSessionsPrerequisiteSchema, TestSessionsSchema,
+};
+use anyhow::{bail, Result};
+use chrono::{DateTime, Duration, Utc};
+use najm_course_libs::{Env, ResourceEnum};
+use najm_course_utils::{get_id, get_iso_date, make_thing};
+use serde_json::json;
+use validator::Validate;
+
+pub const ATTEMPT_GRACE_SECONDS: i64 = 30;
+
+pub struct AttemptsRepository<'a> {
+	state: &'a AppState,
+}
+
+fn parse_date(value: &str) -> Result<DateTime<Utc>> {
+	match DateTime::parse_from_rfc3339(value) {
+		Ok(date) => Ok(date.with_timezone(&Utc)),
+		Err(_) => bail!("Invalid date format"),
+	}
+}
+
+fn ensure_session_window(
+	test: &TestSessionsSchema,
+	now: DateTime<Utc>,
+) -> Result<()> {
+	if now < parse_date(&test.start_date)? {
+		bail!("Session has not started");
+	}
+	if now >= parse_date(&test.end_date)? {
+		bail!("Session has ended");
+	}
+	Ok(())
+}
+
+/// Fails unless the attempt was started by `user_id`. `None` skips the check
+/// for callers allowed to see every attempt.
+fn ensure_owner(attempt: &AttemptsSchema, user_id: Option<&str>) -> Result<()> {
+	if user_id.is_some_and(|user_id| attempt.user.id.to_raw() != user_id) {
+		bail!("Attempt does not belong to user");
+	}
+	Ok(())
+}
+
+impl<'a> AttemptsRepository<'a> {
+	pub fn new(state: &'a AppState) -> Self {
+		Self { state }
+	}
+
+	pub async fn query_raw_attempt_by_id(&self, id: &str) -> Result<AttemptsSchema> {
+		let db = &self.state.surrealdb_ws;
+		let attempt: Option<AttemptsSchema> =
+			db.select((ResourceEnum::Attempts.to_string(), id)).await?;
+		match attempt {
+			Some(a) if !a.is_deleted => Ok(a),
+			_ => bail!("Attempt not found"),
+		}
+	}
+
+	pub async fn query_attempt_by_user(
+		&self,
+		user_id: &str,
+		session_id: &str,
+		test_id: &str,
+	) -> Result<Option<AttemptsSchema>> {
+		let db = &self.state.surrealdb_ws;
+		let attempts: Vec<AttemptsSchema> = db
+			.query(format!(
+				"SELECT * FROM {} WHERE user = app_users:⟨{}⟩ AND session = app_sessions:⟨{}⟩ AND test = app_tests:⟨{}⟩ AND is_deleted = false ORDER BY created_at DESC LIMIT 1",
+				ResourceEnum::Attempts,
+				user_id,
+				session_id,
+				test_id
+			))
+			.await?
+			.take(0)?;
+		Ok(attempts.into_iter().next())
+	}
+
+	/// Resolves the attempt a channel is opened for. Only the owner can open
+	/// it, and only while the attempt is in progress.
+	pub async fn query_open_channel(
+		&self,
+		id: &str,
+		user_id: Option<&str>,
+	) -> Result<AttemptsSchema> {
+		let attempt = self.query_raw_attempt_by_id(id).await?;
+		ensure_owner(&attempt, user_id)?;
+		let attempt = self.query_close_if_expired(attempt).await?;
+		if attempt.status != AttemptsStatusEnum::InProgress.to_string() {
+			bail!("Attempt is not in progress");
+		}
+		Ok(attempt)
+	}
+
+	/// Live query on one attempt. Dropping the stream kills the live query.
+	pub async fn query_live_attempt(
+		&self,
+		id: &str,
+	) -> Result<surrealdb::method::Stream<Option<AttemptsSchema>>> {
+		let db = &self.state.surrealdb_ws;
+		Ok(
+			db.select((ResourceEnum::Attempts.to_string(), id))
+				.live()
+				.await?,
+		)
+	}
+
+	pub async fn query_attempt_history(
+		&self,
+		user_id: &str,
+		session_id: &str,
+		test_id: &str,
+	) -> Result<Vec<AttemptsSchema>> {
+		let db = &self.state.surrealdb_ws;
+		let attempts: Vec<AttemptsSchema> = db
+			.query(format!(
+				"SELECT * FROM {} WHERE user = app_users:⟨{}⟩ AND session = app_sessions:⟨{}⟩ AND test = app_tests:⟨{}⟩ AND is_deleted = false ORDER BY created_at ASC",
+				ResourceEnum::Attempts,
+				user_id,
+				session_id,
+				test_id
+			))
+			.await?
+			.take(0)?;
+		Ok(attempts)
+	}
+
+	pub async fn query_update_status(
+		&self,
+		id: &str,
+		status: AttemptsStatusEnum,
+	) -> Result<AttemptsSchema> {
+		let db = &self.state.surrealdb_ws;
+		let now = get_iso_date();
+		let submitted_at = match status {
+			AttemptsStatusEnum::InProgress => None,
+			_ => Some(now.clone()),
+		};
+		let attempt_thing = make_thing(&ResourceEnum::Attempts.to_string(), id);
+		let record_key = get_id(&attempt_thing)?;
+		let updated: Option<AttemptsSchema> = db
+			.update(record_key)
+			.merge(json!({
+				"status": status.to_string(),
+				"submitted_at": submitted_at,
+				"updated_at": now,
+			}))
+			.await?;
+		match updated {
+			Some(a) => Ok(a),
+			None => bail!("Failed to update attempt"),
+		}
+	}
+
+	/// Flips an in-progress attempt to submitted in one conditional update so
+	/// concurrent submits cannot both win. `None` when it was not in progress.
+	pub async fn query_claim_submission(
+		&self,
+		id: &str,
+	) -> Result<Option<AttemptsSchema>> {
+		let db = &self.state.surrealdb_ws;
+		let claimed: Vec<AttemptsSchema> = db
+			.query("UPDATE type::thing($table, $id) SET status = $submitted, submitted_at = $now, updated_at = $now WHERE status = $in_progress RETURN AFTER")
+			.bind(("table", ResourceEnum::Attempts.to_string()))
+			.bind(("id", id.to_string()))
+			.bind(("submitted", AttemptsStatusEnum::Submitted.to_string()))
+			.bind(("in_progress", AttemptsStatusEnum::InProgress.to_string()))
+			.bind(("now", get_iso_date()))
+			.await?
+			.take(0)?;
+		Ok(claimed.into_iter().next())
+	}
+
+	pub async fn query_close_if_expired(
+		&self,
+		attempt: AttemptsSchema,
+	) -> Result<AttemptsSchema> {
+		if attempt.status != AttemptsStatusEnum::InProgress.to_string() {
+			return Ok(attempt);
+		}
+		let deadline = parse_date(&attempt.deadline_at)?;
+		if Utc::now() <= deadline + Duration::seconds(ATTEMPT_GRACE_SECONDS) {
+			return Ok(attempt);
+		}
+		self
+			.query_update_status(&attempt.id.id.to_raw(), AttemptsStatusEnum::Expired)
+			.await
+	}
+
+	pub async fn query_expire_overdue(&self) -> Result<usize> {
+		let db = &self.state.surrealdb_ws;
+		let attempts: Vec<AttemptsSchema> = db
+			.query(format!(
+				"SELECT * FROM {} WHERE status = '{}' AND is_deleted = false",
+				ResourceEnum::Attempts,
+				AttemptsStatusEnum::InProgress
+			))
+			.await?
+			.take(0)?;
+		let mut expired = 0;
+		for attempt in attempts {
+			let closed = self.query_close_if_expired(attempt).await?;
+			if closed.status == AttemptsStatusEnum::Expired.to_string() {
+				expired += 1;
+			}
+		}
+		Ok(expired)
+	}
+
+	pub async fn query_ensure_accepting_answers(
+		&self,
+		user_id: &str,
+		session_id: &str,
+		test_id: &str,
+	) -> Result<Option<AttemptsSchema>> {
+		let Some(attempt) = self
+			.query_attempt_by_user(user_id, session_id, test_id)
+			.await?
+		else {
+			if !Env::new().allow_legacy_answers {
+				bail!("No attempt in progress");
+			}
+			let session = SessionsRepository::new(self.state)
+				.query_raw_session_by_id(session_id)
+				.await?;
+			let Some(test) = session.tests.iter().find(|t| t.test.id.to_raw() == test_id)
+			else {
+				bail!("Test not found in session");
+			};
+			ensure_session_window(test, Utc::now())?;
+			self
+				.query_ensure_unlocked(user_id, session_id, test_id)
+				.await?;
+			return Ok(None);
+		};
+		let attempt = self.query_close_if_expired(attempt).await?;
+		if attempt.status == AttemptsStatusEnum::Submitted.to_string() {
+			bail!("Attempt already submitted");
+		}
+		if attempt.status == AttemptsStatusEnum::Expired.to_string() {
+			bail!("Attempt deadline has passed");
+		}
+		Ok(Some(attempt))
+	}
+
+	/// Fails while the test's prerequisite has not been submitted by the user or
+	/// the configured gap window is not open.
+	pub async fn query_ensure_unlocked(
+		&self,
+		user_id: &str,
+		session_id: &str,
+		test_id: &str,
+	) -> Result<()> {
+		let db = &self.state.surrealdb_ws;
+		let prerequisites: Vec<Option<TestSessionsPrerequisiteSchema>> = db
+			.query(format!(
+				"SELECT VALUE tests[WHERE test = app_tests:⟨{}⟩][0].prerequisite FROM {}:⟨{}⟩",
+				test_id,
+				ResourceEnum::Sessions,
+				session_id
+			))
+			.await?
+			.take(0)?;
+		let Some(prerequisite) = prerequisites.into_iter().flatten().next() else {
+			return Ok(());
+		};
+		let predecessor = self
+			.query_attempt_history(user_id, session_id, &prerequisite.test.id.to_raw())
+			.await?
+			.into_iter()
+			.find(|a| a.submitted_at.is_some());
+		let lock =
+			test_lock(Some(&prerequisite), predecessor.as_ref(), false, Utc::now());
+		if lock.status != TestSessionsLockStatusEnum::Unlocked.to_string() {
+			bail!(lock.reason.unwrap_or("Test is locked".into()));
+		}
+		Ok(())
+	}
+
+	pub async fn query_attempt_by_id(
+		&self,
+		id: &str,
+		user_id: Option<&str>,
+	) -> Result<AttemptsItemDto> {
+		let attempt = self.query_raw_attempt_by_id(id).await?;
+		ensure_owner(&attempt, user_id)?;
+		let attempt = self.query_close_if_expired(attempt).await?;
+		Ok(AttemptsItemDto::from(attempt))
+	}
+
+	pub async fn query_start_attempt(
+		&self,
+		user_id: &str,
+		payload: AttemptsStartRequestDto,
+	) -> Result<AttemptsItemDto> {
+		payload.validate()?;
+		let db = &self.state.surrealdb_ws;
+		let session_repo = SessionsRepository::new(self.state);
+		let session = session_repo
+			.query_raw_session_by_id(&payload.session_id)
+			.await?;
+		if !session.is_active {
+			bail!("Session is not active");
+		}
+		let Some(test) = session
+			.tests
+			.iter()
+			.find(|t| t.test.id.to_raw() == payload.test_id)
+		else {
+			bail!("Test not found in session");
+		};
+		let mut history = self
+			.query_attempt_history(user_id, &payload.session_id, &payload.test_id)
+			.await?;
+		let now = Utc::now();
+		if let Some(existing) = history.pop() {
+			let existing = self.query_close_if_expired(existing).await?;
+			if existing.status == AttemptsStatusEnum::InProgress.to_string() {
+				return Ok(AttemptsItemDto::from(existing));
+			}
+			history.push(existing);
+			let policy = session.retake_policy.clone().unwrap_or_default();
+			ensure_retake_allowed(&policy, &history, now)?;
+		}
+		self
+			.query_ensure_unlocked(user_id, &payload.session_id, &payload.test_id)
+			.await?;
+		ensure_session_window(test, now)?;
+		let end_date = parse_date(&test.end_date)?;
+		let deadline = match test.duration_minutes {
+			Some(minutes) => (now + Duration::minutes(minutes as i64)).min(end_date),
+			None => end_date,
+		};
+		let question_order = session_repo
+			.query_session_by_id(&payload.session_id)
+			.await?
+			.tests
+			.into_iter()
+			.find(|t| t.test.id == payload.test_id)
+			.map(|t| {
+				t.arranged_for(user_id, &payload.session_id)
+					.question_order()
+			})
+			.unwrap_or_default();
+		let id = surrealdb::Uuid::new_v4().to_string();
+		let started_at = now.to_rfc3339();
+		let attempt = AttemptsSchema {
+			id: make_thing(&ResourceEnum::Attempts.to_string(), &id),
+			user: make_thing(&ResourceEnum::Users.to_string(), user_id),
+			session: make_thing(&ResourceEnum::Sessions.to_string(), &payload.session_id),
+			test: make_thing(&ResourceEnum::Tests.to_string(), &payload.test_id),
+			status: AttemptsStatusEnum::InProgress.to_string(),
+			attempt_number: history.len() as u32 + 1,
+			started_at: started_at.clone(),
+			deadline_at: deadline.to_rfc3339(),
+			last_heartbeat_at: started_at.clone(),
+			submitted_at: None,
+			question_order,
+			is_deleted: false,
+			created_at: started_at.clone(),
+			updated_at: started_at,
+		};
+		let created: Option<AttemptsSchema> = db
+			.create((ResourceEnum::Attempts.to_string(), &id))
+			.content(attempt)
+			.await?;
+		match created {
+			Some(a) => Ok(AttemptsItemDto::from(a)),
+			None => bail!("Failed to start attempt"),
+		}
+	}
+
+	pub async fn query_heartbeat_attempt(
+		&self,
+		id: &str,
+		user_id: &str,
+	) -> Result<AttemptsItemDto> {
+		let db = &self.state.surrealdb_ws;
+		let attempt = self.query_raw_attempt_by_id(id).await?;
+		ensure_owner(&attempt, Some(user_id))?;
+		let attempt = self.query_close_if_expired(attempt).await?;
+		if attempt.status != AttemptsStatusEnum::InProgress.to_string() {
+			bail!("Attempt is not in progress");
+		}
+		let now = get_iso_date();
+		let attempt_thing = make_thing(&ResourceEnum::Attempts.to_string(), id);
+		let record_key = get_id(&attempt_thing)?;
+		let updated: Option<AttemptsSchema> = db
+			.update(record_key)
+			.merge(json!({ "last_heartbeat_at": now, "updated_at": now }))
+			.await?;
+		match updated {
+			Some(a) => Ok(AttemptsItemDto::from(a)),
+			None => bail!("Failed to update attempt"),
+		}
+	}
+
+	pub async fn query_submit_attempt(
+		&self,
+		id: &str,
+		user_id: &str,
+		payload: AttemptsSubmitRequestDto,
+	) -> Result<TestsItemAnswersDto> {
+		payload.validate()?;
+		let attempt = self.query_raw_attempt_by_id(id).await?;
+		ensure_owner(&attempt, Some(user_id))?;
+		let attempt = self.query_close_if_expired(attempt).await?;
+		if attempt.status == AttemptsStatusEnum::Submitted.to_string() {
+			bail!("Attempt already submitted");
+		}
+		if attempt.status == AttemptsStatusEnum::Expired.to_string() {
+			bail!("Attempt deadline has passed");
+		}
+		let Some(attempt) = self.query_claim_submission(id).await? else {
+			bail!("Attempt already submitted");
+		};
+		let result = match AnswersRepository::new(self.state)
+			.query_create_for_attempt(
+				AnswersCreateRequestDto {
+					user_id: attempt.user.id.to_raw(),
+					test_id: attempt.test.id.to_raw(),
+					session_id: attempt.session.id.to_raw(),
+					answers: payload.answers,
+				},
+				Some(&attempt),
+			)
+			.await
+		{
+			Ok(result) => result,
+			Err(e) => {
+				self
+					.query_update_status(id, AttemptsStatusEnum::InProgress)
+					.await?;
+				return Err(e);
+			}
+		};
+		if let Err(e) = SessionResultsRepository::new(self.state)
+			.query_compute_result(SessionResultsComputeRequestDto {
+				user_id: attempt.user.id.to_raw(),
+				session_id: attempt.session.id.to_raw(),
+			})
+			.await
+		{
+			log::error!("Failed to compute session result: {}", e);
+		}
+		Ok(result)
+	}
+
+	pub async fn query_history(
+		&self,
+		user_id: &str,
+		query: AttemptsHistoryQueryDto,
+	) -> Result<AttemptsHistoryDto> {
+		query.validate()?;
+		let session = SessionsRepository::new(self.state)
+			.query_raw_session_by_id(&query.session_id)
+			.await?;
+		if !session
+			.tests
+			.iter()
+			.any(|t| t.test.id.to_raw() == query.test_id)
+		{
+			bail!("Test not found in session");
+		}
+		let policy = session.retake_policy.unwrap_or_default();
+		let selection: SessionsScoreSelectionEnum = policy.score_selection.parse()?;
+		let mut history = vec![];
+		for attempt in self
+			.query_attempt_history(user_id, &query.session_id, &query.test_id)
+			.await?
+		{
+			history.push(self.query_close_if_expired(attempt).await?);
+		}
+		let answers_repo = AnswersRepository::new(self.state);
+		let mut attempts = Vec::with_capacity(history.len());
+		let mut scored = vec![];
+		for attempt in &history {
+			let score = if attempt.status == AttemptsStatusEnum::InProgress.to_string() {
+				None
+			} else {
+				match answers_repo
+					.query_test_with_attempt_answers(
+						&query.session_id,
+						&query.test_id,
+						user_id,
+						Some(attempt.clone()),
+					)
+					.await
+				{
+					Ok(data) => {
+						let score = data.score;
+						scored.push(data);
+						Some(score)
+					}
+					Err(e) if e.to_string() == "No answers found" => None,
+					Err(e) => return Err(e),
+				}
+			};
+			attempts.push(AttemptsHistoryItemDto {
+				id: attempt.id.id.to_raw(),
+				attempt_number: attempt.attempt_number,
+				status: attempt.status.clone(),
+				started_at: attempt.started_at.clone(),
+				submitted_at: attempt.submitted_at.clone(),
+				score,
+			});
+		}
+		Ok(AttemptsHistoryDto {
+			session_id: query.session_id,
+			test_id: query.test_id,
+			max_attempts: policy.max_attempts,
+			score_selection: selection.to_string(),
+			selected_score: select_attempt_score(selection, scored).map(|s| s.score),
+			next_attempt_at: next_attempt_at(&policy, &history)
+				.map(|d| d.max(Utc::now()).to_rfc3339()),
+			attempts,
+		})
+	}
+}
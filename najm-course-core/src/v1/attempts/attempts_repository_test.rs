@@ -0,0 +1,593 @@
+use super::*;
+use crate::{
+	answers::AnswerEntryDto, create_mock_app_state, PermissionsEnum,
+	PermissionsItemDtoRaw, RolesItemDtoRaw, SessionsRepository, UsersItemDtoRaw,
+};
+use anyhow::Result;
+use chrono::{Duration, Utc};
+use najm_course_entities::SurrealWsClient;
+use najm_course_utils::{get_iso_date, make_thing};
+use surrealdb::Uuid;
+
+pub struct AttemptFixture {
+	pub user_id: String,
+	pub session_id: String,
+	pub test_id: String,
+	pub question_id: String,
+	pub option_id: String,
+}
+
+pub async fn seed_attempt_dependencies(
+	db: &SurrealWsClient,
+	start_offset_minutes: i64,
+	end_offset_minutes: i64,
+	duration_minutes: Option<u32>,
+) -> Result<AttemptFixture> {
+	let now = get_iso_date();
+	let fixture = AttemptFixture {
+		user_id: Uuid::new_v4().to_string(),
+		session_id: Uuid::new_v4().to_string(),
+		test_id: Uuid::new_v4().to_string(),
+		question_id: Uuid::new_v4().to_string(),
+		option_id: Uuid::new_v4().to_string(),
+	};
+	let start_date =
+		(Utc::now() + Duration::minutes(start_offset_minutes)).to_rfc3339();
+	let end_date = (Utc::now() + Duration::minutes(end_offset_minutes)).to_rfc3339();
+	let duration = duration_minutes
+		.map(|d| d.to_string())
+		.unwrap_or("NONE".into());
+
+	db.query(format!(
+		"CREATE app_options:⟨{}⟩ SET label = 'Option A', is_correct = true, is_deleted = false, created_at = '{now}', updated_at = '{now}'",
+		fixture.option_id
+	))
+	.await?;
+	db.query(format!(
+		"CREATE app_questions:⟨{}⟩ SET question = 'What is Rust?', discussion = 'A language', options = [app_options:⟨{}⟩], is_deleted = false, created_at = '{now}', updated_at = '{now}'",
+		fixture.question_id, fixture.option_id
+	))
+	.await?;
+	db.query(format!(
+		"CREATE app_tests:⟨{}⟩ SET name = 'Attempt Test', questions = [app_questions:⟨{}⟩], is_deleted = false, created_at = '{now}', updated_at = '{now}'",
+		fixture.test_id, fixture.question_id
+	))
+	.await?;
+	db.query(format!(
+		"CREATE app_sessions:⟨{}⟩ SET name = 'Attempt Session', category = 'Akademik', description = 'Attempt Description', student_type = 'SMA', tests = [{{ test: app_tests:⟨{}⟩, shuffle: false, weight: 1.0, multiplier: 1.0, start_date: '{start_date}', end_date: '{end_date}', duration_minutes: {duration} }}], is_active = true, is_deleted = false, created_at = '{now}', updated_at = '{now}'",
+		fixture.session_id, fixture.test_id
+	))
+	.await?;
+	Ok(fixture)
+}
+
+pub fn build_start_payload(fixture: &AttemptFixture) -> AttemptsStartRequestDto {
+	AttemptsStartRequestDto {
+		session_id: fixture.session_id.clone(),
+		test_id: fixture.test_id.clone(),
+	}
+}
+
+/// The authenticated user the auth middleware would resolve for `user_id`.
+pub fn build_user(
+	user_id: &str,
+	permissions: Vec<PermissionsEnum>,
+) -> UsersItemDtoRaw {
+	let now = get_iso_date();
+	UsersItemDtoRaw {
+		id: make_thing("app_users", user_id),
+		fullname: "Attempt User".into(),
+		email: format!("{user_id}@example.com"),
+		avatar: None,
+		phone_number: "08123456789".into(),
+		referred_by: None,
+		referral_code: None,
+		student_type: "SMA".into(),
+		is_active: true,
+		is_profile_completed: true,
+		is_deleted: false,
+		identity_number: None,
+		religion: None,
+		gender: None,
+		birthdate: None,
+		role: RolesItemDtoRaw {
+			id: make_thing("app_roles", &Uuid::new_v4().to_string()),
+			name: "Student".into(),
+			permissions: permissions
+				.into_iter()
+				.map(|p| PermissionsItemDtoRaw {
+					id: make_thing("app_permissions", &Uuid::new_v4().to_string()),
+					name: p.to_string(),
+					created_at: None,
+					updated_at: None,
+				})
+				.collect(),
+			is_deleted: false,
+			created_at: None,
+			updated_at: None,
+		},
+		password: "".into(),
+		created_at: now.clone(),
+		updated_at: now,
+	}
+}
+
+pub fn build_submit_payload(fixture: &AttemptFixture) -> AttemptsSubmitRequestDto {
+	AttemptsSubmitRequestDto {
+		answers: vec![AnswerEntryDto {
+			question_id: fixture.question_id.clone(),
+			option_id: Some(fixture.option_id.clone()),
+			option_ids: None,
+			statements: None,
+			numeric_value: None,
+			text: None,
+			answered_at: None,
+		}],
+	}
+}
+
+#[tokio::test]
+async fn test_query_start_attempt_should_cap_deadline_by_duration() {
+	let state = create_mock_app_state().await;
+	let fixture = seed_attempt_dependencies(&state.surrealdb_ws, -10, 120, Some(30))
+		.await
+		.unwrap();
+	let repo = AttemptsRepository::new(&state);
+	let attempt = repo
+		.query_start_attempt(&fixture.user_id, build_start_payload(&fixture))
+		.await
+		.unwrap();
+	assert_eq!(attempt.status, AttemptsStatusEnum::InProgress.to_string());
+	assert!(attempt.remaining_seconds > 29 * 60);
+	assert!(attempt.remaining_seconds <= 30 * 60);
+}
+
+#[tokio::test]
+async fn test_query_start_attempt_should_cap_deadline_by_session_end() {
+	let state = create_mock_app_state().await;
+	let fixture = seed_attempt_dependencies(&state.surrealdb_ws, -10, 5, Some(90))
+		.await
+		.unwrap();
+	let repo = AttemptsRepository::new(&state);
+	let attempt = repo
+		.query_start_attempt(&fixture.user_id, build_start_payload(&fixture))
+		.await
+		.unwrap();
+	assert!(attempt.remaining_seconds <= 5 * 60);
+}
+
+#[tokio::test]
+async fn test_query_start_attempt_should_resume_in_progress_attempt() {
+	let state = create_mock_app_state().await;
+	let fixture = seed_attempt_dependencies(&state.surrealdb_ws, -10, 120, Some(30))
+		.await
+		.unwrap();
+	let repo = AttemptsRepository::new(&state);
+	let first = repo
+		.query_start_attempt(&fixture.user_id, build_start_payload(&fixture))
+		.await
+		.unwrap();
+	let second = repo
+		.query_start_attempt(&fixture.user_id, build_start_payload(&fixture))
+		.await
+		.unwrap();
+	assert_eq!(first.id, second.id);
+	assert_eq!(first.deadline_at, second.deadline_at);
+}
+
+#[tokio::test]
+async fn test_query_start_attempt_should_fail_outside_session_window() {
+	let state = create_mock_app_state().await;
+	let repo = AttemptsRepository::new(&state);
+	let upcoming = seed_attempt_dependencies(&state.surrealdb_ws, 60, 120, None)
+		.await
+		.unwrap();
+	let result = repo
+		.query_start_attempt(&upcoming.user_id, build_start_payload(&upcoming))
+		.await;
+	assert_eq!(result.unwrap_err().to_string(), "Session has not started");
+	let ended = seed_attempt_dependencies(&state.surrealdb_ws, -120, -60, None)
+		.await
+		.unwrap();
+	let result = repo
+		.query_start_attempt(&ended.user_id, build_start_payload(&ended))
+		.await;
+	assert_eq!(result.unwrap_err().to_string(), "Session has ended");
+}
+
+#[tokio::test]
+async fn test_query_heartbeat_attempt_should_succeed() {
+	let state = create_mock_app_state().await;
+	let fixture = seed_attempt_dependencies(&state.surrealdb_ws, -10, 120, Some(30))
+		.await
+		.unwrap();
+	let repo = AttemptsRepository::new(&state);
+	let attempt = repo
+		.query_start_attempt(&fixture.user_id, build_start_payload(&fixture))
+		.await
+		.unwrap();
+	let result = repo
+		.query_heartbeat_attempt(&attempt.id, &fixture.user_id)
+		.await;
+	assert!(
+		result.is_ok(),
+		"Heartbeat failed: {:?}",
+		result.unwrap_err()
+	);
+}
+
+#[tokio::test]
+async fn test_query_submit_attempt_should_close_attempt() {
+	let state = create_mock_app_state().await;
+	let fixture = seed_attempt_dependencies(&state.surrealdb_ws, -10, 120, Some(30))
+		.await
+		.unwrap();
+	let repo = AttemptsRepository::new(&state);
+	let attempt = repo
+		.query_start_attempt(&fixture.user_id, build_start_payload(&fixture))
+		.await
+		.unwrap();
+	let result = repo
+		.query_submit_attempt(
+			&attempt.id,
+			&fixture.user_id,
+			build_submit_payload(&fixture),
+		)
+		.await;
+	assert!(result.is_ok(), "Submit failed: {:?}", result.unwrap_err());
+	let closed = repo.query_attempt_by_id(&attempt.id, None).await.unwrap();
+	assert_eq!(closed.status, AttemptsStatusEnum::Submitted.to_string());
+	assert!(closed.submitted_at.is_some());
+	let again = repo
+		.query_submit_attempt(
+			&attempt.id,
+			&fixture.user_id,
+			build_submit_payload(&fixture),
+		)
+		.await;
+	assert_eq!(again.unwrap_err().to_string(), "Attempt already submitted");
+}
+
+#[tokio::test]
+async fn test_query_submit_attempt_should_accept_one_of_concurrent_submits() {
+	let state = create_mock_app_state().await;
+	let fixture = seed_attempt_dependencies(&state.surrealdb_ws, -10, 120, Some(30))
+		.await
+		.unwrap();
+	let repo = AttemptsRepository::new(&state);
+	let attempt = repo
+		.query_start_attempt(&fixture.user_id, build_start_payload(&fixture))
+		.await
+		.unwrap();
+	let (first, second) = tokio::join!(
+		repo.query_submit_attempt(
+			&attempt.id,
+			&fixture.user_id,
+			build_submit_payload(&fixture)
+		),
+		repo.query_submit_attempt(
+			&attempt.id,
+			&fixture.user_id,
+			build_submit_payload(&fixture)
+		),
+	);
+	assert_eq!(first.is_ok() as u8 + second.is_ok() as u8, 1);
+}
+
+#[tokio::test]
+async fn test_query_submit_attempt_should_reject_another_user() {
+	let state = create_mock_app_state().await;
+	let fixture = seed_attempt_dependencies(&state.surrealdb_ws, -10, 120, Some(30))
+		.await
+		.unwrap();
+	let repo = AttemptsRepository::new(&state);
+	let attempt = repo
+		.query_start_attempt(&fixture.user_id, build_start_payload(&fixture))
+		.await
+		.unwrap();
+	let other = Uuid::new_v4().to_string();
+	let result = repo
+		.query_submit_attempt(&attempt.id, &other, build_submit_payload(&fixture))
+		.await;
+	assert_eq!(
+		result.unwrap_err().to_string(),
+		"Attempt does not belong to user"
+	);
+	let detail = repo.query_attempt_by_id(&attempt.id, None).await.unwrap();
+	assert_eq!(detail.status, AttemptsStatusEnum::InProgress.to_string());
+	let hidden = repo.query_attempt_by_id(&attempt.id, Some(&other)).await;
+	assert!(hidden.is_err());
+}
+
+#[tokio::test]
+async fn test_query_submit_attempt_should_reject_after_deadline() {
+	let state = create_mock_app_state().await;
+	let db = &state.surrealdb_ws;
+	let fixture = seed_attempt_dependencies(db, -10, 120, Some(30))
+		.await
+		.unwrap();
+	let repo = AttemptsRepository::new(&state);
+	let attempt = repo
+		.query_start_attempt(&fixture.user_id, build_start_payload(&fixture))
+		.await
+		.unwrap();
+	let past = (Utc::now() - Duration::minutes(5)).to_rfc3339();
+	db.query(format!(
+		"UPDATE app_attempts:⟨{}⟩ SET deadline_at = '{}'",
+		attempt.id, past
+	))
+	.await
+	.unwrap();
+	let result = repo
+		.query_submit_attempt(
+			&attempt.id,
+			&fixture.user_id,
+			build_submit_payload(&fixture),
+		)
+		.await;
+	assert_eq!(
+		result.unwrap_err().to_string(),
+		"Attempt deadline has passed"
+	);
+	let closed = repo.query_attempt_by_id(&attempt.id, None).await.unwrap();
+	assert_eq!(closed.status, AttemptsStatusEnum::Expired.to_string());
+}
+
+#[tokio::test]
+async fn test_query_expire_overdue_should_close_attempts() {
+	let state = create_mock_app_state().await;
+	let db = &state.surrealdb_ws;
+	let fixture = seed_attempt_dependencies(db, -10, 120, Some(30))
+		.await
+		.unwrap();
+	let repo = AttemptsRepository::new(&state);
+	let attempt = repo
+		.query_start_attempt(&fixture.user_id, build_start_payload(&fixture))
+		.await
+		.unwrap();
+	let past = (Utc::now() - Duration::minutes(5)).to_rfc3339();
+	db.query(format!(
+		"UPDATE app_attempts:⟨{}⟩ SET deadline_at = '{}'",
+		attempt.id, past
+	))
+	.await
+	.unwrap();
+	let expired = repo.query_expire_overdue().await.unwrap();
+	assert!(expired >= 1);
+	let raw = repo.query_raw_attempt_by_id(&attempt.id).await.unwrap();
+	assert_eq!(raw.status, AttemptsStatusEnum::Expired.to_string());
+}
+
+#[tokio::test]
+async fn test_query_create_answers_should_reject_expired_attempt() {
+	let state = create_mock_app_state().await;
+	let db = &state.surrealdb_ws;
+	let fixture = seed_attempt_dependencies(db, -10, 120, Some(30))
+		.await
+		.unwrap();
+	let repo = AttemptsRepository::new(&state);
+	let attempt = repo
+		.query_start_attempt(&fixture.user_id, build_start_payload(&fixture))
+		.await
+		.unwrap();
+	let past = (Utc::now() - Duration::minutes(5)).to_rfc3339();
+	db.query(format!(
+		"UPDATE app_attempts:⟨{}⟩ SET deadline_at = '{}'",
+		attempt.id, past
+	))
+	.await
+	.unwrap();
+	let answers_repo = crate::answers::AnswersRepository::new(&state);
+	let result = answers_repo
+		.query_create(crate::answers::AnswersCreateRequestDto {
+			user_id: fixture.user_id.clone(),
+			session_id: fixture.session_id.clone(),
+			test_id: fixture.test_id.clone(),
+			answers: build_submit_payload(&fixture).answers,
+		})
+		.await;
+	assert_eq!(
+		result.unwrap_err().to_string(),
+		"Attempt deadline has passed"
+	);
+}
+
+#[tokio::test]
+async fn test_query_start_attempt_should_store_presented_order() {
+	let state = create_mock_app_state().await;
+	let (session_id, test_id) =
+		crate::sessions::sessions_repository_test::seed_shuffled_session(
+			&state.surrealdb_ws,
+		)
+		.await
+		.unwrap();
+	let user_id = Uuid::new_v4().to_string();
+	let attempt = AttemptsRepository::new(&state)
+		.query_start_attempt(
+			&user_id,
+			AttemptsStartRequestDto {
+				session_id: session_id.clone(),
+				test_id,
+			},
+		)
+		.await
+		.unwrap();
+	let detail = crate::SessionsRepository::new(&state)
+		.query_session_by_id_for_user(&session_id, &user_id, true)
+		.await
+		.unwrap();
+	let presented: Vec<(String, Vec<String>)> = detail.tests[0]
+		.question_order()
+		.into_iter()
+		.map(|o| (o.question, o.options))
+		.collect();
+	let stored: Vec<(String, Vec<String>)> = attempt
+		.question_order
+		.into_iter()
+		.map(|o| (o.question_id, o.option_ids))
+		.collect();
+	assert_eq!(stored.len(), 8);
+	assert_eq!(stored, presented);
+}
+
+#[tokio::test]
+async fn test_query_start_attempt_should_wait_for_prerequisite() {
+	let state = create_mock_app_state().await;
+	let db = &state.surrealdb_ws;
+	let fixture = seed_attempt_dependencies(db, -10, 120, Some(30))
+		.await
+		.unwrap();
+	let next_test_id = Uuid::new_v4().to_string();
+	let now = get_iso_date();
+	let start_date = (Utc::now() - Duration::minutes(10)).to_rfc3339();
+	let end_date = (Utc::now() + Duration::minutes(120)).to_rfc3339();
+	db.query(format!(
+		"CREATE app_tests:⟨{next_test_id}⟩ SET name = 'Next Test', questions = [], is_deleted = false, created_at = '{now}', updated_at = '{now}';
+		UPDATE app_sessions:⟨{}⟩ SET tests += {{ test: app_tests:⟨{next_test_id}⟩, shuffle: false, weight: 1.0, multiplier: 1.0, start_date: '{start_date}', end_date: '{end_date}', prerequisite: {{ test: app_tests:⟨{}⟩ }} }};",
+		fixture.session_id, fixture.test_id
+	))
+	.await
+	.unwrap();
+	let repo = AttemptsRepository::new(&state);
+	let next = AttemptsStartRequestDto {
+		test_id: next_test_id.clone(),
+		..build_start_payload(&fixture)
+	};
+	let locked = repo
+		.query_start_attempt(&fixture.user_id, next.clone())
+		.await;
+	assert_eq!(
+		locked.unwrap_err().to_string(),
+		"Prerequisite test has not been submitted"
+	);
+	let detail = SessionsRepository::new(&state)
+		.query_session_by_id_for_user(&fixture.session_id, &fixture.user_id, true)
+		.await
+		.unwrap();
+	let lock = detail.tests[1].lock.clone().expect("Expected lock");
+	assert_eq!(lock.status, "locked");
+	let attempt = repo
+		.query_start_attempt(&fixture.user_id, build_start_payload(&fixture))
+		.await
+		.unwrap();
+	repo
+		.query_submit_attempt(
+			&attempt.id,
+			&fixture.user_id,
+			build_submit_payload(&fixture),
+		)
+		.await
+		.unwrap();
+	let started = repo
+		.query_start_attempt(&fixture.user_id, next)
+		.await
+		.unwrap();
+	assert_eq!(started.status, AttemptsStatusEnum::InProgress.to_string());
+}
+
+#[tokio::test]
+async fn test_query_start_attempt_should_allow_retakes_with_separate_answers() {
+	let state = create_mock_app_state().await;
+	let db = &state.surrealdb_ws;
+	let fixture = seed_attempt_dependencies(db, -10, 120, Some(30))
+		.await
+		.unwrap();
+	let wrong_option_id = Uuid::new_v4().to_string();
+	let now = get_iso_date();
+	db.query(format!(
+		"CREATE app_options:⟨{wrong_option_id}⟩ SET label = 'Option B', is_correct = false, is_deleted = false, created_at = '{now}', updated_at = '{now}';
+		UPDATE app_questions:⟨{}⟩ SET options += app_options:⟨{wrong_option_id}⟩;
+		UPDATE app_sessions:⟨{}⟩ SET retake_policy = {{ max_attempts: 2, score_selection: 'best' }};",
+		fixture.question_id, fixture.session_id
+	))
+	.await
+	.unwrap();
+	let repo = AttemptsRepository::new(&state);
+	let first = repo
+		.query_start_attempt(&fixture.user_id, build_start_payload(&fixture))
+		.await
+		.unwrap();
+	assert_eq!(first.attempt_number, 1);
+	repo
+		.query_submit_attempt(
+			&first.id,
+			&fixture.user_id,
+			build_submit_payload(&fixture),
+		)
+		.await
+		.unwrap();
+	let second = repo
+		.query_start_attempt(&fixture.user_id, build_start_payload(&fixture))
+		.await
+		.unwrap();
+	assert_eq!(second.attempt_number, 2);
+	let mut wrong = build_submit_payload(&fixture);
+	wrong.answers[0].option_id = Some(wrong_option_id);
+	let retake = repo
+		.query_submit_attempt(&second.id, &fixture.user_id, wrong)
+		.await
+		.unwrap();
+	assert_eq!(retake.questions.len(), 1);
+	assert!(!retake.questions[0].is_correct);
+	let history = repo
+		.query_history(
+			&fixture.user_id,
+			AttemptsHistoryQueryDto {
+				session_id: fixture.session_id.clone(),
+				test_id: fixture.test_id.clone(),
+				user_id: None,
+			},
+		)
+		.await
+		.unwrap();
+	assert_eq!(history.attempts.len(), 2);
+	assert!(history.attempts[0].score > history.attempts[1].score);
+	assert_eq!(history.selected_score, history.attempts[0].score);
+	assert_eq!(history.next_attempt_at, None);
+	let result = crate::SessionResultsRepository::new(&state)
+		.query_result_by_user(&fixture.user_id, &fixture.session_id)
+		.await
+		.unwrap();
+	assert_eq!(Some(result.tests[0].score), history.selected_score);
+	let third = repo
+		.query_start_attempt(&fixture.user_id, build_start_payload(&fixture))
+		.await;
+	assert_eq!(third.unwrap_err().to_string(), "Maximum attempts reached");
+}
+
+#[tokio::test]
+async fn test_query_open_channel_should_require_owner_and_open_attempt() {
+	let state = create_mock_app_state().await;
+	let fixture = seed_attempt_dependencies(&state.surrealdb_ws, -10, 120, Some(30))
+		.await
+		.unwrap();
+	let repo = AttemptsRepository::new(&state);
+	let attempt = repo
+		.query_start_attempt(&fixture.user_id, build_start_payload(&fixture))
+		.await
+		.unwrap();
+	assert!(repo
+		.query_open_channel(&attempt.id, Some(&fixture.user_id))
+		.await
+		.is_ok());
+	let other = repo
+		.query_open_channel(&attempt.id, Some("someone_else"))
+		.await;
+	assert_eq!(
+		other.unwrap_err().to_string(),
+		"Attempt does not belong to user"
+	);
+	repo
+		.query_submit_attempt(
+			&attempt.id,
+			&fixture.user_id,
+			build_submit_payload(&fixture),
+		)
+		.await
+		.unwrap();
+	let closed = repo.query_open_channel(&attempt.id, None).await;
+	assert_eq!(
+		closed.unwrap_err().to_string(),
+		"Attempt is not in progress"
+	);
+}
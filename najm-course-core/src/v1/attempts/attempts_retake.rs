@@ -0,0 +1,74 @@
+use super::{AttemptsSchema, AttemptsStatusEnum};
+use crate::{
+	answers::TestsItemAnswersDto, SessionsRetakePolicySchema,
+	SessionsScoreSelectionEnum,
+};
+use anyhow::{bail, Result};
+use chrono::{DateTime, Duration, Utc};
+
+fn finished_at(attempt: &AttemptsSchema) -> Option<DateTime<Utc>> {
+	attempt
+		.submitted_at
+		.as_deref()
+		.unwrap_or(&attempt.deadline_at)
+		.parse::<DateTime<Utc>>()
+		.ok()
+}
+
+/// Earliest time the next attempt may start, or `None` once every attempt
+/// allowed by the policy has been used.
+pub fn next_attempt_at(
+	policy: &SessionsRetakePolicySchema,
+	history: &[AttemptsSchema],
+) -> Option<DateTime<Utc>> {
+	if history.len() >= policy.max_attempts as usize {
+		return None;
+	}
+	let Some(last) = history.last() else {
+		return Some(DateTime::<Utc>::MIN_UTC);
+	};
+	if last.status == AttemptsStatusEnum::InProgress.to_string() {
+		return None;
+	}
+	let cooldown = Duration::minutes(policy.cooldown_minutes.unwrap_or(0) as i64);
+	Some(finished_at(last).unwrap_or(DateTime::<Utc>::MIN_UTC) + cooldown)
+}
+
+pub fn ensure_retake_allowed(
+	policy: &SessionsRetakePolicySchema,
+	history: &[AttemptsSchema],
+	now: DateTime<Utc>,
+) -> Result<()> {
+	match next_attempt_at(policy, history) {
+		None => bail!("Maximum attempts reached"),
+		Some(available_at) if now < available_at => {
+			bail!("Retake cooldown has not elapsed")
+		}
+		Some(_) => Ok(()),
+	}
+}
+
+/// Picks the scored attempt that counts for the session result. Averages keep
+/// the last attempt's answers and combine the scores of every attempt.
+pub fn select_attempt_score(
+	selection: SessionsScoreSelectionEnum,
+	scored: Vec<TestsItemAnswersDto>,
+) -> Option<TestsItemAnswersDto> {
+	match selection {
+		SessionsScoreSelectionEnum::Last => scored.into_iter().last(),
+		SessionsScoreSelectionEnum::Best => {
+			scored.into_iter().rev().max_by_key(|attempt| attempt.score)
+		}
+		SessionsScoreSelectionEnum::Average => {
+			let count = scored.len() as f64;
+			let score = scored.iter().map(|a| a.score as f64).sum::<f64>() / count;
+			let thetas: Option<Vec<f64>> = scored.iter().map(|a| a.theta).collect();
+			let pending_grading = scored.iter().map(|a| a.pending_grading).sum();
+			let mut last = scored.into_iter().last()?;
+			last.score = score.round() as i32;
+			last.theta = thetas.map(|t| t.iter().sum::<f64>() / count);
+			last.pending_grading = pending_grading;
+			Some(last)
+		}
+	}
+}
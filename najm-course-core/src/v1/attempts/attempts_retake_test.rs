@@ -0,0 +1,115 @@
+use super::*;
+use crate::{
+	answers::TestsItemAnswersDto, SessionsRetakePolicySchema,
+	SessionsScoreSelectionEnum,
+};
+use chrono::{Duration, TimeZone, Utc};
+use najm_course_utils::make_thing;
+
+fn attempt(
+	status: AttemptsStatusEnum,
+	submitted_at: Option<&str>,
+) -> AttemptsSchema {
+	AttemptsSchema {
+		id: make_thing("app_attempts", "attempt"),
+		user: make_thing("app_users", "user"),
+		session: make_thing("app_sessions", "session"),
+		test: make_thing("app_tests", "test"),
+		status: status.to_string(),
+		attempt_number: 1,
+		started_at: "2025-01-01T00:00:00Z".into(),
+		deadline_at: "2025-01-01T02:00:00Z".into(),
+		last_heartbeat_at: "2025-01-01T00:00:00Z".into(),
+		submitted_at: submitted_at.map(String::from),
+		question_order: vec![],
+		is_deleted: false,
+		created_at: "2025-01-01T00:00:00Z".into(),
+		updated_at: "2025-01-01T00:00:00Z".into(),
+	}
+}
+
+fn scored(score: i32, theta: Option<f64>) -> TestsItemAnswersDto {
+	TestsItemAnswersDto {
+		id: format!("attempt_{score}"),
+		name: "Test".into(),
+		score,
+		theta,
+		kecermatan: None,
+		pending_grading: 0,
+		is_revealed: true,
+		questions: vec![],
+		created_at: "2025-01-01T00:00:00Z".into(),
+		updated_at: "2025-01-01T00:00:00Z".into(),
+	}
+}
+
+fn policy(
+	max_attempts: u32,
+	cooldown_minutes: Option<u32>,
+) -> SessionsRetakePolicySchema {
+	SessionsRetakePolicySchema {
+		max_attempts,
+		cooldown_minutes,
+		..Default::default()
+	}
+}
+
+#[test]
+fn test_ensure_retake_allowed_should_enforce_limit_and_cooldown() {
+	let submitted = Utc.with_ymd_and_hms(2025, 1, 1, 1, 0, 0).unwrap();
+	let history = vec![attempt(
+		AttemptsStatusEnum::Submitted,
+		Some("2025-01-01T01:00:00Z"),
+	)];
+	let err =
+		ensure_retake_allowed(&Default::default(), &history, submitted).unwrap_err();
+	assert_eq!(err.to_string(), "Maximum attempts reached");
+	let cooldown = policy(3, Some(30));
+	let err =
+		ensure_retake_allowed(&cooldown, &history, submitted + Duration::minutes(10))
+			.unwrap_err();
+	assert_eq!(err.to_string(), "Retake cooldown has not elapsed");
+	assert!(ensure_retake_allowed(
+		&cooldown,
+		&history,
+		submitted + Duration::minutes(30)
+	)
+	.is_ok());
+	assert!(ensure_retake_allowed(&cooldown, &[], submitted).is_ok());
+}
+
+#[test]
+fn test_next_attempt_at_should_use_deadline_of_expired_attempt() {
+	let history = vec![attempt(AttemptsStatusEnum::Expired, None)];
+	assert_eq!(
+		next_attempt_at(&policy(2, Some(60)), &history),
+		Some(Utc.with_ymd_and_hms(2025, 1, 1, 3, 0, 0).unwrap())
+	);
+	let running = vec![attempt(AttemptsStatusEnum::InProgress, None)];
+	assert_eq!(next_attempt_at(&policy(2, None), &running), None);
+}
+
+#[test]
+fn test_select_attempt_score_should_follow_selection() {
+	let attempts = || {
+		vec![
+			scored(60, Some(0.5)),
+			scored(90, Some(1.5)),
+			scored(75, None),
+		]
+	};
+	let best = select_attempt_score(SessionsScoreSelectionEnum::Best, attempts());
+	assert_eq!(best.map(|a| a.score), Some(90));
+	let last = select_attempt_score(SessionsScoreSelectionEnum::Last, attempts());
+	assert_eq!(last.map(|a| a.score), Some(75));
+	let average =
+		select_attempt_score(SessionsScoreSelectionEnum::Average, attempts()).unwrap();
+	assert_eq!(average.score, 75);
+	assert_eq!(average.theta, None);
+	let thetas = vec![scored(60, Some(0.5)), scored(91, Some(1.5))];
+	let average =
+		select_attempt_score(SessionsScoreSelectionEnum::Average, thetas).unwrap();
+	assert_eq!(average.score, 76);
+	assert_eq!(average.theta, Some(1.0));
+	assert!(select_attempt_score(SessionsScoreSelectionEnum::Best, vec![]).is_none());
+}
@@ -0,0 +1,28 @@
+use serde::{Deserialize, Serialize};
+use surrealdb::sql::Thing;
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct AttemptsSchema {
+	pub id: Thing,
+	pub user: Thing,
+	pub session: Thing,
+	pub test: Thing,
+	pub status: String,
+	#[serde(default)]
+	pub attempt_number: u32,
+	pub started_at: String,
+	pub deadline_at: String,
+	pub last_heartbeat_at: String,
+	pub submitted_at: Option<String>,
+	#[serde(default)]
+	pub question_order: Vec<AttemptsQuestionOrderSchema>,
+	pub is_deleted: bool,
+	pub created_at: String,
+	pub updated_at: String,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct AttemptsQuestionOrderSchema {
+	pub question: String,
+	pub options: Vec<String>,
+}
@@ -0,0 +1,185 @@
+use super::{
+	serve_attempt_channel, AttemptsHistoryQueryDto, AttemptsRepository,
+	AttemptsStartRequestDto, AttemptsSubmitRequestDto,
+};
+use crate::{
+	answers::AnswersRepository, common_response, eligibility_guard, success_response,
+	AppState, EnrollmentsCandidateDto, ResponseSuccessDto, SessionsRepository,
+	SessionsViewerDto, UsersItemDtoRaw,
+};
+use axum::{extract::ws::WebSocketUpgrade, http::StatusCode, response::Response};
+
+pub struct AttemptsService;
+
+fn attempt_error_status(msg: &str) -> StatusCode {
+	match msg {
+		"Attempt not found" | "Session not found" => StatusCode::NOT_FOUND,
+		"Session has not started"
+		| "Session has ended"
+		| "Session is not active"
+		| "Attempt deadline has passed"
+		| "Retake cooldown has not elapsed"
+		| "Attempt does not belong to user"
+		| "Prerequisite test has not been submitted"
+		| "Prerequisite gap has not elapsed"
+		| "Prerequisite gap window has passed" => StatusCode::FORBIDDEN,
+		"Maximum attempts reached"
+		| "Attempt already submitted"
+		| "Attempt is not in progress" => StatusCode::CONFLICT,
+		"Test not found in session" | "Test not found" | "Invalid date format" => {
+			StatusCode::BAD_REQUEST
+		}
+		_ => StatusCode::INTERNAL_SERVER_ERROR,
+	}
+}
+
+impl AttemptsService {
+	pub async fn get_attempt_history(
+		state: &AppState,
+		viewer: SessionsViewerDto,
+		query: AttemptsHistoryQueryDto,
+	) -> Response {
+		let user_id = if viewer.is_reviewer {
+			query.user_id.clone().or(viewer.user_id.clone())
+		} else {
+			viewer.user_id.clone()
+		};
+		let Some(user_id) = user_id else {
+			return common_response(StatusCode::BAD_REQUEST, "User is required");
+		};
+		let repo = AttemptsRepository::new(state);
+		let session_id = query.session_id.clone();
+		let result = match repo.query_history(&user_id, query).await {
+			Ok(data) if !viewer.is_reviewer => SessionsRepository::new(state)
+				.query_is_revealed(&session_id, true)
+				.await
+				.map(|revealed| {
+					if revealed {
+						data
+					} else {
+						data.without_scores()
+					}
+				}),
+			result => result,
+		};
+		match result {
+			Ok(data) => success_response(ResponseSuccessDto { data }),
+			Err(e) => {
+				let msg = e.to_string();
+				common_response(attempt_error_status(&msg), &msg)
+			}
+		}
+	}
+
+	pub async fn open_channel(
+		state: AppState,
+		id: String,
+		user: &UsersItemDtoRaw,
+		ws: WebSocketUpgrade,
+	) -> Response {
+		let repo = AttemptsRepository::new(&state);
+		let user_id = user.id.id.to_raw();
+		let attempt = match repo.query_open_channel(&id, Some(&user_id)).await {
+			Ok(attempt) => attempt,
+			Err(e) => {
+				let msg = e.to_string();
+				return common_response(attempt_error_status(&msg), &msg);
+			}
+		};
+		let candidate = EnrollmentsCandidateDto::from(user);
+		ws.on_upgrade(move |socket| {
+			serve_attempt_channel(state, socket, attempt, candidate)
+		})
+	}
+
+	pub async fn get_attempt_by_id(
+		state: &AppState,
+		id: String,
+		viewer: SessionsViewerDto,
+	) -> Response {
+		let repo = AttemptsRepository::new(state);
+		let owner = viewer.user_id.as_deref().filter(|_| !viewer.is_reviewer);
+		match repo.query_attempt_by_id(&id, owner).await {
+			Ok(data) => success_response(ResponseSuccessDto { data }),
+			Err(e) => {
+				let msg = e.to_string();
+				common_response(attempt_error_status(&msg), &msg)
+			}
+		}
+	}
+
+	pub async fn start_attempt(
+		state: &AppState,
+		user_id: String,
+		payload: AttemptsStartRequestDto,
+	) -> Response {
+		let repo = AttemptsRepository::new(state);
+		match repo.query_start_attempt(&user_id, payload).await {
+			Ok(data) => success_response(ResponseSuccessDto { data }),
+			Err(e) => {
+				let msg = e.to_string();
+				common_response(attempt_error_status(&msg), &msg)
+			}
+		}
+	}
+
+	pub async fn heartbeat_attempt(
+		state: &AppState,
+		id: String,
+		user_id: String,
+	) -> Response {
+		let repo = AttemptsRepository::new(state);
+		match repo.query_heartbeat_attempt(&id, &user_id).await {
+			Ok(data) => success_response(ResponseSuccessDto { data }),
+			Err(e) => {
+				let msg = e.to_string();
+				common_response(attempt_error_status(&msg), &msg)
+			}
+		}
+	}
+
+	pub async fn submit_attempt(
+		state: &AppState,
+		id: String,
+		user: &UsersItemDtoRaw,
+		payload: AttemptsSubmitRequestDto,
+	) -> Response {
+		let repo = AttemptsRepository::new(state);
+		let session_id = match repo.query_raw_attempt_by_id(&id).await {
+			Ok(attempt) => attempt.session.id.to_raw(),
+			Err(e) => {
+				let msg = e.to_string();
+				return common_response(attempt_error_status(&msg), &msg);
+			}
+		};
+		if let Err(response) = eligibility_guard(state, Some(user), &session_id).await {
+			return response;
+		}
+		let user_id = user.id.id.to_raw();
+		let viewer = SessionsViewerDto::from(Some(user));
+		let result = match repo.query_submit_attempt(&id, &user_id, payload).await {
+			Ok(data) => match repo.query_raw_attempt_by_id(&id).await {
+				Ok(attempt) => {
+					AnswersRepository::new(state)
+						.query_apply_visibility(
+							data,
+							&viewer,
+							&attempt.user.id.to_raw(),
+							&attempt.session.id.to_raw(),
+							&attempt.test.id.to_raw(),
+						)
+						.await
+				}
+				Err(e) => Err(e),
+			},
+			Err(e) => Err(e),
+		};
+		match result {
+			Ok(data) => success_response(ResponseSuccessDto { data }),
+			Err(e) => {
+				let msg = e.to_string();
+				common_response(attempt_error_status(&msg), &msg)
+			}
+		}
+	}
+}
@@ -0,0 +1,41 @@
+use axum::{
+	routing::{get, post, put},
+	Router,
+};
+
+pub mod attempts_channel;
+pub mod attempts_controller;
+pub mod attempts_dto;
+pub mod attempts_enum;
+pub mod attempts_repository;
+pub mod attempts_retake;
+pub mod attempts_schema;
+pub mod attempts_service;
+
+#[cfg(test)]
+pub mod attempts_channel_test;
+#[cfg(test)]
+pub mod attempts_controller_test;
+#[cfg(test)]
+pub mod attempts_repository_test;
+#[cfg(test)]
+pub mod attempts_retake_test;
+
+pub use attempts_channel::*;
+pub use attempts_controller::*;
+pub use attempts_dto::*;
+pub use attempts_enum::*;
+pub use attempts_repository::*;
+pub use attempts_retake::*;
+pub use attempts_schema::*;
+pub use attempts_service::*;
+
+pub fn attempts_router() -> Router {
+	Router::new()
+		.route("/start", post(post_start_attempt))
+		.route("/history", get(get_attempt_history))
+		.route("/detail/{id}", get(get_attempt_by_id))
+		.route("/channel/{id}", get(get_attempt_channel))
+		.route("/heartbeat/{id}", put(put_heartbeat_attempt))
+		.route("/submit/{id}", post(post_submit_attempt))
+}
@@ -2,16 +2,41 @@ use crate::{
 	common_response, extract_email, AppState, UsersItemDtoRaw, UsersRepository,
 };
 use axum::{
-	extract::Request, http::StatusCode, middleware::Next, response::Response,
+	extract::Request,
+	http::{header, HeaderValue, StatusCode},
+	middleware::Next,
+	response::Response,
 	Extension,
 };
 use std::convert::Infallible;
 
+/// Browsers cannot set headers on a WebSocket handshake, so those requests
+/// may pass the token as an `access_token` query parameter instead.
+fn websocket_token(req: &Request) -> Option<HeaderValue> {
+	let is_websocket = req
+		.headers()
+		.get(header::UPGRADE)
+		.and_then(|v| v.to_str().ok())
+		.is_some_and(|v| v.eq_ignore_ascii_case("websocket"));
+	if !is_websocket || req.headers().contains_key(header::AUTHORIZATION) {
+		return None;
+	}
+	req
+		.uri()
+		.query()?
+		.split('&')
+		.find_map(|pair| pair.strip_prefix("access_token="))
+		.and_then(|token| HeaderValue::from_str(&format!("Bearer {}", token)).ok())
+}
+
 pub async fn auth_middleware(
 	Extension(state): Extension<AppState>,
 	mut req: Request,
 	next: Next,
 ) -> Result<Response, Infallible> {
+	if let Some(token) = websocket_token(&req) {
+		req.headers_mut().insert(header::AUTHORIZATION, token);
+	}
 	let headers = req.headers();
 
 	let email = match extract_email(headers) {
@@ -1,7 +1,7 @@
 use crate::{
-	answers::{AnswersCreateRequestDto, TestsItemAnswersDto}, options::{OptionsCreateRequestDto, OptionsItemDto, OptionsResponseListDto, OptionsUpdateRequestDto}, questions::{QuestionsCreateRequestDto, QuestionsItemDto, QuestionsResponseListDto}, sessions::{SessionsCreateRequestDto, SessionsDetailResponseDto, SessionsResponseDto, SessionsUpdateRequestDto}, storage::{StorageRequestDto, StorageResponseDto}, tests::{TestsCreateRequestDto, TestsItemDto, TestsResponseListDto, TestsUpdateRequestDto}, v1::{
-		answers, auth, options, permissions, storage, questions, roles, sessions, tests, users, AuthLoginRequestDto, AuthLoginResponsetDto, AuthResendOtpRequestDto, AuthVerifyEmailRequestDto
-	}, AuthNewPasswordRequestDto, AuthRefreshTokenRequestDto, MessageResponseDto, MetaRequestDto, MetaResponseDto, PermissionsItemDto, PermissionsRequestDto, QuestionsUpdateRequestDto, ResponseListSuccessDto, ResponseSuccessDto, RolesItemDto, RolesRequestCreateDto, RolesRequestUpdateDto, TokenDto, UsersCreateRequestDto, UsersDetailItemDto, UsersItemDto, UsersListItemDto, UsersUpdateRequestDto
+	answers::{AnswerStatementDto, AnswersCreateRequestDto, AnswersGradeRequestDto, AnswersGradingDto, AnswersGradingItemDto, AnswersRubricScoreDto, AnswersSaveRequestDto, AnswersSaveResponseDto, TestsItemAnswersDto}, attempts::{AttemptsChannelEventDto, AttemptsChannelRequestDto, AttemptsHistoryDto, AttemptsHistoryItemDto, AttemptsItemDto, AttemptsQuestionOrderDto, AttemptsStartRequestDto, AttemptsSubmitRequestDto}, irt::{IrtCalibrateRequestDto, IrtCalibrateResponseDto, IrtItemParamsDto}, options::{OptionsCreateRequestDto, OptionsItemDto, OptionsResponseListDto, OptionsUpdateRequestDto}, questions::{QuestionsAnalysisDto, QuestionsAnalysisItemDto, QuestionsOptionAnalysisDto, QuestionsAnswerKeyDto, QuestionsCreateRequestDto, QuestionsItemDto, QuestionsResponseListDto, QuestionsTagsDto}, monitoring::{MonitoringEventDto, MonitoringStudentDto}, proctoring::{ProctoringEventCountDto, ProctoringEventsCreateRequestDto, ProctoringEventsItemDto, ProctoringTimelineDto}, enrollments::{EnrollmentsBulkRequestDto, EnrollmentsBulkResponseDto, EnrollmentsCreateRequestDto, EnrollmentsItemDto, EnrollmentsSelfRequestDto}, sessions::{SessionsBroadcastDto, SessionsBroadcastRequestDto, SessionsBundleConflictDto, SessionsBundleIdMapDto, SessionsBundleImportRequestDto, SessionsBundleImportResponseDto, SessionsCreateRequestDto, SessionsDetailResponseDto, SessionsEligibilityDto, SessionsResponseDto, SessionsRetakePolicyDto, SessionsUpdateRequestDto, SessionsVisibilityDto, TestSessionsLockDto, TestSessionsPrerequisiteDto}, storage::{StorageRequestDto, StorageResponseDto}, tests::{TestsBlueprintItemDto, TestsGenerateRequestDto, TestsGenerateResponseDto, TestsGenerateSectionDto, TestsImportPreviewDto, TestsImportRequestDto, TestsImportRowErrorDto, TestsCreateRequestDto, TestsItemDto, TestsResponseListDto, TestsUpdateRequestDto}, session_results::{SessionResultsComputeRequestDto, SessionResultsItemDto, SessionResultsLeaderboardDto, SessionResultsLeaderboardEntryDto, SessionResultsRankDto, SessionResultsTestItemDto}, v1::{
+		answers, attempts, auth, enrollments, irt, monitoring, options, permissions, proctoring, storage, questions, roles, session_results, sessions, tests, users, AuthLoginRequestDto, AuthLoginResponsetDto, AuthResendOtpRequestDto, AuthVerifyEmailRequestDto
+	}, AuthNewPasswordRequestDto, AuthRefreshTokenRequestDto, MessageResponseDto, MetaRequestDto, MetaResponseDto, PermissionsItemDto, PermissionsRequestDto, QuestionsUpdateRequestDto, ResponseListSuccessDto, ResponseSuccessDto, RolesItemDto, RolesRequestCreateDto, RolesRequestUpdateDto, ScoringConfigDto, ScoringRuleDto, KecermatanResultDto, KecermatanColumnDto, TokenDto, UsersCreateRequestDto, UsersDetailItemDto, UsersItemDto, UsersListItemDto, UsersUpdateRequestDto
 };
 use utoipa::{
 	openapi::security::{Http, HttpAuthScheme, SecurityScheme},
@@ -42,24 +42,59 @@ use utoipa::{
      questions::questions_controller::post_create_question,
      questions::questions_controller::put_update_question,
      questions::questions_controller::delete_question,
+     questions::questions_controller::get_question_analysis_list,
+     questions::questions_controller::get_question_analysis_by_id,
      tests::tests_controller::get_test_list,
      tests::tests_controller::get_test_by_id,
      tests::tests_controller::post_create_test,
+     tests::tests_controller::post_generate_test,
+     tests::tests_controller::post_import_test,
+     tests::tests_controller::get_export_test,
      tests::tests_controller::put_update_test,
      tests::tests_controller::delete_test,
      answers::answers_controller::get_answer_by_id,
      answers::answers_controller::post_create_answer,
+     answers::answers_controller::put_save_answer,
      answers::answers_controller::delete_answer,
+     answers::answers_controller::get_grading_queue,
+     answers::answers_controller::put_grade_answer,
+     answers::answers_controller::post_finalize_grading,
+     attempts::attempts_controller::get_attempt_history,
+     attempts::attempts_controller::get_attempt_by_id,
+     attempts::attempts_controller::get_attempt_channel,
+     attempts::attempts_controller::post_start_attempt,
+     attempts::attempts_controller::put_heartbeat_attempt,
+     attempts::attempts_controller::post_submit_attempt,
+     irt::irt_controller::post_calibrate_test,
      sessions::sessions_controller::get_session_list,
      sessions::sessions_controller::get_session_by_id,
      sessions::sessions_controller::post_create_session,
      sessions::sessions_controller::put_update_session,
      sessions::sessions_controller::delete_session,
+     sessions::sessions_controller::post_broadcast_session,
+     sessions::sessions_controller::get_export_session,
+     sessions::sessions_controller::post_import_session,
      permissions::permissions_controller::get_permission_list,
      permissions::permissions_controller::get_permission_by_id,
      permissions::permissions_controller::post_create_permission,
      permissions::permissions_controller::put_update_permission,
      permissions::permissions_controller::delete_permission,
+     session_results::session_results_controller::get_session_result_list,
+     session_results::session_results_controller::get_session_result_by_id,
+     session_results::session_results_controller::get_session_result_by_user,
+     session_results::session_results_controller::post_compute_session_result,
+     session_results::session_results_controller::get_session_leaderboard,
+     session_results::session_results_controller::get_session_rank_by_user,
+     session_results::session_results_controller::get_export_session_results,
+     session_results::session_results_controller::get_session_result_report,
+     enrollments::enrollments_controller::get_enrollment_list,
+     enrollments::enrollments_controller::post_self_enroll,
+     enrollments::enrollments_controller::post_create_enrollment,
+     enrollments::enrollments_controller::post_bulk_enroll,
+     enrollments::enrollments_controller::delete_enrollment,
+     proctoring::proctoring_controller::post_create_proctoring_event,
+     proctoring::proctoring_controller::get_proctoring_timeline,
+     monitoring::monitoring_controller::get_session_monitor,
      storage::storage_controller::post_upload
     ),
     components(
@@ -91,19 +126,81 @@ use utoipa::{
            QuestionsCreateRequestDto,
            QuestionsUpdateRequestDto,
            QuestionsResponseListDto,
+           QuestionsTagsDto,
+           QuestionsAnswerKeyDto,
+           QuestionsAnalysisDto,
+           QuestionsAnalysisItemDto,
+           QuestionsOptionAnalysisDto,
            TestsItemDto,
            TestsCreateRequestDto,
            TestsUpdateRequestDto,
            TestsResponseListDto,
+           TestsBlueprintItemDto,
+           TestsGenerateRequestDto,
+           TestsGenerateSectionDto,
+           TestsImportRequestDto,
+           TestsImportRowErrorDto,
+           TestsImportPreviewDto,
+           TestsGenerateResponseDto,
            SessionsCreateRequestDto,
            SessionsResponseDto,
            SessionsDetailResponseDto,
            SessionsUpdateRequestDto,
+           ScoringConfigDto,
+           ScoringRuleDto,
+           KecermatanResultDto,
+           KecermatanColumnDto,
            TestsCreateRequestDto,
            TestsUpdateRequestDto,
            TestsResponseListDto,
            TestsItemAnswersDto,
            AnswersCreateRequestDto,
+           AnswersSaveRequestDto,
+           AnswerStatementDto,
+           AnswersGradeRequestDto,
+           AnswersRubricScoreDto,
+           AnswersGradingDto,
+           AnswersGradingItemDto,
+           AnswersSaveResponseDto,
+           AttemptsStartRequestDto,
+           AttemptsSubmitRequestDto,
+           AttemptsItemDto,
+           AttemptsHistoryDto,
+           AttemptsHistoryItemDto,
+           AttemptsQuestionOrderDto,
+           IrtCalibrateRequestDto,
+           IrtCalibrateResponseDto,
+           IrtItemParamsDto,
+           SessionResultsComputeRequestDto,
+           SessionResultsItemDto,
+           SessionResultsTestItemDto,
+           SessionResultsLeaderboardDto,
+           SessionResultsLeaderboardEntryDto,
+           SessionResultsRankDto,
+           SessionsEligibilityDto,
+           SessionsRetakePolicyDto,
+           SessionsVisibilityDto,
+           SessionsBroadcastRequestDto,
+           SessionsBroadcastDto,
+           SessionsBundleImportRequestDto,
+           SessionsBundleImportResponseDto,
+           SessionsBundleIdMapDto,
+           SessionsBundleConflictDto,
+           AttemptsChannelRequestDto,
+           AttemptsChannelEventDto,
+           ProctoringEventsCreateRequestDto,
+           ProctoringEventsItemDto,
+           ProctoringEventCountDto,
+           ProctoringTimelineDto,
+           MonitoringStudentDto,
+           MonitoringEventDto,
+           TestSessionsPrerequisiteDto,
+           TestSessionsLockDto,
+           EnrollmentsSelfRequestDto,
+           EnrollmentsCreateRequestDto,
+           EnrollmentsBulkRequestDto,
+           EnrollmentsItemDto,
+           EnrollmentsBulkResponseDto,
            StorageRequestDto,
            StorageResponseDto,
            ResponseSuccessDto<AuthLoginResponsetDto>,
@@ -117,9 +214,29 @@ use utoipa::{
            ResponseSuccessDto<OptionsItemDto>,
            ResponseListSuccessDto<Vec<TestsResponseListDto>>,
            ResponseSuccessDto<TestsItemDto>,
+           ResponseSuccessDto<TestsGenerateResponseDto>,
+           ResponseSuccessDto<TestsImportPreviewDto>,
            ResponseListSuccessDto<Vec<QuestionsResponseListDto>>,
            ResponseSuccessDto<QuestionsItemDto>,
-           ResponseSuccessDto<TestsItemAnswersDto>
+           ResponseSuccessDto<QuestionsAnalysisDto>,
+           ResponseSuccessDto<QuestionsAnalysisItemDto>,
+           ResponseSuccessDto<TestsItemAnswersDto>,
+           ResponseSuccessDto<AnswersSaveResponseDto>,
+           ResponseSuccessDto<AnswersGradingItemDto>,
+           ResponseListSuccessDto<Vec<AnswersGradingItemDto>>,
+           ResponseSuccessDto<AttemptsItemDto>,
+           ResponseSuccessDto<IrtCalibrateResponseDto>,
+           ResponseSuccessDto<SessionResultsItemDto>,
+           ResponseListSuccessDto<Vec<SessionResultsItemDto>>,
+           ResponseSuccessDto<SessionResultsLeaderboardDto>,
+           ResponseSuccessDto<SessionResultsRankDto>,
+           ResponseSuccessDto<EnrollmentsItemDto>,
+           ResponseListSuccessDto<Vec<EnrollmentsItemDto>>,
+           ResponseSuccessDto<EnrollmentsBulkResponseDto>,
+           ResponseSuccessDto<ProctoringEventsItemDto>,
+           ResponseSuccessDto<ProctoringTimelineDto>,
+           ResponseSuccessDto<SessionsBroadcastDto>,
+           ResponseSuccessDto<SessionsBundleImportResponseDto>
         )
     ),
     info(
@@ -161,6 +278,24 @@ use utoipa::{
         (
             name = "Questions", description = "List of Questions Endpoints"
         ),
+        (
+            name = "Attempts", description = "List of Attempts Endpoints"
+        ),
+        (
+            name = "IRT", description = "List of IRT Endpoints"
+        ),
+        (
+            name = "Session Results", description = "List of Session Results Endpoints"
+        ),
+        (
+            name = "Enrollments", description = "List of Enrollments Endpoints"
+        ),
+        (
+            name = "Proctoring", description = "List of Proctoring Endpoints"
+        ),
+        (
+            name = "Monitoring", description = "List of Monitoring Endpoints"
+        ),
     )
 )]
 
@@ -0,0 +1,163 @@
+use super::{
+	EnrollmentsBulkRequestDto, EnrollmentsBulkResponseDto, EnrollmentsCandidateDto,
+	EnrollmentsCreateRequestDto, EnrollmentsItemDto, EnrollmentsQueryDto,
+	EnrollmentsSelfRequestDto, EnrollmentsService,
+};
+use crate::{
+	permissions_guard, AppState, MessageResponseDto, MetaRequestDto, PermissionsEnum,
+	ResponseListSuccessDto, ResponseSuccessDto, UsersItemDtoRaw,
+};
+use axum::{
+	extract::{Path, Query},
+	response::IntoResponse,
+	Extension, Json,
+};
+
+#[utoipa::path(
+	get,
+	security(("Bearer" = [])),
+	path = "/v1/enrollments",
+	params(
+		("page" = Option<i64>, Query, description = "Page number"),
+		("per_page" = Option<i64>, Query, description = "Items per page"),
+		("sort_by" = Option<String>, Query, description = "Sort by field"),
+		("order" = Option<String>, Query, description = "Order ASC or DESC"),
+		("session_id" = Option<String>, Query, description = "Filter by session ID"),
+		("user_id" = Option<String>, Query, description = "Filter by user ID"),
+	),
+	responses(
+		(status = 200, description = "Get enrollment list", body = ResponseListSuccessDto<Vec<EnrollmentsItemDto>>)
+	),
+	tag = "Enrollments"
+)]
+pub async fn get_enrollment_list(
+	headers: axum::http::HeaderMap,
+	Extension(state): Extension<AppState>,
+	Query(meta): Query<MetaRequestDto>,
+	Query(query): Query<EnrollmentsQueryDto>,
+) -> impl IntoResponse {
+	match permissions_guard(
+		&headers,
+		state.clone(),
+		vec![PermissionsEnum::ManageEnrollments],
+	)
+	.await
+	{
+		Ok(_) => EnrollmentsService::get_enrollment_list(&state, query, meta).await,
+		Err(response) => response,
+	}
+}
+
+#[utoipa::path(
+	post,
+	security(("Bearer" = [])),
+	path = "/v1/enrollments/self",
+	request_body = EnrollmentsSelfRequestDto,
+	responses(
+		(status = 200, description = "Enroll the current user into a session", body = ResponseSuccessDto<EnrollmentsItemDto>)
+	),
+	tag = "Enrollments"
+)]
+pub async fn post_self_enroll(
+	headers: axum::http::HeaderMap,
+	Extension(state): Extension<AppState>,
+	extensions: axum::http::Extensions,
+	Json(payload): Json<EnrollmentsSelfRequestDto>,
+) -> impl IntoResponse {
+	match permissions_guard(
+		&headers,
+		state.clone(),
+		vec![PermissionsEnum::CreateEnrollments],
+	)
+	.await
+	{
+		Ok(_) => {
+			let candidate = extensions
+				.get::<UsersItemDtoRaw>()
+				.map(EnrollmentsCandidateDto::from);
+			EnrollmentsService::self_enroll(&state, candidate, payload.session_id).await
+		}
+		Err(response) => response,
+	}
+}
+
+#[utoipa::path(
+	post,
+	security(("Bearer" = [])),
+	path = "/v1/enrollments/create",
+	request_body = EnrollmentsCreateRequestDto,
+	responses(
+		(status = 200, description = "Enroll a user into a session", body = ResponseSuccessDto<EnrollmentsItemDto>)
+	),
+	tag = "Enrollments"
+)]
+pub async fn post_create_enrollment(
+	headers: axum::http::HeaderMap,
+	Extension(state): Extension<AppState>,
+	Json(payload): Json<EnrollmentsCreateRequestDto>,
+) -> impl IntoResponse {
+	match permissions_guard(
+		&headers,
+		state.clone(),
+		vec![PermissionsEnum::ManageEnrollments],
+	)
+	.await
+	{
+		Ok(_) => EnrollmentsService::create_enrollment(&state, payload).await,
+		Err(response) => response,
+	}
+}
+
+#[utoipa::path(
+	post,
+	security(("Bearer" = [])),
+	path = "/v1/enrollments/bulk",
+	request_body = EnrollmentsBulkRequestDto,
+	responses(
+		(status = 200, description = "Enroll many users into a session", body = ResponseSuccessDto<EnrollmentsBulkResponseDto>)
+	),
+	tag = "Enrollments"
+)]
+pub async fn post_bulk_enroll(
+	headers: axum::http::HeaderMap,
+	Extension(state): Extension<AppState>,
+	Json(payload): Json<EnrollmentsBulkRequestDto>,
+) -> impl IntoResponse {
+	match permissions_guard(
+		&headers,
+		state.clone(),
+		vec![PermissionsEnum::ManageEnrollments],
+	)
+	.await
+	{
+		Ok(_) => EnrollmentsService::bulk_enroll(&state, payload).await,
+		Err(response) => response,
+	}
+}
+
+#[utoipa::path(
+	delete,
+	security(("Bearer" = [])),
+	path = "/v1/enrollments/delete/{id}",
+	params(("id" = String, Path, description = "Enrollment ID")),
+	responses(
+		(status = 200, description = "Delete enrollment", body = MessageResponseDto)
+	),
+	tag = "Enrollments"
+)]
+pub async fn delete_enrollment(
+	headers: axum::http::HeaderMap,
+	Extension(state): Extension<AppState>,
+	Path(id): Path<String>,
+) -> impl IntoResponse {
+	match permissions_guard(
+		&headers,
+		state.clone(),
+		vec![PermissionsEnum::ManageEnrollments],
+	)
+	.await
+	{
+		Ok(_) => EnrollmentsService::delete_enrollment(&state, id).await,
+		Err(response) => response,
+	}
+}
@@ -0,0 +1,79 @@
+use super::enrollments_repository_test::seed_enrollment_session;
+use super::{
+	enrollments_router, EnrollmentsCreateRequestDto, EnrollmentsSelfRequestDto,
+};
+use crate::{create_mock_app_state, AppState, PermissionsEnum};
+use axum::{Extension, Router};
+use axum_test::TestServer;
+use najm_course_utils::authorized;
+
+fn create_test_app(state: AppState) -> TestServer {
+	let app = Router::new()
+		.nest("/v1/enrollments", enrollments_router())
+		.layer(Extension(state));
+	TestServer::new(app).unwrap()
+}
+
+#[tokio::test]
+async fn test_get_enrollment_list_should_return_200() {
+	let state = create_mock_app_state().await;
+	let server = create_test_app(state);
+	let res = authorized::<()>(
+		&server,
+		"GET",
+		"/v1/enrollments?session_id=any_session",
+		vec![&PermissionsEnum::ManageEnrollments.to_string()],
+		None,
+	)
+	.await;
+	assert_eq!(res.status_code(), 200);
+}
+
+#[tokio::test]
+async fn test_post_create_enrollment_should_return_404_if_user_not_found() {
+	let state = create_mock_app_state().await;
+	let session_id = seed_enrollment_session(&state.surrealdb_ws, "Ghost", "", "NONE")
+		.await
+		.unwrap();
+	let server = create_test_app(state);
+	let payload = EnrollmentsCreateRequestDto {
+		user_id: "ghost".into(),
+		session_id,
+		group: None,
+	};
+	let res = authorized(
+		&server,
+		"POST",
+		"/v1/enrollments/create",
+		vec![&PermissionsEnum::ManageEnrollments.to_string()],
+		Some(&payload),
+	)
+	.await;
+	assert_eq!(res.status_code(), 404);
+	let res = authorized(
+		&server,
+		"POST",
+		"/v1/enrollments/create",
+		vec![&PermissionsEnum::CreateEnrollments.to_string()],
+		Some(&payload),
+	)
+	.await;
+	assert_eq!(res.status_code(), 403);
+}
+
+#[tokio::test]
+async fn test_post_self_enroll_should_return_401_without_user() {
+	let state = create_mock_app_state().await;
+	let server = create_test_app(state);
+	let res = authorized(
+		&server,
+		"POST",
+		"/v1/enrollments/self",
+		vec![&PermissionsEnum::CreateEnrollments.to_string()],
+		Some(&EnrollmentsSelfRequestDto {
+			session_id: "any_session".into(),
+		}),
+	)
+	.await;
+	assert_eq!(res.status_code(), 401);
+}
@@ -0,0 +1,99 @@
+use super::EnrollmentsSchema;
+use crate::{PermissionsEnum, UsersItemDtoRaw};
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+use validator::Validate;
+
+#[derive(Clone, Debug, Serialize, Deserialize, Validate, ToSchema)]
+pub struct EnrollmentsCreateRequestDto {
+	#[validate(length(min = 1))]
+	#[schema(example = "uuid")]
+	pub user_id: String,
+
+	#[validate(length(min = 1))]
+	#[schema(example = "uuid")]
+	pub session_id: String,
+
+	#[schema(example = "IPA-1")]
+	pub group: Option<String>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, Validate, ToSchema)]
+pub struct EnrollmentsBulkRequestDto {
+	#[validate(length(min = 1))]
+	#[schema(example = "uuid")]
+	pub session_id: String,
+
+	#[validate(length(min = 1))]
+	pub user_ids: Vec<String>,
+
+	#[schema(example = "IPA-1")]
+	pub group: Option<String>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, Validate, ToSchema)]
+pub struct EnrollmentsSelfRequestDto {
+	#[validate(length(min = 1))]
+	#[schema(example = "uuid")]
+	pub session_id: String,
+}
+
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct EnrollmentsQueryDto {
+	pub session_id: Option<String>,
+	pub user_id: Option<String>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, ToSchema)]
+pub struct EnrollmentsItemDto {
+	pub id: String,
+	pub user_id: String,
+	pub session_id: String,
+	#[schema(example = "admin")]
+	pub source: String,
+	pub group: Option<String>,
+	pub created_at: String,
+	pub updated_at: String,
+}
+
+impl From<EnrollmentsSchema> for EnrollmentsItemDto {
+	fn from(value: EnrollmentsSchema) -> Self {
+		Self {
+			id: value.id.id.to_raw(),
+			user_id: value.user.id.to_raw(),
+			session_id: value.session.id.to_raw(),
+			source: value.source,
+			group: value.group,
+			created_at: value.created_at,
+			updated_at: value.updated_at,
+		}
+	}
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, ToSchema)]
+pub struct EnrollmentsBulkResponseDto {
+	pub enrolled: Vec<EnrollmentsItemDto>,
+	pub skipped: Vec<String>,
+}
+
+/// The user an eligibility check is made for. Enrollment managers bypass
+/// every rule.
+#[derive(Clone, Debug, Default)]
+pub struct EnrollmentsCandidateDto {
+	pub user_id: String,
+	pub student_type: String,
+	pub role: String,
+	pub is_manager: bool,
+}
+
+impl From<&UsersItemDtoRaw> for EnrollmentsCandidateDto {
+	fn from(value: &UsersItemDtoRaw) -> Self {
+		let manage = PermissionsEnum::ManageEnrollments.to_string();
+		Self {
+			user_id: value.id.id.to_raw(),
+			student_type: value.student_type.clone(),
+			role: value.role.name.clone(),
+			is_manager: value.role.permissions.iter().any(|p| p.name == manage),
+		}
+	}
+}
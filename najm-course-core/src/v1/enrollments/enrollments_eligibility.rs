@@ -0,0 +1,99 @@
+use super::{EnrollmentsCandidateDto, EnrollmentsSchema};
+use crate::SessionsSchema;
+use anyhow::{bail, Result};
+use najm_course_libs::ResourceEnum;
+
+fn quote(value: &str) -> String {
+	serde_json::to_string(value).unwrap_or_else(|_| "''".into())
+}
+
+/// Checks the candidate's student type and role against the session. An empty
+/// session student type accepts everyone.
+pub fn check_profile(
+	session: &SessionsSchema,
+	candidate: &EnrollmentsCandidateDto,
+) -> Result<()> {
+	if candidate.is_manager {
+		return Ok(());
+	}
+	let student_type = session.student_type.trim();
+	if !student_type.is_empty()
+		&& !student_type.eq_ignore_ascii_case(candidate.student_type.trim())
+	{
+		bail!("Student type is not eligible for this session");
+	}
+	let rules = session.eligibility.clone().unwrap_or_default();
+	if !rules.roles.is_empty() && !rules.roles.contains(&candidate.role) {
+		bail!("Role is not eligible for this session");
+	}
+	Ok(())
+}
+
+/// Full eligibility check. Group rules match the group of the candidate's
+/// enrollment in this session.
+pub fn check_eligibility(
+	session: &SessionsSchema,
+	candidate: &EnrollmentsCandidateDto,
+	enrollment: Option<&EnrollmentsSchema>,
+) -> Result<()> {
+	check_profile(session, candidate)?;
+	if candidate.is_manager {
+		return Ok(());
+	}
+	let rules = session.eligibility.clone().unwrap_or_default();
+	let group = enrollment.and_then(|e| e.group.as_ref());
+	if !rules.groups.is_empty() && !group.is_some_and(|g| rules.groups.contains(g)) {
+		bail!("Group is not eligible for this session");
+	}
+	if rules.requires_enrollment && enrollment.is_none() {
+		bail!("Enrollment is required for this session");
+	}
+	Ok(())
+}
+
+/// SurrealQL conditions mirroring `check_eligibility` for session listings,
+/// given every active enrollment of the candidate.
+pub fn eligibility_conditions(
+	candidate: &EnrollmentsCandidateDto,
+	enrollments: &[EnrollmentsSchema],
+) -> Vec<String> {
+	if candidate.is_manager {
+		return vec![];
+	}
+	let session = |e: &EnrollmentsSchema| {
+		format!("{}:⟨{}⟩", ResourceEnum::Sessions, e.session.id.to_raw())
+	};
+	let enrolled: Vec<String> = enrollments.iter().map(session).collect();
+	let grouped: Vec<String> = enrollments
+		.iter()
+		.filter_map(|e| {
+			e.group.as_ref().map(|group| {
+				format!(
+					"(id = {} AND {} INSIDE eligibility.groups)",
+					session(e),
+					quote(group)
+				)
+			})
+		})
+		.collect();
+	let grouped = if grouped.is_empty() {
+		"false".to_string()
+	} else {
+		grouped.join(" OR ")
+	};
+	vec![
+		format!(
+			"(string::trim(student_type) = '' OR string::lowercase(string::trim(student_type)) = {})",
+			quote(&candidate.student_type.trim().to_lowercase())
+		),
+		format!(
+			"(array::len(eligibility.roles ?? []) = 0 OR {} INSIDE eligibility.roles)",
+			quote(&candidate.role)
+		),
+		format!("(array::len(eligibility.groups ?? []) = 0 OR {})", grouped),
+		format!(
+			"(eligibility.requires_enrollment != true OR id INSIDE [{}])",
+			enrolled.join(", ")
+		),
+	]
+}
@@ -0,0 +1,109 @@
+use super::*;
+use crate::{SessionsEligibilitySchema, SessionsSchema};
+use najm_course_utils::make_thing;
+
+fn session(
+	student_type: &str,
+	eligibility: SessionsEligibilitySchema,
+) -> SessionsSchema {
+	SessionsSchema {
+		id: make_thing("app_sessions", "session"),
+		name: "Session".into(),
+		tests: vec![],
+		category: "Akademik".into(),
+		description: "Session".into(),
+		student_type: student_type.into(),
+		passing_grade: None,
+		eligibility: Some(eligibility),
+		retake_policy: None,
+		visibility: None,
+		is_active: true,
+		is_deleted: false,
+		created_at: "2025-01-01T00:00:00Z".into(),
+		updated_at: "2025-01-01T00:00:00Z".into(),
+	}
+}
+
+fn enrollment(group: Option<&str>) -> EnrollmentsSchema {
+	EnrollmentsSchema {
+		id: make_thing("app_enrollments", "enrollment"),
+		user: make_thing("app_users", "user"),
+		session: make_thing("app_sessions", "session"),
+		source: EnrollmentsSourceEnum::Admin.to_string(),
+		group: group.map(String::from),
+		is_deleted: false,
+		created_at: "2025-01-01T00:00:00Z".into(),
+		updated_at: "2025-01-01T00:00:00Z".into(),
+	}
+}
+
+fn candidate(student_type: &str, role: &str) -> EnrollmentsCandidateDto {
+	EnrollmentsCandidateDto {
+		user_id: "user".into(),
+		student_type: student_type.into(),
+		role: role.into(),
+		is_manager: false,
+	}
+}
+
+#[test]
+fn test_check_profile_should_match_student_type_and_role() {
+	let rules = SessionsEligibilitySchema {
+		roles: vec!["Student".into()],
+		..Default::default()
+	};
+	let open = session("", rules.clone());
+	assert!(check_profile(&open, &candidate("Alumni", "Student")).is_ok());
+	let sma = session("SMA", rules);
+	assert!(check_profile(&sma, &candidate(" sma ", "Student")).is_ok());
+	assert_eq!(
+		check_profile(&sma, &candidate("TNI", "Student"))
+			.unwrap_err()
+			.to_string(),
+		"Student type is not eligible for this session"
+	);
+	assert_eq!(
+		check_profile(&sma, &candidate("SMA", "Tutor"))
+			.unwrap_err()
+			.to_string(),
+		"Role is not eligible for this session"
+	);
+}
+
+#[test]
+fn test_check_eligibility_should_require_enrollment_group() {
+	let grouped = session(
+		"",
+		SessionsEligibilitySchema {
+			groups: vec!["IPA-1".into()],
+			requires_enrollment: true,
+			..Default::default()
+		},
+	);
+	let student = candidate("SMA", "Student");
+	assert!(
+		check_eligibility(&grouped, &student, Some(&enrollment(Some("IPA-1")))).is_ok()
+	);
+	assert!(check_eligibility(&grouped, &student, Some(&enrollment(None))).is_err());
+	assert!(check_eligibility(&grouped, &student, None).is_err());
+	let manager = EnrollmentsCandidateDto {
+		is_manager: true,
+		..candidate("TNI", "Admin")
+	};
+	assert!(check_eligibility(&grouped, &manager, None).is_ok());
+}
+
+#[test]
+fn test_eligibility_conditions_should_quote_values() {
+	let student = candidate("SMA", "Student \"Lead\"");
+	let conditions = eligibility_conditions(&student, &[enrollment(Some("IPA-1"))]);
+	assert_eq!(conditions.len(), 4);
+	assert!(conditions[1].contains(r#""Student \"Lead\"" INSIDE eligibility.roles"#));
+	assert!(conditions[2].contains(r#"id = app_sessions:⟨session⟩ AND "IPA-1""#));
+	assert!(conditions[3].contains("INSIDE [app_sessions:⟨session⟩]"));
+	let manager = EnrollmentsCandidateDto {
+		is_manager: true,
+		..student
+	};
+	assert!(eligibility_conditions(&manager, &[]).is_empty());
+}
@@ -0,0 +1,19 @@
+use std::fmt;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum EnrollmentsSourceEnum {
+	SelfEnrolled,
+	Admin,
+	Bulk,
+}
+
+impl fmt::Display for EnrollmentsSourceEnum {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		let source_str = match self {
+			EnrollmentsSourceEnum::SelfEnrolled => "self",
+			EnrollmentsSourceEnum::Admin => "admin",
+			EnrollmentsSourceEnum::Bulk => "bulk",
+		};
+		write!(f, "{}", source_str)
+	}
+}
@@ -0,0 +1,27 @@
+use super::{
+	enrollment_error_status, EnrollmentsCandidateDto, EnrollmentsRepository,
+};
+use crate::{common_response, AppState, UsersItemDtoRaw};
+use axum::{http::StatusCode, response::Response};
+
+/// Rejects users who are not eligible for the session, and requests without a
+/// resolved user.
+pub async fn eligibility_guard(
+	state: &AppState,
+	user: Option<&UsersItemDtoRaw>,
+	session_id: &str,
+) -> Result<(), Response> {
+	let Some(user) = user else {
+		return Err(common_response(
+			StatusCode::UNAUTHORIZED,
+			"Unauthorized user",
+		));
+	};
+	EnrollmentsRepository::new(state)
+		.query_ensure_eligible(&EnrollmentsCandidateDto::from(user), session_id)
+		.await
+		.map_err(|e| {
+			let msg = e.to_string();
+			common_response(enrollment_error_status(&msg), &msg)
+		})
+}
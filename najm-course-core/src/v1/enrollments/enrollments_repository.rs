@@ -0,0 +1,274 @@
+use super::{
+	check_eligibility, check_profile, EnrollmentsBulkRequestDto,
+	EnrollmentsBulkResponseDto, EnrollmentsCandidateDto, EnrollmentsCreateRequestDto,
+	EnrollmentsItemDto, EnrollmentsQueryDto, EnrollmentsSchema, EnrollmentsSourceEnum,
+};
+use crate::{AppState, MetaRequestDto, ResponseListSuccessDto, SessionsRepository};
+use anyhow::{bail, Result};
+use najm_course_libs::ResourceEnum;
+use najm_course_utils::{get_id, get_iso_date, make_thing, query_list_with_meta};
+use serde_json::json;
+use validator::Validate;
+
+pub struct EnrollmentsRepository<'a> {
+	state: &'a AppState,
+}
+
+impl<'a> EnrollmentsRepository<'a> {
+	pub fn new(state: &'a AppState) -> Self {
+		Self { state }
+	}
+
+	pub async fn query_raw_enrollment_by_id(
+		&self,
+		id: &str,
+	) -> Result<EnrollmentsSchema> {
+		let db = &self.state.surrealdb_ws;
+		let enrollment: Option<EnrollmentsSchema> = db
+			.select((ResourceEnum::Enrollments.to_string(), id))
+			.await?;
+		match enrollment {
+			Some(e) if !e.is_deleted => Ok(e),
+			_ => bail!("Enrollment not found"),
+		}
+	}
+
+	pub async fn query_enrollments_by_user(
+		&self,
+		user_id: &str,
+	) -> Result<Vec<EnrollmentsSchema>> {
+		let db = &self.state.surrealdb_ws;
+		let enrollments: Vec<EnrollmentsSchema> = db
+			.query(format!(
+				"SELECT * FROM {} WHERE user = app_users:⟨{}⟩ AND is_deleted = false",
+				ResourceEnum::Enrollments,
+				user_id
+			))
+			.await?
+			.take(0)?;
+		Ok(enrollments)
+	}
+
+	pub async fn query_enrollment_by_user(
+		&self,
+		user_id: &str,
+		session_id: &str,
+	) -> Result<Option<EnrollmentsSchema>> {
+		let db = &self.state.surrealdb_ws;
+		let enrollments: Vec<EnrollmentsSchema> = db
+			.query(format!(
+				"SELECT * FROM {} WHERE user = app_users:⟨{}⟩ AND session = app_sessions:⟨{}⟩ AND is_deleted = false LIMIT 1",
+				ResourceEnum::Enrollments,
+				user_id,
+				session_id
+			))
+			.await?
+			.take(0)?;
+		Ok(enrollments.into_iter().next())
+	}
+
+	pub async fn query_ensure_eligible(
+		&self,
+		candidate: &EnrollmentsCandidateDto,
+		session_id: &str,
+	) -> Result<()> {
+		if candidate.is_manager {
+			return Ok(());
+		}
+		let session = SessionsRepository::new(self.state)
+			.query_raw_session_by_id(session_id)
+			.await?;
+		let enrollment = self
+			.query_enrollment_by_user(&candidate.user_id, session_id)
+			.await?;
+		check_eligibility(&session, candidate, enrollment.as_ref())
+	}
+
+	pub async fn query_enrollment_list(
+		&self,
+		query: EnrollmentsQueryDto,
+		meta: MetaRequestDto,
+	) -> Result<ResponseListSuccessDto<Vec<EnrollmentsItemDto>>> {
+		let mut conditions = vec!["is_deleted = false".to_string()];
+		if let Some(session_id) = &query.session_id {
+			conditions.push(format!("session = app_sessions:⟨{}⟩", session_id));
+		}
+		if let Some(user_id) = &query.user_id {
+			conditions.push(format!("user = app_users:⟨{}⟩", user_id));
+		}
+		let raw = query_list_with_meta::<EnrollmentsSchema>(
+			&self.state.surrealdb_ws,
+			&ResourceEnum::Enrollments.to_string(),
+			&meta,
+			conditions,
+			None,
+		)
+		.await?;
+		Ok(ResponseListSuccessDto {
+			data: raw.data.into_iter().map(EnrollmentsItemDto::from).collect(),
+			meta: raw.meta,
+		})
+	}
+
+	async fn query_existing_users(&self, user_ids: &[String]) -> Result<Vec<String>> {
+		let db = &self.state.surrealdb_ws;
+		let things: Vec<String> = user_ids
+			.iter()
+			.map(|id| format!("{}:⟨{}⟩", ResourceEnum::Users, id))
+			.collect();
+		let existing: Vec<String> = db
+			.query(format!(
+				"SELECT VALUE meta::id(id) FROM {} WHERE id INSIDE [{}] AND is_deleted = false",
+				ResourceEnum::Users,
+				things.join(", ")
+			))
+			.await?
+			.take(0)?;
+		Ok(existing)
+	}
+
+	async fn query_insert_enrollment(
+		&self,
+		user_id: &str,
+		session_id: &str,
+		group: Option<String>,
+		source: EnrollmentsSourceEnum,
+	) -> Result<EnrollmentsItemDto> {
+		let db = &self.state.surrealdb_ws;
+		let id = surrealdb::Uuid::new_v4().to_string();
+		let now = get_iso_date();
+		let enrollment = EnrollmentsSchema {
+			id: make_thing(&ResourceEnum::Enrollments.to_string(), &id),
+			user: make_thing(&ResourceEnum::Users.to_string(), user_id),
+			session: make_thing(&ResourceEnum::Sessions.to_string(), session_id),
+			source: source.to_string(),
+			group: group.filter(|g| !g.trim().is_empty()),
+			is_deleted: false,
+			created_at: now.clone(),
+			updated_at: now,
+		};
+		let created: Option<EnrollmentsSchema> = db
+			.create((ResourceEnum::Enrollments.to_string(), id))
+			.content(enrollment)
+			.await?;
+		match created {
+			Some(e) => Ok(EnrollmentsItemDto::from(e)),
+			None => bail!("Failed to create enrollment"),
+		}
+	}
+
+	pub async fn query_self_enroll(
+		&self,
+		candidate: &EnrollmentsCandidateDto,
+		session_id: &str,
+	) -> Result<EnrollmentsItemDto> {
+		let session = SessionsRepository::new(self.state)
+			.query_raw_session_by_id(session_id)
+			.await?;
+		let rules = session.eligibility.clone().unwrap_or_default();
+		if !rules.allow_self_enrollment {
+			bail!("Self enrollment is not allowed");
+		}
+		check_profile(&session, candidate)?;
+		if !rules.groups.is_empty() {
+			bail!("Group is not eligible for this session");
+		}
+		if self
+			.query_enrollment_by_user(&candidate.user_id, session_id)
+			.await?
+			.is_some()
+		{
+			bail!("Already enrolled");
+		}
+		self
+			.query_insert_enrollment(
+				&candidate.user_id,
+				session_id,
+				None,
+				EnrollmentsSourceEnum::SelfEnrolled,
+			)
+			.await
+	}
+
+	pub async fn query_create_enrollment(
+		&self,
+		payload: EnrollmentsCreateRequestDto,
+	) -> Result<EnrollmentsItemDto> {
+		payload.validate()?;
+		SessionsRepository::new(self.state)
+			.query_raw_session_by_id(&payload.session_id)
+			.await?;
+		if self
+			.query_existing_users(std::slice::from_ref(&payload.user_id))
+			.await?
+			.is_empty()
+		{
+			bail!("User not found");
+		}
+		if self
+			.query_enrollment_by_user(&payload.user_id, &payload.session_id)
+			.await?
+			.is_some()
+		{
+			bail!("Already enrolled");
+		}
+		self
+			.query_insert_enrollment(
+				&payload.user_id,
+				&payload.session_id,
+				payload.group,
+				EnrollmentsSourceEnum::Admin,
+			)
+			.await
+	}
+
+	pub async fn query_bulk_enroll(
+		&self,
+		payload: EnrollmentsBulkRequestDto,
+	) -> Result<EnrollmentsBulkResponseDto> {
+		payload.validate()?;
+		SessionsRepository::new(self.state)
+			.query_raw_session_by_id(&payload.session_id)
+			.await?;
+		let mut user_ids = payload.user_ids.clone();
+		user_ids.sort();
+		user_ids.dedup();
+		let existing = self.query_existing_users(&user_ids).await?;
+		let mut enrolled = vec![];
+		let mut skipped = vec![];
+		for user_id in user_ids {
+			let is_enrolled = self
+				.query_enrollment_by_user(&user_id, &payload.session_id)
+				.await?
+				.is_some();
+			if !existing.contains(&user_id) || is_enrolled {
+				skipped.push(user_id);
+				continue;
+			}
+			enrolled.push(
+				self
+					.query_insert_enrollment(
+						&user_id,
+						&payload.session_id,
+						payload.group.clone(),
+						EnrollmentsSourceEnum::Bulk,
+					)
+					.await?,
+			);
+		}
+		Ok(EnrollmentsBulkResponseDto { enrolled, skipped })
+	}
+
+	pub async fn query_delete_enrollment(&self, id: &str) -> Result<String> {
+		let db = &self.state.surrealdb_ws;
+		let enrollment = self.query_raw_enrollment_by_id(id).await?;
+		let _: Option<EnrollmentsSchema> = db
+			.update(get_id(&enrollment.id)?)
+			.merge(json!({
+				"is_deleted": true,
+				"updated_at": get_iso_date(),
+			}))
+			.await?;
+		Ok("Success delete enrollment".into())
+	}
+}
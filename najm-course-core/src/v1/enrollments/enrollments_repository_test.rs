@@ -0,0 +1,289 @@
+use super::{
+	EnrollmentsBulkRequestDto, EnrollmentsCandidateDto, EnrollmentsCreateRequestDto,
+	EnrollmentsQueryDto, EnrollmentsRepository,
+};
+use crate::{create_mock_app_state, MetaRequestDto, SessionsRepository};
+use anyhow::Result;
+use najm_course_entities::SurrealWsClient;
+use najm_course_utils::get_iso_date;
+use surrealdb::Uuid;
+
+pub async fn seed_enrollment_session(
+	db: &SurrealWsClient,
+	name: &str,
+	student_type: &str,
+	eligibility: &str,
+) -> Result<String> {
+	let now = get_iso_date();
+	let session_id = Uuid::new_v4().to_string();
+	db.query(format!(
+		"CREATE app_sessions:⟨{session_id}⟩ SET name = '{name}', category = 'Akademik', description = 'Enrollment', student_type = '{student_type}', passing_grade = NONE, eligibility = {eligibility}, tests = [], is_active = true, is_deleted = false, created_at = '{now}', updated_at = '{now}';"
+	))
+	.await?;
+	Ok(session_id)
+}
+
+pub async fn seed_enrollment_user(
+	db: &SurrealWsClient,
+	student_type: &str,
+) -> Result<String> {
+	let now = get_iso_date();
+	let user_id = Uuid::new_v4().to_string();
+	db.query(format!(
+		"CREATE app_users:⟨{user_id}⟩ SET fullname = 'Peserta', student_type = '{student_type}', is_deleted = false, created_at = '{now}', updated_at = '{now}';"
+	))
+	.await?;
+	Ok(user_id)
+}
+
+fn candidate(user_id: &str, student_type: &str) -> EnrollmentsCandidateDto {
+	EnrollmentsCandidateDto {
+		user_id: user_id.into(),
+		student_type: student_type.into(),
+		role: "Student".into(),
+		is_manager: false,
+	}
+}
+
+#[tokio::test]
+async fn test_query_self_enroll_should_check_profile_and_duplicates() {
+	let state = create_mock_app_state().await;
+	let db = &state.surrealdb_ws;
+	let session_id = seed_enrollment_session(
+		db,
+		"Self Enroll",
+		"SMA",
+		"{ requires_enrollment: true, allow_self_enrollment: true }",
+	)
+	.await
+	.unwrap();
+	let repo = EnrollmentsRepository::new(&state);
+	let alumni = candidate(&Uuid::new_v4().to_string(), "Alumni");
+	let res = repo.query_self_enroll(&alumni, &session_id).await;
+	assert_eq!(
+		res.unwrap_err().to_string(),
+		"Student type is not eligible for this session"
+	);
+	let student = candidate(&Uuid::new_v4().to_string(), "sma");
+	let res = repo.query_ensure_eligible(&student, &session_id).await;
+	assert_eq!(
+		res.unwrap_err().to_string(),
+		"Enrollment is required for this session"
+	);
+	let enrollment = repo.query_self_enroll(&student, &session_id).await.unwrap();
+	assert_eq!(enrollment.source, "self");
+	assert!(repo
+		.query_ensure_eligible(&student, &session_id)
+		.await
+		.is_ok());
+	let res = repo.query_self_enroll(&student, &session_id).await;
+	assert_eq!(res.unwrap_err().to_string(), "Already enrolled");
+}
+
+#[tokio::test]
+async fn test_query_self_enroll_should_fail_if_not_allowed() {
+	let state = create_mock_app_state().await;
+	let session_id = seed_enrollment_session(
+		&state.surrealdb_ws,
+		"Closed",
+		"",
+		"{ requires_enrollment: true }",
+	)
+	.await
+	.unwrap();
+	let res = EnrollmentsRepository::new(&state)
+		.query_self_enroll(&candidate("user", "SMA"), &session_id)
+		.await;
+	assert_eq!(
+		res.unwrap_err().to_string(),
+		"Self enrollment is not allowed"
+	);
+}
+
+#[tokio::test]
+async fn test_query_ensure_eligible_should_match_enrollment_group() {
+	let state = create_mock_app_state().await;
+	let db = &state.surrealdb_ws;
+	let session_id =
+		seed_enrollment_session(db, "Grouped", "", "{ groups: ['IPA-1'] }")
+			.await
+			.unwrap();
+	let repo = EnrollmentsRepository::new(&state);
+	let mut users = vec![];
+	for group in ["IPA-1", "IPS-1"] {
+		let user_id = seed_enrollment_user(db, "SMA").await.unwrap();
+		repo
+			.query_create_enrollment(EnrollmentsCreateRequestDto {
+				user_id: user_id.clone(),
+				session_id: session_id.clone(),
+				group: Some(group.into()),
+			})
+			.await
+			.unwrap();
+		users.push(user_id);
+	}
+	assert!(repo
+		.query_ensure_eligible(&candidate(&users[0], "SMA"), &session_id)
+		.await
+		.is_ok());
+	let res = repo
+		.query_ensure_eligible(&candidate(&users[1], "SMA"), &session_id)
+		.await;
+	assert_eq!(
+		res.unwrap_err().to_string(),
+		"Group is not eligible for this session"
+	);
+	let manager = EnrollmentsCandidateDto {
+		is_manager: true,
+		..candidate("admin", "")
+	};
+	assert!(repo
+		.query_ensure_eligible(&manager, &session_id)
+		.await
+		.is_ok());
+}
+
+#[tokio::test]
+async fn test_query_bulk_enroll_should_skip_unknown_and_existing_users() {
+	let state = create_mock_app_state().await;
+	let db = &state.surrealdb_ws;
+	let session_id = seed_enrollment_session(db, "Bulk", "", "NONE")
+		.await
+		.unwrap();
+	let repo = EnrollmentsRepository::new(&state);
+	let first = seed_enrollment_user(db, "SMA").await.unwrap();
+	let second = seed_enrollment_user(db, "SMA").await.unwrap();
+	repo
+		.query_create_enrollment(EnrollmentsCreateRequestDto {
+			user_id: first.clone(),
+			session_id: session_id.clone(),
+			group: None,
+		})
+		.await
+		.unwrap();
+	let res = repo
+		.query_bulk_enroll(EnrollmentsBulkRequestDto {
+			session_id: session_id.clone(),
+			user_ids: vec![
+				first.clone(),
+				second.clone(),
+				second.clone(),
+				"ghost".into(),
+			],
+			group: Some("IPA-1".into()),
+		})
+		.await
+		.unwrap();
+	assert_eq!(res.enrolled.len(), 1);
+	assert_eq!(res.enrolled[0].user_id, second);
+	assert_eq!(res.enrolled[0].group.as_deref(), Some("IPA-1"));
+	assert_eq!(res.skipped.len(), 2);
+	let query = EnrollmentsQueryDto {
+		session_id: Some(session_id.clone()),
+		user_id: None,
+	};
+	let list = repo
+		.query_enrollment_list(query.clone(), MetaRequestDto::default())
+		.await
+		.unwrap();
+	assert_eq!(list.data.len(), 2);
+	repo
+		.query_delete_enrollment(&res.enrolled[0].id)
+		.await
+		.unwrap();
+	let list = repo
+		.query_enrollment_list(query, MetaRequestDto::default())
+		.await
+		.unwrap();
+	assert_eq!(list.data.len(), 1);
+}
+
+#[tokio::test]
+async fn test_query_create_enrollment_should_fail_if_user_not_found() {
+	let state = create_mock_app_state().await;
+	let session_id = seed_enrollment_session(&state.surrealdb_ws, "Ghost", "", "NONE")
+		.await
+		.unwrap();
+	let res = EnrollmentsRepository::new(&state)
+		.query_create_enrollment(EnrollmentsCreateRequestDto {
+			user_id: "ghost".into(),
+			session_id,
+			group: None,
+		})
+		.await;
+	assert_eq!(res.unwrap_err().to_string(), "User not found");
+}
+
+#[tokio::test]
+async fn test_query_session_list_for_user_should_hide_ineligible_sessions() {
+	let state = create_mock_app_state().await;
+	let db = &state.surrealdb_ws;
+	let token = Uuid::new_v4().simple().to_string();
+	let open = seed_enrollment_session(db, &format!("{token} open"), "SMA", "NONE")
+		.await
+		.unwrap();
+	seed_enrollment_session(db, &format!("{token} alumni"), "Alumni", "NONE")
+		.await
+		.unwrap();
+	let enrolled = seed_enrollment_session(
+		db,
+		&format!("{token} enrolled"),
+		"",
+		"{ requires_enrollment: true }",
+	)
+	.await
+	.unwrap();
+	let grouped = seed_enrollment_session(
+		db,
+		&format!("{token} grouped"),
+		"",
+		"{ groups: ['IPA-1'], roles: ['Student'] }",
+	)
+	.await
+	.unwrap();
+	let user_id = seed_enrollment_user(db, "SMA").await.unwrap();
+	let student = candidate(&user_id, "SMA");
+	let meta = MetaRequestDto {
+		search: Some(token.clone()),
+		per_page: Some(50),
+		..Default::default()
+	};
+	let sessions = SessionsRepository::new(&state);
+	let visible = |list: Vec<crate::SessionsResponseDto>| {
+		let mut ids: Vec<String> = list.into_iter().map(|s| s.id).collect();
+		ids.sort();
+		ids
+	};
+	let list = sessions
+		.query_session_list_for_user(meta.clone(), &student)
+		.await
+		.unwrap();
+	assert_eq!(visible(list.data), vec![open.clone()]);
+	let repo = EnrollmentsRepository::new(&state);
+	for (session_id, group) in [(&enrolled, None), (&grouped, Some("IPA-1".into()))] {
+		repo
+			.query_create_enrollment(EnrollmentsCreateRequestDto {
+				user_id: user_id.clone(),
+				session_id: session_id.clone(),
+				group,
+			})
+			.await
+			.unwrap();
+	}
+	let list = sessions
+		.query_session_list_for_user(meta.clone(), &student)
+		.await
+		.unwrap();
+	let mut expected = vec![open, enrolled, grouped];
+	expected.sort();
+	assert_eq!(visible(list.data), expected);
+	let manager = EnrollmentsCandidateDto {
+		is_manager: true,
+		..student
+	};
+	let list = sessions
+		.query_session_list_for_user(meta, &manager)
+		.await
+		.unwrap();
+	assert_eq!(list.data.len(), 4);
+}
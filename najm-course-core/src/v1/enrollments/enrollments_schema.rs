@@ -0,0 +1,14 @@
+use serde::{Deserialize, Serialize};
+use surrealdb::sql::Thing;
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct EnrollmentsSchema {
+	pub id: Thing,
+	pub user: Thing,
+	pub session: Thing,
+	pub source: String,
+	pub group: Option<String>,
+	pub is_deleted: bool,
+	pub created_at: String,
+	pub updated_at: String,
+}
@@ -0,0 +1,104 @@
+use super::{
+	EnrollmentsBulkRequestDto, EnrollmentsCandidateDto, EnrollmentsCreateRequestDto,
+	EnrollmentsQueryDto, EnrollmentsRepository,
+};
+use crate::{
+	common_response, success_list_response, success_response, validate_request,
+	AppState, MetaRequestDto, ResponseSuccessDto,
+};
+use axum::{http::StatusCode, response::Response};
+
+pub struct EnrollmentsService;
+
+pub fn enrollment_error_status(msg: &str) -> StatusCode {
+	match msg {
+		"Enrollment not found" | "Session not found" | "User not found" => {
+			StatusCode::NOT_FOUND
+		}
+		"Already enrolled" => StatusCode::CONFLICT,
+		_ if msg.contains("not eligible")
+			|| msg == "Enrollment is required for this session"
+			|| msg == "Self enrollment is not allowed" =>
+		{
+			StatusCode::FORBIDDEN
+		}
+		_ => StatusCode::INTERNAL_SERVER_ERROR,
+	}
+}
+
+impl EnrollmentsService {
+	pub async fn get_enrollment_list(
+		state: &AppState,
+		query: EnrollmentsQueryDto,
+		meta: MetaRequestDto,
+	) -> Response {
+		let repo = EnrollmentsRepository::new(state);
+		match repo.query_enrollment_list(query, meta).await {
+			Ok(data) => success_list_response(data),
+			Err(e) => common_response(StatusCode::BAD_REQUEST, &e.to_string()),
+		}
+	}
+
+	pub async fn self_enroll(
+		state: &AppState,
+		candidate: Option<EnrollmentsCandidateDto>,
+		session_id: String,
+	) -> Response {
+		let Some(candidate) = candidate else {
+			return common_response(StatusCode::UNAUTHORIZED, "Unauthorized user");
+		};
+		let repo = EnrollmentsRepository::new(state);
+		match repo.query_self_enroll(&candidate, &session_id).await {
+			Ok(data) => success_response(ResponseSuccessDto { data }),
+			Err(e) => {
+				let msg = e.to_string();
+				common_response(enrollment_error_status(&msg), &msg)
+			}
+		}
+	}
+
+	pub async fn create_enrollment(
+		state: &AppState,
+		payload: EnrollmentsCreateRequestDto,
+	) -> Response {
+		if let Err((status, message)) = validate_request(&payload) {
+			return common_response(status, &message);
+		}
+		let repo = EnrollmentsRepository::new(state);
+		match repo.query_create_enrollment(payload).await {
+			Ok(data) => success_response(ResponseSuccessDto { data }),
+			Err(e) => {
+				let msg = e.to_string();
+				common_response(enrollment_error_status(&msg), &msg)
+			}
+		}
+	}
+
+	pub async fn bulk_enroll(
+		state: &AppState,
+		payload: EnrollmentsBulkRequestDto,
+	) -> Response {
+		if let Err((status, message)) = validate_request(&payload) {
+			return common_response(status, &message);
+		}
+		let repo = EnrollmentsRepository::new(state);
+		match repo.query_bulk_enroll(payload).await {
+			Ok(data) => success_response(ResponseSuccessDto { data }),
+			Err(e) => {
+				let msg = e.to_string();
+				common_response(enrollment_error_status(&msg), &msg)
+			}
+		}
+	}
+
+	pub async fn delete_enrollment(state: &AppState, id: String) -> Response {
+		let repo = EnrollmentsRepository::new(state);
+		match repo.query_delete_enrollment(&id).await {
+			Ok(msg) => common_response(StatusCode::OK, &msg),
+			Err(e) => {
+				let msg = e.to_string();
+				common_response(enrollment_error_status(&msg), &msg)
+			}
+		}
+	}
+}
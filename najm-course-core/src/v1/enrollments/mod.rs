@@ -0,0 +1,38 @@
+use axum::{
+	routing::{delete, get, post},
+	Router,
+};
+
+pub mod enrollments_controller;
+pub mod enrollments_dto;
+pub mod enrollments_eligibility;
+pub mod enrollments_enum;
+pub mod enrollments_guard;
+pub mod enrollments_repository;
+pub mod enrollments_schema;
+pub mod enrollments_service;
+
+#[cfg(test)]
+pub mod enrollments_controller_test;
+#[cfg(test)]
+pub mod enrollments_eligibility_test;
+#[cfg(test)]
+pub mod enrollments_repository_test;
+
+pub use enrollments_controller::*;
+pub use enrollments_dto::*;
+pub use enrollments_eligibility::*;
+pub use enrollments_enum::*;
+pub use enrollments_guard::*;
+pub use enrollments_repository::*;
+pub use enrollments_schema::*;
+pub use enrollments_service::*;
+
+pub fn enrollments_router() -> Router {
+	Router::new()
+		.route("/", get(get_enrollment_list))
+		.route("/self", post(post_self_enroll))
+		.route("/create", post(post_create_enrollment))
+		.route("/bulk", post(post_bulk_enroll))
+		.route("/delete/{id}", delete(delete_enrollment))
+}
@@ -0,0 +1,32 @@
+use super::{IrtCalibrateRequestDto, IrtCalibrateResponseDto, IrtService};
+use crate::{permissions_guard, AppState, PermissionsEnum, ResponseSuccessDto};
+use axum::{response::IntoResponse, Extension, Json};
+
+#[utoipa::path(
+	post,
+	security(
+		("Bearer" = [])
+	),
+	path = "/v1/irt/calibrate",
+	request_body = IrtCalibrateRequestDto,
+	responses(
+		(status = 200, description = "Calibrate IRT item parameters for a test", body = ResponseSuccessDto<IrtCalibrateResponseDto>)
+	),
+	tag = "IRT"
+)]
+pub async fn post_calibrate_test(
+	headers: axum::http::HeaderMap,
+	Extension(state): Extension<AppState>,
+	Json(payload): Json<IrtCalibrateRequestDto>,
+) -> impl IntoResponse {
+	match permissions_guard(
+		&headers,
+		state.clone(),
+		vec![PermissionsEnum::CalibrateQuestions],
+	)
+	.await
+	{
+		Ok(_) => IrtService::calibrate_test(&state, payload).await,
+		Err(response) => response,
+	}
+}
@@ -0,0 +1,34 @@
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+use validator::Validate;
+
+#[derive(Clone, Debug, Serialize, Deserialize, Validate, ToSchema)]
+pub struct IrtCalibrateRequestDto {
+	#[validate(length(min = 1))]
+	#[schema(example = "uuid")]
+	pub test_id: String,
+
+	#[schema(example = "2pl")]
+	pub model: String,
+
+	#[schema(example = 30)]
+	pub min_responses: Option<u32>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, ToSchema)]
+pub struct IrtItemParamsDto {
+	pub question_id: String,
+	pub difficulty: f64,
+	pub discrimination: f64,
+	pub guessing: f64,
+	pub sample_size: u32,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, ToSchema)]
+pub struct IrtCalibrateResponseDto {
+	pub test_id: String,
+	pub model: String,
+	pub respondents: u32,
+	pub skipped: u32,
+	pub items: Vec<IrtItemParamsDto>,
+}
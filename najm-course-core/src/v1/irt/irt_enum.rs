@@ -0,0 +1,32 @@
+use std::fmt;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum IrtModelEnum {
+	OnePl,
+	TwoPl,
+	ThreePl,
+}
+
+impl fmt::Display for IrtModelEnum {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		let model_str = match self {
+			IrtModelEnum::OnePl => "1pl",
+			IrtModelEnum::TwoPl => "2pl",
+			IrtModelEnum::ThreePl => "3pl",
+		};
+		write!(f, "{}", model_str)
+	}
+}
+
+impl std::str::FromStr for IrtModelEnum {
+	type Err = anyhow::Error;
+
+	fn from_str(value: &str) -> Result<Self, Self::Err> {
+		match value {
+			"1pl" => Ok(IrtModelEnum::OnePl),
+			"2pl" => Ok(IrtModelEnum::TwoPl),
+			"3pl" => Ok(IrtModelEnum::ThreePl),
+			_ => anyhow::bail!("Invalid IRT model"),
+		}
+	}
+}
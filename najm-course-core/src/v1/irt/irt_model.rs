@@ -0,0 +1,245 @@
+use super::{IrtModelEnum, IrtParamsSchema};
+
+pub const THETA_MIN: f64 = -4.0;
+pub const THETA_MAX: f64 = 4.0;
+const QUADRATURE_POINTS: usize = 81;
+const OUTER_ITERATIONS: usize = 30;
+const ITEM_ITERATIONS: usize = 50;
+const LEARNING_RATE: f64 = 0.5;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct ItemParams {
+	pub discrimination: f64,
+	pub difficulty: f64,
+	pub guessing: f64,
+}
+
+impl Default for ItemParams {
+	fn default() -> Self {
+		Self {
+			discrimination: 1.0,
+			difficulty: 0.0,
+			guessing: 0.0,
+		}
+	}
+}
+
+impl From<&IrtParamsSchema> for ItemParams {
+	fn from(value: &IrtParamsSchema) -> Self {
+		Self {
+			discrimination: value.discrimination,
+			difficulty: value.difficulty,
+			guessing: value.guessing,
+		}
+	}
+}
+
+fn logistic(value: f64) -> f64 {
+	1.0 / (1.0 + (-value).exp())
+}
+
+fn logit(p: f64) -> f64 {
+	(p / (1.0 - p)).ln()
+}
+
+pub fn probability(theta: f64, item: &ItemParams) -> f64 {
+	let s = logistic(item.discrimination * (theta - item.difficulty));
+	(item.guessing + (1.0 - item.guessing) * s).clamp(1e-9, 1.0 - 1e-9)
+}
+
+fn log_likelihood(theta: f64, responses: &[(ItemParams, bool)]) -> f64 {
+	responses
+		.iter()
+		.map(|(item, correct)| {
+			let p = probability(theta, item);
+			if *correct {
+				p.ln()
+			} else {
+				(1.0 - p).ln()
+			}
+		})
+		.sum()
+}
+
+pub fn estimate_theta_mle(responses: &[(ItemParams, bool)]) -> f64 {
+	if responses.is_empty() {
+		return 0.0;
+	}
+	if responses.iter().all(|(_, correct)| *correct) {
+		return THETA_MAX;
+	}
+	if responses.iter().all(|(_, correct)| !*correct) {
+		return THETA_MIN;
+	}
+	let mut theta = 0.0;
+	for _ in 0..50 {
+		let mut gradient = 0.0;
+		let mut information = 0.0;
+		for (item, correct) in responses {
+			let s = logistic(item.discrimination * (theta - item.difficulty));
+			let p = probability(theta, item);
+			let dp = item.discrimination * (1.0 - item.guessing) * s * (1.0 - s);
+			let x = if *correct { 1.0 } else { 0.0 };
+			gradient += (x - p) * dp / (p * (1.0 - p));
+			information += dp * dp / (p * (1.0 - p));
+		}
+		if information <= f64::EPSILON {
+			break;
+		}
+		let step = gradient / information;
+		theta = (theta + step).clamp(THETA_MIN, THETA_MAX);
+		if step.abs() < 1e-6 {
+			break;
+		}
+	}
+	theta
+}
+
+pub fn estimate_theta_eap(responses: &[(ItemParams, bool)]) -> f64 {
+	let step = (THETA_MAX - THETA_MIN) / (QUADRATURE_POINTS - 1) as f64;
+	let points: Vec<(f64, f64)> = (0..QUADRATURE_POINTS)
+		.map(|index| {
+			let theta = THETA_MIN + step * index as f64;
+			(
+				theta,
+				-0.5 * theta * theta + log_likelihood(theta, responses),
+			)
+		})
+		.collect();
+	let max_log = points
+		.iter()
+		.map(|(_, log)| *log)
+		.fold(f64::NEG_INFINITY, f64::max);
+	let (weighted, total) =
+		points
+			.iter()
+			.fold((0.0, 0.0), |(weighted, total), (theta, log)| {
+				let weight = (log - max_log).exp();
+				(weighted + theta * weight, total + weight)
+			});
+	weighted / total
+}
+
+pub fn scale_theta(theta: f64) -> i32 {
+	(500.0 + 100.0 * theta).round().clamp(0.0, 1000.0) as i32
+}
+
+fn fit_item(
+	thetas: &[f64],
+	responses: &[(usize, bool)],
+	model: IrtModelEnum,
+	initial: ItemParams,
+) -> ItemParams {
+	let mut item = initial;
+	let n = responses.len() as f64;
+	for _ in 0..ITEM_ITERATIONS {
+		let (mut grad_a, mut grad_b, mut grad_c) = (0.0, 0.0, 0.0);
+		for (user, correct) in responses {
+			let theta = thetas[*user];
+			let s = logistic(item.discrimination * (theta - item.difficulty));
+			let p = probability(theta, &item);
+			let x = if *correct { 1.0 } else { 0.0 };
+			let residual = (x - p) / (p * (1.0 - p));
+			let slope = (1.0 - item.guessing) * s * (1.0 - s);
+			grad_a += residual * slope * (theta - item.difficulty);
+			grad_b -= residual * slope * item.discrimination;
+			grad_c += residual * (1.0 - s);
+		}
+		item.difficulty =
+			(item.difficulty + LEARNING_RATE * grad_b / n).clamp(THETA_MIN, THETA_MAX);
+		if model != IrtModelEnum::OnePl {
+			item.discrimination =
+				(item.discrimination + LEARNING_RATE * grad_a / n).clamp(0.2, 3.0);
+		}
+		if model == IrtModelEnum::ThreePl {
+			item.guessing =
+				(item.guessing + LEARNING_RATE * 0.1 * grad_c / n).clamp(0.0, 0.35);
+		}
+	}
+	item
+}
+
+fn standardize(thetas: &mut [f64]) {
+	if thetas.len() < 2 {
+		return;
+	}
+	let n = thetas.len() as f64;
+	let mean = thetas.iter().sum::<f64>() / n;
+	let sd = (thetas.iter().map(|t| (t - mean).powi(2)).sum::<f64>() / n).sqrt();
+	for theta in thetas.iter_mut() {
+		*theta = if sd > f64::EPSILON {
+			(*theta - mean) / sd
+		} else {
+			*theta - mean
+		};
+	}
+}
+
+pub fn calibrate(
+	matrix: &[Vec<Option<bool>>],
+	item_count: usize,
+	model: IrtModelEnum,
+	min_responses: usize,
+) -> Vec<Option<ItemParams>> {
+	let mut thetas: Vec<f64> = matrix
+		.iter()
+		.map(|row| {
+			let answered = row.iter().flatten().count() as f64;
+			let correct = row.iter().flatten().filter(|c| **c).count() as f64;
+			logit((correct + 0.5) / (answered + 1.0))
+		})
+		.collect();
+	standardize(&mut thetas);
+	let item_responses: Vec<Vec<(usize, bool)>> = (0..item_count)
+		.map(|item| {
+			matrix
+				.iter()
+				.enumerate()
+				.filter_map(|(user, row)| {
+					row.get(item).copied().flatten().map(|c| (user, c))
+				})
+				.collect()
+		})
+		.collect();
+	let mut items: Vec<Option<ItemParams>> = item_responses
+		.iter()
+		.map(|responses| {
+			if responses.len() < min_responses.max(1) {
+				return None;
+			}
+			let correct = responses.iter().filter(|(_, c)| *c).count() as f64;
+			let p = (correct + 0.5) / (responses.len() as f64 + 1.0);
+			Some(ItemParams {
+				difficulty: (-logit(p)).clamp(THETA_MIN, THETA_MAX),
+				guessing: if model == IrtModelEnum::ThreePl {
+					0.05
+				} else {
+					0.0
+				},
+				..Default::default()
+			})
+		})
+		.collect();
+	for _ in 0..OUTER_ITERATIONS {
+		for (index, item) in items.iter_mut().enumerate() {
+			if let Some(params) = item {
+				*params = fit_item(&thetas, &item_responses[index], model, *params);
+			}
+		}
+		for (user, row) in matrix.iter().enumerate() {
+			let responses: Vec<(ItemParams, bool)> = row
+				.iter()
+				.enumerate()
+				.filter_map(|(index, answer)| {
+					match (items.get(index).copied().flatten(), answer) {
+						(Some(params), Some(correct)) => Some((params, *correct)),
+						_ => None,
+					}
+				})
+				.collect();
+			thetas[user] = estimate_theta_mle(&responses);
+		}
+		standardize(&mut thetas);
+	}
+	items
+}
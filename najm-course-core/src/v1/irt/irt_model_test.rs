@@ -0,0 +1,92 @@
+use super::*;
+use rand::{rngs::StdRng, Rng, SeedableRng};
+
+fn simulate(
+	difficulties: &[f64],
+	respondents: usize,
+	seed: u64,
+) -> Vec<Vec<Option<bool>>> {
+	let mut rng = StdRng::seed_from_u64(seed);
+	(0..respondents)
+		.map(|_| {
+			let theta: f64 = rng.random_range(-2.5..2.5);
+			difficulties
+				.iter()
+				.map(|b| {
+					let item = ItemParams {
+						difficulty: *b,
+						..Default::default()
+					};
+					Some(rng.random::<f64>() < probability(theta, &item))
+				})
+				.collect()
+		})
+		.collect()
+}
+
+#[test]
+fn test_probability_should_be_half_at_difficulty() {
+	let item = ItemParams {
+		difficulty: 1.0,
+		..Default::default()
+	};
+	assert!((probability(1.0, &item) - 0.5).abs() < 1e-9);
+}
+
+#[test]
+fn test_scale_theta_should_clamp_to_range() {
+	assert_eq!(scale_theta(0.0), 500);
+	assert_eq!(scale_theta(1.25), 625);
+	assert_eq!(scale_theta(-9.0), 0);
+	assert_eq!(scale_theta(9.0), 1000);
+}
+
+#[test]
+fn test_estimate_theta_should_increase_with_correct_answers() {
+	let items: Vec<ItemParams> = [-1.0, -0.5, 0.0, 0.5, 1.0]
+		.iter()
+		.map(|b| ItemParams {
+			difficulty: *b,
+			..Default::default()
+		})
+		.collect();
+	let low: Vec<(ItemParams, bool)> = items
+		.iter()
+		.enumerate()
+		.map(|(i, it)| (*it, i < 1))
+		.collect();
+	let high: Vec<(ItemParams, bool)> = items
+		.iter()
+		.enumerate()
+		.map(|(i, it)| (*it, i < 4))
+		.collect();
+	assert!(estimate_theta_eap(&high) > estimate_theta_eap(&low));
+	assert!(estimate_theta_mle(&high) > estimate_theta_mle(&low));
+	let all_correct: Vec<(ItemParams, bool)> =
+		items.iter().map(|it| (*it, true)).collect();
+	assert_eq!(estimate_theta_mle(&all_correct), THETA_MAX);
+	assert!(estimate_theta_eap(&all_correct) < THETA_MAX);
+}
+
+#[test]
+fn test_calibrate_should_recover_difficulty_order() {
+	let difficulties = [-1.5, -0.5, 0.5, 1.5];
+	let matrix = simulate(&difficulties, 400, 42);
+	let fitted = calibrate(&matrix, difficulties.len(), IrtModelEnum::OnePl, 30);
+	let estimates: Vec<f64> = fitted.iter().map(|p| p.unwrap().difficulty).collect();
+	assert!(estimates.windows(2).all(|w| w[0] < w[1]), "{:?}", estimates);
+	for (estimate, truth) in estimates.iter().zip(difficulties) {
+		assert!((estimate - truth).abs() < 0.75, "{:?}", estimates);
+	}
+}
+
+#[test]
+fn test_calibrate_should_skip_items_below_min_responses() {
+	let mut matrix = simulate(&[0.0, 0.0], 10, 7);
+	for row in matrix.iter_mut() {
+		row[1] = None;
+	}
+	let fitted = calibrate(&matrix, 2, IrtModelEnum::TwoPl, 5);
+	assert!(fitted[0].is_some());
+	assert!(fitted[1].is_none());
+}
@@ -0,0 +1,133 @@
+use super::{
+	calibrate, IrtCalibrateRequestDto, IrtCalibrateResponseDto, IrtItemParamsDto,
+	IrtModelEnum, IrtParamsSchema, IrtQuestionSchema, IrtResponseSchema,
+};
+use crate::{AppState, TestsRepository};
+use anyhow::Result;
+use najm_course_libs::ResourceEnum;
+use najm_course_utils::{get_id, get_iso_date, make_thing};
+use serde_json::json;
+use std::collections::HashMap;
+use validator::Validate;
+
+pub const IRT_DEFAULT_MIN_RESPONSES: u32 = 30;
+
+pub struct IrtRepository<'a> {
+	state: &'a AppState,
+}
+
+impl<'a> IrtRepository<'a> {
+	pub fn new(state: &'a AppState) -> Self {
+		Self { state }
+	}
+
+	pub async fn query_item_params(
+		&self,
+		question_ids: &[String],
+	) -> Result<HashMap<String, IrtParamsSchema>> {
+		if question_ids.is_empty() {
+			return Ok(HashMap::new());
+		}
+		let db = &self.state.surrealdb_ws;
+		let ids = question_ids
+			.iter()
+			.map(|id| format!("{}:⟨{}⟩", ResourceEnum::Questions, id))
+			.collect::<Vec<_>>()
+			.join(", ");
+		let questions: Vec<IrtQuestionSchema> = db
+			.query(format!(
+				"SELECT id, irt FROM {} WHERE id INSIDE [{}]",
+				ResourceEnum::Questions,
+				ids
+			))
+			.await?
+			.take(0)?;
+		Ok(
+			questions
+				.into_iter()
+				.filter_map(|q| q.irt.map(|irt| (q.id.id.to_raw(), irt)))
+				.collect(),
+		)
+	}
+
+	pub async fn query_calibrate_test(
+		&self,
+		payload: IrtCalibrateRequestDto,
+	) -> Result<IrtCalibrateResponseDto> {
+		payload.validate()?;
+		let model: IrtModelEnum = payload.model.parse()?;
+		let min_responses =
+			payload.min_responses.unwrap_or(IRT_DEFAULT_MIN_RESPONSES) as usize;
+		let db = &self.state.surrealdb_ws;
+		let test = TestsRepository::new(self.state)
+			.query_raw_test_by_id(&payload.test_id)
+			.await?;
+		let question_ids: Vec<String> =
+			test.questions.iter().map(|q| q.id.to_raw()).collect();
+		let responses: Vec<IrtResponseSchema> = db
+			.query(format!(
+				"SELECT user, question, is_correct FROM {} WHERE test = app_tests:⟨{}⟩ AND is_deleted = false",
+				ResourceEnum::Answers,
+				payload.test_id
+			))
+			.await?
+			.take(0)?;
+		let item_index: HashMap<&str, usize> = question_ids
+			.iter()
+			.enumerate()
+			.map(|(index, id)| (id.as_str(), index))
+			.collect();
+		let mut user_index: HashMap<String, usize> = HashMap::new();
+		let mut matrix: Vec<Vec<Option<bool>>> = Vec::new();
+		for response in responses {
+			let question_id = response.question.id.to_raw();
+			let Some(item) = item_index.get(question_id.as_str()) else {
+				continue;
+			};
+			let user = *user_index
+				.entry(response.user.id.to_raw())
+				.or_insert_with(|| {
+					matrix.push(vec![None; question_ids.len()]);
+					matrix.len() - 1
+				});
+			matrix[user][*item] = Some(response.is_correct);
+		}
+		let fitted = calibrate(&matrix, question_ids.len(), model, min_responses);
+		let now = get_iso_date();
+		let mut items = Vec::new();
+		for (index, params) in fitted.iter().enumerate() {
+			let Some(params) = params else {
+				continue;
+			};
+			let sample_size = matrix.iter().filter(|row| row[index].is_some()).count();
+			let irt = IrtParamsSchema {
+				model: model.to_string(),
+				difficulty: params.difficulty,
+				discrimination: params.discrimination,
+				guessing: params.guessing,
+				sample_size: sample_size as u32,
+				calibrated_at: now.clone(),
+			};
+			let question_thing =
+				make_thing(&ResourceEnum::Questions.to_string(), &question_ids[index]);
+			let _: Option<IrtQuestionSchema> = db
+				.update(get_id(&question_thing)?)
+				.merge(json!({ "irt": irt }))
+				.await?;
+			items.push(IrtItemParamsDto {
+				question_id: question_ids[index].clone(),
+				difficulty: irt.difficulty,
+				discrimination: irt.discrimination,
+				guessing: irt.guessing,
+				sample_size: irt.sample_size,
+			});
+		}
+		Ok(IrtCalibrateResponseDto {
+			test_id: payload.test_id,
+			model: model.to_string(),
+			respondents: matrix.len() as u32,
+			skipped: (question_ids.len() - items.len()) as u32,
+			items,
+		})
+	}
+}
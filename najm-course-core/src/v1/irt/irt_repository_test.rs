@@ -0,0 +1,138 @@
+use super::*;
+use crate::{answers::AnswersRepository, create_mock_app_state};
+use anyhow::Result;
+use najm_course_entities::SurrealWsClient;
+use najm_course_utils::get_iso_date;
+use surrealdb::Uuid;
+
+pub struct IrtFixture {
+	pub test_id: String,
+	pub session_id: String,
+	pub question_ids: Vec<String>,
+	pub user_ids: Vec<String>,
+}
+
+pub async fn seed_irt_dependencies(db: &SurrealWsClient) -> Result<IrtFixture> {
+	let now = get_iso_date();
+	let test_id = Uuid::new_v4().to_string();
+	let session_id = Uuid::new_v4().to_string();
+	let thresholds = [8, 20, 32];
+	let mut question_ids = Vec::new();
+	let mut options = Vec::new();
+	for _ in thresholds {
+		let question_id = Uuid::new_v4().to_string();
+		let correct_id = Uuid::new_v4().to_string();
+		let wrong_id = Uuid::new_v4().to_string();
+		db.query(format!(
+			"CREATE app_options:⟨{correct_id}⟩ SET label = 'Correct', is_correct = true, is_deleted = false, created_at = '{now}', updated_at = '{now}';
+			CREATE app_options:⟨{wrong_id}⟩ SET label = 'Wrong', is_correct = false, is_deleted = false, created_at = '{now}', updated_at = '{now}';
+			CREATE app_questions:⟨{question_id}⟩ SET question = 'Question', discussion = 'Discussion', options = [app_options:⟨{correct_id}⟩, app_options:⟨{wrong_id}⟩], is_deleted = false, created_at = '{now}', updated_at = '{now}';"
+		))
+		.await?;
+		question_ids.push(question_id);
+		options.push((correct_id, wrong_id));
+	}
+	let question_things = question_ids
+		.iter()
+		.map(|id| format!("app_questions:⟨{}⟩", id))
+		.collect::<Vec<_>>()
+		.join(", ");
+	db.query(format!(
+		"CREATE app_tests:⟨{test_id}⟩ SET name = 'IRT Test', questions = [{question_things}], is_deleted = false, created_at = '{now}', updated_at = '{now}';
+		CREATE app_sessions:⟨{session_id}⟩ SET name = 'IRT Session', category = 'Akademik', description = 'IRT', student_type = 'SMA', tests = [{{ test: app_tests:⟨{test_id}⟩, shuffle: false, weight: 1.0, multiplier: 1.0, start_date: '2025-01-01T00:00:00Z', end_date: '2099-01-01T00:00:00Z', scoring: {{ strategy: 'irt', params: {{ method: 'eap' }} }} }}], is_active = true, is_deleted = false, created_at = '{now}', updated_at = '{now}';"
+	))
+	.await?;
+	let mut user_ids = Vec::new();
+	for user in 0..40 {
+		let user_id = Uuid::new_v4().to_string();
+		for (index, threshold) in thresholds.iter().enumerate() {
+			let is_correct = user >= *threshold || (user + index) % 7 == 0;
+			let option_id = if is_correct {
+				&options[index].0
+			} else {
+				&options[index].1
+			};
+			db.query(format!(
+				"CREATE app_answers SET user = app_users:⟨{user_id}⟩, test = app_tests:⟨{test_id}⟩, session = app_sessions:⟨{session_id}⟩, question = app_questions:⟨{}⟩, option = app_options:⟨{option_id}⟩, is_correct = {is_correct}, is_deleted = false, created_at = '{now}', updated_at = '{now}'",
+				question_ids[index]
+			))
+			.await?;
+		}
+		user_ids.push(user_id);
+	}
+	Ok(IrtFixture {
+		test_id,
+		session_id,
+		question_ids,
+		user_ids,
+	})
+}
+
+#[tokio::test]
+async fn test_query_calibrate_test_should_store_item_params() {
+	let state = create_mock_app_state().await;
+	let fixture = seed_irt_dependencies(&state.surrealdb_ws).await.unwrap();
+	let repo = IrtRepository::new(&state);
+	let result = repo
+		.query_calibrate_test(IrtCalibrateRequestDto {
+			test_id: fixture.test_id.clone(),
+			model: "1pl".into(),
+			min_responses: Some(5),
+		})
+		.await
+		.unwrap();
+	assert_eq!(result.respondents, 40);
+	assert_eq!(result.items.len(), 3);
+	assert!(result.items[0].difficulty < result.items[2].difficulty);
+	let params = repo.query_item_params(&fixture.question_ids).await.unwrap();
+	assert_eq!(params.len(), 3);
+	assert_eq!(params[&fixture.question_ids[0]].model, "1pl");
+}
+
+#[tokio::test]
+async fn test_query_calibrate_test_should_fail_with_invalid_model() {
+	let state = create_mock_app_state().await;
+	let repo = IrtRepository::new(&state);
+	let result = repo
+		.query_calibrate_test(IrtCalibrateRequestDto {
+			test_id: "any".into(),
+			model: "4pl".into(),
+			min_responses: None,
+		})
+		.await;
+	assert_eq!(result.unwrap_err().to_string(), "Invalid IRT model");
+}
+
+#[tokio::test]
+async fn test_irt_scoring_should_rank_students_by_ability() {
+	let state = create_mock_app_state().await;
+	let fixture = seed_irt_dependencies(&state.surrealdb_ws).await.unwrap();
+	IrtRepository::new(&state)
+		.query_calibrate_test(IrtCalibrateRequestDto {
+			test_id: fixture.test_id.clone(),
+			model: "2pl".into(),
+			min_responses: Some(5),
+		})
+		.await
+		.unwrap();
+	let answers_repo = AnswersRepository::new(&state);
+	let weak = answers_repo
+		.query_test_with_answers(
+			&fixture.session_id,
+			&fixture.test_id,
+			&fixture.user_ids[1],
+		)
+		.await
+		.unwrap();
+	let strong = answers_repo
+		.query_test_with_answers(
+			&fixture.session_id,
+			&fixture.test_id,
+			&fixture.user_ids[39],
+		)
+		.await
+		.unwrap();
+	assert!(weak.theta.is_some());
+	assert!(strong.score > weak.score);
+	assert!((0..=1000).contains(&strong.score));
+}
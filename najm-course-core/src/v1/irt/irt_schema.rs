@@ -0,0 +1,25 @@
+use serde::{Deserialize, Serialize};
+use surrealdb::sql::Thing;
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct IrtParamsSchema {
+	pub model: String,
+	pub difficulty: f64,
+	pub discrimination: f64,
+	pub guessing: f64,
+	pub sample_size: u32,
+	pub calibrated_at: String,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct IrtResponseSchema {
+	pub user: Thing,
+	pub question: Thing,
+	pub is_correct: bool,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct IrtQuestionSchema {
+	pub id: Thing,
+	pub irt: Option<IrtParamsSchema>,
+}
@@ -0,0 +1,26 @@
+use super::{IrtCalibrateRequestDto, IrtRepository};
+use crate::{common_response, success_response, AppState, ResponseSuccessDto};
+use axum::{http::StatusCode, response::Response};
+
+pub struct IrtService;
+
+impl IrtService {
+	pub async fn calibrate_test(
+		state: &AppState,
+		payload: IrtCalibrateRequestDto,
+	) -> Response {
+		let repo = IrtRepository::new(state);
+		match repo.query_calibrate_test(payload).await {
+			Ok(data) => success_response(ResponseSuccessDto { data }),
+			Err(e) => {
+				let msg = e.to_string();
+				let status = match msg.as_str() {
+					"Test not found" => StatusCode::NOT_FOUND,
+					"Invalid IRT model" => StatusCode::BAD_REQUEST,
+					_ => StatusCode::INTERNAL_SERVER_ERROR,
+				};
+				common_response(status, &msg)
+			}
+		}
+	}
+}
@@ -0,0 +1,26 @@
+use axum::{routing::post, Router};
+
+pub mod irt_controller;
+pub mod irt_dto;
+pub mod irt_enum;
+pub mod irt_model;
+pub mod irt_repository;
+pub mod irt_schema;
+pub mod irt_service;
+
+#[cfg(test)]
+pub mod irt_model_test;
+#[cfg(test)]
+pub mod irt_repository_test;
+
+pub use irt_controller::*;
+pub use irt_dto::*;
+pub use irt_enum::*;
+pub use irt_model::*;
+pub use irt_repository::*;
+pub use irt_schema::*;
+pub use irt_service::*;
+
+pub fn irt_router() -> Router {
+	Router::new().route("/calibrate", post(post_calibrate_test))
+}
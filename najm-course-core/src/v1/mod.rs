@@ -1,24 +1,38 @@
 use answers::answers_router;
 use axum::{middleware::from_fn, Router};
 pub mod answers;
+pub mod attempts;
 pub mod auth;
 pub mod docs;
+pub mod enrollments;
 pub mod flags;
+pub mod irt;
+pub mod monitoring;
 pub mod options;
 pub mod permissions;
+pub mod proctoring;
 pub mod questions;
 pub mod roles;
+pub mod scoring;
+pub mod session_results;
 pub mod sessions;
 pub mod storage;
 pub mod tests;
 pub mod users;
 
+pub use attempts::*;
 pub use auth::*;
 pub use docs::*;
+pub use enrollments::*;
+pub use irt::*;
+pub use monitoring::*;
 pub use options::*;
 pub use permissions::*;
+pub use proctoring::*;
 pub use questions::*;
 pub use roles::*;
+pub use scoring::*;
+pub use session_results::*;
 pub use sessions::*;
 pub use storage::*;
 pub use tests::*;
@@ -35,6 +49,12 @@ pub async fn routes() -> Router {
 		.nest("/questions", questions_router())
 		.nest("/tests", tests_router())
 		.nest("/answers", answers_router())
+		.nest("/attempts", attempts_router())
+		.nest("/irt", irt_router())
+		.nest("/session-results", session_results_router())
+		.nest("/enrollments", enrollments_router())
+		.nest("/proctoring", proctoring_router())
+		.nest("/monitoring", monitoring_router())
 		.nest("/storage", storage_router().await)
 		.layer(from_fn(auth_middleware::auth_middleware));
 	Router::new().merge(public_routes).merge(protected_routes)
@@ -0,0 +1,24 @@
+use axum::{routing::get, Router};
+
+pub mod monitoring_controller;
+pub mod monitoring_dto;
+pub mod monitoring_feed;
+pub mod monitoring_repository;
+pub mod monitoring_schema;
+pub mod monitoring_service;
+
+#[cfg(test)]
+pub mod monitoring_feed_test;
+#[cfg(test)]
+pub mod monitoring_repository_test;
+
+pub use monitoring_controller::*;
+pub use monitoring_dto::*;
+pub use monitoring_feed::*;
+pub use monitoring_repository::*;
+pub use monitoring_schema::*;
+pub use monitoring_service::*;
+
+pub fn monitoring_router() -> Router {
+	Router::new().route("/session/{id}", get(get_session_monitor))
+}
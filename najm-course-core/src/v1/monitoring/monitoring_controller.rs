@@ -0,0 +1,39 @@
+use super::{MonitoringEventDto, MonitoringQueryDto, MonitoringService};
+use crate::{permissions_guard, AppState, PermissionsEnum};
+use axum::{
+	extract::{Path, Query},
+	http::HeaderMap,
+	response::IntoResponse,
+	Extension,
+};
+
+#[utoipa::path(
+	get,
+	security(("Bearer" = [])),
+	path = "/v1/monitoring/session/{id}",
+	params(
+		("id" = String, Path, description = "Session ID"),
+		("test_id" = Option<String>, Query, description = "Only watch attempts of this test"),
+	),
+	responses(
+		(status = 200, description = "Server-Sent Events stream of in-progress students, answer progress, disconnects and submissions", body = MonitoringEventDto, content_type = "text/event-stream")
+	),
+	tag = "Monitoring"
+)]
+pub async fn get_session_monitor(
+	headers: HeaderMap,
+	Extension(state): Extension<AppState>,
+	Path(id): Path<String>,
+	Query(query): Query<MonitoringQueryDto>,
+) -> impl IntoResponse {
+	match permissions_guard(
+		&headers,
+		state.clone(),
+		vec![PermissionsEnum::MonitorSessions],
+	)
+	.await
+	{
+		Ok(_) => MonitoringService::watch_session(&state, id, query).await,
+		Err(response) => response,
+	}
+}
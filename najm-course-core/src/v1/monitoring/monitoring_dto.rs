@@ -0,0 +1,54 @@
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct MonitoringQueryDto {
+	pub test_id: Option<String>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, ToSchema, PartialEq)]
+pub struct MonitoringStudentDto {
+	pub attempt_id: String,
+	pub user_id: String,
+	pub test_id: String,
+	#[schema(example = "in_progress")]
+	pub status: String,
+	#[schema(example = 12)]
+	pub answered_count: u32,
+	#[schema(example = 40)]
+	pub question_count: u32,
+	#[schema(example = 1800)]
+	pub remaining_seconds: i64,
+	pub last_heartbeat_at: String,
+	pub is_disconnected: bool,
+	pub submitted_at: Option<String>,
+}
+
+/// Events streamed to staff watching a session. The SSE event name matches
+/// the `type` field.
+#[derive(Clone, Debug, Serialize, Deserialize, ToSchema)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum MonitoringEventDto {
+	Snapshot {
+		students: Vec<MonitoringStudentDto>,
+		server_time: String,
+	},
+	Started(MonitoringStudentDto),
+	Progress(MonitoringStudentDto),
+	Disconnected(MonitoringStudentDto),
+	Reconnected(MonitoringStudentDto),
+	Submitted(MonitoringStudentDto),
+}
+
+impl MonitoringEventDto {
+	pub fn name(&self) -> &'static str {
+		match self {
+			MonitoringEventDto::Snapshot { .. } => "snapshot",
+			MonitoringEventDto::Started(_) => "started",
+			MonitoringEventDto::Progress(_) => "progress",
+			MonitoringEventDto::Disconnected(_) => "disconnected",
+			MonitoringEventDto::Reconnected(_) => "reconnected",
+			MonitoringEventDto::Submitted(_) => "submitted",
+		}
+	}
+}
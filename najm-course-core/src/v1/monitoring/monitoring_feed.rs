@@ -0,0 +1,201 @@
+use super::{MonitoringEventDto, MonitoringRepository, MonitoringStudentDto};
+use crate::{AppState, AttemptsItemDto, AttemptsSchema, AttemptsStatusEnum};
+use anyhow::Result;
+use axum::response::sse::Event;
+use chrono::{DateTime, Utc};
+use futures_util::{Stream, StreamExt};
+use std::{collections::HashSet, time::Duration};
+use surrealdb::Action;
+use tokio::sync::mpsc;
+
+/// Seconds between full snapshots, which also refresh the remaining time.
+pub const MONITOR_TICK_SECONDS: u64 = 15;
+
+/// An attempt without a heartbeat for this long is reported as disconnected.
+pub const DISCONNECT_AFTER_SECONDS: i64 = 60;
+
+pub fn is_disconnected(
+	status: &str,
+	last_heartbeat_at: &str,
+	now: DateTime<Utc>,
+) -> bool {
+	if status != AttemptsStatusEnum::InProgress.to_string() {
+		return false;
+	}
+	DateTime::parse_from_rfc3339(last_heartbeat_at)
+		.map(|at| {
+			(now - at.with_timezone(&Utc)).num_seconds() > DISCONNECT_AFTER_SECONDS
+		})
+		.unwrap_or(false)
+}
+
+pub fn monitoring_student(
+	attempt: AttemptsSchema,
+	answered_count: u32,
+) -> MonitoringStudentDto {
+	let is_disconnected =
+		is_disconnected(&attempt.status, &attempt.last_heartbeat_at, Utc::now());
+	let question_count = attempt.question_order.len() as u32;
+	let item = AttemptsItemDto::from(attempt);
+	MonitoringStudentDto {
+		attempt_id: item.id,
+		user_id: item.user_id,
+		test_id: item.test_id,
+		status: item.status,
+		answered_count,
+		question_count,
+		remaining_seconds: item.remaining_seconds,
+		last_heartbeat_at: item.last_heartbeat_at,
+		is_disconnected,
+		submitted_at: item.submitted_at,
+	}
+}
+
+/// Remembers what a single watcher has been told so disconnects and
+/// submissions are announced once.
+#[derive(Debug, Default)]
+pub struct MonitoringFeed {
+	test_id: Option<String>,
+	disconnected: HashSet<String>,
+	closed: HashSet<String>,
+}
+
+impl MonitoringFeed {
+	pub fn new(test_id: Option<String>) -> Self {
+		Self {
+			test_id,
+			..Default::default()
+		}
+	}
+
+	pub fn test_id(&self) -> Option<&str> {
+		self.test_id.as_deref()
+	}
+
+	pub fn includes(&self, test_id: &str) -> bool {
+		self.test_id.as_deref().is_none_or(|t| t == test_id)
+	}
+
+	/// Disconnect and reconnect transitions since the previous sweep.
+	pub fn sweep(
+		&mut self,
+		students: &[MonitoringStudentDto],
+	) -> Vec<MonitoringEventDto> {
+		let mut events = vec![];
+		for student in students {
+			if student.is_disconnected {
+				if self.disconnected.insert(student.attempt_id.clone()) {
+					events.push(MonitoringEventDto::Disconnected(student.clone()));
+				}
+			} else if self.disconnected.remove(&student.attempt_id) {
+				events.push(MonitoringEventDto::Reconnected(student.clone()));
+			}
+		}
+		events
+	}
+
+	pub fn attempt_changed(
+		&mut self,
+		action: Action,
+		student: MonitoringStudentDto,
+	) -> Option<MonitoringEventDto> {
+		if !self.includes(&student.test_id) {
+			return None;
+		}
+		match action {
+			Action::Create => Some(MonitoringEventDto::Started(student)),
+			Action::Update
+				if student.status != AttemptsStatusEnum::InProgress.to_string() =>
+			{
+				self.disconnected.remove(&student.attempt_id);
+				self
+					.closed
+					.insert(student.attempt_id.clone())
+					.then_some(MonitoringEventDto::Submitted(student))
+			}
+			Action::Update if !student.is_disconnected => self
+				.disconnected
+				.remove(&student.attempt_id)
+				.then_some(MonitoringEventDto::Reconnected(student)),
+			_ => None,
+		}
+	}
+}
+
+/// Streams monitoring events for a session until the watcher disconnects.
+/// Answer writes and attempt changes arrive through live queries; a ticker
+/// sends full snapshots and detects stale heartbeats.
+pub fn monitoring_stream(
+	state: AppState,
+	session_id: String,
+	feed: MonitoringFeed,
+) -> impl Stream<Item = Result<Event, axum::Error>> {
+	let (sender, receiver) = mpsc::channel(32);
+	tokio::spawn(async move {
+		if let Err(e) = run_monitoring_feed(&state, &session_id, feed, sender).await {
+			log::error!("Monitoring feed closed: {}", e);
+		}
+	});
+	futures_util::stream::unfold(receiver, |mut receiver| async move {
+		let event = receiver.recv().await?;
+		Some((event, receiver))
+	})
+	.map(|event: MonitoringEventDto| {
+		Event::default().event(event.name()).json_data(&event)
+	})
+}
+
+async fn run_monitoring_feed(
+	state: &AppState,
+	session_id: &str,
+	mut feed: MonitoringFeed,
+	sender: mpsc::Sender<MonitoringEventDto>,
+) -> Result<()> {
+	let repo = MonitoringRepository::new(state);
+	let mut answers = repo.query_live_answers(session_id).await?;
+	let mut attempts = repo.query_live_attempts(session_id).await?;
+	let mut ticker = tokio::time::interval(Duration::from_secs(MONITOR_TICK_SECONDS));
+	loop {
+		let events = tokio::select! {
+			_ = ticker.tick() => {
+				let students = repo.query_students(session_id, feed.test_id()).await?;
+				let mut events = feed.sweep(&students);
+				events.push(MonitoringEventDto::Snapshot {
+					students,
+					server_time: Utc::now().to_rfc3339(),
+				});
+				events
+			}
+			Some(notification) = answers.next() => {
+				let notification = notification?;
+				let attempt = match notification.data.attempt {
+					Some(attempt) if notification.action != Action::Delete => attempt,
+					_ => continue,
+				};
+				if !feed.includes(&notification.data.test.id.to_raw()) {
+					continue;
+				}
+				let student = repo.query_student(&attempt.id.to_raw()).await?;
+				vec![MonitoringEventDto::Progress(student)]
+			}
+			Some(notification) = attempts.next() => {
+				let notification = notification?;
+				let answered = repo
+					.query_answered_count(&notification.data.id.id.to_raw())
+					.await?;
+				let student = monitoring_student(notification.data, answered);
+				match feed.attempt_changed(notification.action, student) {
+					Some(event) => vec![event],
+					None => continue,
+				}
+			}
+			_ = sender.closed() => break,
+		};
+		for event in events {
+			if sender.send(event).await.is_err() {
+				return Ok(());
+			}
+		}
+	}
+	Ok(())
+}
@@ -0,0 +1,88 @@
+use super::*;
+use crate::AttemptsStatusEnum;
+use chrono::{Duration, Utc};
+use surrealdb::Action;
+
+fn student(
+	attempt_id: &str,
+	test_id: &str,
+	status: AttemptsStatusEnum,
+) -> MonitoringStudentDto {
+	MonitoringStudentDto {
+		attempt_id: attempt_id.into(),
+		user_id: "user".into(),
+		test_id: test_id.into(),
+		status: status.to_string(),
+		answered_count: 3,
+		question_count: 10,
+		remaining_seconds: 600,
+		last_heartbeat_at: Utc::now().to_rfc3339(),
+		is_disconnected: false,
+		submitted_at: None,
+	}
+}
+
+#[test]
+fn test_is_disconnected_should_flag_stale_heartbeats_of_open_attempts() {
+	let now = Utc::now();
+	let stale = (now - Duration::seconds(DISCONNECT_AFTER_SECONDS + 5)).to_rfc3339();
+	let fresh = (now - Duration::seconds(5)).to_rfc3339();
+	let in_progress = AttemptsStatusEnum::InProgress.to_string();
+	assert!(is_disconnected(&in_progress, &stale, now));
+	assert!(!is_disconnected(&in_progress, &fresh, now));
+	assert!(!is_disconnected(
+		&AttemptsStatusEnum::Submitted.to_string(),
+		&stale,
+		now
+	));
+}
+
+#[test]
+fn test_sweep_should_announce_disconnect_and_reconnect_once() {
+	let mut feed = MonitoringFeed::new(None);
+	let mut stuck = student("a1", "t1", AttemptsStatusEnum::InProgress);
+	stuck.is_disconnected = true;
+	let events = feed.sweep(&[stuck.clone()]);
+	assert_eq!(events.len(), 1);
+	assert_eq!(events[0].name(), "disconnected");
+	assert!(feed.sweep(&[stuck.clone()]).is_empty());
+	stuck.is_disconnected = false;
+	let events = feed.sweep(&[stuck]);
+	assert_eq!(events.len(), 1);
+	assert_eq!(events[0].name(), "reconnected");
+}
+
+#[test]
+fn test_attempt_changed_should_report_submission_once_and_filter_by_test() {
+	let mut feed = MonitoringFeed::new(Some("t1".into()));
+	let started = student("a1", "t1", AttemptsStatusEnum::InProgress);
+	assert_eq!(
+		feed
+			.attempt_changed(Action::Create, started.clone())
+			.map(|e| e.name()),
+		Some("started")
+	);
+	assert!(feed.attempt_changed(Action::Update, started).is_none());
+	let submitted = student("a1", "t1", AttemptsStatusEnum::Submitted);
+	assert_eq!(
+		feed
+			.attempt_changed(Action::Update, submitted.clone())
+			.map(|e| e.name()),
+		Some("submitted")
+	);
+	assert!(feed.attempt_changed(Action::Update, submitted).is_none());
+	let other_test = student("a2", "t2", AttemptsStatusEnum::InProgress);
+	assert!(feed.attempt_changed(Action::Create, other_test).is_none());
+}
+
+#[test]
+fn test_event_name_should_match_serialized_type() {
+	let event = MonitoringEventDto::Progress(student(
+		"a1",
+		"t1",
+		AttemptsStatusEnum::InProgress,
+	));
+	let json = serde_json::to_value(&event).unwrap();
+	assert_eq!(json["type"], event.name());
+	assert_eq!(json["answered_count"], 3);
+}
@@ -0,0 +1,154 @@
+use super::{
+	monitoring_student, MonitoringAnswerSchema, MonitoringAnsweredCountSchema,
+	MonitoringStudentDto,
+};
+use crate::{
+	AppState, AttemptsRepository, AttemptsSchema, AttemptsStatusEnum,
+	SessionsRepository,
+};
+use anyhow::{bail, Result};
+use najm_course_libs::ResourceEnum;
+use std::collections::HashMap;
+use surrealdb::{method::QueryStream, Notification};
+
+pub struct MonitoringRepository<'a> {
+	state: &'a AppState,
+}
+
+impl<'a> MonitoringRepository<'a> {
+	pub fn new(state: &'a AppState) -> Self {
+		Self { state }
+	}
+
+	pub async fn query_ensure_watchable(
+		&self,
+		session_id: &str,
+		test_id: Option<&str>,
+	) -> Result<()> {
+		let session = SessionsRepository::new(self.state)
+			.query_raw_session_by_id(session_id)
+			.await?;
+		if let Some(test_id) = test_id {
+			if !session.tests.iter().any(|t| t.test.id.to_raw() == test_id) {
+				bail!("Test not found in session");
+			}
+		}
+		Ok(())
+	}
+
+	async fn query_answered_counts(
+		&self,
+		filter: String,
+	) -> Result<Vec<MonitoringAnsweredCountSchema>> {
+		let db = &self.state.surrealdb_ws;
+		let counts: Vec<MonitoringAnsweredCountSchema> = db
+			.query(format!(
+				"SELECT attempt, count() AS answered FROM {} WHERE {} AND attempt != NONE AND is_deleted = false GROUP BY attempt",
+				ResourceEnum::Answers,
+				filter
+			))
+			.await?
+			.take(0)?;
+		Ok(counts)
+	}
+
+	pub async fn query_answered_count(&self, attempt_id: &str) -> Result<u32> {
+		let counts = self
+			.query_answered_counts(format!(
+				"attempt = {}:⟨{}⟩",
+				ResourceEnum::Attempts,
+				attempt_id
+			))
+			.await?;
+		Ok(counts.first().map(|c| c.answered).unwrap_or(0))
+	}
+
+	pub async fn query_student(
+		&self,
+		attempt_id: &str,
+	) -> Result<MonitoringStudentDto> {
+		let attempt = AttemptsRepository::new(self.state)
+			.query_raw_attempt_by_id(attempt_id)
+			.await?;
+		let answered = self.query_answered_count(attempt_id).await?;
+		Ok(monitoring_student(attempt, answered))
+	}
+
+	/// Students currently working on the session. Attempts past their deadline
+	/// are closed on the way, which the attempt live query reports as a
+	/// submission.
+	pub async fn query_students(
+		&self,
+		session_id: &str,
+		test_id: Option<&str>,
+	) -> Result<Vec<MonitoringStudentDto>> {
+		let db = &self.state.surrealdb_ws;
+		let test_filter = test_id
+			.map(|t| format!(" AND test = {}:⟨{}⟩", ResourceEnum::Tests, t))
+			.unwrap_or_default();
+		let attempts: Vec<AttemptsSchema> = db
+			.query(format!(
+				"SELECT * FROM {} WHERE session = {}:⟨{}⟩ AND status = '{}' AND is_deleted = false{} ORDER BY started_at ASC",
+				ResourceEnum::Attempts,
+				ResourceEnum::Sessions,
+				session_id,
+				AttemptsStatusEnum::InProgress,
+				test_filter
+			))
+			.await?
+			.take(0)?;
+		let answered: HashMap<String, u32> = self
+			.query_answered_counts(format!(
+				"session = {}:⟨{}⟩",
+				ResourceEnum::Sessions,
+				session_id
+			))
+			.await?
+			.into_iter()
+			.map(|c| (c.attempt.id.to_raw(), c.answered))
+			.collect();
+		let attempts_repo = AttemptsRepository::new(self.state);
+		let mut students = vec![];
+		for attempt in attempts {
+			let attempt = attempts_repo.query_close_if_expired(attempt).await?;
+			if attempt.status != AttemptsStatusEnum::InProgress.to_string() {
+				continue;
+			}
+			let count = answered.get(&attempt.id.id.to_raw()).copied().unwrap_or(0);
+			students.push(monitoring_student(attempt, count));
+		}
+		Ok(students)
+	}
+
+	pub async fn query_live_answers(
+		&self,
+		session_id: &str,
+	) -> Result<QueryStream<Notification<MonitoringAnswerSchema>>> {
+		let db = &self.state.surrealdb_ws;
+		let mut response = db
+			.query(format!(
+				"LIVE SELECT attempt, test FROM {} WHERE session = {}:⟨{}⟩",
+				ResourceEnum::Answers,
+				ResourceEnum::Sessions,
+				session_id
+			))
+			.await?;
+		Ok(response.stream::<Notification<MonitoringAnswerSchema>>(0)?)
+	}
+
+	pub async fn query_live_attempts(
+		&self,
+		session_id: &str,
+	) -> Result<QueryStream<Notification<AttemptsSchema>>> {
+		let db = &self.state.surrealdb_ws;
+		let mut response = db
+			.query(format!(
+				"LIVE SELECT * FROM {} WHERE session = {}:⟨{}⟩",
+				ResourceEnum::Attempts,
+				ResourceEnum::Sessions,
+				session_id
+			))
+			.await?;
+		Ok(response.stream::<Notification<AttemptsSchema>>(0)?)
+	}
+}
@@ -0,0 +1,92 @@
+use super::*;
+use crate::{
+	answers::{AnswersRepository, AnswersSaveRequestDto},
+	attempts::attempts_repository_test::{
+		build_start_payload, seed_attempt_dependencies, AttemptFixture,
+	},
+	create_mock_app_state, AttemptsRepository,
+};
+use anyhow::Result;
+use futures_util::StreamExt;
+use std::time::Duration;
+
+fn save_payload(fixture: &AttemptFixture) -> AnswersSaveRequestDto {
+	AnswersSaveRequestDto {
+		user_id: fixture.user_id.clone(),
+		test_id: fixture.test_id.clone(),
+		session_id: fixture.session_id.clone(),
+		question_id: fixture.question_id.clone(),
+		option_id: Some(fixture.option_id.clone()),
+		option_ids: None,
+		statements: None,
+		numeric_value: None,
+		text: None,
+		answered_at: None,
+	}
+}
+
+#[tokio::test]
+async fn test_query_students_should_count_answers_and_filter_by_test() -> Result<()>
+{
+	let state = create_mock_app_state().await;
+	let fixture =
+		seed_attempt_dependencies(&state.surrealdb_ws, -10, 120, Some(30)).await?;
+	let attempt = AttemptsRepository::new(&state)
+		.query_start_attempt(&fixture.user_id, build_start_payload(&fixture))
+		.await?;
+	AnswersRepository::new(&state)
+		.query_save(save_payload(&fixture))
+		.await?;
+	let repo = MonitoringRepository::new(&state);
+	let students = repo.query_students(&fixture.session_id, None).await?;
+	assert_eq!(students.len(), 1);
+	assert_eq!(students[0].attempt_id, attempt.id);
+	assert_eq!(students[0].answered_count, 1);
+	assert_eq!(students[0].question_count, 1);
+	assert!(students[0].remaining_seconds > 0);
+	assert!(!students[0].is_disconnected);
+	let filtered = repo
+		.query_students(&fixture.session_id, Some("another_test"))
+		.await?;
+	assert!(filtered.is_empty());
+	Ok(())
+}
+
+#[tokio::test]
+async fn test_query_live_answers_should_notify_answer_writes() -> Result<()> {
+	let state = create_mock_app_state().await;
+	let fixture =
+		seed_attempt_dependencies(&state.surrealdb_ws, -10, 120, Some(30)).await?;
+	let attempt = AttemptsRepository::new(&state)
+		.query_start_attempt(&fixture.user_id, build_start_payload(&fixture))
+		.await?;
+	let repo = MonitoringRepository::new(&state);
+	let mut answers = repo.query_live_answers(&fixture.session_id).await?;
+	AnswersRepository::new(&state)
+		.query_save(save_payload(&fixture))
+		.await?;
+	let notification = tokio::time::timeout(Duration::from_secs(5), answers.next())
+		.await?
+		.expect("answer write should be streamed")?;
+	let attempt_id = notification.data.attempt.map(|a| a.id.to_raw());
+	assert_eq!(attempt_id.as_deref(), Some(attempt.id.as_str()));
+	Ok(())
+}
+
+#[tokio::test]
+async fn test_query_ensure_watchable_should_reject_foreign_test() -> Result<()> {
+	let state = create_mock_app_state().await;
+	let fixture =
+		seed_attempt_dependencies(&state.surrealdb_ws, -10, 120, Some(30)).await?;
+	let repo = MonitoringRepository::new(&state);
+	repo
+		.query_ensure_watchable(&fixture.session_id, Some(&fixture.test_id))
+		.await?;
+	let result = repo
+		.query_ensure_watchable(&fixture.session_id, Some("another_test"))
+		.await;
+	assert_eq!(result.unwrap_err().to_string(), "Test not found in session");
+	let result = repo.query_ensure_watchable("missing", None).await;
+	assert_eq!(result.unwrap_err().to_string(), "Session not found");
+	Ok(())
+}
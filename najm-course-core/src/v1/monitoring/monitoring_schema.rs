@@ -0,0 +1,15 @@
+use serde::{Deserialize, Serialize};
+use surrealdb::sql::Thing;
+
+/// The slice of an answer write the monitoring feed reacts to.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct MonitoringAnswerSchema {
+	pub attempt: Option<Thing>,
+	pub test: Thing,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct MonitoringAnsweredCountSchema {
+	pub attempt: Thing,
+	pub answered: u32,
+}
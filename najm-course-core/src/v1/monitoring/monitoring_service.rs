@@ -0,0 +1,42 @@
+use super::{
+	monitoring_stream, MonitoringFeed, MonitoringQueryDto, MonitoringRepository,
+};
+use crate::{common_response, AppState};
+use axum::{
+	http::StatusCode,
+	response::{
+		sse::{KeepAlive, Sse},
+		IntoResponse, Response,
+	},
+};
+
+pub struct MonitoringService;
+
+fn monitoring_error_status(msg: &str) -> StatusCode {
+	match msg {
+		"Session not found" => StatusCode::NOT_FOUND,
+		"Test not found in session" => StatusCode::BAD_REQUEST,
+		_ => StatusCode::INTERNAL_SERVER_ERROR,
+	}
+}
+
+impl MonitoringService {
+	pub async fn watch_session(
+		state: &AppState,
+		session_id: String,
+		query: MonitoringQueryDto,
+	) -> Response {
+		let repo = MonitoringRepository::new(state);
+		if let Err(e) = repo
+			.query_ensure_watchable(&session_id, query.test_id.as_deref())
+			.await
+		{
+			let msg = e.to_string();
+			return common_response(monitoring_error_status(&msg), &msg);
+		}
+		let feed = MonitoringFeed::new(query.test_id);
+		Sse::new(monitoring_stream(state.clone(), session_id, feed))
+			.keep_alive(KeepAlive::default())
+			.into_response()
+	}
+}
@@ -4,7 +4,7 @@ use super::{
 };
 use crate::{
 	permissions_guard, AppState, MessageResponseDto, MetaRequestDto, PermissionsEnum,
-	ResponseListSuccessDto, ResponseSuccessDto,
+	ResponseListSuccessDto, ResponseSuccessDto, SessionsViewerDto, UsersItemDtoRaw,
 };
 use axum::{
 	extract::{Path, Query},
@@ -64,6 +64,7 @@ pub async fn get_option_list(
 pub async fn get_option_by_id(
 	headers: axum::http::HeaderMap,
 	Extension(state): Extension<AppState>,
+	extensions: axum::http::Extensions,
 	Path(id): Path<String>,
 ) -> impl IntoResponse {
 	match permissions_guard(
@@ -73,7 +74,10 @@ pub async fn get_option_by_id(
 	)
 	.await
 	{
-		Ok(_) => OptionsService::get_option_by_id(&state, id).await,
+		Ok(_) => {
+			let viewer = SessionsViewerDto::from(extensions.get::<UsersItemDtoRaw>());
+			OptionsService::get_option_by_id(&state, id, viewer).await
+		}
 		Err(response) => response,
 	}
 }
@@ -25,6 +25,7 @@ pub struct OptionsUpdateRequestDto {
 pub struct OptionsItemDto {
 	pub id: String,
 	pub label: String,
+	pub display_label: Option<String>,
 	pub image_url: Option<String>,
 	pub is_correct: Option<bool>,
 	pub points: Option<i32>,
@@ -65,6 +66,7 @@ impl From<OptionsSchema> for OptionsItemDto {
 				_ => "".to_string(),
 			},
 			label: o.label.unwrap_or("".into()),
+			display_label: None,
 			is_correct: Some(o.is_correct),
 			points: o.points,
 			image_url: o.image_url,
@@ -73,3 +75,13 @@ impl From<OptionsSchema> for OptionsItemDto {
 		}
 	}
 }
+
+impl OptionsItemDto {
+	pub fn without_answer_key(self) -> Self {
+		Self {
+			is_correct: None,
+			points: None,
+			..self
+		}
+	}
+}
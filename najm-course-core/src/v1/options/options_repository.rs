@@ -82,6 +82,7 @@ impl<'a> OptionsRepository<'a> {
 		Ok(OptionsItemDto {
 			id: extract_id(&option.id),
 			label: option.label.unwrap_or("".into()),
+			display_label: None,
 			image_url: option.image_url,
 			is_correct: None,
 			points: option.points,
@@ -105,6 +106,7 @@ impl<'a> OptionsRepository<'a> {
 		Ok(OptionsItemDto {
 			id: extract_id(&option.id),
 			label: option.label.unwrap_or("".into()),
+			display_label: None,
 			image_url: option.image_url,
 			is_correct: None,
 			points: option.points,
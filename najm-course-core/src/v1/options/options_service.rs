@@ -2,6 +2,7 @@ use super::{OptionsCreateRequestDto, OptionsRepository, OptionsUpdateRequestDto}
 use crate::{
 	common_response, success_list_response, success_response, validate_request,
 	AppState, MetaRequestDto, ResponseListSuccessDto, ResponseSuccessDto,
+	SessionsViewerDto,
 };
 use axum::{http::StatusCode, response::Response};
 
@@ -22,9 +23,16 @@ impl OptionsService {
 		}
 	}
 
-	pub async fn get_option_by_id(state: &AppState, id: String) -> Response {
+	pub async fn get_option_by_id(
+		state: &AppState,
+		id: String,
+		viewer: SessionsViewerDto,
+	) -> Response {
 		let repo = OptionsRepository::new(state);
 		match repo.query_option_by_id(id).await {
+			Ok(option) if !viewer.is_reviewer => success_response(ResponseSuccessDto {
+				data: option.without_answer_key(),
+			}),
 			Ok(option) => success_response(ResponseSuccessDto { data: option }),
 			Err(e) => common_response(StatusCode::NOT_FOUND, &e.to_string()),
 		}
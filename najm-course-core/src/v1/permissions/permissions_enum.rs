@@ -43,6 +43,25 @@ pub enum PermissionsEnum {
 	CreateQuestions,
 	UpdateQuestions,
 	DeleteQuestions,
+	ReadDetailAttempts,
+	CreateAttempts,
+	UpdateAttempts,
+	CalibrateQuestions,
+	ReadListSessionResults,
+	ReadDetailSessionResults,
+	CreateSessionResults,
+	AnalyzeQuestions,
+	GradeAnswers,
+	ManageEnrollments,
+	CreateEnrollments,
+	ReadListAttempts,
+	ReviewAnswerKeys,
+	CreateProctoringEvents,
+	ReadListProctoringEvents,
+	BroadcastSessions,
+	MonitorSessions,
+	TransferSessions,
+	ExportSessionResults,
 }
 
 impl fmt::Display for PermissionsEnum {
@@ -89,6 +108,25 @@ impl fmt::Display for PermissionsEnum {
 			PermissionsEnum::CreateQuestions => "Create Questions",
 			PermissionsEnum::UpdateQuestions => "Update Questions",
 			PermissionsEnum::DeleteQuestions => "Delete Questions",
+			PermissionsEnum::ReadDetailAttempts => "Read Detail Attempts",
+			PermissionsEnum::CreateAttempts => "Create Attempts",
+			PermissionsEnum::UpdateAttempts => "Update Attempts",
+			PermissionsEnum::CalibrateQuestions => "Calibrate Questions",
+			PermissionsEnum::ReadListSessionResults => "Read List Session Results",
+			PermissionsEnum::ReadDetailSessionResults => "Read Detail Session Results",
+			PermissionsEnum::CreateSessionResults => "Create Session Results",
+			PermissionsEnum::AnalyzeQuestions => "Analyze Questions",
+			PermissionsEnum::GradeAnswers => "Grade Answers",
+			PermissionsEnum::ManageEnrollments => "Manage Enrollments",
+			PermissionsEnum::CreateEnrollments => "Create Enrollments",
+			PermissionsEnum::ReadListAttempts => "Read List Attempts",
+			PermissionsEnum::ReviewAnswerKeys => "Review Answer Keys",
+			PermissionsEnum::CreateProctoringEvents => "Create Proctoring Events",
+			PermissionsEnum::ReadListProctoringEvents => "Read List Proctoring Events",
+			PermissionsEnum::BroadcastSessions => "Broadcast Sessions",
+			PermissionsEnum::MonitorSessions => "Monitor Sessions",
+			PermissionsEnum::TransferSessions => "Transfer Sessions",
+			PermissionsEnum::ExportSessionResults => "Export Session Results",
 		};
 		write!(f, "{}", permission_str)
 	}
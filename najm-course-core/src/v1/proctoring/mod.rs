@@ -0,0 +1,31 @@
+use axum::{
+	routing::{get, post},
+	Router,
+};
+
+pub mod proctoring_controller;
+pub mod proctoring_dto;
+pub mod proctoring_enum;
+pub mod proctoring_repository;
+pub mod proctoring_schema;
+pub mod proctoring_service;
+pub mod proctoring_suspicion;
+
+#[cfg(test)]
+pub mod proctoring_repository_test;
+#[cfg(test)]
+pub mod proctoring_suspicion_test;
+
+pub use proctoring_controller::*;
+pub use proctoring_dto::*;
+pub use proctoring_enum::*;
+pub use proctoring_repository::*;
+pub use proctoring_schema::*;
+pub use proctoring_service::*;
+pub use proctoring_suspicion::*;
+
+pub fn proctoring_router() -> Router {
+	Router::new()
+		.route("/create", post(post_create_proctoring_event))
+		.route("/attempt/{id}", get(get_proctoring_timeline))
+}
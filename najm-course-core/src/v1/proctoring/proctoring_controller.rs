@@ -0,0 +1,95 @@
+use super::{
+	ProctoringEventsCreateRequestDto, ProctoringEventsItemDto, ProctoringService,
+	ProctoringTimelineDto,
+};
+use crate::{
+	permissions_guard, AppState, PermissionsEnum, ResponseSuccessDto, UsersItemDtoRaw,
+};
+use axum::{
+	extract::{ConnectInfo, Path},
+	http::HeaderMap,
+	response::IntoResponse,
+	Extension, Json,
+};
+use std::net::SocketAddr;
+
+/// Prefers what the reverse proxy appended itself; client-supplied
+/// X-Forwarded-For entries to the left of the last hop are spoofable.
+pub fn client_ip(headers: &HeaderMap, peer: Option<SocketAddr>) -> Option<String> {
+	headers
+		.get("x-real-ip")
+		.and_then(|v| v.to_str().ok())
+		.or_else(|| {
+			headers
+				.get("x-forwarded-for")
+				.and_then(|v| v.to_str().ok())
+				.and_then(|v| v.rsplit(',').next())
+		})
+		.map(|v| v.trim().to_string())
+		.filter(|v| !v.is_empty())
+		.or_else(|| peer.map(|addr| addr.ip().to_string()))
+}
+
+#[utoipa::path(
+	post,
+	security(("Bearer" = [])),
+	path = "/v1/proctoring/create",
+	request_body = ProctoringEventsCreateRequestDto,
+	responses(
+		(status = 200, description = "Report an integrity event during an attempt", body = ResponseSuccessDto<ProctoringEventsItemDto>)
+	),
+	tag = "Proctoring"
+)]
+pub async fn post_create_proctoring_event(
+	headers: HeaderMap,
+	Extension(state): Extension<AppState>,
+	extensions: axum::http::Extensions,
+	Json(payload): Json<ProctoringEventsCreateRequestDto>,
+) -> impl IntoResponse {
+	match permissions_guard(
+		&headers,
+		state.clone(),
+		vec![PermissionsEnum::CreateProctoringEvents],
+	)
+	.await
+	{
+		Ok(_) => {
+			let user_id = extensions
+				.get::<UsersItemDtoRaw>()
+				.map(|user| user.id.id.to_raw());
+			let peer = extensions
+				.get::<ConnectInfo<SocketAddr>>()
+				.map(|ConnectInfo(addr)| *addr);
+			let ip = client_ip(&headers, peer);
+			ProctoringService::create_event(&state, payload, user_id, ip).await
+		}
+		Err(response) => response,
+	}
+}
+
+#[utoipa::path(
+	get,
+	security(("Bearer" = [])),
+	path = "/v1/proctoring/attempt/{id}",
+	params(("id" = String, Path, description = "Attempt ID")),
+	responses(
+		(status = 200, description = "Get the integrity event timeline of an attempt", body = ResponseSuccessDto<ProctoringTimelineDto>)
+	),
+	tag = "Proctoring"
+)]
+pub async fn get_proctoring_timeline(
+	headers: HeaderMap,
+	Extension(state): Extension<AppState>,
+	Path(id): Path<String>,
+) -> impl IntoResponse {
+	match permissions_guard(
+		&headers,
+		state.clone(),
+		vec![PermissionsEnum::ReadListProctoringEvents],
+	)
+	.await
+	{
+		Ok(_) => ProctoringService::get_timeline(&state, id).await,
+		Err(response) => response,
+	}
+}
@@ -0,0 +1,69 @@
+use super::ProctoringEventsSchema;
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+use validator::Validate;
+
+#[derive(Clone, Debug, Serialize, Deserialize, Validate, ToSchema)]
+pub struct ProctoringEventsCreateRequestDto {
+	#[validate(length(min = 1))]
+	#[schema(example = "uuid")]
+	pub attempt_id: String,
+
+	#[validate(length(min = 1))]
+	#[schema(example = "window_blur")]
+	pub event_type: String,
+
+	#[validate(length(max = 500))]
+	#[schema(example = "Switched to another tab for 12 seconds")]
+	pub detail: Option<String>,
+
+	#[schema(example = "2025-01-01T08:15:00Z")]
+	pub occurred_at: Option<String>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, ToSchema)]
+pub struct ProctoringEventsItemDto {
+	pub id: String,
+	pub attempt_id: String,
+	#[schema(example = "window_blur")]
+	pub event_type: String,
+	pub detail: Option<String>,
+	#[schema(example = "203.0.113.7")]
+	pub ip_address: Option<String>,
+	pub occurred_at: String,
+	pub created_at: String,
+}
+
+impl From<ProctoringEventsSchema> for ProctoringEventsItemDto {
+	fn from(value: ProctoringEventsSchema) -> Self {
+		ProctoringEventsItemDto {
+			id: value.id.id.to_raw(),
+			attempt_id: value.attempt.id.to_raw(),
+			event_type: value.event_type,
+			detail: value.detail,
+			ip_address: value.ip_address,
+			occurred_at: value.occurred_at,
+			created_at: value.created_at,
+		}
+	}
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, ToSchema)]
+pub struct ProctoringEventCountDto {
+	#[schema(example = "window_blur")]
+	pub event_type: String,
+	#[schema(example = 3)]
+	pub count: u32,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, ToSchema)]
+pub struct ProctoringTimelineDto {
+	pub attempt_id: String,
+	pub user_id: String,
+	pub session_id: String,
+	pub test_id: String,
+	#[schema(example = 36)]
+	pub suspicion_score: u32,
+	pub counts: Vec<ProctoringEventCountDto>,
+	pub events: Vec<ProctoringEventsItemDto>,
+}
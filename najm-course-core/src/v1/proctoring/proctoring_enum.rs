@@ -0,0 +1,59 @@
+use std::fmt;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ProctoringEventTypeEnum {
+	WindowBlur,
+	FullscreenExit,
+	CopyPaste,
+	MultipleMonitors,
+	IpChange,
+}
+
+impl ProctoringEventTypeEnum {
+	pub const ALL: [ProctoringEventTypeEnum; 5] = [
+		ProctoringEventTypeEnum::WindowBlur,
+		ProctoringEventTypeEnum::FullscreenExit,
+		ProctoringEventTypeEnum::CopyPaste,
+		ProctoringEventTypeEnum::MultipleMonitors,
+		ProctoringEventTypeEnum::IpChange,
+	];
+
+	/// How much one occurrence adds to the suspicion score.
+	pub fn weight(&self) -> u32 {
+		match self {
+			ProctoringEventTypeEnum::WindowBlur => 2,
+			ProctoringEventTypeEnum::FullscreenExit => 5,
+			ProctoringEventTypeEnum::CopyPaste => 8,
+			ProctoringEventTypeEnum::IpChange => 10,
+			ProctoringEventTypeEnum::MultipleMonitors => 15,
+		}
+	}
+}
+
+impl fmt::Display for ProctoringEventTypeEnum {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		let event_str = match self {
+			ProctoringEventTypeEnum::WindowBlur => "window_blur",
+			ProctoringEventTypeEnum::FullscreenExit => "fullscreen_exit",
+			ProctoringEventTypeEnum::CopyPaste => "copy_paste",
+			ProctoringEventTypeEnum::MultipleMonitors => "multiple_monitors",
+			ProctoringEventTypeEnum::IpChange => "ip_change",
+		};
+		write!(f, "{}", event_str)
+	}
+}
+
+impl std::str::FromStr for ProctoringEventTypeEnum {
+	type Err = anyhow::Error;
+
+	fn from_str(value: &str) -> Result<Self, Self::Err> {
+		match value {
+			"window_blur" => Ok(ProctoringEventTypeEnum::WindowBlur),
+			"fullscreen_exit" => Ok(ProctoringEventTypeEnum::FullscreenExit),
+			"copy_paste" => Ok(ProctoringEventTypeEnum::CopyPaste),
+			"multiple_monitors" => Ok(ProctoringEventTypeEnum::MultipleMonitors),
+			"ip_change" => Ok(ProctoringEventTypeEnum::IpChange),
+			_ => anyhow::bail!("Invalid event type"),
+		}
+	}
+}
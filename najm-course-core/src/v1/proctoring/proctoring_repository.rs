@@ -0,0 +1,159 @@
+use super::{
+	count_events, suspicion_score, ProctoringEventTypeEnum,
+	ProctoringEventsCreateRequestDto, ProctoringEventsItemDto, ProctoringEventsSchema,
+	ProctoringTimelineDto,
+};
+use crate::{AppState, AttemptsRepository, AttemptsSchema, AttemptsStatusEnum};
+use anyhow::{bail, Result};
+use chrono::{DateTime, Utc};
+use najm_course_libs::ResourceEnum;
+use najm_course_utils::{get_iso_date, make_thing};
+use validator::Validate;
+
+pub struct ProctoringRepository<'a> {
+	state: &'a AppState,
+}
+
+impl<'a> ProctoringRepository<'a> {
+	pub fn new(state: &'a AppState) -> Self {
+		Self { state }
+	}
+
+	pub async fn query_events_by_attempt(
+		&self,
+		attempt_id: &str,
+	) -> Result<Vec<ProctoringEventsSchema>> {
+		let db = &self.state.surrealdb_ws;
+		let events: Vec<ProctoringEventsSchema> = db
+			.query(format!(
+				"SELECT * FROM {} WHERE attempt = {}:⟨{}⟩ ORDER BY occurred_at ASC, created_at ASC",
+				ResourceEnum::ProctoringEvents,
+				ResourceEnum::Attempts,
+				attempt_id
+			))
+			.await?
+			.take(0)?;
+		Ok(events)
+	}
+
+	async fn query_last_ip_address(&self, attempt_id: &str) -> Result<Option<String>> {
+		let events = self.query_events_by_attempt(attempt_id).await?;
+		Ok(
+			events
+				.into_iter()
+				.filter(|e| e.ip_address.is_some())
+				.max_by(|a, b| a.created_at.cmp(&b.created_at))
+				.and_then(|e| e.ip_address),
+		)
+	}
+
+	async fn query_insert_event(
+		&self,
+		attempt: &AttemptsSchema,
+		event_type: ProctoringEventTypeEnum,
+		detail: Option<String>,
+		ip_address: Option<String>,
+		occurred_at: String,
+	) -> Result<ProctoringEventsItemDto> {
+		let db = &self.state.surrealdb_ws;
+		let id = surrealdb::Uuid::new_v4().to_string();
+		let event = ProctoringEventsSchema {
+			id: make_thing(&ResourceEnum::ProctoringEvents.to_string(), &id),
+			attempt: attempt.id.clone(),
+			user: attempt.user.clone(),
+			session: attempt.session.clone(),
+			test: attempt.test.clone(),
+			event_type: event_type.to_string(),
+			detail: detail.filter(|d| !d.trim().is_empty()),
+			ip_address,
+			occurred_at,
+			created_at: get_iso_date(),
+		};
+		let created: Option<ProctoringEventsSchema> = db
+			.create((ResourceEnum::ProctoringEvents.to_string(), id))
+			.content(event)
+			.await?;
+		match created {
+			Some(e) => Ok(ProctoringEventsItemDto::from(e)),
+			None => bail!("Failed to create proctoring event"),
+		}
+	}
+
+	/// Records an integrity event reported by the exam client. The request
+	/// address is stored with every event; when it differs from the last one
+	/// seen for the attempt an `ip_change` event is logged as well, so the
+	/// change is captured even if the client never reports it.
+	pub async fn query_create(
+		&self,
+		payload: ProctoringEventsCreateRequestDto,
+		user_id: Option<&str>,
+		ip_address: Option<String>,
+	) -> Result<ProctoringEventsItemDto> {
+		payload.validate()?;
+		let event_type: ProctoringEventTypeEnum = payload.event_type.parse()?;
+		let occurred_at = match &payload.occurred_at {
+			Some(value) => DateTime::parse_from_rfc3339(value)
+				.map_err(|_| anyhow::anyhow!("Invalid date format"))?
+				.with_timezone(&Utc)
+				.to_rfc3339(),
+			None => get_iso_date(),
+		};
+		let attempt_repo = AttemptsRepository::new(self.state);
+		let attempt = attempt_repo
+			.query_raw_attempt_by_id(&payload.attempt_id)
+			.await?;
+		let attempt = attempt_repo.query_close_if_expired(attempt).await?;
+		if user_id.is_some_and(|user_id| attempt.user.id.to_raw() != user_id) {
+			bail!("Attempt does not belong to user");
+		}
+		if attempt.status != AttemptsStatusEnum::InProgress.to_string() {
+			bail!("Attempt is not in progress");
+		}
+		let previous_ip = self.query_last_ip_address(&payload.attempt_id).await?;
+		if let (Some(previous), Some(current)) = (&previous_ip, &ip_address) {
+			if previous != current && event_type != ProctoringEventTypeEnum::IpChange {
+				self
+					.query_insert_event(
+						&attempt,
+						ProctoringEventTypeEnum::IpChange,
+						Some(format!("Changed from {} to {}", previous, current)),
+						ip_address.clone(),
+						occurred_at.clone(),
+					)
+					.await?;
+			}
+		}
+		self
+			.query_insert_event(
+				&attempt,
+				event_type,
+				payload.detail,
+				ip_address,
+				occurred_at,
+			)
+			.await
+	}
+
+	pub async fn query_timeline(
+		&self,
+		attempt_id: &str,
+	) -> Result<ProctoringTimelineDto> {
+		let attempt = AttemptsRepository::new(self.state)
+			.query_raw_attempt_by_id(attempt_id)
+			.await?;
+		let events = self.query_events_by_attempt(attempt_id).await?;
+		let counts = count_events(events.iter().map(|e| e.event_type.as_str()));
+		Ok(ProctoringTimelineDto {
+			attempt_id: attempt.id.id.to_raw(),
+			user_id: attempt.user.id.to_raw(),
+			session_id: attempt.session.id.to_raw(),
+			test_id: attempt.test.id.to_raw(),
+			suspicion_score: suspicion_score(&counts),
+			counts,
+			events: events
+				.into_iter()
+				.map(ProctoringEventsItemDto::from)
+				.collect(),
+		})
+	}
+}
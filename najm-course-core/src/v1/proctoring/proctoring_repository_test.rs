@@ -0,0 +1,77 @@
+use super::*;
+use crate::{
+	attempts::attempts_repository_test::{
+		build_start_payload, seed_attempt_dependencies,
+	},
+	create_mock_app_state, AttemptsRepository,
+};
+use anyhow::Result;
+
+fn event(
+	attempt_id: &str,
+	event_type: ProctoringEventTypeEnum,
+) -> ProctoringEventsCreateRequestDto {
+	ProctoringEventsCreateRequestDto {
+		attempt_id: attempt_id.into(),
+		event_type: event_type.to_string(),
+		detail: None,
+		occurred_at: None,
+	}
+}
+
+#[tokio::test]
+async fn test_query_create_should_log_ip_change_and_score_timeline() -> Result<()> {
+	let state = create_mock_app_state().await;
+	let fixture =
+		seed_attempt_dependencies(&state.surrealdb_ws, -10, 120, Some(30)).await?;
+	let attempt = AttemptsRepository::new(&state)
+		.query_start_attempt(&fixture.user_id, build_start_payload(&fixture))
+		.await?;
+	let repo = ProctoringRepository::new(&state);
+	repo
+		.query_create(
+			event(&attempt.id, ProctoringEventTypeEnum::WindowBlur),
+			Some(&fixture.user_id),
+			Some("203.0.113.7".into()),
+		)
+		.await?;
+	repo
+		.query_create(
+			event(&attempt.id, ProctoringEventTypeEnum::CopyPaste),
+			Some(&fixture.user_id),
+			Some("198.51.100.4".into()),
+		)
+		.await?;
+	let timeline = repo.query_timeline(&attempt.id).await?;
+	assert_eq!(timeline.events.len(), 3);
+	let ip_change = timeline
+		.events
+		.iter()
+		.find(|e| e.event_type == ProctoringEventTypeEnum::IpChange.to_string())
+		.expect("ip change should be logged");
+	assert_eq!(ip_change.ip_address.as_deref(), Some("198.51.100.4"));
+	assert_eq!(timeline.suspicion_score, 2 + 8 + 10);
+	Ok(())
+}
+
+#[tokio::test]
+async fn test_query_create_should_reject_other_users_attempt() -> Result<()> {
+	let state = create_mock_app_state().await;
+	let fixture =
+		seed_attempt_dependencies(&state.surrealdb_ws, -10, 120, Some(30)).await?;
+	let attempt = AttemptsRepository::new(&state)
+		.query_start_attempt(&fixture.user_id, build_start_payload(&fixture))
+		.await?;
+	let result = ProctoringRepository::new(&state)
+		.query_create(
+			event(&attempt.id, ProctoringEventTypeEnum::FullscreenExit),
+			Some("someone_else"),
+			None,
+		)
+		.await;
+	assert_eq!(
+		result.unwrap_err().to_string(),
+		"Attempt does not belong to user"
+	);
+	Ok(())
+}
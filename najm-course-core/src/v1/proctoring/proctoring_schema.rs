@@ -0,0 +1,16 @@
+use serde::{Deserialize, Serialize};
+use surrealdb::sql::Thing;
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct ProctoringEventsSchema {
+	pub id: Thing,
+	pub attempt: Thing,
+	pub user: Thing,
+	pub session: Thing,
+	pub test: Thing,
+	pub event_type: String,
+	pub detail: Option<String>,
+	pub ip_address: Option<String>,
+	pub occurred_at: String,
+	pub created_at: String,
+}
@@ -0,0 +1,52 @@
+use super::{ProctoringEventsCreateRequestDto, ProctoringRepository};
+use crate::{
+	common_response, success_response, validate_request, AppState, ResponseSuccessDto,
+};
+use axum::{http::StatusCode, response::Response};
+
+pub struct ProctoringService;
+
+fn proctoring_error_status(msg: &str) -> StatusCode {
+	match msg {
+		"Attempt not found" => StatusCode::NOT_FOUND,
+		"Attempt does not belong to user" => StatusCode::FORBIDDEN,
+		"Attempt is not in progress" => StatusCode::CONFLICT,
+		"Invalid event type" | "Invalid date format" => StatusCode::BAD_REQUEST,
+		_ => StatusCode::INTERNAL_SERVER_ERROR,
+	}
+}
+
+impl ProctoringService {
+	pub async fn create_event(
+		state: &AppState,
+		payload: ProctoringEventsCreateRequestDto,
+		user_id: Option<String>,
+		ip_address: Option<String>,
+	) -> Response {
+		if let Err((status, message)) = validate_request(&payload) {
+			return common_response(status, &message);
+		}
+		let repo = ProctoringRepository::new(state);
+		match repo
+			.query_create(payload, user_id.as_deref(), ip_address)
+			.await
+		{
+			Ok(data) => success_response(ResponseSuccessDto { data }),
+			Err(e) => {
+				let msg = e.to_string();
+				common_response(proctoring_error_status(&msg), &msg)
+			}
+		}
+	}
+
+	pub async fn get_timeline(state: &AppState, attempt_id: String) -> Response {
+		let repo = ProctoringRepository::new(state);
+		match repo.query_timeline(&attempt_id).await {
+			Ok(data) => success_response(ResponseSuccessDto { data }),
+			Err(e) => {
+				let msg = e.to_string();
+				common_response(proctoring_error_status(&msg), &msg)
+			}
+		}
+	}
+}
@@ -0,0 +1,37 @@
+use super::{ProctoringEventCountDto, ProctoringEventTypeEnum};
+
+pub const MAX_SUSPICION_SCORE: u32 = 100;
+
+/// Counts events per known type, in a fixed order so timelines compare
+/// easily. Unknown types stored by older clients are ignored.
+pub fn count_events<'a>(
+	event_types: impl IntoIterator<Item = &'a str>,
+) -> Vec<ProctoringEventCountDto> {
+	let mut counts: Vec<ProctoringEventCountDto> = ProctoringEventTypeEnum::ALL
+		.iter()
+		.map(|t| ProctoringEventCountDto {
+			event_type: t.to_string(),
+			count: 0,
+		})
+		.collect();
+	for event_type in event_types {
+		if let Some(entry) = counts.iter_mut().find(|c| c.event_type == event_type) {
+			entry.count += 1;
+		}
+	}
+	counts
+}
+
+/// Weighted sum of the event counts, capped at `MAX_SUSPICION_SCORE`.
+pub fn suspicion_score(counts: &[ProctoringEventCountDto]) -> u32 {
+	counts
+		.iter()
+		.filter_map(|c| {
+			c.event_type
+				.parse::<ProctoringEventTypeEnum>()
+				.ok()
+				.map(|t| t.weight() * c.count)
+		})
+		.sum::<u32>()
+		.min(MAX_SUSPICION_SCORE)
+}
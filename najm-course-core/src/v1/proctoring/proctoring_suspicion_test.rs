@@ -0,0 +1,25 @@
+use super::*;
+
+#[test]
+fn test_count_events_should_ignore_unknown_types() {
+	let counts =
+		count_events(["window_blur", "window_blur", "screenshot", "ip_change"]);
+	let count = |t: ProctoringEventTypeEnum| {
+		counts
+			.iter()
+			.find(|c| c.event_type == t.to_string())
+			.map(|c| c.count)
+	};
+	assert_eq!(counts.len(), ProctoringEventTypeEnum::ALL.len());
+	assert_eq!(count(ProctoringEventTypeEnum::WindowBlur), Some(2));
+	assert_eq!(count(ProctoringEventTypeEnum::IpChange), Some(1));
+	assert_eq!(count(ProctoringEventTypeEnum::CopyPaste), Some(0));
+}
+
+#[test]
+fn test_suspicion_score_should_weight_and_cap() {
+	let counts = count_events(["fullscreen_exit", "multiple_monitors"]);
+	assert_eq!(suspicion_score(&counts), 5 + 15);
+	let flood = count_events(std::iter::repeat_n("copy_paste", 20));
+	assert_eq!(suspicion_score(&flood), MAX_SUSPICION_SCORE);
+}
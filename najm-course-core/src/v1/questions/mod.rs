@@ -3,19 +3,25 @@ use axum::{
 	Router,
 };
 
+pub mod questions_analysis;
 pub mod questions_controller;
 pub mod questions_dto;
+pub mod questions_enum;
 pub mod questions_repository;
 pub mod questions_schema;
 pub mod questions_service;
 
+#[cfg(test)]
+pub mod questions_analysis_test;
 #[cfg(test)]
 pub mod questions_controller_test;
 #[cfg(test)]
 pub mod questions_repository_test;
 
+pub use questions_analysis::*;
 pub use questions_controller::*;
 pub use questions_dto::*;
+pub use questions_enum::*;
 pub use questions_repository::*;
 pub use questions_schema::*;
 pub use questions_service::*;
@@ -23,6 +29,8 @@ pub use questions_service::*;
 pub fn questions_router() -> Router {
 	Router::new()
 		.route("/", get(get_question_list))
+		.route("/analysis", get(get_question_analysis_list))
+		.route("/analysis/{id}", get(get_question_analysis_by_id))
 		.route("/create", post(post_create_question))
 		.route("/detail/{id}", get(get_question_by_id))
 		.route("/update/{id}", put(put_update_question))
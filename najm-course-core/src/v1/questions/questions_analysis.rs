@@ -0,0 +1,184 @@
+use super::{
+	QuestionsAnalysisFlagEnum, QuestionsAnalysisItemDto, QuestionsOptionAnalysisDto,
+};
+
+pub const ANALYSIS_DEFAULT_MIN_RESPONSES: u32 = 30;
+pub const ANALYSIS_GROUP_FRACTION: f64 = 0.27;
+pub const ANALYSIS_MIN_DIFFICULTY: f64 = 0.2;
+pub const ANALYSIS_MAX_DIFFICULTY: f64 = 0.9;
+pub const ANALYSIS_MIN_DISCRIMINATION: f64 = 0.2;
+pub const ANALYSIS_MIN_DISTRACTOR_RATE: f64 = 0.05;
+
+#[derive(Clone, Debug)]
+pub struct ItemResponse {
+	pub option_id: String,
+	pub is_correct: bool,
+	pub total_score: f64,
+}
+
+#[derive(Clone, Debug)]
+pub struct ItemOption {
+	pub id: String,
+	pub label: String,
+	pub is_correct: bool,
+}
+
+fn round4(value: f64) -> f64 {
+	(value * 10_000.0).round() / 10_000.0
+}
+
+fn rate(count: usize, total: usize) -> f64 {
+	if total == 0 {
+		0.0
+	} else {
+		round4(count as f64 / total as f64)
+	}
+}
+
+pub fn difficulty_index(responses: &[ItemResponse]) -> f64 {
+	rate(
+		responses.iter().filter(|r| r.is_correct).count(),
+		responses.len(),
+	)
+}
+
+/// Corrected point-biserial: the item is removed from each total so it
+/// does not correlate with itself.
+pub fn point_biserial(responses: &[ItemResponse]) -> Option<f64> {
+	let n = responses.len() as f64;
+	let rest: Vec<f64> = responses
+		.iter()
+		.map(|r| r.total_score - if r.is_correct { 1.0 } else { 0.0 })
+		.collect();
+	let correct = responses.iter().filter(|r| r.is_correct).count() as f64;
+	if correct == 0.0 || correct == n {
+		return None;
+	}
+	let mean = rest.iter().sum::<f64>() / n;
+	let sd = (rest.iter().map(|x| (x - mean).powi(2)).sum::<f64>() / n).sqrt();
+	if sd == 0.0 {
+		return None;
+	}
+	let (sum_correct, sum_incorrect) =
+		responses
+			.iter()
+			.zip(&rest)
+			.fold((0.0, 0.0), |(c, i), (r, x)| {
+				if r.is_correct {
+					(c + x, i)
+				} else {
+					(c, i + x)
+				}
+			});
+	let mean_correct = sum_correct / correct;
+	let mean_incorrect = sum_incorrect / (n - correct);
+	let p = correct / n;
+	Some(round4(
+		(mean_correct - mean_incorrect) / sd * (p * (1.0 - p)).sqrt(),
+	))
+}
+
+/// Indices of the top and bottom scorer groups (27% rule, never overlapping).
+pub fn scorer_groups(responses: &[ItemResponse]) -> (Vec<usize>, Vec<usize>) {
+	let n = responses.len();
+	if n < 2 {
+		return (vec![], vec![]);
+	}
+	let size = ((n as f64 * ANALYSIS_GROUP_FRACTION).round() as usize).clamp(1, n / 2);
+	let mut order: Vec<usize> = (0..n).collect();
+	order.sort_by(|a, b| {
+		responses[*b]
+			.total_score
+			.partial_cmp(&responses[*a].total_score)
+			.unwrap_or(std::cmp::Ordering::Equal)
+	});
+	let top = order[..size].to_vec();
+	let bottom = order[n - size..].to_vec();
+	(top, bottom)
+}
+
+pub fn analyze_item(
+	question_id: &str,
+	question: &str,
+	options: &[ItemOption],
+	responses: &[ItemResponse],
+	min_responses: u32,
+) -> QuestionsAnalysisItemDto {
+	let (top, bottom) = scorer_groups(responses);
+	let enough = responses.len() >= min_responses as usize;
+	let count_in = |group: &[usize], option_id: &str| {
+		group
+			.iter()
+			.filter(|i| responses[**i].option_id == option_id)
+			.count()
+	};
+	let option_stats: Vec<QuestionsOptionAnalysisDto> = options
+		.iter()
+		.map(|option| {
+			let selection_count = responses
+				.iter()
+				.filter(|r| r.option_id == option.id)
+				.count();
+			let selection_rate = rate(selection_count, responses.len());
+			let top_group_rate = rate(count_in(&top, &option.id), top.len());
+			let bottom_group_rate = rate(count_in(&bottom, &option.id), bottom.len());
+			let mut flags = vec![];
+			if enough && !option.is_correct {
+				if selection_rate < ANALYSIS_MIN_DISTRACTOR_RATE {
+					flags.push(QuestionsAnalysisFlagEnum::NonFunctioningDistractor);
+				}
+				if top_group_rate > bottom_group_rate {
+					flags.push(QuestionsAnalysisFlagEnum::AttractsTopScorers);
+				}
+			}
+			QuestionsOptionAnalysisDto {
+				option_id: option.id.clone(),
+				label: option.label.clone(),
+				is_correct: option.is_correct,
+				selection_count: selection_count as u32,
+				selection_rate,
+				top_group_rate,
+				bottom_group_rate,
+				discrimination: round4(top_group_rate - bottom_group_rate),
+				flags: flags.iter().map(|f| f.to_string()).collect(),
+			}
+		})
+		.collect();
+	let difficulty = difficulty_index(responses);
+	let discrimination = point_biserial(responses);
+	let mut flags = vec![];
+	if !enough {
+		flags.push(QuestionsAnalysisFlagEnum::InsufficientResponses);
+	} else {
+		if difficulty < ANALYSIS_MIN_DIFFICULTY {
+			flags.push(QuestionsAnalysisFlagEnum::TooDifficult);
+		}
+		if difficulty > ANALYSIS_MAX_DIFFICULTY {
+			flags.push(QuestionsAnalysisFlagEnum::TooEasy);
+		}
+		match discrimination {
+			Some(d) if d < 0.0 => {
+				flags.push(QuestionsAnalysisFlagEnum::NegativeDiscrimination)
+			}
+			Some(d) if d < ANALYSIS_MIN_DISCRIMINATION => {
+				flags.push(QuestionsAnalysisFlagEnum::LowDiscrimination)
+			}
+			None => flags.push(QuestionsAnalysisFlagEnum::LowDiscrimination),
+			_ => {}
+		}
+		if option_stats.iter().any(|o| !o.flags.is_empty()) {
+			flags.push(QuestionsAnalysisFlagEnum::PoorDistractors);
+		}
+	}
+	QuestionsAnalysisItemDto {
+		question_id: question_id.to_string(),
+		question: question.to_string(),
+		respondents: responses.len() as u32,
+		correct_count: responses.iter().filter(|r| r.is_correct).count() as u32,
+		difficulty_index: difficulty,
+		discrimination_index: discrimination,
+		is_flagged: !flags.is_empty(),
+		flags: flags.iter().map(|f| f.to_string()).collect(),
+		options: option_stats,
+	}
+}
@@ -0,0 +1,90 @@
+use super::*;
+
+fn response(option_id: &str, is_correct: bool, total_score: f64) -> ItemResponse {
+	ItemResponse {
+		option_id: option_id.into(),
+		is_correct,
+		total_score,
+	}
+}
+
+fn options() -> Vec<ItemOption> {
+	["a", "b", "c"]
+		.iter()
+		.map(|id| ItemOption {
+			id: id.to_string(),
+			label: id.to_uppercase(),
+			is_correct: *id == "b",
+		})
+		.collect()
+}
+
+#[test]
+fn test_difficulty_index_should_be_proportion_correct() {
+	let responses = vec![
+		response("b", true, 3.0),
+		response("a", false, 1.0),
+		response("b", true, 2.0),
+		response("c", false, 0.0),
+	];
+	assert_eq!(difficulty_index(&responses), 0.5);
+	assert_eq!(difficulty_index(&[]), 0.0);
+}
+
+#[test]
+fn test_point_biserial_should_exclude_item_from_total() {
+	let responses = vec![
+		response("b", true, 2.0),
+		response("b", true, 2.0),
+		response("a", false, 0.0),
+		response("a", false, 0.0),
+	];
+	assert_eq!(point_biserial(&responses), Some(1.0));
+	let reversed = vec![
+		response("b", true, 1.0),
+		response("b", true, 1.0),
+		response("a", false, 1.0),
+		response("a", false, 1.0),
+	];
+	assert_eq!(point_biserial(&reversed), Some(-1.0));
+}
+
+#[test]
+fn test_point_biserial_should_be_none_without_variance() {
+	let responses = vec![response("b", true, 2.0), response("b", true, 1.0)];
+	assert_eq!(point_biserial(&responses), None);
+}
+
+#[test]
+fn test_scorer_groups_should_not_overlap() {
+	let responses: Vec<ItemResponse> =
+		(0..10).map(|i| response("b", true, i as f64)).collect();
+	let (top, bottom) = scorer_groups(&responses);
+	assert_eq!(top, vec![9, 8, 7]);
+	assert_eq!(bottom, vec![2, 1, 0]);
+	let (top, bottom) = scorer_groups(&responses[..2]);
+	assert_eq!((top, bottom), (vec![1], vec![0]));
+}
+
+#[test]
+fn test_analyze_item_should_flag_distractors() {
+	let responses = vec![
+		response("b", true, 2.0),
+		response("a", false, 2.0),
+		response("b", true, 1.0),
+		response("a", false, 0.0),
+	];
+	let item = analyze_item("q", "Question", &options(), &responses, 1);
+	let c = item.options.iter().find(|o| o.option_id == "c").unwrap();
+	assert!(c.flags.contains(&"non_functioning_distractor".to_string()));
+	assert!(item.flags.contains(&"poor_distractors".to_string()));
+	assert!(item.is_flagged);
+}
+
+#[test]
+fn test_analyze_item_should_only_flag_insufficient_responses() {
+	let responses = vec![response("b", true, 1.0)];
+	let item = analyze_item("q", "Question", &options(), &responses, 30);
+	assert_eq!(item.flags, vec!["insufficient_responses".to_string()]);
+	assert!(item.options.iter().all(|o| o.flags.is_empty()));
+}
@@ -5,12 +5,13 @@ use axum::{
 };
 
 use super::{
+	QuestionsAnalysisDto, QuestionsAnalysisItemDto, QuestionsAnalysisQueryDto,
 	QuestionsCreateRequestDto, QuestionsItemDto, QuestionsResponseListDto,
-	QuestionsService, QuestionsUpdateRequestDto,
+	QuestionsService, QuestionsTagsDto, QuestionsUpdateRequestDto,
 };
 use crate::{
 	permissions_guard, AppState, MessageResponseDto, MetaRequestDto, PermissionsEnum,
-	ResponseListSuccessDto, ResponseSuccessDto,
+	ResponseListSuccessDto, ResponseSuccessDto, SessionsViewerDto, UsersItemDtoRaw,
 };
 
 #[utoipa::path(
@@ -27,6 +28,11 @@ use crate::{
 		("order" = Option<String>, Query, description = "Order ASC or DESC"),
 		("filter" = Option<String>, Query, description = "Filter value"),
 		("filter_by" = Option<String>, Query, description = "Field to filter by"),
+		("subject" = Option<String>, Query, description = "Filter by subject tag"),
+		("topic" = Option<String>, Query, description = "Filter by topic tag"),
+		("sub_topic" = Option<String>, Query, description = "Filter by sub-topic tag"),
+		("difficulty" = Option<String>, Query, description = "Filter by difficulty: easy, medium or hard"),
+		("source" = Option<String>, Query, description = "Filter by source tag"),
 	),
 	responses(
 		(status = 200, description = "Get question list", body = ResponseListSuccessDto<Vec<QuestionsResponseListDto>>)
@@ -36,7 +42,9 @@ use crate::{
 pub async fn get_question_list(
 	headers: axum::http::HeaderMap,
 	Extension(state): Extension<AppState>,
+	extensions: axum::http::Extensions,
 	Query(meta): Query<MetaRequestDto>,
+	Query(tags): Query<QuestionsTagsDto>,
 ) -> impl IntoResponse {
 	match permissions_guard(
 		&headers,
@@ -45,7 +53,10 @@ pub async fn get_question_list(
 	)
 	.await
 	{
-		Ok(_) => QuestionsService::get_question_list(&state, meta).await,
+		Ok(_) => {
+			let viewer = SessionsViewerDto::from(extensions.get::<UsersItemDtoRaw>());
+			QuestionsService::get_question_list(&state, meta, tags, viewer).await
+		}
 		Err(response) => response,
 	}
 }
@@ -65,6 +76,7 @@ pub async fn get_question_list(
 pub async fn get_question_by_id(
 	headers: axum::http::HeaderMap,
 	Extension(state): Extension<AppState>,
+	extensions: axum::http::Extensions,
 	Path(id): Path<String>,
 ) -> impl IntoResponse {
 	match permissions_guard(
@@ -74,7 +86,78 @@ pub async fn get_question_by_id(
 	)
 	.await
 	{
-		Ok(_) => QuestionsService::get_question_by_id(&state, id).await,
+		Ok(_) => {
+			let viewer = SessionsViewerDto::from(extensions.get::<UsersItemDtoRaw>());
+			QuestionsService::get_question_by_id(&state, id, viewer).await
+		}
+		Err(response) => response,
+	}
+}
+
+#[utoipa::path(
+	get,
+	security(
+		("Bearer" = [])
+	),
+	path = "/v1/questions/analysis",
+	params(
+		("session_id" = Option<String>, Query, description = "Session ID"),
+		("test_id" = Option<String>, Query, description = "Test ID"),
+		("min_responses" = Option<u32>, Query, description = "Minimum responses before flagging"),
+	),
+	responses(
+		(status = 200, description = "Get item analysis for answered questions", body = ResponseSuccessDto<QuestionsAnalysisDto>)
+	),
+	tag = "Questions"
+)]
+pub async fn get_question_analysis_list(
+	headers: axum::http::HeaderMap,
+	Extension(state): Extension<AppState>,
+	Query(query): Query<QuestionsAnalysisQueryDto>,
+) -> impl IntoResponse {
+	match permissions_guard(
+		&headers,
+		state.clone(),
+		vec![PermissionsEnum::AnalyzeQuestions],
+	)
+	.await
+	{
+		Ok(_) => QuestionsService::get_question_analysis_list(&state, query).await,
+		Err(response) => response,
+	}
+}
+
+#[utoipa::path(
+	get,
+	security(
+		("Bearer" = [])
+	),
+	path = "/v1/questions/analysis/{id}",
+	params(
+		("id" = String, Path, description = "Question ID"),
+		("session_id" = Option<String>, Query, description = "Session ID"),
+		("test_id" = Option<String>, Query, description = "Test ID"),
+		("min_responses" = Option<u32>, Query, description = "Minimum responses before flagging"),
+	),
+	responses(
+		(status = 200, description = "Get item analysis by question ID", body = ResponseSuccessDto<QuestionsAnalysisItemDto>)
+	),
+	tag = "Questions"
+)]
+pub async fn get_question_analysis_by_id(
+	headers: axum::http::HeaderMap,
+	Extension(state): Extension<AppState>,
+	Path(id): Path<String>,
+	Query(query): Query<QuestionsAnalysisQueryDto>,
+) -> impl IntoResponse {
+	match permissions_guard(
+		&headers,
+		state.clone(),
+		vec![PermissionsEnum::AnalyzeQuestions],
+	)
+	.await
+	{
+		Ok(_) => QuestionsService::get_question_analysis_by_id(&state, id, query).await,
 		Err(response) => response,
 	}
 }
@@ -36,6 +36,9 @@ fn generate_question_payload() -> QuestionsCreateRequestDto {
 		discussion: Some("Discussion here".into()),
 		question_image_url: None,
 		discussion_image_url: None,
+		question_type: None,
+		answer_key: None,
+		tags: None,
 		options: vec![generate_option("A", false), generate_option("B", true)],
 	}
 }
@@ -104,6 +107,9 @@ async fn test_put_update_question_should_return_200() {
 		discussion: Some("Updated discussion".into()),
 		question_image_url: None,
 		discussion_image_url: None,
+		question_type: None,
+		answer_key: None,
+		tags: None,
 		options: vec![
 			OptionsUpdateRequestDto {
 				id: "".into(),
@@ -166,6 +172,9 @@ async fn test_create_question_should_fail_if_empty_question() {
 			discussion: Some("Valid".into()),
 			question_image_url: None,
 			discussion_image_url: None,
+			question_type: None,
+			answer_key: None,
+			tags: None,
 			options: vec![],
 		}),
 	)
@@ -187,6 +196,9 @@ async fn test_create_question_should_fail_if_empty_discussion() {
 			discussion: Some("".into()),
 			question_image_url: None,
 			discussion_image_url: None,
+			question_type: None,
+			answer_key: None,
+			tags: None,
 			options: vec![],
 		}),
 	)
@@ -208,6 +220,9 @@ async fn test_create_question_should_fail_if_empty_options() {
 			discussion: Some("Valid".into()),
 			question_image_url: None,
 			discussion_image_url: None,
+			question_type: None,
+			answer_key: None,
+			tags: None,
 			options: vec![],
 		}),
 	)
@@ -225,6 +240,9 @@ async fn test_update_question_should_fail_if_not_found() {
 		discussion: Some("Updated".into()),
 		question_image_url: None,
 		discussion_image_url: None,
+		question_type: None,
+		answer_key: None,
+		tags: None,
 		options: vec![OptionsUpdateRequestDto {
 			id: "".into(),
 			label: Some("Updated A".into()),
@@ -273,3 +291,77 @@ async fn test_delete_question_should_return_404_if_not_found() {
 	.await;
 	assert_eq!(res.status_code(), 404);
 }
+
+#[tokio::test]
+async fn test_get_question_analysis_should_return_200() {
+	let state = create_mock_app_state().await;
+	let server = create_test_app(state.clone());
+	let repo = QuestionsRepository::new(&state);
+	let id = repo
+		.query_create_question(generate_question_payload())
+		.await
+		.unwrap();
+	let res = authorized::<()>(
+		&server,
+		"GET",
+		&format!("/v1/questions/analysis/{}?session_id=any_session", id),
+		vec![&PermissionsEnum::AnalyzeQuestions.to_string()],
+		None,
+	)
+	.await;
+	assert_eq!(res.status_code(), 200);
+	let res = authorized::<()>(
+		&server,
+		"GET",
+		"/v1/questions/analysis?session_id=any_session",
+		vec![&PermissionsEnum::AnalyzeQuestions.to_string()],
+		None,
+	)
+	.await;
+	assert_eq!(res.status_code(), 200);
+}
+
+#[tokio::test]
+async fn test_get_question_analysis_should_return_400_without_scope() {
+	let state = create_mock_app_state().await;
+	let server = create_test_app(state);
+	let res = authorized::<()>(
+		&server,
+		"GET",
+		"/v1/questions/analysis",
+		vec![&PermissionsEnum::AnalyzeQuestions.to_string()],
+		None,
+	)
+	.await;
+	assert_eq!(res.status_code(), 400);
+}
+
+#[tokio::test]
+async fn test_get_question_analysis_should_return_404_if_not_found() {
+	let state = create_mock_app_state().await;
+	let server = create_test_app(state);
+	let res = authorized::<()>(
+		&server,
+		"GET",
+		"/v1/questions/analysis/not_found",
+		vec![&PermissionsEnum::AnalyzeQuestions.to_string()],
+		None,
+	)
+	.await;
+	assert_eq!(res.status_code(), 404);
+}
+
+#[tokio::test]
+async fn test_get_question_list_should_filter_by_tags() {
+	let state = create_mock_app_state().await;
+	let server = create_test_app(state);
+	let res = authorized::<()>(
+		&server,
+		"GET",
+		"/v1/questions?subject=Matematika&difficulty=hard&search=aljabar",
+		vec![&PermissionsEnum::ReadListQuestions.to_string()],
+		None,
+	)
+	.await;
+	assert_eq!(res.status_code(), 200);
+}
@@ -1,6 +1,10 @@
-use super::{QuestionsDetailSchema, QuestionsSchema};
+use super::{
+	QuestionsAnswerKeySchema, QuestionsDetailSchema, QuestionsDifficultyEnum,
+	QuestionsSchema, QuestionsTagsSchema, QuestionsTypeEnum,
+};
 use crate::{
 	OptionsCreateRequestDto, OptionsItemDto, OptionsSchema, OptionsUpdateRequestDto,
+	ScoringRuleDto, ScoringRuleSchema,
 };
 use serde::{Deserialize, Serialize};
 use utoipa::ToSchema;
@@ -12,7 +16,10 @@ pub struct QuestionsCreateRequestDto {
 	pub discussion: Option<String>,
 	pub question_image_url: Option<String>,
 	pub discussion_image_url: Option<String>,
-	#[validate(length(min = 1, message = "At least one option is required"))]
+	#[schema(example = "single_choice")]
+	pub question_type: Option<String>,
+	pub answer_key: Option<QuestionsAnswerKeyDto>,
+	pub tags: Option<QuestionsTagsDto>,
 	#[validate]
 	pub options: Vec<OptionsCreateRequestDto>,
 }
@@ -25,11 +32,149 @@ pub struct QuestionsUpdateRequestDto {
 	pub discussion: Option<String>,
 	pub question_image_url: Option<String>,
 	pub discussion_image_url: Option<String>,
-	#[validate(length(min = 1, message = "At least one option is required"))]
+	#[schema(example = "single_choice")]
+	pub question_type: Option<String>,
+	pub answer_key: Option<QuestionsAnswerKeyDto>,
+	pub tags: Option<QuestionsTagsDto>,
 	#[validate]
 	pub options: Vec<OptionsUpdateRequestDto>,
 }
 
+#[derive(Clone, Debug, Default, Serialize, Deserialize, ToSchema)]
+pub struct QuestionsTagsDto {
+	#[schema(example = "Matematika")]
+	pub subject: Option<String>,
+	#[schema(example = "Aljabar")]
+	pub topic: Option<String>,
+	#[schema(example = "Persamaan Linear")]
+	pub sub_topic: Option<String>,
+	#[schema(example = "medium")]
+	pub difficulty: Option<String>,
+	#[schema(example = "UTBK 2024")]
+	pub source: Option<String>,
+}
+
+impl QuestionsTagsDto {
+	pub fn validate_difficulty(&self) -> anyhow::Result<()> {
+		if let Some(difficulty) = &self.difficulty {
+			difficulty.parse::<QuestionsDifficultyEnum>()?;
+		}
+		Ok(())
+	}
+
+	pub fn conditions(&self) -> (Vec<String>, Vec<(String, String)>) {
+		let fields = [
+			("subject", &self.subject),
+			("topic", &self.topic),
+			("sub_topic", &self.sub_topic),
+			("difficulty", &self.difficulty),
+			("source", &self.source),
+		];
+		let mut conditions = vec![];
+		let mut bindings = vec![];
+		for (field, value) in fields {
+			if let Some(value) = value.as_ref().filter(|v| !v.is_empty()) {
+				conditions.push(format!("tags.{} = $tag_{}", field, field));
+				bindings.push((format!("tag_{}", field), value.clone()));
+			}
+		}
+		(conditions, bindings)
+	}
+}
+
+impl From<QuestionsTagsSchema> for QuestionsTagsDto {
+	fn from(value: QuestionsTagsSchema) -> Self {
+		QuestionsTagsDto {
+			subject: value.subject,
+			topic: value.topic,
+			sub_topic: value.sub_topic,
+			difficulty: value.difficulty,
+			source: value.source,
+		}
+	}
+}
+
+impl From<QuestionsTagsDto> for QuestionsTagsSchema {
+	fn from(value: QuestionsTagsDto) -> Self {
+		QuestionsTagsSchema {
+			subject: value.subject,
+			topic: value.topic,
+			sub_topic: value.sub_topic,
+			difficulty: value.difficulty,
+			source: value.source,
+		}
+	}
+}
+
+#[derive(Clone, Debug, Default, Serialize, Deserialize, ToSchema)]
+pub struct QuestionsAnswerKeyDto {
+	#[schema(example = 12.5)]
+	pub numeric_answer: Option<f64>,
+	#[schema(example = 0.05)]
+	pub numeric_tolerance: Option<f64>,
+	#[schema(example = 4)]
+	pub points: Option<i32>,
+	pub scoring_rule: Option<ScoringRuleDto>,
+}
+
+impl From<QuestionsAnswerKeySchema> for QuestionsAnswerKeyDto {
+	fn from(value: QuestionsAnswerKeySchema) -> Self {
+		QuestionsAnswerKeyDto {
+			numeric_answer: value.numeric_answer,
+			numeric_tolerance: value.numeric_tolerance,
+			points: value.points,
+			scoring_rule: value.scoring_rule.map(ScoringRuleDto::from),
+		}
+	}
+}
+
+impl From<QuestionsAnswerKeyDto> for QuestionsAnswerKeySchema {
+	fn from(value: QuestionsAnswerKeyDto) -> Self {
+		QuestionsAnswerKeySchema {
+			numeric_answer: value.numeric_answer,
+			numeric_tolerance: value.numeric_tolerance,
+			points: value.points,
+			scoring_rule: value.scoring_rule.map(ScoringRuleSchema::from),
+		}
+	}
+}
+
+/// Checks that options and answer key fit the question type and returns the
+/// resolved type.
+pub fn validate_question_shape(
+	question_type: Option<&str>,
+	correct_options: &[bool],
+	answer_key: Option<&QuestionsAnswerKeyDto>,
+) -> anyhow::Result<QuestionsTypeEnum> {
+	let question_type = QuestionsTypeEnum::resolve(question_type)?;
+	match question_type {
+		QuestionsTypeEnum::SingleChoice | QuestionsTypeEnum::TrueFalse => {
+			if correct_options.is_empty() {
+				anyhow::bail!("Options must not be empty");
+			}
+		}
+		QuestionsTypeEnum::MultipleCorrect => {
+			if correct_options.len() < 2 || !correct_options.contains(&true) {
+				anyhow::bail!(
+					"Multiple correct questions must have at least two options and one correct option"
+				);
+			}
+		}
+		QuestionsTypeEnum::Numeric => {
+			let numeric_answer = answer_key.and_then(|key| key.numeric_answer);
+			if !numeric_answer.is_some_and(f64::is_finite) {
+				anyhow::bail!("Numeric questions must have a numeric answer");
+			}
+			let tolerance = answer_key.and_then(|key| key.numeric_tolerance);
+			if tolerance.is_some_and(|tolerance| tolerance.is_nan() || tolerance < 0.0) {
+				anyhow::bail!("Numeric tolerance must not be negative");
+			}
+		}
+		QuestionsTypeEnum::Essay => {}
+	}
+	Ok(question_type)
+}
+
 #[derive(Clone, Debug, Serialize, Deserialize, ToSchema)]
 pub struct QuestionsItemDto {
 	pub id: String,
@@ -38,6 +183,9 @@ pub struct QuestionsItemDto {
 	pub question_image_url: Option<String>,
 	pub discussion_image_url: Option<String>,
 	pub options: Vec<OptionsItemDto>,
+	pub question_type: String,
+	pub answer_key: Option<QuestionsAnswerKeyDto>,
+	pub tags: Option<QuestionsTagsDto>,
 	pub created_at: String,
 	pub updated_at: String,
 }
@@ -47,6 +195,7 @@ pub struct QuestionsResponseListDto {
 	pub id: String,
 	pub question: String,
 	pub discussion: String,
+	pub tags: Option<QuestionsTagsDto>,
 	pub created_at: String,
 	pub updated_at: String,
 }
@@ -61,12 +210,22 @@ impl From<QuestionsSchema> for QuestionsResponseListDto {
 			id,
 			question: value.question.unwrap_or("".into()),
 			discussion: value.discussion.unwrap_or("".into()),
+			tags: value.tags.map(QuestionsTagsDto::from),
 			created_at: value.created_at,
 			updated_at: value.updated_at,
 		}
 	}
 }
 
+impl QuestionsResponseListDto {
+	pub fn without_answer_key(self) -> Self {
+		Self {
+			discussion: String::new(),
+			..self
+		}
+	}
+}
+
 impl QuestionsItemDto {
 	pub fn from_with_options(
 		value: QuestionsDetailSchema,
@@ -90,8 +249,69 @@ impl QuestionsItemDto {
 			question_image_url: value.question_image_url,
 			discussion_image_url: value.discussion_image_url,
 			options: mapped_options,
+			question_type: value
+				.question_type
+				.unwrap_or_else(|| QuestionsTypeEnum::default().to_string()),
+			answer_key: value.answer_key.map(QuestionsAnswerKeyDto::from),
+			tags: value.tags.map(QuestionsTagsDto::from),
 			created_at: value.created_at,
 			updated_at: value.updated_at,
 		}
 	}
+
+	pub fn without_answer_key(self) -> Self {
+		Self {
+			discussion: String::new(),
+			discussion_image_url: None,
+			options: self
+				.options
+				.into_iter()
+				.map(OptionsItemDto::without_answer_key)
+				.collect(),
+			answer_key: None,
+			..self
+		}
+	}
+}
+
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct QuestionsAnalysisQueryDto {
+	pub session_id: Option<String>,
+	pub test_id: Option<String>,
+	pub min_responses: Option<u32>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, ToSchema)]
+pub struct QuestionsOptionAnalysisDto {
+	pub option_id: String,
+	pub label: String,
+	pub is_correct: bool,
+	pub selection_count: u32,
+	pub selection_rate: f64,
+	pub top_group_rate: f64,
+	pub bottom_group_rate: f64,
+	pub discrimination: f64,
+	pub flags: Vec<String>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, ToSchema)]
+pub struct QuestionsAnalysisItemDto {
+	pub question_id: String,
+	pub question: String,
+	pub respondents: u32,
+	pub correct_count: u32,
+	pub difficulty_index: f64,
+	pub discrimination_index: Option<f64>,
+	pub is_flagged: bool,
+	pub flags: Vec<String>,
+	pub options: Vec<QuestionsOptionAnalysisDto>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, ToSchema)]
+pub struct QuestionsAnalysisDto {
+	pub session_id: Option<String>,
+	pub test_id: Option<String>,
+	pub respondents: u32,
+	pub flagged_count: u32,
+	pub items: Vec<QuestionsAnalysisItemDto>,
 }
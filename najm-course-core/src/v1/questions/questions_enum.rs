@@ -0,0 +1,109 @@
+use std::fmt;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum QuestionsAnalysisFlagEnum {
+	InsufficientResponses,
+	TooDifficult,
+	TooEasy,
+	LowDiscrimination,
+	NegativeDiscrimination,
+	PoorDistractors,
+	NonFunctioningDistractor,
+	AttractsTopScorers,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum QuestionsTypeEnum {
+	#[default]
+	SingleChoice,
+	MultipleCorrect,
+	TrueFalse,
+	Numeric,
+	Essay,
+}
+
+impl QuestionsTypeEnum {
+	pub fn resolve(value: Option<&str>) -> anyhow::Result<Self> {
+		match value {
+			Some(value) if !value.is_empty() => value.parse(),
+			_ => Ok(Self::default()),
+		}
+	}
+}
+
+impl fmt::Display for QuestionsTypeEnum {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		let type_str = match self {
+			QuestionsTypeEnum::SingleChoice => "single_choice",
+			QuestionsTypeEnum::MultipleCorrect => "multiple_correct",
+			QuestionsTypeEnum::TrueFalse => "true_false",
+			QuestionsTypeEnum::Numeric => "numeric",
+			QuestionsTypeEnum::Essay => "essay",
+		};
+		write!(f, "{}", type_str)
+	}
+}
+
+impl std::str::FromStr for QuestionsTypeEnum {
+	type Err = anyhow::Error;
+
+	fn from_str(value: &str) -> Result<Self, Self::Err> {
+		match value {
+			"single_choice" => Ok(QuestionsTypeEnum::SingleChoice),
+			"multiple_correct" => Ok(QuestionsTypeEnum::MultipleCorrect),
+			"true_false" => Ok(QuestionsTypeEnum::TrueFalse),
+			"numeric" => Ok(QuestionsTypeEnum::Numeric),
+			"essay" => Ok(QuestionsTypeEnum::Essay),
+			_ => anyhow::bail!("Invalid question type"),
+		}
+	}
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum QuestionsDifficultyEnum {
+	Easy,
+	Medium,
+	Hard,
+}
+
+impl fmt::Display for QuestionsDifficultyEnum {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		let difficulty_str = match self {
+			QuestionsDifficultyEnum::Easy => "easy",
+			QuestionsDifficultyEnum::Medium => "medium",
+			QuestionsDifficultyEnum::Hard => "hard",
+		};
+		write!(f, "{}", difficulty_str)
+	}
+}
+
+impl std::str::FromStr for QuestionsDifficultyEnum {
+	type Err = anyhow::Error;
+
+	fn from_str(value: &str) -> Result<Self, Self::Err> {
+		match value {
+			"easy" => Ok(QuestionsDifficultyEnum::Easy),
+			"medium" => Ok(QuestionsDifficultyEnum::Medium),
+			"hard" => Ok(QuestionsDifficultyEnum::Hard),
+			_ => anyhow::bail!("Invalid difficulty"),
+		}
+	}
+}
+
+impl fmt::Display for QuestionsAnalysisFlagEnum {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		let flag_str = match self {
+			QuestionsAnalysisFlagEnum::InsufficientResponses => "insufficient_responses",
+			QuestionsAnalysisFlagEnum::TooDifficult => "too_difficult",
+			QuestionsAnalysisFlagEnum::TooEasy => "too_easy",
+			QuestionsAnalysisFlagEnum::LowDiscrimination => "low_discrimination",
+			QuestionsAnalysisFlagEnum::NegativeDiscrimination => "negative_discrimination",
+			QuestionsAnalysisFlagEnum::PoorDistractors => "poor_distractors",
+			QuestionsAnalysisFlagEnum::NonFunctioningDistractor => {
+				"non_functioning_distractor"
+			}
+			QuestionsAnalysisFlagEnum::AttractsTopScorers => "attracts_top_scorers",
+		};
+		write!(f, "{}", flag_str)
+	}
+}
@@ -1,13 +1,17 @@
 use super::{
-	QuestionsCreateRequestDto, QuestionsDetailSchema, QuestionsItemDto,
-	QuestionsResponseListDto, QuestionsSchema, QuestionsUpdateRequestDto,
+	analyze_item, validate_question_shape, ItemOption, ItemResponse,
+	QuestionsAnalysisDto, QuestionsAnalysisItemDto, QuestionsAnalysisQueryDto,
+	QuestionsAnalysisResponseSchema, QuestionsCreateRequestDto, QuestionsDetailSchema,
+	QuestionsItemDto, QuestionsResponseListDto, QuestionsSchema, QuestionsTagsDto,
+	QuestionsUpdateRequestDto, ANALYSIS_DEFAULT_MIN_RESPONSES,
 };
 use crate::{
-	get_id, make_thing, query_list_with_meta, AppState, MetaRequestDto, OptionsSchema,
-	ResourceEnum, ResponseListSuccessDto,
+	get_id, make_thing, query_list_with_bindings, AppState, MetaRequestDto,
+	OptionsSchema, ResourceEnum, ResponseListSuccessDto,
 };
 use anyhow::{bail, Result};
 use najm_course_utils::get_iso_date;
+use std::collections::{HashMap, HashSet};
 use surrealdb::Uuid;
 use validator::Validate;
 
@@ -33,22 +37,28 @@ impl<'a> QuestionsRepository<'a> {
 	pub async fn query_question_list(
 		&self,
 		meta: MetaRequestDto,
+		tags: QuestionsTagsDto,
 	) -> Result<ResponseListSuccessDto<Vec<QuestionsResponseListDto>>> {
 		let mut conditions = vec!["is_deleted = false".into()];
 		if meta.search.is_some() {
-			conditions
-				.push("string::contains(string::lowercase(question ?? ''), $search)".into());
+			conditions.push(
+				"(string::contains(string::lowercase(question ?? ''), $search) OR string::contains(string::lowercase(tags.topic ?? ''), $search) OR string::contains(string::lowercase(tags.sub_topic ?? ''), $search))"
+					.into(),
+			);
 		}
 		if meta.filter_by.is_some() && meta.filter.is_some() {
 			let filter_by = meta.filter_by.as_ref().unwrap();
 			conditions.push(format!("{} = $filter", filter_by));
 		}
-		let raw = query_list_with_meta::<QuestionsSchema>(
+		let (tag_conditions, bindings) = tags.conditions();
+		conditions.extend(tag_conditions);
+		let raw = query_list_with_bindings::<QuestionsSchema>(
 			&self.state.surrealdb_ws,
 			&ResourceEnum::Questions.to_string(),
 			&meta,
 			conditions,
 			None,
+			bindings,
 		)
 		.await?;
 		let transformed = raw
@@ -84,8 +94,18 @@ impl<'a> QuestionsRepository<'a> {
 		payload: QuestionsCreateRequestDto,
 	) -> Result<String> {
 		payload.validate()?;
-		if payload.options.is_empty() {
-			bail!("Options must not be empty");
+		let correct_options: Vec<bool> = payload
+			.options
+			.iter()
+			.map(|option| option.is_correct)
+			.collect();
+		let question_type = validate_question_shape(
+			payload.question_type.as_deref(),
+			&correct_options,
+			payload.answer_key.as_ref(),
+		)?;
+		if let Some(tags) = &payload.tags {
+			tags.validate_difficulty()?;
 		}
 
 		let db = &self.state.surrealdb_ws;
@@ -117,6 +137,10 @@ impl<'a> QuestionsRepository<'a> {
 			question_image_url: payload.question_image_url,
 			discussion_image_url: payload.discussion_image_url,
 			options: option_things,
+			question_type: Some(question_type.to_string()),
+			answer_key: payload.answer_key.map(Into::into),
+			tags: payload.tags.map(Into::into),
+			irt: None,
 			is_deleted: false,
 			created_at: get_iso_date(),
 			updated_at: get_iso_date(),
@@ -134,8 +158,18 @@ impl<'a> QuestionsRepository<'a> {
 		data: QuestionsUpdateRequestDto,
 	) -> Result<String> {
 		data.validate()?;
-		if data.options.is_empty() {
-			bail!("Options must not be empty");
+		let correct_options: Vec<bool> = data
+			.options
+			.iter()
+			.map(|option| option.is_correct)
+			.collect();
+		let question_type = validate_question_shape(
+			data.question_type.as_deref(),
+			&correct_options,
+			data.answer_key.as_ref(),
+		)?;
+		if let Some(tags) = &data.tags {
+			tags.validate_difficulty()?;
 		}
 
 		let db = &self.state.surrealdb_ws;
@@ -171,6 +205,10 @@ impl<'a> QuestionsRepository<'a> {
 			question_image_url: data.question_image_url,
 			discussion_image_url: data.discussion_image_url,
 			options: option_things,
+			question_type: Some(question_type.to_string()),
+			answer_key: data.answer_key.map(Into::into),
+			tags: data.tags.map(Into::into),
+			irt: existing.irt,
 			is_deleted: existing.is_deleted,
 			created_at: existing.created_at,
 			updated_at: get_iso_date(),
@@ -202,4 +240,187 @@ impl<'a> QuestionsRepository<'a> {
 			None => bail!("Failed to delete question"),
 		}
 	}
+
+	pub async fn query_analysis(
+		&self,
+		query: QuestionsAnalysisQueryDto,
+	) -> Result<QuestionsAnalysisDto> {
+		if query.session_id.is_none() && query.test_id.is_none() {
+			bail!("Session ID or test ID is required");
+		}
+		self.query_item_analysis(None, query).await
+	}
+
+	pub async fn query_question_analysis(
+		&self,
+		id: &str,
+		query: QuestionsAnalysisQueryDto,
+	) -> Result<QuestionsAnalysisItemDto> {
+		self.query_raw_question_by_id(id).await?;
+		let analysis = self
+			.query_item_analysis(Some(&[id.to_string()]), query)
+			.await?;
+		match analysis.items.into_iter().next() {
+			Some(item) => Ok(item),
+			None => bail!("Question not found"),
+		}
+	}
+
+	async fn query_item_analysis(
+		&self,
+		question_ids: Option<&[String]>,
+		query: QuestionsAnalysisQueryDto,
+	) -> Result<QuestionsAnalysisDto> {
+		let db = &self.state.surrealdb_ws;
+		let min_responses = query
+			.min_responses
+			.unwrap_or(ANALYSIS_DEFAULT_MIN_RESPONSES);
+		let mut scope = vec!["is_deleted = false".to_string()];
+		if let Some(session_id) = &query.session_id {
+			scope.push(format!(
+				"session = {}:⟨{}⟩",
+				ResourceEnum::Sessions,
+				session_id
+			));
+		}
+		if let Some(test_id) = &query.test_id {
+			scope.push(format!("test = {}:⟨{}⟩", ResourceEnum::Tests, test_id));
+		}
+		let mut conditions = scope.clone();
+		if let Some(ids) = question_ids {
+			let things = ids
+				.iter()
+				.map(|id| format!("{}:⟨{}⟩", ResourceEnum::Questions, id))
+				.collect::<Vec<_>>()
+				.join(", ");
+			conditions.push(format!("question INSIDE [{}]", things));
+		}
+		let responses: Vec<QuestionsAnalysisResponseSchema> = db
+			.query(format!(
+				"SELECT user, session, question, option, is_correct, updated_at FROM {} WHERE {} ORDER BY updated_at ASC",
+				ResourceEnum::Answers,
+				conditions.join(" AND ")
+			))
+			.await?
+			.take(0)?;
+		let sitting = |r: &QuestionsAnalysisResponseSchema| {
+			(r.user.id.to_raw(), r.session.id.to_raw())
+		};
+		let sittings: HashSet<(String, String)> =
+			responses.iter().map(sitting).collect();
+		let mut totals: HashMap<(String, String), HashMap<String, bool>> =
+			HashMap::new();
+		if !sittings.is_empty() {
+			let users: HashSet<String> = responses
+				.iter()
+				.map(|r| format!("{}:⟨{}⟩", ResourceEnum::Users, r.user.id.to_raw()))
+				.collect();
+			let sessions: HashSet<String> = responses
+				.iter()
+				.map(|r| format!("{}:⟨{}⟩", ResourceEnum::Sessions, r.session.id.to_raw()))
+				.collect();
+			scope.push(format!(
+				"user INSIDE [{}]",
+				users.into_iter().collect::<Vec<_>>().join(", ")
+			));
+			scope.push(format!(
+				"session INSIDE [{}]",
+				sessions.into_iter().collect::<Vec<_>>().join(", ")
+			));
+			let answers: Vec<QuestionsAnalysisResponseSchema> = db
+				.query(format!(
+					"SELECT user, session, question, option, is_correct, updated_at FROM {} WHERE {} ORDER BY updated_at ASC",
+					ResourceEnum::Answers,
+					scope.join(" AND ")
+				))
+				.await?
+				.take(0)?;
+			for answer in answers {
+				let key = sitting(&answer);
+				if sittings.contains(&key) {
+					totals
+						.entry(key)
+						.or_default()
+						.insert(answer.question.id.to_raw(), answer.is_correct);
+				}
+			}
+		}
+		let mut by_question: HashMap<String, HashMap<(String, String), ItemResponse>> =
+			HashMap::new();
+		for response in &responses {
+			let key = sitting(response);
+			let total_score = totals
+				.get(&key)
+				.map(|answers| answers.values().filter(|c| **c).count())
+				.unwrap_or(0) as f64;
+			by_question
+				.entry(response.question.id.to_raw())
+				.or_default()
+				.insert(
+					key,
+					ItemResponse {
+						option_id: response
+							.option
+							.as_ref()
+							.map(|option| option.id.to_raw())
+							.unwrap_or_default(),
+						is_correct: response.is_correct,
+						total_score,
+					},
+				);
+		}
+		let ids: Vec<String> = match question_ids {
+			Some(ids) => ids.to_vec(),
+			None => by_question.keys().cloned().collect(),
+		};
+		let questions: Vec<QuestionsDetailSchema> = if ids.is_empty() {
+			vec![]
+		} else {
+			db.query(format!(
+				"SELECT * FROM {} WHERE id INSIDE [{}] ORDER BY created_at ASC FETCH options",
+				ResourceEnum::Questions,
+				ids
+					.iter()
+					.map(|id| format!("{}:⟨{}⟩", ResourceEnum::Questions, id))
+					.collect::<Vec<_>>()
+					.join(", ")
+			))
+			.await?
+			.take(0)?
+		};
+		let items: Vec<QuestionsAnalysisItemDto> = questions
+			.into_iter()
+			.map(|question| {
+				let question_id = question.id.id.to_raw();
+				let options: Vec<ItemOption> = question
+					.options
+					.iter()
+					.flatten()
+					.map(|option| ItemOption {
+						id: option.id.id.to_raw(),
+						label: option.label.clone().unwrap_or_default(),
+						is_correct: option.is_correct,
+					})
+					.collect();
+				let item_responses: Vec<ItemResponse> = by_question
+					.remove(&question_id)
+					.map(|responses| responses.into_values().collect())
+					.unwrap_or_default();
+				analyze_item(
+					&question_id,
+					&question.question.unwrap_or_default(),
+					&options,
+					&item_responses,
+					min_responses,
+				)
+			})
+			.collect();
+		Ok(QuestionsAnalysisDto {
+			session_id: query.session_id,
+			test_id: query.test_id,
+			respondents: sittings.len() as u32,
+			flagged_count: items.iter().filter(|item| item.is_flagged).count() as u32,
+			items,
+		})
+	}
 }
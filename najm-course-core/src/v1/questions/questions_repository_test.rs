@@ -3,7 +3,8 @@ use crate::{
 	v1::{
 		options::{OptionsCreateRequestDto, OptionsUpdateRequestDto},
 		questions::{
-			QuestionsCreateRequestDto, QuestionsRepository, QuestionsUpdateRequestDto,
+			QuestionsAnalysisQueryDto, QuestionsAnswerKeyDto, QuestionsCreateRequestDto,
+			QuestionsRepository, QuestionsTagsDto, QuestionsUpdateRequestDto,
 		},
 	},
 };
@@ -24,6 +25,9 @@ fn generate_question_payload() -> QuestionsCreateRequestDto {
 		discussion: Some("This is a discussion".into()),
 		question_image_url: None,
 		discussion_image_url: None,
+		question_type: None,
+		answer_key: None,
+		tags: None,
 		options: vec![
 			generate_option("Option A", false),
 			generate_option("Option B", true),
@@ -44,7 +48,9 @@ async fn test_create_question_should_succeed() {
 async fn test_get_question_list_should_return_data() {
 	let state = create_mock_app_state().await;
 	let repo = QuestionsRepository::new(&state);
-	let res = repo.query_question_list(Default::default()).await;
+	let res = repo
+		.query_question_list(Default::default(), Default::default())
+		.await;
 	assert!(res.is_ok());
 }
 
@@ -54,7 +60,10 @@ async fn test_get_question_by_id_should_return_data() {
 	let repo = QuestionsRepository::new(&state);
 	let payload = generate_question_payload();
 	let _ = repo.query_create_question(payload.clone()).await.unwrap();
-	let all = repo.query_question_list(Default::default()).await.unwrap();
+	let all = repo
+		.query_question_list(Default::default(), Default::default())
+		.await
+		.unwrap();
 	let latest = all.data.last().expect("Expected at least one question");
 	let res = repo.query_question_by_id(&latest.id.clone()).await;
 	assert!(res.is_ok());
@@ -84,6 +93,9 @@ async fn test_update_question_should_succeed() {
 		discussion: Some("Updated discussion".into()),
 		question_image_url: None,
 		discussion_image_url: None,
+		question_type: None,
+		answer_key: None,
+		tags: None,
 		options: vec![
 			OptionsUpdateRequestDto {
 				id: question.options[0].id.clone(),
@@ -129,9 +141,233 @@ async fn test_delete_question_should_fail_if_already_deleted() {
 	let repo = QuestionsRepository::new(&state);
 	let payload = generate_question_payload();
 	let _ = repo.query_create_question(payload.clone()).await.unwrap();
-	let all = repo.query_question_list(Default::default()).await.unwrap();
+	let all = repo
+		.query_question_list(Default::default(), Default::default())
+		.await
+		.unwrap();
 	let latest = all.data.last().expect("Expected at least one question");
 	let _ = repo.query_delete_question(latest.id.clone()).await.unwrap();
 	let res = repo.query_delete_question(latest.id.clone()).await;
 	assert!(res.is_err());
 }
+
+async fn seed_analysis_answers(
+	state: &crate::AppState,
+	session_id: &str,
+) -> (String, String) {
+	let repo = QuestionsRepository::new(state);
+	let mut question_ids = vec![];
+	for _ in 0..2 {
+		let mut payload = generate_question_payload();
+		payload.options.push(generate_option("Option C", false));
+		question_ids.push(repo.query_create_question(payload).await.unwrap());
+	}
+	let now = najm_course_utils::get_iso_date();
+	let mut statements = vec![];
+	for (user, correct) in [
+		("strong_a", true),
+		("strong_b", true),
+		("weak_a", false),
+		("weak_b", false),
+	] {
+		let user_id = format!("{}_{}", user, Uuid::new_v4());
+		for question_id in &question_ids {
+			let question = repo.query_question_by_id(question_id).await.unwrap();
+			let option = question
+				.options
+				.iter()
+				.find(|o| o.is_correct == Some(correct) && o.label != "Option C")
+				.unwrap();
+			statements.push(format!(
+				"CREATE app_answers SET user = app_users:⟨{user_id}⟩, test = app_tests:⟨analysis⟩, session = app_sessions:⟨{session_id}⟩, question = app_questions:⟨{question_id}⟩, option = app_options:⟨{}⟩, is_correct = {correct}, is_deleted = false, created_at = '{now}', updated_at = '{now}';",
+				option.id
+			));
+		}
+	}
+	state
+		.surrealdb_ws
+		.query(statements.join("\n"))
+		.await
+		.unwrap();
+	(question_ids[0].clone(), question_ids[1].clone())
+}
+
+#[tokio::test]
+async fn test_query_question_analysis_should_compute_statistics() {
+	let state = create_mock_app_state().await;
+	let repo = QuestionsRepository::new(&state);
+	let session_id = Uuid::new_v4().to_string();
+	let (question_id, _) = seed_analysis_answers(&state, &session_id).await;
+	let item = repo
+		.query_question_analysis(
+			&question_id,
+			QuestionsAnalysisQueryDto {
+				session_id: Some(session_id),
+				test_id: None,
+				min_responses: Some(1),
+			},
+		)
+		.await
+		.unwrap();
+	assert_eq!(item.respondents, 4);
+	assert_eq!(item.correct_count, 2);
+	assert_eq!(item.difficulty_index, 0.5);
+	assert_eq!(item.discrimination_index, Some(1.0));
+	let unused = item.options.iter().find(|o| o.label == "Option C").unwrap();
+	assert_eq!(unused.selection_count, 0);
+	assert!(unused
+		.flags
+		.contains(&"non_functioning_distractor".to_string()));
+	assert!(item.flags.contains(&"poor_distractors".to_string()));
+}
+
+#[tokio::test]
+async fn test_query_analysis_should_be_scoped_to_session() {
+	let state = create_mock_app_state().await;
+	let repo = QuestionsRepository::new(&state);
+	let session_id = Uuid::new_v4().to_string();
+	seed_analysis_answers(&state, &session_id).await;
+	seed_analysis_answers(&state, &Uuid::new_v4().to_string()).await;
+	let analysis = repo
+		.query_analysis(QuestionsAnalysisQueryDto {
+			session_id: Some(session_id),
+			test_id: None,
+			min_responses: None,
+		})
+		.await
+		.unwrap();
+	assert_eq!(analysis.respondents, 4);
+	assert_eq!(analysis.items.len(), 2);
+	assert!(analysis
+		.items
+		.iter()
+		.all(|item| item.flags == vec!["insufficient_responses".to_string()]));
+}
+
+#[tokio::test]
+async fn test_query_analysis_should_fail_without_scope() {
+	let state = create_mock_app_state().await;
+	let repo = QuestionsRepository::new(&state);
+	let res = repo.query_analysis(Default::default()).await;
+	assert!(res.is_err());
+}
+
+#[tokio::test]
+async fn test_query_question_analysis_without_answers_should_be_empty() {
+	let state = create_mock_app_state().await;
+	let repo = QuestionsRepository::new(&state);
+	let id = repo
+		.query_create_question(generate_question_payload())
+		.await
+		.unwrap();
+	let item = repo
+		.query_question_analysis(&id, Default::default())
+		.await
+		.unwrap();
+	assert_eq!(item.respondents, 0);
+	assert_eq!(item.options.len(), 2);
+}
+
+#[tokio::test]
+async fn test_query_question_list_should_filter_by_tags() {
+	let state = create_mock_app_state().await;
+	let repo = QuestionsRepository::new(&state);
+	let subject = format!("Subject {}", Uuid::new_v4());
+	for difficulty in ["easy", "hard", "hard"] {
+		let mut payload = generate_question_payload();
+		payload.tags = Some(QuestionsTagsDto {
+			subject: Some(subject.clone()),
+			topic: Some("Aljabar".into()),
+			sub_topic: None,
+			difficulty: Some(difficulty.into()),
+			source: Some("UTBK 2024".into()),
+		});
+		repo.query_create_question(payload).await.unwrap();
+	}
+	let res = repo
+		.query_question_list(
+			Default::default(),
+			QuestionsTagsDto {
+				subject: Some(subject.clone()),
+				difficulty: Some("hard".into()),
+				..Default::default()
+			},
+		)
+		.await
+		.unwrap();
+	assert_eq!(res.data.len(), 2);
+	assert!(res.data.iter().all(|q| {
+		q.tags.as_ref().and_then(|t| t.difficulty.as_deref()) == Some("hard")
+	}));
+	assert_eq!(res.meta.and_then(|m| m.total), Some(2));
+	let injected = repo
+		.query_question_list(
+			Default::default(),
+			QuestionsTagsDto {
+				subject: Some(format!("{}\" OR true OR \"", subject)),
+				..Default::default()
+			},
+		)
+		.await
+		.unwrap();
+	assert!(injected.data.is_empty());
+}
+
+#[tokio::test]
+async fn test_create_question_should_fail_if_invalid_difficulty() {
+	let state = create_mock_app_state().await;
+	let repo = QuestionsRepository::new(&state);
+	let mut payload = generate_question_payload();
+	payload.tags = Some(QuestionsTagsDto {
+		difficulty: Some("impossible".into()),
+		..Default::default()
+	});
+	let res = repo.query_create_question(payload).await;
+	assert_eq!(res.unwrap_err().to_string(), "Invalid difficulty");
+}
+
+#[tokio::test]
+async fn test_create_question_should_store_type_and_answer_key() {
+	let state = create_mock_app_state().await;
+	let repo = QuestionsRepository::new(&state);
+	let mut payload = generate_question_payload();
+	payload.question_type = Some("numeric".into());
+	payload.answer_key = Some(QuestionsAnswerKeyDto {
+		numeric_answer: Some(9.81),
+		numeric_tolerance: Some(0.01),
+		points: Some(5),
+		scoring_rule: None,
+	});
+	payload.options = vec![];
+	let id = repo.query_create_question(payload).await.unwrap();
+	let question = repo.query_question_by_id(&id).await.unwrap();
+	assert_eq!(question.question_type, "numeric");
+	let key = question.answer_key.expect("Expected answer key");
+	assert_eq!(key.numeric_answer, Some(9.81));
+	assert_eq!(key.points, Some(5));
+}
+
+#[tokio::test]
+async fn test_create_question_should_validate_shape_per_type() {
+	let state = create_mock_app_state().await;
+	let repo = QuestionsRepository::new(&state);
+	let mut numeric = generate_question_payload();
+	numeric.question_type = Some("numeric".into());
+	let res = repo.query_create_question(numeric).await;
+	assert_eq!(
+		res.unwrap_err().to_string(),
+		"Numeric questions must have a numeric answer"
+	);
+	let mut multiple = generate_question_payload();
+	multiple.question_type = Some("multiple_correct".into());
+	multiple.options = vec![generate_option("Option A", true)];
+	assert!(repo.query_create_question(multiple).await.is_err());
+	let mut essay = generate_question_payload();
+	essay.question_type = Some("essay".into());
+	essay.options = vec![];
+	assert!(repo.query_create_question(essay).await.is_ok());
+	let mut unknown = generate_question_payload();
+	unknown.question_type = Some("matching".into());
+	let res = repo.query_create_question(unknown).await;
+	assert_eq!(res.unwrap_err().to_string(), "Invalid question type");
+}
@@ -1,4 +1,4 @@
-use crate::OptionsSchema;
+use crate::{IrtParamsSchema, OptionsSchema, ScoringRuleSchema};
 use serde::{Deserialize, Serialize};
 use surrealdb::sql::Thing;
 
@@ -10,11 +10,32 @@ pub struct QuestionsSchema {
 	pub question_image_url: Option<String>,
 	pub discussion_image_url: Option<String>,
 	pub options: Vec<Thing>,
+	pub question_type: Option<String>,
+	pub answer_key: Option<QuestionsAnswerKeySchema>,
+	pub tags: Option<QuestionsTagsSchema>,
+	pub irt: Option<IrtParamsSchema>,
 	pub is_deleted: bool,
 	pub created_at: String,
 	pub updated_at: String,
 }
 
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct QuestionsTagsSchema {
+	pub subject: Option<String>,
+	pub topic: Option<String>,
+	pub sub_topic: Option<String>,
+	pub difficulty: Option<String>,
+	pub source: Option<String>,
+}
+
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct QuestionsAnswerKeySchema {
+	pub numeric_answer: Option<f64>,
+	pub numeric_tolerance: Option<f64>,
+	pub points: Option<i32>,
+	pub scoring_rule: Option<ScoringRuleSchema>,
+}
+
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct QuestionsDetailSchema {
 	pub id: Thing,
@@ -23,7 +44,19 @@ pub struct QuestionsDetailSchema {
 	pub question_image_url: Option<String>,
 	pub discussion_image_url: Option<String>,
 	pub options: Vec<Option<OptionsSchema>>,
+	pub question_type: Option<String>,
+	pub answer_key: Option<QuestionsAnswerKeySchema>,
+	pub tags: Option<QuestionsTagsSchema>,
 	pub is_deleted: bool,
 	pub created_at: String,
 	pub updated_at: String,
 }
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct QuestionsAnalysisResponseSchema {
+	pub user: Thing,
+	pub session: Thing,
+	pub question: Thing,
+	pub option: Option<Thing>,
+	pub is_correct: bool,
+}
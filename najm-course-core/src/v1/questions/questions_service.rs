@@ -1,9 +1,11 @@
 use super::{
-	QuestionsCreateRequestDto, QuestionsRepository, QuestionsUpdateRequestDto,
+	QuestionsAnalysisQueryDto, QuestionsCreateRequestDto, QuestionsRepository,
+	QuestionsTagsDto, QuestionsUpdateRequestDto,
 };
 use crate::{
 	common_response, success_list_response, success_response, validate_request,
 	AppState, MetaRequestDto, ResponseListSuccessDto, ResponseSuccessDto,
+	SessionsViewerDto,
 };
 use axum::{http::StatusCode, response::Response};
 
@@ -13,12 +15,23 @@ impl QuestionsService {
 	pub async fn get_question_list(
 		state: &AppState,
 		meta: MetaRequestDto,
+		tags: QuestionsTagsDto,
+		viewer: SessionsViewerDto,
 	) -> Response {
 		let repo = QuestionsRepository::new(state);
-		match repo.query_question_list(meta).await {
+		match repo.query_question_list(meta, tags).await {
 			Ok(data) => {
+				let data_list = if viewer.is_reviewer {
+					data.data
+				} else {
+					data
+						.data
+						.into_iter()
+						.map(|q| q.without_answer_key())
+						.collect()
+				};
 				let response = ResponseListSuccessDto {
-					data: data.data,
+					data: data_list,
 					meta: data.meta,
 				};
 				success_list_response(response)
@@ -27,14 +40,58 @@ impl QuestionsService {
 		}
 	}
 
-	pub async fn get_question_by_id(state: &AppState, id: String) -> Response {
+	pub async fn get_question_by_id(
+		state: &AppState,
+		id: String,
+		viewer: SessionsViewerDto,
+	) -> Response {
 		let repo = QuestionsRepository::new(state);
 		match repo.query_question_by_id(&id.clone()).await {
+			Ok(question) if !viewer.is_reviewer => success_response(ResponseSuccessDto {
+				data: question.without_answer_key(),
+			}),
 			Ok(question) => success_response(ResponseSuccessDto { data: question }),
 			Err(e) => common_response(StatusCode::NOT_FOUND, &e.to_string()),
 		}
 	}
 
+	pub async fn get_question_analysis_list(
+		state: &AppState,
+		query: QuestionsAnalysisQueryDto,
+	) -> Response {
+		let repo = QuestionsRepository::new(state);
+		match repo.query_analysis(query).await {
+			Ok(data) => success_response(ResponseSuccessDto { data }),
+			Err(e) => {
+				let msg = e.to_string();
+				if msg.contains("is required") {
+					common_response(StatusCode::BAD_REQUEST, &msg)
+				} else {
+					common_response(StatusCode::INTERNAL_SERVER_ERROR, &msg)
+				}
+			}
+		}
+	}
+
+	pub async fn get_question_analysis_by_id(
+		state: &AppState,
+		id: String,
+		query: QuestionsAnalysisQueryDto,
+	) -> Response {
+		let repo = QuestionsRepository::new(state);
+		match repo.query_question_analysis(&id, query).await {
+			Ok(data) => success_response(ResponseSuccessDto { data }),
+			Err(e) => {
+				let msg = e.to_string();
+				if msg == "Question not found" {
+					common_response(StatusCode::NOT_FOUND, &msg)
+				} else {
+					common_response(StatusCode::INTERNAL_SERVER_ERROR, &msg)
+				}
+			}
+		}
+	}
+
 	pub async fn create_question(
 		state: &AppState,
 		payload: QuestionsCreateRequestDto,
@@ -47,7 +104,10 @@ impl QuestionsService {
 			Ok(msg) => common_response(StatusCode::CREATED, &msg),
 			Err(e) => {
 				let msg = e.to_string();
-				if msg.contains("must not be empty") || msg.contains("non-empty label") {
+				if msg.contains("must")
+					|| msg.contains("non-empty label")
+					|| msg.starts_with("Invalid")
+				{
 					common_response(StatusCode::BAD_REQUEST, &msg)
 				} else {
 					common_response(StatusCode::INTERNAL_SERVER_ERROR, &msg)
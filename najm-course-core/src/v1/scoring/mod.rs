@@ -0,0 +1,14 @@
+pub mod scoring_dto;
+pub mod scoring_enum;
+pub mod scoring_registry;
+pub mod scoring_schema;
+pub mod scoring_strategy;
+
+#[cfg(test)]
+pub mod scoring_strategy_test;
+
+pub use scoring_dto::*;
+pub use scoring_enum::*;
+pub use scoring_registry::*;
+pub use scoring_schema::*;
+pub use scoring_strategy::*;
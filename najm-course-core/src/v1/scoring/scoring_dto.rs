@@ -0,0 +1,92 @@
+use super::{ScoringConfigSchema, ScoringRuleSchema};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use utoipa::ToSchema;
+
+#[derive(Clone, Debug, Serialize, Deserialize, ToSchema)]
+pub struct ScoringConfigDto {
+	#[schema(example = "points_scaled")]
+	pub strategy: String,
+
+	#[serde(default)]
+	#[schema(value_type = Object, example = json!({ "max_points": 500, "scale": 100 }))]
+	pub params: Value,
+}
+
+impl From<ScoringConfigSchema> for ScoringConfigDto {
+	fn from(value: ScoringConfigSchema) -> Self {
+		Self {
+			strategy: value.strategy,
+			params: value.params,
+		}
+	}
+}
+
+impl From<ScoringConfigDto> for ScoringConfigSchema {
+	fn from(value: ScoringConfigDto) -> Self {
+		Self {
+			strategy: value.strategy,
+			params: value.params,
+		}
+	}
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, ToSchema)]
+pub struct ScoringRuleDto {
+	#[schema(example = 4.0)]
+	pub correct: f64,
+
+	#[schema(example = -1.0)]
+	pub wrong: f64,
+
+	#[schema(example = 0.0)]
+	pub blank: f64,
+
+	#[serde(default)]
+	#[schema(example = true)]
+	pub partial_credit: bool,
+}
+
+impl From<ScoringRuleSchema> for ScoringRuleDto {
+	fn from(value: ScoringRuleSchema) -> Self {
+		Self {
+			correct: value.correct,
+			wrong: value.wrong,
+			blank: value.blank,
+			partial_credit: value.partial_credit,
+		}
+	}
+}
+
+impl From<ScoringRuleDto> for ScoringRuleSchema {
+	fn from(value: ScoringRuleDto) -> Self {
+		Self {
+			correct: value.correct,
+			wrong: value.wrong,
+			blank: value.blank,
+			partial_credit: value.partial_credit,
+		}
+	}
+}
+
+#[derive(Clone, Debug, Default, Serialize, Deserialize, ToSchema)]
+pub struct KecermatanColumnDto {
+	pub column: u32,
+	pub answered: u32,
+	pub correct: u32,
+	pub errors: u32,
+	pub late: u32,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, ToSchema)]
+pub struct KecermatanResultDto {
+	pub correct_count: u32,
+	pub error_count: u32,
+	#[schema(example = 12.5)]
+	pub panker: f64,
+	#[schema(example = 3)]
+	pub tianker: u32,
+	#[schema(example = 0.92)]
+	pub consistency: f64,
+	pub columns: Vec<KecermatanColumnDto>,
+}
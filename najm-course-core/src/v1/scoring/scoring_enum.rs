@@ -0,0 +1,25 @@
+use std::fmt;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ScoringStrategyEnum {
+	CorrectCount,
+	PointsScaled,
+	PointsSum,
+	Kecermatan,
+	Irt,
+	None,
+}
+
+impl fmt::Display for ScoringStrategyEnum {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		let strategy_str = match self {
+			ScoringStrategyEnum::CorrectCount => "correct_count",
+			ScoringStrategyEnum::PointsScaled => "points_scaled",
+			ScoringStrategyEnum::PointsSum => "points_sum",
+			ScoringStrategyEnum::Kecermatan => "kecermatan",
+			ScoringStrategyEnum::Irt => "irt",
+			ScoringStrategyEnum::None => "none",
+		};
+		write!(f, "{}", strategy_str)
+	}
+}
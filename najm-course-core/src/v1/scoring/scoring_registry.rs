@@ -0,0 +1,65 @@
+use super::{
+	CorrectCountStrategy, IrtStrategy, KecermatanStrategy, NoneStrategy,
+	PointsScaledStrategy, PointsSumStrategy, ScoringInput, ScoringOutput,
+	ScoringStrategy,
+};
+use anyhow::{bail, Result};
+use lazy_static::lazy_static;
+use serde_json::Value;
+use std::collections::HashMap;
+
+lazy_static! {
+	pub static ref SCORING_REGISTRY: ScoringRegistry = ScoringRegistry::default();
+}
+
+pub struct ScoringRegistry {
+	strategies: HashMap<String, Box<dyn ScoringStrategy>>,
+}
+
+impl Default for ScoringRegistry {
+	fn default() -> Self {
+		let mut registry = Self::new();
+		registry.register(Box::new(CorrectCountStrategy));
+		registry.register(Box::new(PointsScaledStrategy));
+		registry.register(Box::new(PointsSumStrategy));
+		registry.register(Box::new(KecermatanStrategy));
+		registry.register(Box::new(IrtStrategy));
+		registry.register(Box::new(NoneStrategy));
+		registry
+	}
+}
+
+impl ScoringRegistry {
+	pub fn new() -> Self {
+		Self {
+			strategies: HashMap::new(),
+		}
+	}
+
+	pub fn register(&mut self, strategy: Box<dyn ScoringStrategy>) {
+		self.strategies.insert(strategy.name(), strategy);
+	}
+
+	pub fn get(&self, name: &str) -> Result<&dyn ScoringStrategy> {
+		match self.strategies.get(name) {
+			Some(strategy) => Ok(strategy.as_ref()),
+			None => bail!("Scoring strategy not found"),
+		}
+	}
+
+	pub fn score(
+		&self,
+		strategy: &str,
+		input: &ScoringInput<'_>,
+	) -> Result<ScoringOutput> {
+		self.get(strategy)?.score(input)
+	}
+
+	pub fn validate(&self, strategy: &str, params: &Value) -> Result<()> {
+		self.get(strategy)?.validate_params(params)
+	}
+
+	pub fn applies_weight(&self, strategy: &str) -> bool {
+		self.get(strategy).is_ok_and(|s| s.applies_weight())
+	}
+}
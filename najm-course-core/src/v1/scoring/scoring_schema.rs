@@ -0,0 +1,73 @@
+use super::ScoringStrategyEnum;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct ScoringConfigSchema {
+	pub strategy: String,
+	#[serde(default)]
+	pub params: Value,
+}
+
+impl ScoringConfigSchema {
+	pub fn new(strategy: ScoringStrategyEnum, params: Value) -> Self {
+		Self {
+			strategy: strategy.to_string(),
+			params,
+		}
+	}
+
+	pub fn infer(category: &str, test_name: &str) -> Self {
+		let test_name = test_name.to_lowercase();
+		match category {
+			"Akademik" => Self::new(ScoringStrategyEnum::CorrectCount, Value::Null),
+			"Psikologi" if test_name.contains("kepribadian") => Self::new(
+				ScoringStrategyEnum::PointsScaled,
+				json!({ "max_points": 500, "scale": 100 }),
+			),
+			"Psikologi" if test_name.contains("kecerdasan") => {
+				Self::new(ScoringStrategyEnum::PointsSum, Value::Null)
+			}
+			"Psikologi" if test_name.contains("kecermatan") => {
+				Self::new(ScoringStrategyEnum::Kecermatan, Value::Null)
+			}
+			_ => Self::new(ScoringStrategyEnum::None, Value::Null),
+		}
+	}
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct ScoringRuleSchema {
+	pub correct: f64,
+	pub wrong: f64,
+	pub blank: f64,
+	#[serde(default)]
+	pub partial_credit: bool,
+}
+
+impl Default for ScoringRuleSchema {
+	fn default() -> Self {
+		Self {
+			correct: 1.0,
+			wrong: 0.0,
+			blank: 0.0,
+			partial_credit: false,
+		}
+	}
+}
+
+impl ScoringRuleSchema {
+	/// Points for one question given whether it was answered and the share of
+	/// it answered correctly.
+	pub fn points(&self, is_answered: bool, credit: f64) -> f64 {
+		if !is_answered {
+			self.blank
+		} else if credit >= 1.0 {
+			self.correct
+		} else if self.partial_credit && credit > 0.0 {
+			self.correct * credit
+		} else {
+			self.wrong
+		}
+	}
+}
@@ -0,0 +1,308 @@
+use super::{
+	KecermatanColumnDto, KecermatanResultDto, ScoringRuleSchema, ScoringStrategyEnum,
+};
+use crate::{
+	answers::QuestionsItemAnswersDto, estimate_theta_eap, estimate_theta_mle,
+	scale_theta, IrtParamsSchema, ItemParams,
+};
+use anyhow::{bail, Result};
+use chrono::{DateTime, Duration, Utc};
+use serde_json::Value;
+use std::collections::HashMap;
+
+pub struct ScoringInput<'a> {
+	pub questions: &'a [QuestionsItemAnswersDto],
+	pub question_order: &'a [String],
+	pub started_at: Option<&'a str>,
+	pub item_params: &'a HashMap<String, IrtParamsSchema>,
+	pub weight: f32,
+	pub multiplier: f32,
+	pub params: &'a Value,
+	pub rule: Option<&'a ScoringRuleSchema>,
+}
+
+#[derive(Clone, Debug, Default)]
+pub struct ScoringOutput {
+	pub score: i32,
+	pub theta: Option<f64>,
+	pub kecermatan: Option<KecermatanResultDto>,
+}
+
+impl From<i32> for ScoringOutput {
+	fn from(score: i32) -> Self {
+		Self {
+			score,
+			theta: None,
+			kecermatan: None,
+		}
+	}
+}
+
+fn parse_date(value: &str) -> Option<DateTime<Utc>> {
+	DateTime::parse_from_rfc3339(value)
+		.ok()
+		.map(|date| date.with_timezone(&Utc))
+}
+
+impl ScoringInput<'_> {
+	pub fn correct_count(&self) -> usize {
+		self.questions.iter().filter(|q| q.is_correct).count()
+	}
+
+	/// Raw score under the test's scoring rule and any per-question overrides.
+	/// Returns `None` when neither is configured. Questions in the order that
+	/// were never answered count as blank under the test rule.
+	pub fn rule_score(&self) -> Option<f64> {
+		if self.rule.is_none() && self.questions.iter().all(|q| q.scoring_rule.is_none())
+		{
+			return None;
+		}
+		let default = self.rule.cloned().unwrap_or_default();
+		let answered: f64 = self
+			.questions
+			.iter()
+			.map(|q| {
+				q.scoring_rule
+					.clone()
+					.map(ScoringRuleSchema::from)
+					.unwrap_or_else(|| default.clone())
+					.points(q.is_answered(), q.credit)
+			})
+			.sum();
+		let skipped = self
+			.question_order
+			.iter()
+			.filter(|id| !self.questions.iter().any(|q| &q.id == *id))
+			.count();
+		Some(answered + skipped as f64 * default.blank)
+	}
+
+	pub fn total_points(&self) -> i32 {
+		self.questions.iter().map(|q| q.points.unwrap_or(0)).sum()
+	}
+
+	pub fn param_f64(&self, key: &str, default: f64) -> f64 {
+		self
+			.params
+			.get(key)
+			.and_then(Value::as_f64)
+			.unwrap_or(default)
+	}
+}
+
+pub trait ScoringStrategy: Send + Sync {
+	fn name(&self) -> String;
+	fn score(&self, input: &ScoringInput<'_>) -> Result<ScoringOutput>;
+
+	fn applies_weight(&self) -> bool {
+		false
+	}
+
+	/// Checks the params a session saves for this strategy.
+	fn validate_params(&self, _params: &Value) -> Result<()> {
+		Ok(())
+	}
+}
+
+pub struct CorrectCountStrategy;
+
+impl ScoringStrategy for CorrectCountStrategy {
+	fn name(&self) -> String {
+		ScoringStrategyEnum::CorrectCount.to_string()
+	}
+
+	fn score(&self, input: &ScoringInput<'_>) -> Result<ScoringOutput> {
+		let raw_score = input.rule_score().unwrap_or(input.correct_count() as f64)
+			* input.multiplier as f64;
+		Ok(((input.weight as f64 * raw_score).round() as i32).into())
+	}
+
+	fn applies_weight(&self) -> bool {
+		true
+	}
+}
+
+pub struct PointsScaledStrategy;
+
+impl ScoringStrategy for PointsScaledStrategy {
+	fn name(&self) -> String {
+		ScoringStrategyEnum::PointsScaled.to_string()
+	}
+
+	fn score(&self, input: &ScoringInput<'_>) -> Result<ScoringOutput> {
+		let max_points = input.param_f64("max_points", 500.0);
+		let scale = input.param_f64("scale", 100.0);
+		if max_points <= 0.0 {
+			bail!("Scoring param max_points must be positive");
+		}
+		Ok(((input.total_points() as f64 * scale / max_points).round() as i32).into())
+	}
+}
+
+pub struct PointsSumStrategy;
+
+impl ScoringStrategy for PointsSumStrategy {
+	fn name(&self) -> String {
+		ScoringStrategyEnum::PointsSum.to_string()
+	}
+
+	fn score(&self, input: &ScoringInput<'_>) -> Result<ScoringOutput> {
+		Ok(input.total_points().into())
+	}
+}
+
+pub struct KecermatanStrategy;
+
+impl ScoringStrategy for KecermatanStrategy {
+	fn name(&self) -> String {
+		ScoringStrategyEnum::Kecermatan.to_string()
+	}
+
+	/// Without columns every answer lands in one column and the panker,
+	/// tianker and consistency figures mean nothing.
+	fn validate_params(&self, params: &Value) -> Result<()> {
+		for key in ["column_size", "column_seconds"] {
+			if !params
+				.get(key)
+				.and_then(Value::as_f64)
+				.is_some_and(|v| v >= 1.0)
+			{
+				bail!("Scoring param {} must be positive", key);
+			}
+		}
+		Ok(())
+	}
+
+	fn score(&self, input: &ScoringInput<'_>) -> Result<ScoringOutput> {
+		let total = input.question_order.len().max(input.questions.len());
+		let column_size = match input.param_f64("column_size", 0.0) as usize {
+			0 => total.max(1),
+			size => size,
+		};
+		let column_seconds = input.param_f64("column_seconds", 0.0) as i64;
+		let column_count = total.div_ceil(column_size).max(1);
+		let started_at = input.started_at.and_then(parse_date).or_else(|| {
+			input
+				.questions
+				.iter()
+				.filter_map(|q| q.answered_at.as_deref().and_then(parse_date))
+				.min()
+		});
+		let mut columns: Vec<KecermatanColumnDto> = (0..column_count)
+			.map(|index| KecermatanColumnDto {
+				column: index as u32 + 1,
+				..Default::default()
+			})
+			.collect();
+		for (index, question) in input.questions.iter().enumerate() {
+			let position = input
+				.question_order
+				.iter()
+				.position(|id| *id == question.id)
+				.unwrap_or(index);
+			let column_index = (position / column_size).min(column_count - 1);
+			let column = &mut columns[column_index];
+			if !question.is_answered() {
+				continue;
+			}
+			let answered_at = question.answered_at.as_deref().and_then(parse_date);
+			if let (true, Some(start), Some(answered)) =
+				(column_seconds > 0, started_at, answered_at)
+			{
+				let column_end =
+					start + Duration::seconds(column_seconds * (column_index as i64 + 1));
+				if answered > column_end {
+					column.late += 1;
+					continue;
+				}
+			}
+			column.answered += 1;
+			if question.is_correct {
+				column.correct += 1;
+			} else {
+				column.errors += 1;
+			}
+		}
+		let worked: Vec<f64> = columns.iter().map(|c| c.answered as f64).collect();
+		let panker = worked.iter().sum::<f64>() / worked.len() as f64;
+		let max_worked = columns.iter().map(|c| c.answered).max().unwrap_or(0);
+		let min_worked = columns.iter().map(|c| c.answered).min().unwrap_or(0);
+		let variance =
+			worked.iter().map(|w| (w - panker).powi(2)).sum::<f64>() / worked.len() as f64;
+		let consistency = if panker > 0.0 {
+			(1.0 - variance.sqrt() / panker).clamp(0.0, 1.0)
+		} else {
+			0.0
+		};
+		let correct_count: u32 = columns.iter().map(|c| c.correct).sum();
+		let error_count: u32 = columns.iter().map(|c| c.errors).sum();
+		Ok(ScoringOutput {
+			score: correct_count as i32,
+			theta: None,
+			kecermatan: Some(KecermatanResultDto {
+				correct_count,
+				error_count,
+				panker: (panker * 100.0).round() / 100.0,
+				tianker: max_worked - min_worked,
+				consistency: (consistency * 10000.0).round() / 10000.0,
+				columns,
+			}),
+		})
+	}
+}
+
+pub struct IrtStrategy;
+
+impl ScoringStrategy for IrtStrategy {
+	fn name(&self) -> String {
+		ScoringStrategyEnum::Irt.to_string()
+	}
+
+	fn score(&self, input: &ScoringInput<'_>) -> Result<ScoringOutput> {
+		let answered: HashMap<&str, bool> = input
+			.questions
+			.iter()
+			.map(|q| (q.id.as_str(), q.is_correct))
+			.collect();
+		let order: Vec<&str> = if input.question_order.is_empty() {
+			input.questions.iter().map(|q| q.id.as_str()).collect()
+		} else {
+			input.question_order.iter().map(String::as_str).collect()
+		};
+		let responses: Vec<(ItemParams, bool)> = order
+			.into_iter()
+			.filter_map(|id| {
+				input.item_params.get(id).map(|params| {
+					(
+						ItemParams::from(params),
+						answered.get(id).copied().unwrap_or(false),
+					)
+				})
+			})
+			.collect();
+		if responses.is_empty() {
+			bail!("IRT parameters not calibrated");
+		}
+		let theta = match input.params.get("method").and_then(Value::as_str) {
+			Some("mle") => estimate_theta_mle(&responses),
+			_ => estimate_theta_eap(&responses),
+		};
+		Ok(ScoringOutput {
+			score: scale_theta(theta),
+			theta: Some((theta * 10000.0).round() / 10000.0),
+			kecermatan: None,
+		})
+	}
+}
+
+pub struct NoneStrategy;
+
+impl ScoringStrategy for NoneStrategy {
+	fn name(&self) -> String {
+		ScoringStrategyEnum::None.to_string()
+	}
+
+	fn score(&self, _input: &ScoringInput<'_>) -> Result<ScoringOutput> {
+		Ok(0.into())
+	}
+}
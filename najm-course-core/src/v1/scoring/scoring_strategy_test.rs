@@ -0,0 +1,285 @@
+use super::*;
+use crate::answers::{OptionsItemAnswersDto, QuestionsItemAnswersDto};
+use serde_json::{json, Value};
+use std::collections::HashMap;
+
+fn build_question(selected_correct: bool, points: i32) -> QuestionsItemAnswersDto {
+	QuestionsItemAnswersDto {
+		id: "question".into(),
+		question: "Question".into(),
+		discussion: "Discussion".into(),
+		question_image_url: None,
+		discussion_image_url: None,
+		options: vec![OptionsItemAnswersDto {
+			id: "option".into(),
+			label: "Option".into(),
+			display_label: None,
+			is_correct: selected_correct,
+			points: Some(points),
+			is_user_selected: true,
+			user_value: None,
+			image_url: None,
+			created_at: "2025-01-01T00:00:00Z".into(),
+			updated_at: "2025-01-01T00:00:00Z".into(),
+		}],
+		question_type: "single_choice".into(),
+		numeric_value: None,
+		text: None,
+		is_correct: selected_correct,
+		points: Some(points),
+		credit: if selected_correct { 1.0 } else { 0.0 },
+		scoring_rule: None,
+		grading: None,
+		tags: None,
+		answered_at: None,
+		created_at: "2025-01-01T00:00:00Z".into(),
+		updated_at: "2025-01-01T00:00:00Z".into(),
+	}
+}
+
+fn score(
+	strategy: &str,
+	params: &Value,
+	questions: &[QuestionsItemAnswersDto],
+) -> i32 {
+	SCORING_REGISTRY
+		.score(
+			strategy,
+			&ScoringInput {
+				questions,
+				question_order: &[],
+				started_at: None,
+				item_params: &HashMap::new(),
+				weight: 2.0,
+				multiplier: 1.5,
+				params,
+				rule: None,
+			},
+		)
+		.unwrap()
+		.score
+}
+
+#[test]
+fn test_correct_count_should_apply_weight_and_multiplier() {
+	let questions = vec![build_question(true, 0), build_question(false, 0)];
+	assert_eq!(score("correct_count", &Value::Null, &questions), 3);
+	assert!(SCORING_REGISTRY.applies_weight("correct_count"));
+	assert!(!SCORING_REGISTRY.applies_weight("points_sum"));
+}
+
+#[test]
+fn test_points_scaled_should_use_params() {
+	let questions = vec![build_question(false, 250), build_question(false, 50)];
+	let params = json!({ "max_points": 300, "scale": 10 });
+	assert_eq!(score("points_scaled", &params, &questions), 10);
+	assert_eq!(score("points_scaled", &Value::Null, &questions), 60);
+}
+
+#[test]
+fn test_points_sum_should_sum_selected_points() {
+	let questions = vec![build_question(false, 4), build_question(true, 5)];
+	assert_eq!(score("points_sum", &Value::Null, &questions), 9);
+}
+
+#[test]
+fn test_unknown_strategy_should_fail() {
+	let result = SCORING_REGISTRY.get("unknown");
+	assert!(result.is_err());
+}
+
+#[test]
+fn test_infer_should_match_legacy_rules() {
+	assert_eq!(
+		ScoringConfigSchema::infer("Akademik", "Matematika").strategy,
+		ScoringStrategyEnum::CorrectCount.to_string()
+	);
+	assert_eq!(
+		ScoringConfigSchema::infer("Psikologi", "Tes Kepribadian").strategy,
+		ScoringStrategyEnum::PointsScaled.to_string()
+	);
+	assert_eq!(
+		ScoringConfigSchema::infer("Psikologi", "Tes Kecerdasan").strategy,
+		ScoringStrategyEnum::PointsSum.to_string()
+	);
+	assert_eq!(
+		ScoringConfigSchema::infer("Psikologi", "Tes Kecermatan").strategy,
+		ScoringStrategyEnum::Kecermatan.to_string()
+	);
+	assert_eq!(
+		ScoringConfigSchema::infer("Saintek", "Fisika").strategy,
+		ScoringStrategyEnum::None.to_string()
+	);
+}
+
+fn build_timed_question(
+	id: &str,
+	is_correct: bool,
+	seconds: i64,
+) -> QuestionsItemAnswersDto {
+	let mut question = build_question(is_correct, 0);
+	question.id = id.into();
+	question.answered_at = Some(
+		(chrono::DateTime::parse_from_rfc3339("2025-01-01T08:00:00Z").unwrap()
+			+ chrono::Duration::seconds(seconds))
+		.to_rfc3339(),
+	);
+	question
+}
+
+#[test]
+fn test_kecermatan_should_score_columns_by_time_window() {
+	let questions = vec![
+		build_timed_question("q1", true, 10),
+		build_timed_question("q2", false, 20),
+		build_timed_question("q3", true, 70),
+		build_timed_question("q4", true, 130),
+	];
+	let order: Vec<String> = ["q1", "q2", "q3", "q4"]
+		.iter()
+		.map(|id| id.to_string())
+		.collect();
+	let params = json!({ "column_size": 2, "column_seconds": 60 });
+	let output = SCORING_REGISTRY
+		.score(
+			"kecermatan",
+			&ScoringInput {
+				questions: &questions,
+				question_order: &order,
+				started_at: Some("2025-01-01T08:00:00Z"),
+				item_params: &HashMap::new(),
+				weight: 1.0,
+				multiplier: 1.0,
+				params: &params,
+				rule: None,
+			},
+		)
+		.unwrap();
+	let result = output.kecermatan.unwrap();
+	assert_eq!(output.score, 2);
+	assert_eq!(result.correct_count, 2);
+	assert_eq!(result.error_count, 1);
+	assert_eq!(result.columns.len(), 2);
+	assert_eq!(result.columns[0].answered, 2);
+	assert_eq!(result.columns[1].answered, 1);
+	assert_eq!(result.columns[1].late, 1);
+	assert_eq!(result.panker, 1.5);
+	assert_eq!(result.tianker, 1);
+	assert_eq!(result.consistency, 0.6667);
+}
+
+#[test]
+fn test_kecermatan_should_require_column_params() {
+	assert_eq!(
+		SCORING_REGISTRY
+			.validate("kecermatan", &Value::Null)
+			.unwrap_err()
+			.to_string(),
+		"Scoring param column_size must be positive"
+	);
+	assert_eq!(
+		SCORING_REGISTRY
+			.validate("kecermatan", &json!({ "column_size": 10 }))
+			.unwrap_err()
+			.to_string(),
+		"Scoring param column_seconds must be positive"
+	);
+	assert!(SCORING_REGISTRY
+		.validate(
+			"kecermatan",
+			&json!({ "column_size": 10, "column_seconds": 60 })
+		)
+		.is_ok());
+	assert!(SCORING_REGISTRY
+		.validate("correct_count", &Value::Null)
+		.is_ok());
+}
+
+fn build_answered(
+	id: &str,
+	is_correct: bool,
+	credit: f64,
+) -> QuestionsItemAnswersDto {
+	let mut question = build_question(is_correct, 0);
+	question.id = id.into();
+	question.credit = credit;
+	question
+}
+
+fn score_with_rule(
+	questions: &[QuestionsItemAnswersDto],
+	order: &[&str],
+	rule: Option<&ScoringRuleSchema>,
+) -> i32 {
+	let order: Vec<String> = order.iter().map(|id| id.to_string()).collect();
+	SCORING_REGISTRY
+		.score(
+			"correct_count",
+			&ScoringInput {
+				questions,
+				question_order: &order,
+				started_at: None,
+				item_params: &HashMap::new(),
+				weight: 1.0,
+				multiplier: 1.0,
+				params: &Value::Null,
+				rule,
+			},
+		)
+		.unwrap()
+		.score
+}
+
+#[test]
+fn test_scoring_rule_should_apply_negative_marking() {
+	let rule = ScoringRuleSchema {
+		correct: 4.0,
+		wrong: -1.0,
+		blank: 0.0,
+		partial_credit: false,
+	};
+	let questions = vec![
+		build_answered("q1", true, 1.0),
+		build_answered("q2", true, 1.0),
+		build_answered("q3", false, 0.0),
+	];
+	let order = ["q1", "q2", "q3", "q4"];
+	assert_eq!(score_with_rule(&questions, &order, Some(&rule)), 7);
+	let blank_penalty = ScoringRuleSchema {
+		blank: -2.0,
+		..rule
+	};
+	assert_eq!(score_with_rule(&questions, &order, Some(&blank_penalty)), 5);
+}
+
+#[test]
+fn test_scoring_rule_should_award_partial_credit_when_enabled() {
+	let rule = ScoringRuleSchema {
+		correct: 4.0,
+		wrong: -1.0,
+		blank: 0.0,
+		partial_credit: true,
+	};
+	let questions = vec![build_answered("q1", false, 0.5)];
+	assert_eq!(score_with_rule(&questions, &["q1"], Some(&rule)), 2);
+	let strict = ScoringRuleSchema {
+		partial_credit: false,
+		..rule
+	};
+	assert_eq!(score_with_rule(&questions, &["q1"], Some(&strict)), -1);
+}
+
+#[test]
+fn test_scoring_rule_should_prefer_question_override() {
+	let mut hard = build_answered("q1", true, 1.0);
+	hard.scoring_rule = Some(
+		ScoringRuleSchema {
+			correct: 5.0,
+			..Default::default()
+		}
+		.into(),
+	);
+	let questions = vec![hard, build_answered("q2", true, 1.0)];
+	assert_eq!(score_with_rule(&questions, &["q1", "q2"], None), 6);
+	assert_eq!(score_with_rule(&questions[1..], &["q2"], None), 1);
+}
@@ -0,0 +1,55 @@
+use axum::{
+	routing::{get, post},
+	Router,
+};
+
+pub mod session_results_controller;
+pub mod session_results_dto;
+pub mod session_results_enum;
+pub mod session_results_export;
+pub mod session_results_ranking;
+pub mod session_results_repository;
+pub mod session_results_schema;
+pub mod session_results_service;
+
+#[cfg(test)]
+pub mod session_results_controller_test;
+#[cfg(test)]
+pub mod session_results_export_test;
+#[cfg(test)]
+pub mod session_results_ranking_test;
+#[cfg(test)]
+pub mod session_results_repository_test;
+
+pub use session_results_controller::*;
+pub use session_results_dto::*;
+pub use session_results_enum::*;
+pub use session_results_export::*;
+pub use session_results_ranking::*;
+pub use session_results_repository::*;
+pub use session_results_schema::*;
+pub use session_results_service::*;
+
+pub fn session_results_router() -> Router {
+	Router::new()
+		.route("/compute", post(post_compute_session_result))
+		.route("/detail/{id}", get(get_session_result_by_id))
+		.route("/session/{session_id}", get(get_session_result_list))
+		.route(
+			"/session/{session_id}/user/{user_id}",
+			get(get_session_result_by_user),
+		)
+		.route(
+			"/session/{session_id}/leaderboard",
+			get(get_session_leaderboard),
+		)
+		.route(
+			"/session/{session_id}/user/{user_id}/rank",
+			get(get_session_rank_by_user),
+		)
+		.route("/session/{session_id}/export", get(get_export_session_results))
+		.route(
+			"/session/{session_id}/user/{user_id}/report",
+			get(get_session_result_report),
+		)
+}
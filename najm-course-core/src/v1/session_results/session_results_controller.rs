@@ -0,0 +1,289 @@
+use super::{
+	SessionResultsComputeRequestDto, SessionResultsExportQueryDto,
+	SessionResultsItemDto, SessionResultsLeaderboardDto,
+	SessionResultsLeaderboardQueryDto, SessionResultsRankDto, SessionResultsService,
+};
+use crate::{
+	permissions_guard, AppState, MessageResponseDto, MetaRequestDto, PermissionsEnum,
+	ResponseListSuccessDto, ResponseSuccessDto, SessionsViewerDto, UsersItemDtoRaw,
+};
+use axum::{
+	extract::{Path, Query},
+	response::IntoResponse,
+	Extension, Json,
+};
+
+#[utoipa::path(
+	get,
+	security(("Bearer" = [])),
+	path = "/v1/session-results/session/{session_id}",
+	params(
+		("session_id" = String, Path, description = "Session ID"),
+		("page" = Option<i64>, Query, description = "Page number"),
+		("per_page" = Option<i64>, Query, description = "Items per page"),
+		("sort_by" = Option<String>, Query, description = "Sort by field"),
+		("order" = Option<String>, Query, description = "Order ASC or DESC"),
+		("filter" = Option<String>, Query, description = "Filter value"),
+		("filter_by" = Option<String>, Query, description = "Field to filter by"),
+	),
+	responses(
+		(status = 200, description = "Get session result list", body = ResponseListSuccessDto<Vec<SessionResultsItemDto>>)
+	),
+	tag = "Session Results"
+)]
+pub async fn get_session_result_list(
+	headers: axum::http::HeaderMap,
+	Extension(state): Extension<AppState>,
+	extensions: axum::http::Extensions,
+	Path(session_id): Path<String>,
+	Query(meta): Query<MetaRequestDto>,
+) -> impl IntoResponse {
+	match permissions_guard(
+		&headers,
+		state.clone(),
+		vec![PermissionsEnum::ReadListSessionResults],
+	)
+	.await
+	{
+		Ok(_) => {
+			let viewer = SessionsViewerDto::from(extensions.get::<UsersItemDtoRaw>());
+			SessionResultsService::get_result_list(&state, session_id, meta, viewer).await
+		}
+		Err(response) => response,
+	}
+}
+
+#[utoipa::path(
+	get,
+	security(("Bearer" = [])),
+	path = "/v1/session-results/detail/{id}",
+	params(("id" = String, Path, description = "Session result ID")),
+	responses(
+		(status = 200, description = "Get session result by ID", body = ResponseSuccessDto<SessionResultsItemDto>)
+	),
+	tag = "Session Results"
+)]
+pub async fn get_session_result_by_id(
+	headers: axum::http::HeaderMap,
+	Extension(state): Extension<AppState>,
+	extensions: axum::http::Extensions,
+	Path(id): Path<String>,
+) -> impl IntoResponse {
+	match permissions_guard(
+		&headers,
+		state.clone(),
+		vec![PermissionsEnum::ReadDetailSessionResults],
+	)
+	.await
+	{
+		Ok(_) => {
+			let viewer = SessionsViewerDto::from(extensions.get::<UsersItemDtoRaw>());
+			SessionResultsService::get_result_by_id(&state, id, viewer).await
+		}
+		Err(response) => response,
+	}
+}
+
+#[utoipa::path(
+	get,
+	security(("Bearer" = [])),
+	path = "/v1/session-results/session/{session_id}/user/{user_id}",
+	params(
+		("session_id" = String, Path, description = "Session ID"),
+		("user_id" = String, Path, description = "User ID"),
+	),
+	responses(
+		(status = 200, description = "Get a student's session result", body = ResponseSuccessDto<SessionResultsItemDto>)
+	),
+	tag = "Session Results"
+)]
+pub async fn get_session_result_by_user(
+	headers: axum::http::HeaderMap,
+	Extension(state): Extension<AppState>,
+	extensions: axum::http::Extensions,
+	Path((session_id, user_id)): Path<(String, String)>,
+) -> impl IntoResponse {
+	match permissions_guard(
+		&headers,
+		state.clone(),
+		vec![PermissionsEnum::ReadDetailSessionResults],
+	)
+	.await
+	{
+		Ok(_) => {
+			let viewer = SessionsViewerDto::from(extensions.get::<UsersItemDtoRaw>());
+			SessionResultsService::get_result_by_user(&state, session_id, user_id, viewer)
+				.await
+		}
+		Err(response) => response,
+	}
+}
+
+#[utoipa::path(
+	post,
+	security(("Bearer" = [])),
+	path = "/v1/session-results/compute",
+	request_body = SessionResultsComputeRequestDto,
+	responses(
+		(status = 200, description = "Compute and store a session result", body = ResponseSuccessDto<SessionResultsItemDto>)
+	),
+	tag = "Session Results"
+)]
+pub async fn post_compute_session_result(
+	headers: axum::http::HeaderMap,
+	Extension(state): Extension<AppState>,
+	extensions: axum::http::Extensions,
+	Json(payload): Json<SessionResultsComputeRequestDto>,
+) -> impl IntoResponse {
+	match permissions_guard(
+		&headers,
+		state.clone(),
+		vec![PermissionsEnum::CreateSessionResults],
+	)
+	.await
+	{
+		Ok(_) => {
+			let viewer = SessionsViewerDto::from(extensions.get::<UsersItemDtoRaw>());
+			SessionResultsService::compute_result(&state, payload, viewer).await
+		}
+		Err(response) => response,
+	}
+}
+
+#[utoipa::path(
+	get,
+	security(("Bearer" = [])),
+	path = "/v1/session-results/session/{session_id}/leaderboard",
+	params(
+		("session_id" = String, Path, description = "Session ID"),
+		("limit" = Option<u32>, Query, description = "Number of top entries"),
+		("student_type" = Option<String>, Query, description = "Rank within student type"),
+	),
+	responses(
+		(status = 200, description = "Get session leaderboard", body = ResponseSuccessDto<SessionResultsLeaderboardDto>)
+	),
+	tag = "Session Results"
+)]
+pub async fn get_session_leaderboard(
+	headers: axum::http::HeaderMap,
+	Extension(state): Extension<AppState>,
+	extensions: axum::http::Extensions,
+	Path(session_id): Path<String>,
+	Query(query): Query<SessionResultsLeaderboardQueryDto>,
+) -> impl IntoResponse {
+	match permissions_guard(
+		&headers,
+		state.clone(),
+		vec![PermissionsEnum::ReadDetailSessionResults],
+	)
+	.await
+	{
+		Ok(_) => {
+			let viewer = SessionsViewerDto::from(extensions.get::<UsersItemDtoRaw>());
+			SessionResultsService::get_leaderboard(&state, session_id, query, viewer).await
+		}
+		Err(response) => response,
+	}
+}
+
+#[utoipa::path(
+	get,
+	security(("Bearer" = [])),
+	path = "/v1/session-results/session/{session_id}/user/{user_id}/rank",
+	params(
+		("session_id" = String, Path, description = "Session ID"),
+		("user_id" = String, Path, description = "User ID"),
+	),
+	responses(
+		(status = 200, description = "Get a student's rank and percentile", body = ResponseSuccessDto<SessionResultsRankDto>)
+	),
+	tag = "Session Results"
+)]
+pub async fn get_session_rank_by_user(
+	headers: axum::http::HeaderMap,
+	Extension(state): Extension<AppState>,
+	extensions: axum::http::Extensions,
+	Path((session_id, user_id)): Path<(String, String)>,
+) -> impl IntoResponse {
+	match permissions_guard(
+		&headers,
+		state.clone(),
+		vec![PermissionsEnum::ReadDetailSessionResults],
+	)
+	.await
+	{
+		Ok(_) => {
+			let viewer = SessionsViewerDto::from(extensions.get::<UsersItemDtoRaw>());
+			SessionResultsService::get_rank_by_user(&state, session_id, user_id, viewer)
+				.await
+		}
+		Err(response) => response,
+	}
+}
+
+#[utoipa::path(
+	get,
+	security(("Bearer" = [])),
+	path = "/v1/session-results/session/{session_id}/export",
+	params(
+		("session_id" = String, Path, description = "Session ID"),
+		("format" = Option<String>, Query, description = "csv (default) or xlsx"),
+	),
+	responses(
+		(status = 200, description = "Download the student × test score grid with ranks", content_type = "application/octet-stream", body = Vec<u8>),
+		(status = 404, description = "Session not found", body = MessageResponseDto)
+	),
+	tag = "Session Results"
+)]
+pub async fn get_export_session_results(
+	headers: axum::http::HeaderMap,
+	Extension(state): Extension<AppState>,
+	Path(session_id): Path<String>,
+	Query(query): Query<SessionResultsExportQueryDto>,
+) -> impl IntoResponse {
+	match permissions_guard(
+		&headers,
+		state.clone(),
+		vec![PermissionsEnum::ExportSessionResults],
+	)
+	.await
+	{
+		Ok(_) => SessionResultsService::export_results(&state, session_id, query).await,
+		Err(response) => response,
+	}
+}
+
+#[utoipa::path(
+	get,
+	security(("Bearer" = [])),
+	path = "/v1/session-results/session/{session_id}/user/{user_id}/report",
+	params(
+		("session_id" = String, Path, description = "Session ID"),
+		("user_id" = String, Path, description = "User ID"),
+	),
+	responses(
+		(status = 200, description = "Download a student's printable PDF report", content_type = "application/pdf", body = Vec<u8>),
+		(status = 404, description = "Session result not found", body = MessageResponseDto)
+	),
+	tag = "Session Results"
+)]
+pub async fn get_session_result_report(
+	headers: axum::http::HeaderMap,
+	Extension(state): Extension<AppState>,
+	extensions: axum::http::Extensions,
+	Path((session_id, user_id)): Path<(String, String)>,
+) -> impl IntoResponse {
+	match permissions_guard(
+		&headers,
+		state.clone(),
+		vec![PermissionsEnum::ReadDetailSessionResults],
+	)
+	.await
+	{
+		Ok(_) => {
+			let viewer = SessionsViewerDto::from(extensions.get::<UsersItemDtoRaw>());
+			SessionResultsService::get_report(&state, session_id, user_id, viewer).await
+		}
+		Err(response) => response,
+	}
+}
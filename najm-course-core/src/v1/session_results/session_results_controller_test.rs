@@ -0,0 +1,226 @@
+use super::session_results_repository_test::seed_session_result_dependencies;
+use super::{session_results_router, SessionResultsComputeRequestDto};
+use crate::{
+	attempts::attempts_repository_test::build_user, create_mock_app_state, AppState,
+	PermissionsEnum,
+};
+use axum::{Extension, Router};
+use axum_test::TestServer;
+use najm_course_utils::authorized;
+use surrealdb::Uuid;
+
+fn create_test_app(state: AppState) -> TestServer {
+	let reviewer = build_user(
+		&Uuid::new_v4().to_string(),
+		vec![PermissionsEnum::ReviewAnswerKeys],
+	);
+	let app = Router::new()
+		.nest("/v1/session-results", session_results_router())
+		.layer(Extension(state))
+		.layer(Extension(reviewer));
+	TestServer::new(app).unwrap()
+}
+
+#[tokio::test]
+async fn test_post_compute_session_result_should_return_200() {
+	let state = create_mock_app_state().await;
+	let fixture = seed_session_result_dependencies(&state.surrealdb_ws, Some(15.0))
+		.await
+		.unwrap();
+	let server = create_test_app(state);
+	let payload = SessionResultsComputeRequestDto {
+		user_id: fixture.user_id.clone(),
+		session_id: fixture.session_id.clone(),
+	};
+	let res = authorized(
+		&server,
+		"POST",
+		"/v1/session-results/compute",
+		vec![&PermissionsEnum::CreateSessionResults.to_string()],
+		Some(&payload),
+	)
+	.await;
+	assert_eq!(res.status_code(), 200);
+	let res = authorized::<()>(
+		&server,
+		"GET",
+		&format!(
+			"/v1/session-results/session/{}/user/{}",
+			fixture.session_id, fixture.user_id
+		),
+		vec![&PermissionsEnum::ReadDetailSessionResults.to_string()],
+		None,
+	)
+	.await;
+	assert_eq!(res.status_code(), 200);
+}
+
+#[tokio::test]
+async fn test_get_session_result_by_id_should_return_404() {
+	let state = create_mock_app_state().await;
+	let server = create_test_app(state);
+	let res = authorized::<()>(
+		&server,
+		"GET",
+		"/v1/session-results/detail/not_found",
+		vec![&PermissionsEnum::ReadDetailSessionResults.to_string()],
+		None,
+	)
+	.await;
+	assert_eq!(res.status_code(), 404);
+}
+
+#[tokio::test]
+async fn test_get_session_result_list_should_return_200() {
+	let state = create_mock_app_state().await;
+	let server = create_test_app(state);
+	let res = authorized::<()>(
+		&server,
+		"GET",
+		"/v1/session-results/session/any_session",
+		vec![&PermissionsEnum::ReadListSessionResults.to_string()],
+		None,
+	)
+	.await;
+	assert_eq!(res.status_code(), 200);
+}
+
+#[tokio::test]
+async fn test_get_session_leaderboard_should_return_200() {
+	let state = create_mock_app_state().await;
+	let fixture = seed_session_result_dependencies(&state.surrealdb_ws, None)
+		.await
+		.unwrap();
+	let server = create_test_app(state);
+	let payload = SessionResultsComputeRequestDto {
+		user_id: fixture.user_id.clone(),
+		session_id: fixture.session_id.clone(),
+	};
+	authorized(
+		&server,
+		"POST",
+		"/v1/session-results/compute",
+		vec![&PermissionsEnum::CreateSessionResults.to_string()],
+		Some(&payload),
+	)
+	.await;
+	let res = authorized::<()>(
+		&server,
+		"GET",
+		&format!(
+			"/v1/session-results/session/{}/leaderboard?limit=5",
+			fixture.session_id
+		),
+		vec![&PermissionsEnum::ReadDetailSessionResults.to_string()],
+		None,
+	)
+	.await;
+	assert_eq!(res.status_code(), 200);
+	let res = authorized::<()>(
+		&server,
+		"GET",
+		&format!(
+			"/v1/session-results/session/{}/user/{}/rank",
+			fixture.session_id, fixture.user_id
+		),
+		vec![&PermissionsEnum::ReadDetailSessionResults.to_string()],
+		None,
+	)
+	.await;
+	assert_eq!(res.status_code(), 200);
+}
+
+#[tokio::test]
+async fn test_get_session_rank_by_user_should_return_404() {
+	let state = create_mock_app_state().await;
+	let server = create_test_app(state);
+	let res = authorized::<()>(
+		&server,
+		"GET",
+		"/v1/session-results/session/any_session/user/any_user/rank",
+		vec![&PermissionsEnum::ReadDetailSessionResults.to_string()],
+		None,
+	)
+	.await;
+	assert_eq!(res.status_code(), 404);
+}
+
+#[tokio::test]
+async fn test_get_export_session_results_and_report_should_return_files() {
+	let state = create_mock_app_state().await;
+	let fixture = seed_session_result_dependencies(&state.surrealdb_ws, Some(15.0))
+		.await
+		.unwrap();
+	let server = create_test_app(state);
+	let payload = SessionResultsComputeRequestDto {
+		user_id: fixture.user_id.clone(),
+		session_id: fixture.session_id.clone(),
+	};
+	authorized(
+		&server,
+		"POST",
+		"/v1/session-results/compute",
+		vec![&PermissionsEnum::CreateSessionResults.to_string()],
+		Some(&payload),
+	)
+	.await;
+	let res = authorized::<()>(
+		&server,
+		"GET",
+		&format!("/v1/session-results/session/{}/export", fixture.session_id),
+		vec![&PermissionsEnum::ExportSessionResults.to_string()],
+		None,
+	)
+	.await;
+	assert_eq!(res.status_code(), 200);
+	assert_eq!(
+		res.header("content-disposition"),
+		"attachment; filename=\"result-session-results.csv\""
+	);
+	let csv = res.text();
+	let lines: Vec<&str> = csv.lines().collect();
+	assert_eq!(
+		lines[0],
+		"Rank,Name,Student Type,Answered Test,Skipped Test,Weighted Total,Percentile,Passed"
+	);
+	assert_eq!(lines.len(), 2);
+
+	let res = authorized::<()>(
+		&server,
+		"GET",
+		&format!(
+			"/v1/session-results/session/{}/user/{}/report",
+			fixture.session_id, fixture.user_id
+		),
+		vec![&PermissionsEnum::ReadDetailSessionResults.to_string()],
+		None,
+	)
+	.await;
+	assert_eq!(res.status_code(), 200);
+	assert_eq!(res.header("content-type"), "application/pdf");
+	assert!(res.as_bytes().starts_with(b"%PDF-"));
+}
+
+#[tokio::test]
+async fn test_get_export_session_results_should_reject_unknown_format() {
+	let state = create_mock_app_state().await;
+	let server = create_test_app(state);
+	let res = authorized::<()>(
+		&server,
+		"GET",
+		"/v1/session-results/session/any_session/export?format=pdf",
+		vec![&PermissionsEnum::ExportSessionResults.to_string()],
+		None,
+	)
+	.await;
+	assert_eq!(res.status_code(), 400);
+	let res = authorized::<()>(
+		&server,
+		"GET",
+		"/v1/session-results/session/any_session/user/any_user/report",
+		vec![&PermissionsEnum::ReadDetailSessionResults.to_string()],
+		None,
+	)
+	.await;
+	assert_eq!(res.status_code(), 404);
+}
@@ -0,0 +1,131 @@
+use super::{SessionResultsSchema, SessionResultsTestSchema};
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+use validator::Validate;
+
+#[derive(Clone, Debug, Serialize, Deserialize, Validate, ToSchema)]
+pub struct SessionResultsComputeRequestDto {
+	#[validate(length(min = 1))]
+	#[schema(example = "uuid")]
+	pub user_id: String,
+
+	#[validate(length(min = 1))]
+	#[schema(example = "uuid")]
+	pub session_id: String,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct SessionResultsLeaderboardQueryDto {
+	pub limit: Option<u32>,
+	pub student_type: Option<String>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct SessionResultsExportQueryDto {
+	pub format: Option<String>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, ToSchema)]
+pub struct SessionResultsLeaderboardEntryDto {
+	pub rank: u32,
+	pub student_type_rank: u32,
+	#[schema(example = "B*** S******")]
+	pub name: String,
+	pub student_type: Option<String>,
+	pub weighted_total: f64,
+	pub percentile: f64,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, ToSchema)]
+pub struct SessionResultsLeaderboardDto {
+	pub session_id: String,
+	pub student_type: Option<String>,
+	pub total_participants: u64,
+	pub entries: Vec<SessionResultsLeaderboardEntryDto>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, ToSchema)]
+pub struct SessionResultsRankDto {
+	pub user_id: String,
+	pub session_id: String,
+	pub weighted_total: f64,
+	pub rank: u32,
+	pub total_participants: u64,
+	pub student_type: Option<String>,
+	pub student_type_rank: u32,
+	pub student_type_participants: u64,
+	pub percentile: f64,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, ToSchema)]
+pub struct SessionResultsTestItemDto {
+	pub test_id: String,
+	pub name: String,
+	pub score: i32,
+	pub theta: Option<f64>,
+	pub weight: f32,
+	pub multiplier: f32,
+	pub is_answered: bool,
+	pub pending_grading: u32,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, ToSchema)]
+pub struct SessionResultsItemDto {
+	pub id: String,
+	pub user_id: String,
+	pub session_id: String,
+	pub student_type: Option<String>,
+	pub tests: Vec<SessionResultsTestItemDto>,
+	#[schema(example = 72.5)]
+	pub weighted_total: f64,
+	#[schema(example = 65.0)]
+	pub passing_grade: Option<f64>,
+	pub passed: Option<bool>,
+	pub is_pending_grading: bool,
+	pub rank: Option<u32>,
+	pub student_type_rank: Option<u32>,
+	#[schema(example = 87.5)]
+	pub percentile: Option<f64>,
+	pub created_at: String,
+	pub updated_at: String,
+}
+
+impl From<SessionResultsTestSchema> for SessionResultsTestItemDto {
+	fn from(value: SessionResultsTestSchema) -> Self {
+		Self {
+			test_id: value.test.id.to_raw(),
+			name: value.name,
+			score: value.score,
+			theta: value.theta,
+			weight: value.weight,
+			multiplier: value.multiplier,
+			is_answered: value.is_answered,
+			pending_grading: value.pending_grading,
+		}
+	}
+}
+
+impl From<SessionResultsSchema> for SessionResultsItemDto {
+	fn from(value: SessionResultsSchema) -> Self {
+		Self {
+			id: value.id.id.to_raw(),
+			user_id: value.user.id.to_raw(),
+			session_id: value.session.id.to_raw(),
+			student_type: value.student_type,
+			tests: value
+				.tests
+				.into_iter()
+				.map(SessionResultsTestItemDto::from)
+				.collect(),
+			weighted_total: value.weighted_total,
+			passing_grade: value.passing_grade,
+			passed: value.passed,
+			is_pending_grading: value.is_pending_grading,
+			rank: value.rank,
+			student_type_rank: value.student_type_rank,
+			percentile: value.percentile,
+			created_at: value.created_at,
+			updated_at: value.updated_at,
+		}
+	}
+}
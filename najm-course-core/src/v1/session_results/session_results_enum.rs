@@ -0,0 +1,43 @@
+use std::fmt;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SessionResultsExportFormatEnum {
+	Csv,
+	Xlsx,
+}
+
+impl SessionResultsExportFormatEnum {
+	pub fn file_extension(&self) -> &'static str {
+		match self {
+			Self::Csv => "csv",
+			Self::Xlsx => "xlsx",
+		}
+	}
+
+	pub fn content_type(&self) -> &'static str {
+		match self {
+			Self::Csv => "text/csv; charset=utf-8",
+			Self::Xlsx => {
+				"application/vnd.openxmlformats-officedocument.spreadsheetml.sheet"
+			}
+		}
+	}
+}
+
+impl fmt::Display for SessionResultsExportFormatEnum {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		write!(f, "{}", self.file_extension())
+	}
+}
+
+impl std::str::FromStr for SessionResultsExportFormatEnum {
+	type Err = anyhow::Error;
+
+	fn from_str(value: &str) -> Result<Self, Self::Err> {
+		match value.to_lowercase().as_str() {
+			"csv" => Ok(Self::Csv),
+			"xlsx" => Ok(Self::Xlsx),
+			_ => anyhow::bail!("Unsupported format"),
+		}
+	}
+}
@@ -0,0 +1,483 @@
+use super::SessionResultsSchema;
+use crate::answers::{
+	pending_grading, QuestionsItemAnswersDto, TestsItemAnswersDto,
+};
+use anyhow::{Error, Result};
+use najm_course_utils::get_iso_date;
+use printpdf::{
+	BuiltinFont, IndirectFontRef, Mm, PdfDocument, PdfDocumentReference,
+	PdfLayerReference,
+};
+use rust_xlsxwriter::{Format, Workbook};
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum SessionResultsExportCell {
+	Text(String),
+	Number(f64),
+	Empty,
+}
+
+impl SessionResultsExportCell {
+	fn text(value: Option<&str>) -> Self {
+		match value {
+			Some(value) if !value.is_empty() => Self::Text(value.to_string()),
+			_ => Self::Empty,
+		}
+	}
+}
+
+#[derive(Clone, Debug)]
+pub struct SessionResultsGrid {
+	pub headers: Vec<String>,
+	pub rows: Vec<Vec<SessionResultsExportCell>>,
+}
+
+#[derive(Clone, Debug)]
+pub struct SessionResultsReport {
+	pub session_name: String,
+	pub result: SessionResultsSchema,
+	pub total_participants: u64,
+	pub tests: Vec<TestsItemAnswersDto>,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct SessionResultsTopicScore {
+	pub topic: String,
+	pub correct: u32,
+	pub total: u32,
+}
+
+fn passed_label(result: &SessionResultsSchema) -> Option<&'static str> {
+	if result.is_pending_grading {
+		return Some("Pending grading");
+	}
+	result
+		.passed
+		.map(|passed| if passed { "Yes" } else { "No" })
+}
+
+/// `tests` lists the session's test IDs and names in session order. Tests a
+/// student did not take are left empty.
+pub fn results_grid(
+	tests: &[(String, String)],
+	results: &[SessionResultsSchema],
+) -> SessionResultsGrid {
+	let mut headers: Vec<String> = ["Rank", "Name", "Student Type"]
+		.iter()
+		.map(|h| h.to_string())
+		.collect();
+	headers.extend(tests.iter().map(|(_, name)| name.clone()));
+	headers.extend(
+		["Weighted Total", "Percentile", "Passed"]
+			.iter()
+			.map(|h| h.to_string()),
+	);
+
+	let mut ordered: Vec<&SessionResultsSchema> = results.iter().collect();
+	ordered.sort_by(|a, b| {
+		a.rank
+			.unwrap_or(u32::MAX)
+			.cmp(&b.rank.unwrap_or(u32::MAX))
+			.then(b.weighted_total.total_cmp(&a.weighted_total))
+			.then(a.fullname.cmp(&b.fullname))
+	});
+	let rows = ordered
+		.into_iter()
+		.map(|result| {
+			let mut row = vec![
+				result.rank.map_or(SessionResultsExportCell::Empty, |rank| {
+					SessionResultsExportCell::Number(rank as f64)
+				}),
+				SessionResultsExportCell::text(result.fullname.as_deref()),
+				SessionResultsExportCell::text(result.student_type.as_deref()),
+			];
+			row.extend(tests.iter().map(|(test_id, _)| {
+				result
+					.tests
+					.iter()
+					.find(|t| t.test.id.to_raw() == *test_id && t.is_answered)
+					.map_or(SessionResultsExportCell::Empty, |t| {
+						SessionResultsExportCell::Number(t.score as f64)
+					})
+			}));
+			row.push(SessionResultsExportCell::Number(result.weighted_total));
+			row.push(result.percentile.map_or(
+				SessionResultsExportCell::Empty,
+				SessionResultsExportCell::Number,
+			));
+			row.push(SessionResultsExportCell::text(passed_label(result)));
+			row
+		})
+		.collect();
+	SessionResultsGrid { headers, rows }
+}
+
+pub fn write_results_csv(grid: &SessionResultsGrid) -> Result<Vec<u8>> {
+	let mut writer = csv::Writer::from_writer(vec![]);
+	writer.write_record(&grid.headers)?;
+	for row in &grid.rows {
+		writer.write_record(row.iter().map(|cell| match cell {
+			SessionResultsExportCell::Text(value) => value.clone(),
+			SessionResultsExportCell::Number(value) => value.to_string(),
+			SessionResultsExportCell::Empty => String::new(),
+		}))?;
+	}
+	writer.into_inner().map_err(|e| Error::msg(e.to_string()))
+}
+
+pub fn write_results_xlsx(grid: &SessionResultsGrid) -> Result<Vec<u8>> {
+	let mut workbook = Workbook::new();
+	let bold = Format::new().set_bold();
+	let worksheet = workbook.add_worksheet().set_name("Results")?;
+	for (col, header) in grid.headers.iter().enumerate() {
+		worksheet.write_string_with_format(0, col as u16, header, &bold)?;
+	}
+	for (index, row) in grid.rows.iter().enumerate() {
+		let row_index = index as u32 + 1;
+		for (col, cell) in row.iter().enumerate() {
+			match cell {
+				SessionResultsExportCell::Text(value) => {
+					worksheet.write_string(row_index, col as u16, value)?;
+				}
+				SessionResultsExportCell::Number(value) => {
+					worksheet.write_number(row_index, col as u16, *value)?;
+				}
+				SessionResultsExportCell::Empty => {}
+			}
+		}
+	}
+	worksheet.set_freeze_panes(1, 2)?;
+	worksheet.autofit();
+	Ok(workbook.save_to_buffer()?)
+}
+
+fn is_pending(question: &QuestionsItemAnswersDto) -> bool {
+	pending_grading(std::slice::from_ref(question)) > 0
+}
+
+fn topic_label(question: &QuestionsItemAnswersDto) -> String {
+	let tags = question.tags.as_ref();
+	let subject = tags
+		.and_then(|t| t.subject.as_deref())
+		.filter(|s| !s.is_empty());
+	let topic = tags
+		.and_then(|t| t.topic.as_deref())
+		.filter(|s| !s.is_empty());
+	match (subject, topic) {
+		(Some(subject), Some(topic)) => format!("{} / {}", subject, topic),
+		(Some(label), None) | (None, Some(label)) => label.to_string(),
+		(None, None) => "Untagged".to_string(),
+	}
+}
+
+/// Correct answers per subject and topic tag across all tests, in the order
+/// topics first appear. Essays awaiting grading are not counted.
+pub fn topic_breakdown(
+	tests: &[TestsItemAnswersDto],
+) -> Vec<SessionResultsTopicScore> {
+	let mut topics: Vec<SessionResultsTopicScore> = vec![];
+	for question in tests
+		.iter()
+		.flat_map(|t| &t.questions)
+		.filter(|q| !is_pending(q))
+	{
+		let topic = topic_label(question);
+		let index = match topics.iter().position(|t| t.topic == topic) {
+			Some(index) => index,
+			None => {
+				topics.push(SessionResultsTopicScore {
+					topic,
+					correct: 0,
+					total: 0,
+				});
+				topics.len() - 1
+			}
+		};
+		topics[index].total += 1;
+		if question.is_correct {
+			topics[index].correct += 1;
+		}
+	}
+	topics
+}
+
+pub fn wrong_answers(
+	test: &TestsItemAnswersDto,
+) -> Vec<(usize, &QuestionsItemAnswersDto)> {
+	test
+		.questions
+		.iter()
+		.enumerate()
+		.filter(|(_, q)| !q.is_correct && !is_pending(q))
+		.map(|(index, q)| (index + 1, q))
+		.collect()
+}
+
+fn option_text(label: &str, display_label: Option<&str>) -> String {
+	match display_label {
+		Some(display) => format!("{}. {}", display, label),
+		None => label.to_string(),
+	}
+}
+
+pub fn user_answer_text(question: &QuestionsItemAnswersDto) -> String {
+	let selected: Vec<String> = question
+		.options
+		.iter()
+		.filter(|o| o.is_user_selected)
+		.map(|o| {
+			let text = option_text(&o.label, o.display_label.as_deref());
+			match o.user_value {
+				Some(value) => {
+					format!("{} ({})", text, if value { "True" } else { "False" })
+				}
+				None => text,
+			}
+		})
+		.collect();
+	if !selected.is_empty() {
+		return selected.join(", ");
+	}
+	if let Some(value) = question.numeric_value {
+		return value.to_string();
+	}
+	match question.text.as_deref() {
+		Some(text) if !text.trim().is_empty() => text.trim().to_string(),
+		_ => "Not answered".to_string(),
+	}
+}
+
+pub fn correct_answer_text(question: &QuestionsItemAnswersDto) -> Option<String> {
+	let correct: Vec<String> = question
+		.options
+		.iter()
+		.filter(|o| o.is_correct)
+		.map(|o| option_text(&o.label, o.display_label.as_deref()))
+		.collect();
+	(!correct.is_empty()).then(|| correct.join(", "))
+}
+
+const PAGE_WIDTH: f32 = 210.0;
+const PAGE_HEIGHT: f32 = 297.0;
+const PAGE_MARGIN: f32 = 18.0;
+const PT_TO_MM: f32 = 0.3528;
+
+fn wrap_text(text: &str, max_chars: usize) -> Vec<String> {
+	let max_chars = max_chars.max(1);
+	let mut lines = vec![];
+	for paragraph in text.lines() {
+		let mut line = String::new();
+		for word in paragraph.split_whitespace() {
+			let mut word: Vec<char> = word.chars().collect();
+			while word.len() > max_chars {
+				if !line.is_empty() {
+					lines.push(std::mem::take(&mut line));
+				}
+				lines.push(word.drain(..max_chars).collect());
+			}
+			let word: String = word.into_iter().collect();
+			if word.is_empty() {
+				continue;
+			}
+			if !line.is_empty()
+				&& line.chars().count() + 1 + word.chars().count() > max_chars
+			{
+				lines.push(std::mem::take(&mut line));
+			}
+			if !line.is_empty() {
+				line.push(' ');
+			}
+			line.push_str(&word);
+		}
+		lines.push(line);
+	}
+	lines
+}
+
+struct ReportPdf {
+	doc: PdfDocumentReference,
+	layer: PdfLayerReference,
+	regular: IndirectFontRef,
+	bold: IndirectFontRef,
+	y: f32,
+}
+
+impl ReportPdf {
+	fn new(title: &str) -> Result<Self> {
+		let (doc, page, layer) =
+			PdfDocument::new(title, Mm(PAGE_WIDTH), Mm(PAGE_HEIGHT), "Report");
+		let regular = doc
+			.add_builtin_font(BuiltinFont::Helvetica)
+			.map_err(|e| Error::msg(e.to_string()))?;
+		let bold = doc
+			.add_builtin_font(BuiltinFont::HelveticaBold)
+			.map_err(|e| Error::msg(e.to_string()))?;
+		let layer = doc.get_page(page).get_layer(layer);
+		Ok(Self {
+			doc,
+			layer,
+			regular,
+			bold,
+			y: PAGE_HEIGHT - PAGE_MARGIN,
+		})
+	}
+
+	fn text(&mut self, text: &str, size: f32, bold: bool, indent: f32) {
+		let line_height = size * PT_TO_MM * 1.4;
+		let char_width = size * PT_TO_MM * 0.5;
+		let max_chars =
+			((PAGE_WIDTH - 2.0 * PAGE_MARGIN - indent) / char_width) as usize;
+		for line in wrap_text(text, max_chars) {
+			if self.y - line_height < PAGE_MARGIN {
+				let (page, layer) =
+					self.doc.add_page(Mm(PAGE_WIDTH), Mm(PAGE_HEIGHT), "Report");
+				self.layer = self.doc.get_page(page).get_layer(layer);
+				self.y = PAGE_HEIGHT - PAGE_MARGIN;
+			}
+			self.y -= line_height;
+			let font = if bold { &self.bold } else { &self.regular };
+			self
+				.layer
+				.use_text(line, size, Mm(PAGE_MARGIN + indent), Mm(self.y), font);
+		}
+	}
+
+	fn gap(&mut self) {
+		self.y -= 4.0;
+	}
+
+	fn finish(self) -> Result<Vec<u8>> {
+		self
+			.doc
+			.save_to_bytes()
+			.map_err(|e| Error::msg(e.to_string()))
+	}
+}
+
+pub fn write_report_pdf(report: &SessionResultsReport) -> Result<Vec<u8>> {
+	let result = &report.result;
+	let student = result.fullname.as_deref().unwrap_or("-");
+	let mut pdf = ReportPdf::new(&format!("{} - {}", report.session_name, student))?;
+	pdf.text("Report Card", 18.0, true, 0.0);
+	pdf.text(&report.session_name, 12.0, true, 0.0);
+	pdf.text(&format!("Student: {}", student), 10.0, false, 0.0);
+	if let Some(student_type) = &result.student_type {
+		pdf.text(&format!("Student type: {}", student_type), 10.0, false, 0.0);
+	}
+	pdf.text(&format!("Generated: {}", get_iso_date()), 8.0, false, 0.0);
+	pdf.gap();
+
+	pdf.text("Summary", 13.0, true, 0.0);
+	pdf.text(
+		&format!("Weighted total: {:.2}", result.weighted_total),
+		10.0,
+		false,
+		0.0,
+	);
+	if let Some(rank) = result.rank {
+		pdf.text(
+			&format!("Rank: {} of {}", rank, report.total_participants),
+			10.0,
+			false,
+			0.0,
+		);
+	}
+	if let Some(percentile) = result.percentile {
+		pdf.text(&format!("Percentile: {:.1}", percentile), 10.0, false, 0.0);
+	}
+	if let Some(grade) = result.passing_grade {
+		let status = match passed_label(result) {
+			Some("Yes") => "Passed",
+			Some("No") => "Not passed",
+			Some(label) => label,
+			None => "-",
+		};
+		pdf.text(
+			&format!("Passing grade: {:.2} ({})", grade, status),
+			10.0,
+			false,
+			0.0,
+		);
+	}
+	pdf.gap();
+
+	pdf.text("Scores", 13.0, true, 0.0);
+	for test in &result.tests {
+		let score = if !test.is_answered {
+			"Not taken".to_string()
+		} else if test.pending_grading > 0 {
+			format!("{} ({} awaiting grading)", test.score, test.pending_grading)
+		} else {
+			test.score.to_string()
+		};
+		pdf.text(
+			&format!("{}: {} (weight {})", test.name, score, test.weight),
+			10.0,
+			false,
+			0.0,
+		);
+	}
+	pdf.gap();
+
+	pdf.text("Topic breakdown", 13.0, true, 0.0);
+	let topics = topic_breakdown(&report.tests);
+	if topics.is_empty() {
+		pdf.text("No graded answers yet.", 10.0, false, 0.0);
+	}
+	for topic in topics {
+		pdf.text(
+			&format!(
+				"{}: {}/{} correct ({:.0}%)",
+				topic.topic,
+				topic.correct,
+				topic.total,
+				topic.correct as f64 * 100.0 / topic.total as f64
+			),
+			10.0,
+			false,
+			0.0,
+		);
+	}
+	pdf.gap();
+
+	pdf.text("Review of wrong answers", 13.0, true, 0.0);
+	let mut has_wrong = false;
+	for test in &report.tests {
+		let wrong = wrong_answers(test);
+		if wrong.is_empty() {
+			continue;
+		}
+		has_wrong = true;
+		pdf.text(&test.name, 11.0, true, 0.0);
+		for (number, question) in wrong {
+			pdf.text(
+				&format!("{}. {}", number, question.question),
+				10.0,
+				false,
+				0.0,
+			);
+			pdf.text(
+				&format!("Your answer: {}", user_answer_text(question)),
+				9.0,
+				false,
+				6.0,
+			);
+			if let Some(correct) = correct_answer_text(question) {
+				pdf.text(&format!("Correct answer: {}", correct), 9.0, false, 6.0);
+			}
+			if !question.discussion.trim().is_empty() {
+				pdf.text(
+					&format!("Discussion: {}", question.discussion.trim()),
+					9.0,
+					false,
+					6.0,
+				);
+			}
+		}
+		pdf.gap();
+	}
+	if !has_wrong {
+		pdf.text("No wrong answers.", 10.0, false, 0.0);
+	}
+	pdf.finish()
+}
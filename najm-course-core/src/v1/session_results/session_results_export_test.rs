@@ -0,0 +1,277 @@
+use super::*;
+use crate::{
+	answers::{OptionsItemAnswersDto, QuestionsItemAnswersDto, TestsItemAnswersDto},
+	QuestionsTagsDto,
+};
+use calamine::{Data, Reader, Xlsx};
+use najm_course_utils::make_thing;
+use std::io::Cursor;
+
+fn test_score(id: &str, score: i32, is_answered: bool) -> SessionResultsTestSchema {
+	SessionResultsTestSchema {
+		test: make_thing("app_tests", id),
+		name: format!("Test {}", id),
+		score,
+		theta: None,
+		weight: 1.0,
+		multiplier: 1.0,
+		is_answered,
+		pending_grading: 0,
+		is_weighted: false,
+	}
+}
+
+fn result(
+	name: &str,
+	rank: Option<u32>,
+	weighted_total: f64,
+	tests: Vec<SessionResultsTestSchema>,
+) -> SessionResultsSchema {
+	SessionResultsSchema {
+		id: make_thing("app_session_results", name),
+		user: make_thing("app_users", name),
+		session: make_thing("app_sessions", "s1"),
+		fullname: Some(name.into()),
+		student_type: Some("SMA".into()),
+		tests,
+		weighted_total,
+		passing_grade: Some(60.0),
+		passed: Some(weighted_total >= 60.0),
+		is_pending_grading: false,
+		rank,
+		student_type_rank: rank,
+		percentile: rank.map(|r| 100.0 - r as f64 * 25.0),
+		is_deleted: false,
+		created_at: "2025-01-01T00:00:00Z".into(),
+		updated_at: "2025-01-01T00:00:00Z".into(),
+	}
+}
+
+fn sample_grid() -> SessionResultsGrid {
+	let tests = vec![
+		("t1".to_string(), "Test t1".to_string()),
+		("t2".to_string(), "Test t2".to_string()),
+	];
+	results_grid(
+		&tests,
+		&[
+			result(
+				"Budi",
+				Some(2),
+				55.5,
+				vec![test_score("t1", 55, true), test_score("t2", 0, false)],
+			),
+			result("Cici", None, 0.0, vec![]),
+			result(
+				"Ani",
+				Some(1),
+				80.0,
+				vec![test_score("t2", 90, true), test_score("t1", 70, true)],
+			),
+		],
+	)
+}
+
+fn question(
+	id: &str,
+	topic: Option<&str>,
+	is_correct: bool,
+	question_type: &str,
+) -> QuestionsItemAnswersDto {
+	let option = |id: &str, display: &str, is_correct: bool, selected: bool| {
+		OptionsItemAnswersDto {
+			id: id.into(),
+			label: format!("Option {}", id),
+			display_label: Some(display.into()),
+			is_correct,
+			points: None,
+			is_user_selected: selected,
+			user_value: None,
+			image_url: None,
+			created_at: "2025-01-01T00:00:00Z".into(),
+			updated_at: "2025-01-01T00:00:00Z".into(),
+		}
+	};
+	let options = if question_type == "essay" {
+		vec![]
+	} else {
+		vec![
+			option(&format!("{}a", id), "A", true, is_correct),
+			option(&format!("{}b", id), "B", false, !is_correct),
+		]
+	};
+	QuestionsItemAnswersDto {
+		id: id.into(),
+		question: format!("Question {}", id),
+		discussion: "Because.".into(),
+		question_image_url: None,
+		discussion_image_url: None,
+		options,
+		question_type: question_type.into(),
+		numeric_value: None,
+		text: (question_type == "essay").then(|| "My essay".to_string()),
+		is_correct,
+		points: None,
+		credit: if is_correct { 1.0 } else { 0.0 },
+		scoring_rule: None,
+		grading: None,
+		tags: topic.map(|topic| QuestionsTagsDto {
+			subject: Some("Matematika".into()),
+			topic: Some(topic.into()),
+			sub_topic: None,
+			difficulty: None,
+			source: None,
+		}),
+		answered_at: None,
+		created_at: "2025-01-01T00:00:00Z".into(),
+		updated_at: "2025-01-01T00:00:00Z".into(),
+	}
+}
+
+fn answers(
+	name: &str,
+	questions: Vec<QuestionsItemAnswersDto>,
+) -> TestsItemAnswersDto {
+	TestsItemAnswersDto {
+		id: name.into(),
+		name: name.into(),
+		score: 0,
+		theta: None,
+		kecermatan: None,
+		pending_grading: 0,
+		is_revealed: true,
+		questions,
+		created_at: "2025-01-01T00:00:00Z".into(),
+		updated_at: "2025-01-01T00:00:00Z".into(),
+	}
+}
+
+#[test]
+fn test_results_grid_should_order_by_rank_and_align_test_columns() {
+	let grid = sample_grid();
+	assert_eq!(
+		grid.headers,
+		vec![
+			"Rank",
+			"Name",
+			"Student Type",
+			"Test t1",
+			"Test t2",
+			"Weighted Total",
+			"Percentile",
+			"Passed"
+		]
+	);
+	let names: Vec<&SessionResultsExportCell> =
+		grid.rows.iter().map(|r| &r[1]).collect();
+	assert_eq!(
+		names,
+		vec![
+			&SessionResultsExportCell::Text("Ani".into()),
+			&SessionResultsExportCell::Text("Budi".into()),
+			&SessionResultsExportCell::Text("Cici".into()),
+		]
+	);
+	assert_eq!(grid.rows[0][3], SessionResultsExportCell::Number(70.0));
+	assert_eq!(grid.rows[0][4], SessionResultsExportCell::Number(90.0));
+	assert_eq!(grid.rows[1][4], SessionResultsExportCell::Empty);
+	assert_eq!(grid.rows[2][0], SessionResultsExportCell::Empty);
+	assert_eq!(grid.rows[1][7], SessionResultsExportCell::Text("No".into()));
+}
+
+#[test]
+fn test_write_results_csv_and_xlsx_should_hold_the_grid() {
+	let grid = sample_grid();
+	let csv = String::from_utf8(write_results_csv(&grid).unwrap()).unwrap();
+	let lines: Vec<&str> = csv.lines().collect();
+	assert_eq!(
+		lines[0],
+		"Rank,Name,Student Type,Test t1,Test t2,Weighted Total,Percentile,Passed"
+	);
+	assert_eq!(lines[1], "1,Ani,SMA,70,90,80,75,Yes");
+	assert_eq!(lines[2], "2,Budi,SMA,55,,55.5,50,No");
+	assert_eq!(lines.len(), 4);
+
+	let bytes = write_results_xlsx(&grid).unwrap();
+	let mut workbook: Xlsx<_> = Xlsx::new(Cursor::new(bytes)).unwrap();
+	let range = workbook.worksheet_range("Results").unwrap();
+	assert_eq!(range.get((0, 3)), Some(&Data::String("Test t1".into())));
+	assert_eq!(range.get((1, 1)), Some(&Data::String("Ani".into())));
+	assert_eq!(range.get((1, 4)), Some(&Data::Float(90.0)));
+	assert_eq!(range.get((2, 5)), Some(&Data::Float(55.5)));
+}
+
+#[test]
+fn test_topic_breakdown_should_group_graded_answers_by_tag() {
+	let mut pending = question("q4", Some("Aljabar"), false, "essay");
+	pending.points = None;
+	let tests = vec![
+		answers(
+			"Test A",
+			vec![
+				question("q1", Some("Aljabar"), true, "single_choice"),
+				question("q2", Some("Geometri"), false, "single_choice"),
+				pending,
+			],
+		),
+		answers(
+			"Test B",
+			vec![
+				question("q3", Some("Aljabar"), false, "single_choice"),
+				question("q5", None, true, "single_choice"),
+			],
+		),
+	];
+	assert_eq!(
+		topic_breakdown(&tests),
+		vec![
+			SessionResultsTopicScore {
+				topic: "Matematika / Aljabar".into(),
+				correct: 1,
+				total: 2,
+			},
+			SessionResultsTopicScore {
+				topic: "Matematika / Geometri".into(),
+				correct: 0,
+				total: 1,
+			},
+			SessionResultsTopicScore {
+				topic: "Untagged".into(),
+				correct: 1,
+				total: 1,
+			},
+		]
+	);
+
+	let wrong = wrong_answers(&tests[0]);
+	assert_eq!(wrong.len(), 1);
+	assert_eq!(wrong[0].0, 2);
+	assert_eq!(user_answer_text(wrong[0].1), "B. Option q2b");
+	assert_eq!(
+		correct_answer_text(wrong[0].1).as_deref(),
+		Some("A. Option q2a")
+	);
+}
+
+#[test]
+fn test_write_report_pdf_should_produce_a_pdf_document() {
+	let mut long = question("q2", Some("Geometri"), false, "single_choice");
+	long.question = "Panjang ".repeat(400);
+	let report = SessionResultsReport {
+		session_name: "Tryout UTBK".into(),
+		result: result(
+			"Ani",
+			Some(1),
+			80.0,
+			vec![test_score("t1", 70, true), test_score("t2", 0, false)],
+		),
+		total_participants: 3,
+		tests: vec![answers(
+			"Test t1",
+			vec![question("q1", Some("Aljabar"), true, "single_choice"), long],
+		)],
+	};
+	let bytes = write_report_pdf(&report).unwrap();
+	assert!(bytes.starts_with(b"%PDF-"));
+	assert!(bytes.len() > 1000);
+}
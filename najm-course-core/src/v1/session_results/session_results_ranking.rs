@@ -0,0 +1,59 @@
+use std::collections::HashMap;
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct RankingPosition {
+	pub rank: u32,
+	pub student_type_rank: u32,
+	pub percentile: f64,
+}
+
+fn competition_ranks(scores: &[f64]) -> Vec<u32> {
+	let mut order: Vec<usize> = (0..scores.len()).collect();
+	order.sort_by(|a, b| scores[*b].total_cmp(&scores[*a]));
+	let mut ranks = vec![0; scores.len()];
+	for (position, index) in order.iter().enumerate() {
+		ranks[*index] = match position {
+			0 => 1,
+			_ if scores[order[position - 1]] == scores[*index] => {
+				ranks[order[position - 1]]
+			}
+			_ => position as u32 + 1,
+		};
+	}
+	ranks
+}
+
+pub fn rank_results(entries: &[(f64, Option<String>)]) -> Vec<RankingPosition> {
+	let total = entries.len();
+	let scores: Vec<f64> = entries.iter().map(|(score, _)| *score).collect();
+	let ranks = competition_ranks(&scores);
+	let mut groups: HashMap<Option<&str>, Vec<usize>> = HashMap::new();
+	for (index, (_, student_type)) in entries.iter().enumerate() {
+		groups
+			.entry(student_type.as_deref())
+			.or_default()
+			.push(index);
+	}
+	let mut group_ranks = vec![0; total];
+	for members in groups.values() {
+		let group_scores: Vec<f64> = members.iter().map(|i| scores[*i]).collect();
+		for (member, rank) in members.iter().zip(competition_ranks(&group_scores)) {
+			group_ranks[*member] = rank;
+		}
+	}
+	let mut sorted = scores.clone();
+	sorted.sort_by(|a, b| a.total_cmp(b));
+	(0..total)
+		.map(|index| {
+			let score = scores[index];
+			let below = sorted.partition_point(|s| *s < score);
+			let equal = sorted.partition_point(|s| *s <= score) - below;
+			let percentile = (below as f64 + 0.5 * equal as f64) / total as f64 * 100.0;
+			RankingPosition {
+				rank: ranks[index],
+				student_type_rank: group_ranks[index],
+				percentile: (percentile * 100.0).round() / 100.0,
+			}
+		})
+		.collect()
+}
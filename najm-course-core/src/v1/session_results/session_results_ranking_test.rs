@@ -0,0 +1,42 @@
+use super::*;
+
+#[test]
+fn test_rank_results_should_share_rank_on_ties() {
+	let entries = vec![
+		(80.0, Some("SMA".to_string())),
+		(95.0, Some("SMA".to_string())),
+		(80.0, Some("Alumni".to_string())),
+		(60.0, Some("SMA".to_string())),
+	];
+	let ranks: Vec<u32> = rank_results(&entries).iter().map(|p| p.rank).collect();
+	assert_eq!(ranks, vec![2, 1, 2, 4]);
+}
+
+#[test]
+fn test_rank_results_should_rank_within_student_type() {
+	let entries = vec![
+		(80.0, Some("SMA".to_string())),
+		(95.0, Some("SMA".to_string())),
+		(70.0, Some("Alumni".to_string())),
+	];
+	let ranks: Vec<u32> = rank_results(&entries)
+		.iter()
+		.map(|p| p.student_type_rank)
+		.collect();
+	assert_eq!(ranks, vec![2, 1, 1]);
+}
+
+#[test]
+fn test_rank_results_should_compute_percentile_rank() {
+	let entries = vec![(50.0, None), (70.0, None), (70.0, None), (90.0, None)];
+	let percentiles: Vec<f64> = rank_results(&entries)
+		.iter()
+		.map(|p| p.percentile)
+		.collect();
+	assert_eq!(percentiles, vec![12.5, 50.0, 50.0, 87.5]);
+}
+
+#[test]
+fn test_rank_results_should_handle_empty_input() {
+	assert!(rank_results(&[]).is_empty());
+}
@@ -0,0 +1,520 @@
+use super::{
+	rank_results, results_grid, SessionResultsComputeRequestDto, SessionResultsGrid,
+	SessionResultsItemDto, SessionResultsLeaderboardDto,
+	SessionResultsLeaderboardEntryDto, SessionResultsLeaderboardQueryDto,
+	SessionResultsRankDto, SessionResultsRankRowSchema, SessionResultsReport,
+	SessionResultsSchema, SessionResultsTestSchema, SessionResultsUserSchema,
+};
+use crate::{
+	answers::{AnswersRepository, TestsItemAnswersDto},
+	select_attempt_score, AppState, AttemptsRepository, AttemptsStatusEnum,
+	CountResult, MetaRequestDto, ResponseListSuccessDto, ScoringConfigSchema,
+	SessionsRepository, SessionsSchema, SessionsScoreSelectionEnum, TestsRepository,
+	SCORING_REGISTRY,
+};
+use anyhow::{bail, Result};
+use najm_course_libs::ResourceEnum;
+use najm_course_utils::{
+	get_iso_date, make_thing, mask_name, query_list_with_bindings,
+};
+use validator::Validate;
+
+pub fn weighted_total(tests: &[SessionResultsTestSchema]) -> f64 {
+	if tests.is_empty() {
+		return 0.0;
+	}
+	let total_weight: f64 = tests.iter().map(|t| t.weight as f64).sum();
+	let total = if total_weight > 0.0 {
+		tests
+			.iter()
+			.map(|t| match t.is_weighted {
+				true => t.score as f64,
+				false => t.weight as f64 * t.score as f64,
+			})
+			.sum::<f64>()
+			/ total_weight
+	} else {
+		tests.iter().map(|t| t.score as f64).sum::<f64>() / tests.len() as f64
+	};
+	(total * 100.0).round() / 100.0
+}
+
+fn score_selection(session: &SessionsSchema) -> Result<SessionsScoreSelectionEnum> {
+	session
+		.retake_policy
+		.clone()
+		.unwrap_or_default()
+		.score_selection
+		.parse()
+}
+
+pub const LEADERBOARD_DEFAULT_LIMIT: u32 = 10;
+pub const LEADERBOARD_MAX_LIMIT: u32 = 100;
+
+pub struct SessionResultsRepository<'a> {
+	state: &'a AppState,
+}
+
+impl<'a> SessionResultsRepository<'a> {
+	pub fn new(state: &'a AppState) -> Self {
+		Self { state }
+	}
+
+	pub async fn query_raw_result_by_user(
+		&self,
+		user_id: &str,
+		session_id: &str,
+	) -> Result<Option<SessionResultsSchema>> {
+		let db = &self.state.surrealdb_ws;
+		let results: Vec<SessionResultsSchema> = db
+			.query(format!(
+				"SELECT * FROM {} WHERE user = type::thing('{}', $user_id) AND session = type::thing('{}', $session_id) AND is_deleted = false LIMIT 1",
+				ResourceEnum::SessionResults,
+				ResourceEnum::Users,
+				ResourceEnum::Sessions
+			))
+			.bind(("user_id", user_id.to_string()))
+			.bind(("session_id", session_id.to_string()))
+			.await?
+			.take(0)?;
+		Ok(results.into_iter().next())
+	}
+
+	pub async fn query_result_by_id(&self, id: &str) -> Result<SessionResultsItemDto> {
+		let db = &self.state.surrealdb_ws;
+		let result: Option<SessionResultsSchema> = db
+			.select((ResourceEnum::SessionResults.to_string(), id))
+			.await?;
+		match result {
+			Some(r) if !r.is_deleted => Ok(SessionResultsItemDto::from(r)),
+			_ => bail!("Session result not found"),
+		}
+	}
+
+	pub async fn query_result_by_user(
+		&self,
+		user_id: &str,
+		session_id: &str,
+	) -> Result<SessionResultsItemDto> {
+		match self.query_raw_result_by_user(user_id, session_id).await? {
+			Some(r) => Ok(SessionResultsItemDto::from(r)),
+			None => bail!("Session result not found"),
+		}
+	}
+
+	pub async fn query_result_list(
+		&self,
+		session_id: &str,
+		meta: MetaRequestDto,
+	) -> Result<ResponseListSuccessDto<Vec<SessionResultsItemDto>>> {
+		let mut conditions = vec![
+			"is_deleted = false".to_string(),
+			format!(
+				"session = type::thing('{}', $session_id)",
+				ResourceEnum::Sessions
+			),
+		];
+		let mut bindings = vec![("session_id".to_string(), session_id.to_string())];
+		if let (Some(filter_by), Some(_filter)) = (&meta.filter_by, &meta.filter) {
+			conditions.push("type::field($filter_by) = $filter".to_string());
+			bindings.push(("filter_by".to_string(), filter_by.clone()));
+		}
+		let raw = query_list_with_bindings::<SessionResultsSchema>(
+			&self.state.surrealdb_ws,
+			&ResourceEnum::SessionResults.to_string(),
+			&meta,
+			conditions,
+			None,
+			bindings,
+		)
+		.await?;
+		Ok(ResponseListSuccessDto {
+			data: raw
+				.data
+				.into_iter()
+				.map(SessionResultsItemDto::from)
+				.collect(),
+			meta: raw.meta,
+		})
+	}
+
+	/// Scores every finished attempt of a test and keeps the one the retake
+	/// policy counts. Users without finished attempts fall back to their latest
+	/// answers.
+	async fn query_counted_attempt(
+		&self,
+		payload: &SessionResultsComputeRequestDto,
+		test_id: &str,
+		selection: SessionsScoreSelectionEnum,
+	) -> Result<TestsItemAnswersDto> {
+		let answers_repo = AnswersRepository::new(self.state);
+		let finished: Vec<_> = AttemptsRepository::new(self.state)
+			.query_attempt_history(&payload.user_id, &payload.session_id, test_id)
+			.await?
+			.into_iter()
+			.filter(|a| a.status != AttemptsStatusEnum::InProgress.to_string())
+			.collect();
+		if finished.is_empty() {
+			return answers_repo
+				.query_test_with_answers(&payload.session_id, test_id, &payload.user_id)
+				.await;
+		}
+		let mut scored = Vec::with_capacity(finished.len());
+		for attempt in finished {
+			match answers_repo
+				.query_test_with_attempt_answers(
+					&payload.session_id,
+					test_id,
+					&payload.user_id,
+					Some(attempt),
+				)
+				.await
+			{
+				Ok(data) => scored.push(data),
+				Err(e) if e.to_string() == "No answers found" => {}
+				Err(e) => return Err(e),
+			}
+		}
+		select_attempt_score(selection, scored)
+			.ok_or_else(|| anyhow::Error::msg("No answers found"))
+	}
+
+	pub async fn query_compute_result(
+		&self,
+		payload: SessionResultsComputeRequestDto,
+	) -> Result<SessionResultsItemDto> {
+		payload.validate()?;
+		let db = &self.state.surrealdb_ws;
+		let session = SessionsRepository::new(self.state)
+			.query_raw_session_by_id(&payload.session_id)
+			.await?;
+		let tests_repo = TestsRepository::new(self.state);
+		let selection = score_selection(&session)?;
+		let mut tests = Vec::with_capacity(session.tests.len());
+		for test in &session.tests {
+			let test_id = test.test.id.to_raw();
+			let scored = self
+				.query_counted_attempt(&payload, &test_id, selection)
+				.await;
+			let (name, score, theta, is_answered, pending_grading) = match scored {
+				Ok(data) => (
+					data.name,
+					data.score,
+					data.theta,
+					true,
+					data.pending_grading,
+				),
+				Err(e) if e.to_string() == "No answers found" => {
+					let name = tests_repo
+						.query_raw_test_by_id(&test_id)
+						.await
+						.map(|t| t.name)
+						.unwrap_or_default();
+					(name, 0, None, false, 0)
+				}
+				Err(e) => return Err(e),
+			};
+			let scoring = test
+				.scoring
+				.clone()
+				.unwrap_or_else(|| ScoringConfigSchema::infer(&session.category, &name));
+			tests.push(SessionResultsTestSchema {
+				test: test.test.clone(),
+				name,
+				score,
+				theta,
+				weight: test.weight,
+				multiplier: test.multiplier,
+				is_answered,
+				pending_grading,
+				is_weighted: SCORING_REGISTRY.applies_weight(&scoring.strategy),
+			});
+		}
+		let total = weighted_total(&tests);
+		let is_pending_grading = tests.iter().any(|t| t.pending_grading > 0);
+		let user: Option<SessionResultsUserSchema> = db
+			.query(format!(
+				"SELECT fullname, student_type FROM {}:⟨{}⟩",
+				ResourceEnum::Users,
+				payload.user_id
+			))
+			.await?
+			.take(0)?;
+		let user = user.unwrap_or(SessionResultsUserSchema {
+			fullname: None,
+			student_type: None,
+		});
+		let now = get_iso_date();
+		let existing = self
+			.query_raw_result_by_user(&payload.user_id, &payload.session_id)
+			.await?;
+		let (id, created_at, rank, student_type_rank, percentile) = match &existing {
+			Some(r) => (
+				r.id.id.to_raw(),
+				r.created_at.clone(),
+				r.rank,
+				r.student_type_rank,
+				r.percentile,
+			),
+			None => (
+				surrealdb::Uuid::new_v4().to_string(),
+				now.clone(),
+				None,
+				None,
+				None,
+			),
+		};
+		let result = SessionResultsSchema {
+			id: make_thing(&ResourceEnum::SessionResults.to_string(), &id),
+			user: make_thing(&ResourceEnum::Users.to_string(), &payload.user_id),
+			session: make_thing(&ResourceEnum::Sessions.to_string(), &payload.session_id),
+			fullname: user.fullname,
+			student_type: user.student_type,
+			tests,
+			weighted_total: total,
+			passing_grade: session.passing_grade,
+			passed: session
+				.passing_grade
+				.filter(|_| !is_pending_grading)
+				.map(|grade| total >= grade),
+			is_pending_grading,
+			rank,
+			student_type_rank,
+			percentile,
+			is_deleted: false,
+			created_at,
+			updated_at: now,
+		};
+		let record: Option<SessionResultsSchema> = match existing {
+			Some(_) => {
+				db.update((ResourceEnum::SessionResults.to_string(), &id))
+					.content(result)
+					.await?
+			}
+			None => {
+				db.create((ResourceEnum::SessionResults.to_string(), &id))
+					.content(result)
+					.await?
+			}
+		};
+		if record.is_none() {
+			bail!("Failed to save session result");
+		}
+		self.query_recompute_ranking(&payload.session_id).await?;
+		self.query_result_by_id(&id).await
+	}
+
+	pub async fn query_recompute_ranking(&self, session_id: &str) -> Result<usize> {
+		let db = &self.state.surrealdb_ws;
+		let rows: Vec<SessionResultsRankRowSchema> = db
+			.query(format!(
+				"SELECT id, weighted_total, student_type, rank, student_type_rank, percentile FROM {} WHERE session = type::thing('{}', $session_id) AND is_deleted = false",
+				ResourceEnum::SessionResults,
+				ResourceEnum::Sessions
+			))
+			.bind(("session_id", session_id.to_string()))
+			.await?
+			.take(0)?;
+		let entries: Vec<(f64, Option<String>)> = rows
+			.iter()
+			.map(|row| (row.weighted_total, row.student_type.clone()))
+			.collect();
+		let statements: Vec<String> = rows
+			.iter()
+			.zip(rank_results(&entries))
+			.filter(|(row, position)| {
+				row.rank != Some(position.rank)
+					|| row.student_type_rank != Some(position.student_type_rank)
+					|| row.percentile != Some(position.percentile)
+			})
+			.map(|(row, position)| {
+				format!(
+					"UPDATE {}:⟨{}⟩ SET rank = {}, student_type_rank = {}, percentile = {};",
+					ResourceEnum::SessionResults,
+					row.id.id.to_raw(),
+					position.rank,
+					position.student_type_rank,
+					position.percentile
+				)
+			})
+			.collect();
+		if !statements.is_empty() {
+			db.query(format!(
+				"BEGIN TRANSACTION; {} COMMIT TRANSACTION;",
+				statements.join(" ")
+			))
+			.await?
+			.check()?;
+		}
+		Ok(statements.len())
+	}
+
+	async fn query_count_participants(
+		&self,
+		session_id: &str,
+		student_type: Option<&Option<String>>,
+	) -> Result<u64> {
+		let db = &self.state.surrealdb_ws;
+		let mut sql = format!(
+			"SELECT count() AS count FROM {} WHERE session = type::thing('{}', $session_id) AND is_deleted = false",
+			ResourceEnum::SessionResults,
+			ResourceEnum::Sessions
+		);
+		if student_type.is_some() {
+			sql.push_str(" AND student_type = $student_type");
+		}
+		sql.push_str(" GROUP ALL");
+		let count: Vec<CountResult> = db
+			.query(sql)
+			.bind(("session_id", session_id.to_string()))
+			.bind(("student_type", student_type.cloned().flatten()))
+			.await?
+			.take(0)?;
+		Ok(count.first().map(|c| c.count).unwrap_or(0))
+	}
+
+	pub async fn query_leaderboard(
+		&self,
+		session_id: &str,
+		query: SessionResultsLeaderboardQueryDto,
+	) -> Result<SessionResultsLeaderboardDto> {
+		let db = &self.state.surrealdb_ws;
+		let limit = query
+			.limit
+			.unwrap_or(LEADERBOARD_DEFAULT_LIMIT)
+			.clamp(1, LEADERBOARD_MAX_LIMIT);
+		let mut sql = format!(
+			"SELECT * FROM {} WHERE session = type::thing('{}', $session_id) AND is_deleted = false",
+			ResourceEnum::SessionResults,
+			ResourceEnum::Sessions
+		);
+		if query.student_type.is_some() {
+			sql.push_str(" AND student_type = $student_type");
+		}
+		sql.push_str(" ORDER BY weighted_total DESC LIMIT $limit");
+		let results: Vec<SessionResultsSchema> = db
+			.query(sql)
+			.bind(("session_id", session_id.to_string()))
+			.bind(("student_type", query.student_type.clone()))
+			.bind(("limit", limit))
+			.await?
+			.take(0)?;
+		let total_participants = self
+			.query_count_participants(
+				session_id,
+				query.student_type.as_ref().map(|_| &query.student_type),
+			)
+			.await?;
+		let entries = results
+			.into_iter()
+			.map(|r| SessionResultsLeaderboardEntryDto {
+				rank: r.rank.unwrap_or_default(),
+				student_type_rank: r.student_type_rank.unwrap_or_default(),
+				name: mask_name(r.fullname.as_deref().unwrap_or_default()),
+				student_type: r.student_type,
+				weighted_total: r.weighted_total,
+				percentile: r.percentile.unwrap_or_default(),
+			})
+			.collect();
+		Ok(SessionResultsLeaderboardDto {
+			session_id: session_id.to_string(),
+			student_type: query.student_type,
+			total_participants,
+			entries,
+		})
+	}
+
+	pub async fn query_export_grid(
+		&self,
+		session_id: &str,
+	) -> Result<(String, SessionResultsGrid)> {
+		let db = &self.state.surrealdb_ws;
+		let session = SessionsRepository::new(self.state)
+			.query_raw_session_by_id(session_id)
+			.await?;
+		let tests_repo = TestsRepository::new(self.state);
+		let mut tests = Vec::with_capacity(session.tests.len());
+		for test in &session.tests {
+			let test_id = test.test.id.to_raw();
+			let name = tests_repo
+				.query_raw_test_by_id(&test_id)
+				.await
+				.map(|t| t.name)
+				.unwrap_or_default();
+			tests.push((test_id, name));
+		}
+		let results: Vec<SessionResultsSchema> = db
+			.query(format!(
+				"SELECT * FROM {} WHERE session = type::thing('{}', $session_id) AND is_deleted = false",
+				ResourceEnum::SessionResults,
+				ResourceEnum::Sessions
+			))
+			.bind(("session_id", session_id.to_string()))
+			.await?
+			.take(0)?;
+		Ok((session.name, results_grid(&tests, &results)))
+	}
+
+	pub async fn query_report(
+		&self,
+		session_id: &str,
+		user_id: &str,
+	) -> Result<SessionResultsReport> {
+		let session = SessionsRepository::new(self.state)
+			.query_raw_session_by_id(session_id)
+			.await?;
+		let Some(result) = self.query_raw_result_by_user(user_id, session_id).await?
+		else {
+			bail!("Session result not found");
+		};
+		let selection = score_selection(&session)?;
+		let payload = SessionResultsComputeRequestDto {
+			user_id: user_id.to_string(),
+			session_id: session_id.to_string(),
+		};
+		let mut tests = Vec::with_capacity(session.tests.len());
+		for test in &session.tests {
+			match self
+				.query_counted_attempt(&payload, &test.test.id.to_raw(), selection)
+				.await
+			{
+				Ok(data) => tests.push(data),
+				Err(e) if e.to_string() == "No answers found" => {}
+				Err(e) => return Err(e),
+			}
+		}
+		let total_participants = self.query_count_participants(session_id, None).await?;
+		Ok(SessionResultsReport {
+			session_name: session.name,
+			result,
+			total_participants,
+			tests,
+		})
+	}
+
+	pub async fn query_rank_by_user(
+		&self,
+		user_id: &str,
+		session_id: &str,
+	) -> Result<SessionResultsRankDto> {
+		let Some(result) = self.query_raw_result_by_user(user_id, session_id).await?
+		else {
+			bail!("Session result not found");
+		};
+		let total_participants = self.query_count_participants(session_id, None).await?;
+		let student_type_participants = self
+			.query_count_participants(session_id, Some(&result.student_type))
+			.await?;
+		Ok(SessionResultsRankDto {
+			user_id: user_id.to_string(),
+			session_id: session_id.to_string(),
+			weighted_total: result.weighted_total,
+			rank: result.rank.unwrap_or_default(),
+			total_participants,
+			student_type: result.student_type,
+			student_type_rank: result.student_type_rank.unwrap_or_default(),
+			student_type_participants,
+			percentile: result.percentile.unwrap_or_default(),
+		})
+	}
+}
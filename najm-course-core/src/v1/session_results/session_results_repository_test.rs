@@ -0,0 +1,318 @@
+use super::*;
+use crate::{create_mock_app_state, MetaRequestDto};
+use anyhow::Result;
+use najm_course_entities::SurrealWsClient;
+use najm_course_utils::{get_iso_date, make_thing};
+use surrealdb::Uuid;
+
+pub struct SessionResultFixture {
+	pub user_id: String,
+	pub session_id: String,
+	pub answered_test_id: String,
+	pub skipped_test_id: String,
+	pub question_id: String,
+	pub high_option_id: String,
+	pub low_option_id: String,
+}
+
+pub async fn seed_session_result_dependencies(
+	db: &SurrealWsClient,
+	passing_grade: Option<f64>,
+) -> Result<SessionResultFixture> {
+	let now = get_iso_date();
+	let fixture = SessionResultFixture {
+		user_id: Uuid::new_v4().to_string(),
+		session_id: Uuid::new_v4().to_string(),
+		answered_test_id: Uuid::new_v4().to_string(),
+		skipped_test_id: Uuid::new_v4().to_string(),
+		question_id: Uuid::new_v4().to_string(),
+		high_option_id: Uuid::new_v4().to_string(),
+		low_option_id: Uuid::new_v4().to_string(),
+	};
+	let question_id = &fixture.question_id;
+	let option_id = &fixture.high_option_id;
+	let low_option_id = &fixture.low_option_id;
+	let passing_grade = passing_grade
+		.map(|grade| grade.to_string())
+		.unwrap_or("NONE".into());
+	db.query(format!(
+		"CREATE app_options:⟨{option_id}⟩ SET label = 'Option A', is_correct = true, points = 80, is_deleted = false, created_at = '{now}', updated_at = '{now}';
+		CREATE app_options:⟨{low_option_id}⟩ SET label = 'Option B', is_correct = false, points = 40, is_deleted = false, created_at = '{now}', updated_at = '{now}';
+		CREATE app_questions:⟨{question_id}⟩ SET question = 'Question', discussion = 'Discussion', options = [app_options:⟨{option_id}⟩, app_options:⟨{low_option_id}⟩], is_deleted = false, created_at = '{now}', updated_at = '{now}';
+		CREATE app_tests:⟨{}⟩ SET name = 'Answered Test', questions = [app_questions:⟨{question_id}⟩], is_deleted = false, created_at = '{now}', updated_at = '{now}';
+		CREATE app_tests:⟨{}⟩ SET name = 'Skipped Test', questions = [], is_deleted = false, created_at = '{now}', updated_at = '{now}';
+		CREATE app_sessions:⟨{}⟩ SET name = 'Result Session', category = 'Akademik', description = 'Result', student_type = 'SMA', passing_grade = {passing_grade}, tests = [{{ test: app_tests:⟨{}⟩, shuffle: false, weight: 1.0, multiplier: 1.0, start_date: '2025-01-01T00:00:00Z', end_date: '2099-01-01T00:00:00Z', scoring: {{ strategy: 'points_sum' }} }}, {{ test: app_tests:⟨{}⟩, shuffle: false, weight: 3.0, multiplier: 1.0, start_date: '2025-01-01T00:00:00Z', end_date: '2099-01-01T00:00:00Z', scoring: {{ strategy: 'points_sum' }} }}], is_active = true, is_deleted = false, created_at = '{now}', updated_at = '{now}';
+		CREATE app_answers SET user = app_users:⟨{}⟩, test = app_tests:⟨{}⟩, session = app_sessions:⟨{}⟩, question = app_questions:⟨{question_id}⟩, option = app_options:⟨{option_id}⟩, is_correct = true, is_deleted = false, created_at = '{now}', updated_at = '{now}';",
+		fixture.answered_test_id,
+		fixture.skipped_test_id,
+		fixture.session_id,
+		fixture.answered_test_id,
+		fixture.skipped_test_id,
+		fixture.user_id,
+		fixture.answered_test_id,
+		fixture.session_id,
+	))
+	.await?;
+	Ok(fixture)
+}
+
+pub async fn seed_participant(
+	db: &SurrealWsClient,
+	fixture: &SessionResultFixture,
+	fullname: &str,
+	student_type: &str,
+	option_id: &str,
+) -> Result<String> {
+	let now = get_iso_date();
+	let user_id = Uuid::new_v4().to_string();
+	db.query(format!(
+		"CREATE app_users:⟨{user_id}⟩ SET fullname = '{fullname}', student_type = '{student_type}', is_deleted = false, created_at = '{now}', updated_at = '{now}';
+		CREATE app_answers SET user = app_users:⟨{user_id}⟩, test = app_tests:⟨{}⟩, session = app_sessions:⟨{}⟩, question = app_questions:⟨{}⟩, option = app_options:⟨{option_id}⟩, is_correct = true, is_deleted = false, created_at = '{now}', updated_at = '{now}';",
+		fixture.answered_test_id, fixture.session_id, fixture.question_id,
+	))
+	.await?;
+	Ok(user_id)
+}
+
+fn build_compute_payload(
+	fixture: &SessionResultFixture,
+) -> SessionResultsComputeRequestDto {
+	SessionResultsComputeRequestDto {
+		user_id: fixture.user_id.clone(),
+		session_id: fixture.session_id.clone(),
+	}
+}
+
+fn build_test_result(score: i32, weight: f32) -> SessionResultsTestSchema {
+	SessionResultsTestSchema {
+		test: make_thing("app_tests", "test"),
+		name: "Test".into(),
+		score,
+		theta: None,
+		weight,
+		multiplier: 1.0,
+		is_answered: true,
+		pending_grading: 0,
+		is_weighted: false,
+	}
+}
+
+#[test]
+fn test_weighted_total_should_use_test_weights() {
+	let tests = vec![build_test_result(80, 1.0), build_test_result(40, 3.0)];
+	assert_eq!(weighted_total(&tests), 50.0);
+}
+
+#[test]
+fn test_weighted_total_should_average_when_weights_are_zero() {
+	let tests = vec![build_test_result(80, 0.0), build_test_result(41, 0.0)];
+	assert_eq!(weighted_total(&tests), 60.5);
+	assert_eq!(weighted_total(&[]), 0.0);
+}
+
+#[test]
+fn test_weighted_total_should_not_reweight_weighted_scores() {
+	let mut tests = vec![build_test_result(80, 1.0), build_test_result(120, 3.0)];
+	tests[1].is_weighted = true;
+	assert_eq!(weighted_total(&tests), 50.0);
+}
+
+#[tokio::test]
+async fn test_query_compute_result_should_aggregate_and_persist() {
+	let state = create_mock_app_state().await;
+	let fixture = seed_session_result_dependencies(&state.surrealdb_ws, Some(15.0))
+		.await
+		.unwrap();
+	let repo = SessionResultsRepository::new(&state);
+	let result = repo
+		.query_compute_result(build_compute_payload(&fixture))
+		.await
+		.unwrap();
+	assert_eq!(result.tests.len(), 2);
+	assert_eq!(result.tests[0].score, 80);
+	assert!(result.tests[0].is_answered);
+	assert_eq!(result.tests[1].name, "Skipped Test");
+	assert!(!result.tests[1].is_answered);
+	assert_eq!(result.weighted_total, 20.0);
+	assert_eq!(result.passed, Some(true));
+	let stored = repo
+		.query_result_by_user(&fixture.user_id, &fixture.session_id)
+		.await
+		.unwrap();
+	assert_eq!(stored.id, result.id);
+}
+
+#[tokio::test]
+async fn test_query_compute_result_twice_should_update_same_record() {
+	let state = create_mock_app_state().await;
+	let fixture = seed_session_result_dependencies(&state.surrealdb_ws, Some(25.0))
+		.await
+		.unwrap();
+	let repo = SessionResultsRepository::new(&state);
+	let first = repo
+		.query_compute_result(build_compute_payload(&fixture))
+		.await
+		.unwrap();
+	let second = repo
+		.query_compute_result(build_compute_payload(&fixture))
+		.await
+		.unwrap();
+	assert_eq!(first.id, second.id);
+	assert_eq!(second.passed, Some(false));
+	let list = repo
+		.query_result_list(&fixture.session_id, Default::default())
+		.await
+		.unwrap();
+	assert_eq!(list.data.len(), 1);
+}
+
+#[tokio::test]
+async fn test_query_result_list_should_bind_filter_field() {
+	let state = create_mock_app_state().await;
+	let fixture = seed_session_result_dependencies(&state.surrealdb_ws, Some(25.0))
+		.await
+		.unwrap();
+	let repo = SessionResultsRepository::new(&state);
+	repo
+		.query_compute_result(build_compute_payload(&fixture))
+		.await
+		.unwrap();
+	let filtered = |filter_by: &str, filter: &str| MetaRequestDto {
+		filter_by: Some(filter_by.into()),
+		filter: Some(filter.into()),
+		..Default::default()
+	};
+	let failed = repo
+		.query_result_list(&fixture.session_id, filtered("passed", "false"))
+		.await
+		.unwrap();
+	assert_eq!(failed.data.len(), 1);
+	let passed = repo
+		.query_result_list(&fixture.session_id, filtered("passed", "true"))
+		.await
+		.unwrap();
+	assert!(passed.data.is_empty());
+	let injected = repo
+		.query_result_list(
+			&fixture.session_id,
+			filtered("passed = true OR true OR passed", "true"),
+		)
+		.await;
+	assert!(injected.is_err());
+}
+
+#[tokio::test]
+async fn test_query_compute_result_without_passing_grade_should_leave_passed_empty()
+{
+	let state = create_mock_app_state().await;
+	let fixture = seed_session_result_dependencies(&state.surrealdb_ws, None)
+		.await
+		.unwrap();
+	let result = SessionResultsRepository::new(&state)
+		.query_compute_result(build_compute_payload(&fixture))
+		.await
+		.unwrap();
+	assert_eq!(result.passing_grade, None);
+	assert_eq!(result.passed, None);
+}
+
+#[tokio::test]
+async fn test_query_result_by_user_should_fail_if_not_computed() {
+	let state = create_mock_app_state().await;
+	let result = SessionResultsRepository::new(&state)
+		.query_result_by_user("missing_user", "missing_session")
+		.await;
+	assert_eq!(result.unwrap_err().to_string(), "Session result not found");
+}
+
+#[tokio::test]
+async fn test_query_leaderboard_should_rank_with_ties_and_mask_names() {
+	let state = create_mock_app_state().await;
+	let db = &state.surrealdb_ws;
+	let fixture = seed_session_result_dependencies(db, None).await.unwrap();
+	let repo = SessionResultsRepository::new(&state);
+	let participants = [
+		("Budi Santoso", "SMA", fixture.high_option_id.clone()),
+		("Siti Aminah", "SMA", fixture.high_option_id.clone()),
+		("Andi Wijaya", "Alumni", fixture.low_option_id.clone()),
+	];
+	let mut user_ids = vec![];
+	for (fullname, student_type, option_id) in &participants {
+		let user_id = seed_participant(db, &fixture, fullname, student_type, option_id)
+			.await
+			.unwrap();
+		repo
+			.query_compute_result(SessionResultsComputeRequestDto {
+				user_id: user_id.clone(),
+				session_id: fixture.session_id.clone(),
+			})
+			.await
+			.unwrap();
+		user_ids.push(user_id);
+	}
+	let leaderboard = repo
+		.query_leaderboard(
+			&fixture.session_id,
+			SessionResultsLeaderboardQueryDto {
+				limit: Some(2),
+				student_type: None,
+			},
+		)
+		.await
+		.unwrap();
+	assert_eq!(leaderboard.total_participants, 3);
+	assert_eq!(leaderboard.entries.len(), 2);
+	assert!(leaderboard.entries.iter().all(|e| e.rank == 1));
+	assert!(leaderboard.entries.iter().any(|e| e.name == "B*** S******"));
+	let low = repo
+		.query_rank_by_user(&user_ids[2], &fixture.session_id)
+		.await
+		.unwrap();
+	assert_eq!(low.rank, 3);
+	assert_eq!(low.student_type_rank, 1);
+	assert_eq!(low.student_type_participants, 1);
+	assert_eq!(low.percentile, 16.67);
+}
+
+#[tokio::test]
+async fn test_query_compute_result_should_rerank_on_late_submission() {
+	let state = create_mock_app_state().await;
+	let db = &state.surrealdb_ws;
+	let fixture = seed_session_result_dependencies(db, None).await.unwrap();
+	let repo = SessionResultsRepository::new(&state);
+	let early =
+		seed_participant(db, &fixture, "Early Bird", "SMA", &fixture.low_option_id)
+			.await
+			.unwrap();
+	let first = repo
+		.query_compute_result(SessionResultsComputeRequestDto {
+			user_id: early.clone(),
+			session_id: fixture.session_id.clone(),
+		})
+		.await
+		.unwrap();
+	assert_eq!(first.rank, Some(1));
+	let late =
+		seed_participant(db, &fixture, "Late Comer", "SMA", &fixture.high_option_id)
+			.await
+			.unwrap();
+	let second = repo
+		.query_compute_result(SessionResultsComputeRequestDto {
+			user_id: late,
+			session_id: fixture.session_id.clone(),
+		})
+		.await
+		.unwrap();
+	assert_eq!(second.rank, Some(1));
+	let early_rank = repo
+		.query_rank_by_user(&early, &fixture.session_id)
+		.await
+		.unwrap();
+	assert_eq!(early_rank.rank, 2);
+	assert_eq!(early_rank.total_participants, 2);
+	let unchanged = repo
+		.query_recompute_ranking(&fixture.session_id)
+		.await
+		.unwrap();
+	assert_eq!(unchanged, 0);
+}
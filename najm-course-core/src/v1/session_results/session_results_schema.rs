@@ -0,0 +1,54 @@
+use serde::{Deserialize, Serialize};
+use surrealdb::sql::Thing;
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct SessionResultsTestSchema {
+	pub test: Thing,
+	pub name: String,
+	pub score: i32,
+	pub theta: Option<f64>,
+	pub weight: f32,
+	pub multiplier: f32,
+	pub is_answered: bool,
+	#[serde(default)]
+	pub pending_grading: u32,
+	#[serde(default)]
+	pub is_weighted: bool,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct SessionResultsSchema {
+	pub id: Thing,
+	pub user: Thing,
+	pub session: Thing,
+	pub fullname: Option<String>,
+	pub student_type: Option<String>,
+	pub tests: Vec<SessionResultsTestSchema>,
+	pub weighted_total: f64,
+	pub passing_grade: Option<f64>,
+	pub passed: Option<bool>,
+	#[serde(default)]
+	pub is_pending_grading: bool,
+	pub rank: Option<u32>,
+	pub student_type_rank: Option<u32>,
+	pub percentile: Option<f64>,
+	pub is_deleted: bool,
+	pub created_at: String,
+	pub updated_at: String,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct SessionResultsUserSchema {
+	pub fullname: Option<String>,
+	pub student_type: Option<String>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct SessionResultsRankRowSchema {
+	pub id: Thing,
+	pub weighted_total: f64,
+	pub student_type: Option<String>,
+	pub rank: Option<u32>,
+	pub student_type_rank: Option<u32>,
+	pub percentile: Option<f64>,
+}
@@ -0,0 +1,256 @@
+use super::{
+	write_report_pdf, write_results_csv, write_results_xlsx,
+	SessionResultsComputeRequestDto, SessionResultsExportFormatEnum,
+	SessionResultsExportQueryDto, SessionResultsLeaderboardQueryDto,
+	SessionResultsRepository,
+};
+use crate::{
+	attachment_response, common_response, slugify, success_list_response,
+	success_response, validate_request, AppState, MetaRequestDto, ResponseSuccessDto,
+	SessionsRepository, SessionsViewerDto,
+};
+use axum::{http::StatusCode, response::Response};
+
+pub struct SessionResultsService;
+
+fn session_result_error_status(msg: &str) -> StatusCode {
+	match msg {
+		"Session result not found" | "Session not found" => StatusCode::NOT_FOUND,
+		"Test not found in session" | "Invalid date format" | "Unsupported format" => {
+			StatusCode::BAD_REQUEST
+		}
+		_ => StatusCode::INTERNAL_SERVER_ERROR,
+	}
+}
+
+/// Students only see scores once the session visibility policy releases them.
+async fn ensure_released(
+	state: &AppState,
+	viewer: &SessionsViewerDto,
+	session_id: &str,
+) -> Result<(), Response> {
+	if viewer.is_reviewer {
+		return Ok(());
+	}
+	match SessionsRepository::new(state)
+		.query_is_revealed(session_id, true)
+		.await
+	{
+		Ok(true) => Ok(()),
+		Ok(false) => Err(common_response(
+			StatusCode::FORBIDDEN,
+			"Scores are not released yet",
+		)),
+		Err(e) => {
+			let msg = e.to_string();
+			Err(common_response(session_result_error_status(&msg), &msg))
+		}
+	}
+}
+
+impl SessionResultsService {
+	pub async fn get_result_by_id(
+		state: &AppState,
+		id: String,
+		viewer: SessionsViewerDto,
+	) -> Response {
+		let repo = SessionResultsRepository::new(state);
+		match repo.query_result_by_id(&id).await {
+			Ok(data) if !viewer.can_view(&data.user_id) => {
+				common_response(StatusCode::FORBIDDEN, "Result belongs to another student")
+			}
+			Ok(data) => match ensure_released(state, &viewer, &data.session_id).await {
+				Ok(()) => success_response(ResponseSuccessDto { data }),
+				Err(response) => response,
+			},
+			Err(e) => {
+				let msg = e.to_string();
+				common_response(session_result_error_status(&msg), &msg)
+			}
+		}
+	}
+
+	pub async fn get_result_by_user(
+		state: &AppState,
+		session_id: String,
+		user_id: String,
+		viewer: SessionsViewerDto,
+	) -> Response {
+		if !viewer.can_view(&user_id) {
+			return common_response(
+				StatusCode::FORBIDDEN,
+				"Result belongs to another student",
+			);
+		}
+		if let Err(response) = ensure_released(state, &viewer, &session_id).await {
+			return response;
+		}
+		let repo = SessionResultsRepository::new(state);
+		match repo.query_result_by_user(&user_id, &session_id).await {
+			Ok(data) => success_response(ResponseSuccessDto { data }),
+			Err(e) => {
+				let msg = e.to_string();
+				common_response(session_result_error_status(&msg), &msg)
+			}
+		}
+	}
+
+	pub async fn get_result_list(
+		state: &AppState,
+		session_id: String,
+		meta: MetaRequestDto,
+		viewer: SessionsViewerDto,
+	) -> Response {
+		if let Err(response) = ensure_released(state, &viewer, &session_id).await {
+			return response;
+		}
+		let repo = SessionResultsRepository::new(state);
+		match repo.query_result_list(&session_id, meta).await {
+			Ok(data) => success_list_response(data),
+			Err(e) => common_response(StatusCode::BAD_REQUEST, &e.to_string()),
+		}
+	}
+
+	pub async fn compute_result(
+		state: &AppState,
+		mut payload: SessionResultsComputeRequestDto,
+		viewer: SessionsViewerDto,
+	) -> Response {
+		if let (false, Some(user_id)) = (viewer.is_reviewer, viewer.user_id) {
+			payload.user_id = user_id;
+		}
+		if let Err((status, message)) = validate_request(&payload) {
+			return common_response(status, &message);
+		}
+		let repo = SessionResultsRepository::new(state);
+		match repo.query_compute_result(payload).await {
+			Ok(data) => success_response(ResponseSuccessDto { data }),
+			Err(e) => {
+				let msg = e.to_string();
+				common_response(session_result_error_status(&msg), &msg)
+			}
+		}
+	}
+
+	pub async fn get_leaderboard(
+		state: &AppState,
+		session_id: String,
+		query: SessionResultsLeaderboardQueryDto,
+		viewer: SessionsViewerDto,
+	) -> Response {
+		if let Err(response) = ensure_released(state, &viewer, &session_id).await {
+			return response;
+		}
+		let repo = SessionResultsRepository::new(state);
+		match repo.query_leaderboard(&session_id, query).await {
+			Ok(data) => success_response(ResponseSuccessDto { data }),
+			Err(e) => {
+				let msg = e.to_string();
+				common_response(session_result_error_status(&msg), &msg)
+			}
+		}
+	}
+
+	pub async fn get_rank_by_user(
+		state: &AppState,
+		session_id: String,
+		user_id: String,
+		viewer: SessionsViewerDto,
+	) -> Response {
+		if !viewer.can_view(&user_id) {
+			return common_response(
+				StatusCode::FORBIDDEN,
+				"Result belongs to another student",
+			);
+		}
+		if let Err(response) = ensure_released(state, &viewer, &session_id).await {
+			return response;
+		}
+		let repo = SessionResultsRepository::new(state);
+		match repo.query_rank_by_user(&user_id, &session_id).await {
+			Ok(data) => success_response(ResponseSuccessDto { data }),
+			Err(e) => {
+				let msg = e.to_string();
+				common_response(session_result_error_status(&msg), &msg)
+			}
+		}
+	}
+
+	pub async fn export_results(
+		state: &AppState,
+		session_id: String,
+		query: SessionResultsExportQueryDto,
+	) -> Response {
+		let format = match query
+			.format
+			.as_deref()
+			.unwrap_or("csv")
+			.parse::<SessionResultsExportFormatEnum>()
+		{
+			Ok(format) => format,
+			Err(e) => return common_response(StatusCode::BAD_REQUEST, &e.to_string()),
+		};
+		let repo = SessionResultsRepository::new(state);
+		let (name, grid) = match repo.query_export_grid(&session_id).await {
+			Ok(data) => data,
+			Err(e) => {
+				let msg = e.to_string();
+				return common_response(session_result_error_status(&msg), &msg);
+			}
+		};
+		let bytes = match format {
+			SessionResultsExportFormatEnum::Csv => write_results_csv(&grid),
+			SessionResultsExportFormatEnum::Xlsx => write_results_xlsx(&grid),
+		};
+		match bytes {
+			Ok(bytes) => attachment_response(
+				format.content_type(),
+				&format!(
+					"{}-results.{}",
+					slugify(&name, "session"),
+					format.file_extension()
+				),
+				bytes,
+			),
+			Err(e) => common_response(StatusCode::INTERNAL_SERVER_ERROR, &e.to_string()),
+		}
+	}
+
+	/// Students may only download their own report, once scores are released.
+	pub async fn get_report(
+		state: &AppState,
+		session_id: String,
+		user_id: String,
+		viewer: SessionsViewerDto,
+	) -> Response {
+		if !viewer.can_view(&user_id) {
+			return common_response(
+				StatusCode::FORBIDDEN,
+				"Report belongs to another student",
+			);
+		}
+		if let Err(response) = ensure_released(state, &viewer, &session_id).await {
+			return response;
+		}
+		let repo = SessionResultsRepository::new(state);
+		let report = match repo.query_report(&session_id, &user_id).await {
+			Ok(data) => data,
+			Err(e) => {
+				let msg = e.to_string();
+				return common_response(session_result_error_status(&msg), &msg);
+			}
+		};
+		let file_name = format!(
+			"{}-{}.pdf",
+			slugify(&report.session_name, "session"),
+			slugify(
+				report.result.fullname.as_deref().unwrap_or_default(),
+				"report"
+			)
+		);
+		match write_report_pdf(&report) {
+			Ok(bytes) => attachment_response("application/pdf", &file_name, bytes),
+			Err(e) => common_response(StatusCode::INTERNAL_SERVER_ERROR, &e.to_string()),
+		}
+	}
+}
@@ -3,22 +3,36 @@ use axum::{
 	Router,
 };
 
+pub mod sessions_bundle;
 pub mod sessions_controller;
 pub mod sessions_dto;
+pub mod sessions_enum;
 pub mod sessions_repository;
 pub mod sessions_schema;
+pub mod sessions_sequence;
 pub mod sessions_service;
+pub mod sessions_visibility;
 
+#[cfg(test)]
+pub mod sessions_bundle_test;
 #[cfg(test)]
 pub mod sessions_controller_test;
 #[cfg(test)]
 pub mod sessions_repository_test;
+#[cfg(test)]
+pub mod sessions_sequence_test;
+#[cfg(test)]
+pub mod sessions_visibility_test;
 
+pub use sessions_bundle::*;
 pub use sessions_controller::*;
 pub use sessions_dto::*;
+pub use sessions_enum::*;
 pub use sessions_repository::*;
 pub use sessions_schema::*;
+pub use sessions_sequence::*;
 pub use sessions_service::*;
+pub use sessions_visibility::*;
 
 pub fn sessions_router() -> Router {
 	Router::new()
@@ -27,4 +41,7 @@ pub fn sessions_router() -> Router {
 		.route("/detail/{id}", get(get_session_by_id))
 		.route("/update/{id}", put(put_update_session))
 		.route("/delete/{id}", delete(delete_session))
+		.route("/broadcast/{id}", post(post_broadcast_session))
+		.route("/export/{id}", get(get_export_session))
+		.route("/import", post(post_import_session))
 }
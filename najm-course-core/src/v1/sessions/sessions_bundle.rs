@@ -0,0 +1,198 @@
+use super::{
+	SessionsBundleConflictDto, SessionsBundleIdMapDto, SessionsBundleSchema,
+	SessionsSchema,
+};
+use crate::{OptionsSchema, QuestionsSchema, TestsSchema};
+use najm_course_utils::{get_iso_date, make_thing};
+use std::collections::{BTreeSet, HashMap, HashSet};
+use surrealdb::{sql::Thing, Uuid};
+
+pub const SESSIONS_BUNDLE_VERSION: u32 = 1;
+pub const SESSIONS_BUNDLE_RENAME_SUFFIX: &str = " (imported)";
+
+/// Image URLs the bundle's questions and options point at. They are not
+/// copied; the target has to reach the same storage.
+pub fn bundle_media(
+	questions: &[QuestionsSchema],
+	options: &[OptionsSchema],
+) -> Vec<String> {
+	questions
+		.iter()
+		.flat_map(|q| [&q.question_image_url, &q.discussion_image_url])
+		.chain(options.iter().map(|o| &o.image_url))
+		.flatten()
+		.filter(|url| !url.trim().is_empty())
+		.cloned()
+		.collect::<BTreeSet<_>>()
+		.into_iter()
+		.collect()
+}
+
+/// Bundles a session with the records it references. Deleted questions and
+/// options are left out, so references are trimmed to what is exported.
+pub fn build_bundle(
+	session: SessionsSchema,
+	mut tests: Vec<TestsSchema>,
+	mut questions: Vec<QuestionsSchema>,
+	options: Vec<OptionsSchema>,
+) -> SessionsBundleSchema {
+	let option_ids: HashSet<String> =
+		options.iter().map(|o| o.id.to_string()).collect();
+	for question in questions.iter_mut() {
+		question
+			.options
+			.retain(|id| option_ids.contains(&id.to_string()));
+	}
+	let question_ids: HashSet<String> =
+		questions.iter().map(|q| q.id.to_string()).collect();
+	for test in tests.iter_mut() {
+		test
+			.questions
+			.retain(|id| question_ids.contains(&id.to_string()));
+	}
+	SessionsBundleSchema {
+		version: SESSIONS_BUNDLE_VERSION,
+		exported_at: get_iso_date(),
+		media: bundle_media(&questions, &options),
+		session,
+		tests,
+		questions,
+		options,
+	}
+}
+
+pub fn bundle_errors(bundle: &SessionsBundleSchema) -> Vec<String> {
+	let mut errors = vec![];
+	let test_ids: HashSet<String> =
+		bundle.tests.iter().map(|t| t.id.to_string()).collect();
+	let question_ids: HashSet<String> =
+		bundle.questions.iter().map(|q| q.id.to_string()).collect();
+	let option_ids: HashSet<String> =
+		bundle.options.iter().map(|o| o.id.to_string()).collect();
+	let session_tests: HashSet<String> = bundle
+		.session
+		.tests
+		.iter()
+		.map(|t| t.test.to_string())
+		.collect();
+	for test_session in &bundle.session.tests {
+		if !test_ids.contains(&test_session.test.to_string()) {
+			errors.push(format!(
+				"Test {} is missing from the bundle",
+				test_session.test.id.to_raw()
+			));
+		}
+		if let Some(prerequisite) = &test_session.prerequisite {
+			if !session_tests.contains(&prerequisite.test.to_string()) {
+				errors.push(format!(
+					"Prerequisite test {} is not part of the session",
+					prerequisite.test.id.to_raw()
+				));
+			}
+		}
+	}
+	for test in &bundle.tests {
+		for id in test
+			.questions
+			.iter()
+			.filter(|id| !question_ids.contains(&id.to_string()))
+		{
+			errors.push(format!(
+				"Question {} is missing from the bundle",
+				id.id.to_raw()
+			));
+		}
+	}
+	for question in &bundle.questions {
+		for id in question
+			.options
+			.iter()
+			.filter(|id| !option_ids.contains(&id.to_string()))
+		{
+			errors.push(format!(
+				"Option {} is missing from the bundle",
+				id.id.to_raw()
+			));
+		}
+	}
+	errors
+}
+
+pub fn rename_conflicts(
+	bundle: &mut SessionsBundleSchema,
+	conflicts: &[SessionsBundleConflictDto],
+) {
+	let renamed: HashSet<&str> =
+		conflicts.iter().map(|c| c.source_id.as_str()).collect();
+	if renamed.contains(bundle.session.id.id.to_raw().as_str()) {
+		bundle.session.name.push_str(SESSIONS_BUNDLE_RENAME_SUFFIX);
+	}
+	for test in bundle.tests.iter_mut() {
+		if renamed.contains(test.id.id.to_raw().as_str()) {
+			test.name.push_str(SESSIONS_BUNDLE_RENAME_SUFFIX);
+		}
+	}
+}
+
+pub fn remap_bundle(
+	bundle: &SessionsBundleSchema,
+) -> (SessionsBundleSchema, Vec<SessionsBundleIdMapDto>) {
+	let mut ids: HashMap<String, Thing> = HashMap::new();
+	let mut id_map = vec![];
+	let records = std::iter::once(&bundle.session.id)
+		.chain(bundle.tests.iter().map(|t| &t.id))
+		.chain(bundle.questions.iter().map(|q| &q.id))
+		.chain(bundle.options.iter().map(|o| &o.id));
+	for id in records {
+		if ids.contains_key(&id.to_string()) {
+			continue;
+		}
+		let target = make_thing(&id.tb, &Uuid::new_v4().to_string());
+		id_map.push(SessionsBundleIdMapDto {
+			resource: id.tb.clone(),
+			source_id: id.id.to_raw(),
+			target_id: target.id.to_raw(),
+		});
+		ids.insert(id.to_string(), target);
+	}
+	let remap = |id: &Thing| {
+		ids
+			.get(&id.to_string())
+			.cloned()
+			.unwrap_or_else(|| id.clone())
+	};
+	let now = get_iso_date();
+
+	let mut remapped = bundle.clone();
+	remapped.session.id = remap(&bundle.session.id);
+	remapped.session.is_deleted = false;
+	remapped.session.created_at = now.clone();
+	remapped.session.updated_at = now.clone();
+	for test_session in remapped.session.tests.iter_mut() {
+		test_session.test = remap(&test_session.test);
+		if let Some(prerequisite) = test_session.prerequisite.as_mut() {
+			prerequisite.test = remap(&prerequisite.test);
+		}
+	}
+	for test in remapped.tests.iter_mut() {
+		test.id = remap(&test.id);
+		test.questions = test.questions.iter().map(remap).collect();
+		test.is_deleted = false;
+		test.created_at = now.clone();
+		test.updated_at = now.clone();
+	}
+	for question in remapped.questions.iter_mut() {
+		question.id = remap(&question.id);
+		question.options = question.options.iter().map(remap).collect();
+		question.is_deleted = false;
+		question.created_at = now.clone();
+		question.updated_at = now.clone();
+	}
+	for option in remapped.options.iter_mut() {
+		option.id = remap(&option.id);
+		option.is_deleted = false;
+		option.created_at = now.clone();
+		option.updated_at = now.clone();
+	}
+	(remapped, id_map)
+}
@@ -0,0 +1,231 @@
+use super::*;
+use crate::{OptionsSchema, QuestionsSchema, TestsSchema};
+use najm_course_utils::make_thing;
+use surrealdb::sql::Thing;
+
+fn option(id: &str, image_url: Option<&str>) -> OptionsSchema {
+	OptionsSchema {
+		id: make_thing("app_options", id),
+		label: Some(id.into()),
+		points: None,
+		image_url: image_url.map(String::from),
+		is_correct: id.ends_with('a'),
+		is_deleted: false,
+		created_at: "2025-01-01T00:00:00Z".into(),
+		updated_at: "2025-01-01T00:00:00Z".into(),
+	}
+}
+
+fn question(id: &str, options: &[&str], image_url: Option<&str>) -> QuestionsSchema {
+	QuestionsSchema {
+		id: make_thing("app_questions", id),
+		question: Some(format!("Question {}", id)),
+		discussion: None,
+		question_image_url: image_url.map(String::from),
+		discussion_image_url: None,
+		options: options
+			.iter()
+			.map(|o| make_thing("app_options", o))
+			.collect(),
+		question_type: None,
+		answer_key: None,
+		tags: None,
+		irt: None,
+		is_deleted: false,
+		created_at: "2025-01-01T00:00:00Z".into(),
+		updated_at: "2025-01-01T00:00:00Z".into(),
+	}
+}
+
+fn test(id: &str, questions: &[&str]) -> TestsSchema {
+	TestsSchema {
+		id: make_thing("app_tests", id),
+		name: format!("Test {}", id),
+		questions: questions
+			.iter()
+			.map(|q| make_thing("app_questions", q))
+			.collect(),
+		is_deleted: false,
+		created_at: "2025-01-01T00:00:00Z".into(),
+		updated_at: "2025-01-01T00:00:00Z".into(),
+	}
+}
+
+fn test_session(test_id: &str, prerequisite: Option<&str>) -> TestSessionsSchema {
+	TestSessionsSchema {
+		test: make_thing("app_tests", test_id),
+		shuffle: false,
+		shuffle_options: None,
+		weight: 1.0,
+		multiplier: 1.0,
+		start_date: "2025-01-01T00:00:00Z".into(),
+		end_date: "2025-01-02T00:00:00Z".into(),
+		duration_minutes: Some(90),
+		scoring: None,
+		scoring_rule: None,
+		prerequisite: prerequisite.map(|test| TestSessionsPrerequisiteSchema {
+			test: make_thing("app_tests", test),
+			min_gap_minutes: None,
+			max_gap_minutes: None,
+		}),
+	}
+}
+
+fn session(tests: Vec<TestSessionsSchema>) -> SessionsSchema {
+	SessionsSchema {
+		id: make_thing("app_sessions", "s1"),
+		name: "Tryout".into(),
+		tests,
+		category: "Saintek".into(),
+		description: "Bundle".into(),
+		student_type: "SMA".into(),
+		passing_grade: Some(60.0),
+		eligibility: None,
+		retake_policy: None,
+		visibility: None,
+		is_active: true,
+		is_deleted: false,
+		created_at: "2025-01-01T00:00:00Z".into(),
+		updated_at: "2025-01-01T00:00:00Z".into(),
+	}
+}
+
+fn sample_bundle() -> SessionsBundleSchema {
+	build_bundle(
+		session(vec![
+			test_session("t1", None),
+			test_session("t2", Some("t1")),
+		]),
+		vec![test("t1", &["q1", "q2"]), test("t2", &["q3"])],
+		vec![
+			question("q1", &["q1a", "q1b"], Some("https://cdn.example.com/a.png")),
+			question("q2", &["q2a", "q2b"], None),
+			question("q3", &["q3a", "q3b"], Some("https://cdn.example.com/a.png")),
+		],
+		["q1a", "q1b", "q2a", "q2b", "q3a"]
+			.iter()
+			.map(|id| {
+				option(
+					id,
+					(*id == "q2b").then_some("https://cdn.example.com/b.png"),
+				)
+			})
+			.collect(),
+	)
+}
+
+#[test]
+fn test_build_bundle_should_trim_references_to_exported_records() {
+	let bundle = sample_bundle();
+	assert_eq!(bundle.version, SESSIONS_BUNDLE_VERSION);
+	assert_eq!(
+		bundle.questions[2].options,
+		vec![make_thing("app_options", "q3a")]
+	);
+	assert_eq!(
+		bundle.media,
+		vec![
+			"https://cdn.example.com/a.png".to_string(),
+			"https://cdn.example.com/b.png".to_string(),
+		]
+	);
+	assert!(bundle_errors(&bundle).is_empty());
+
+	let json = serde_json::to_string(&bundle).unwrap();
+	let parsed: SessionsBundleSchema = serde_json::from_str(&json).unwrap();
+	assert_eq!(
+		parsed.session.tests[1].prerequisite,
+		bundle.session.tests[1].prerequisite
+	);
+	assert_eq!(parsed.tests[0].questions, bundle.tests[0].questions);
+}
+
+#[test]
+fn test_bundle_errors_should_report_references_outside_the_bundle() {
+	let mut bundle = sample_bundle();
+	bundle.tests.remove(1);
+	bundle.tests[0]
+		.questions
+		.push(make_thing("app_questions", "q9"));
+	bundle.questions[0]
+		.options
+		.push(make_thing("app_options", "q9a"));
+	bundle.session.tests[0].prerequisite = Some(TestSessionsPrerequisiteSchema {
+		test: make_thing("app_tests", "t9"),
+		min_gap_minutes: None,
+		max_gap_minutes: None,
+	});
+	assert_eq!(
+		bundle_errors(&bundle),
+		vec![
+			"Prerequisite test t9 is not part of the session",
+			"Test t2 is missing from the bundle",
+			"Question q9 is missing from the bundle",
+			"Option q9a is missing from the bundle",
+		]
+	);
+}
+
+#[test]
+fn test_remap_bundle_should_give_every_record_a_new_id() {
+	let bundle = sample_bundle();
+	let (remapped, id_map) = remap_bundle(&bundle);
+	assert_eq!(id_map.len(), 1 + 2 + 3 + 5);
+	assert_eq!(id_map[0].resource, "app_sessions");
+	assert_eq!(id_map[0].source_id, "s1");
+	assert_eq!(remapped.session.id.id.to_raw(), id_map[0].target_id);
+
+	let target = |source: &str| -> String {
+		id_map
+			.iter()
+			.find(|m| m.source_id == source)
+			.map(|m| m.target_id.clone())
+			.unwrap()
+	};
+	let raw = |ids: &[Thing]| ids.iter().map(|id| id.id.to_raw()).collect::<Vec<_>>();
+	assert_eq!(remapped.session.tests[0].test.id.to_raw(), target("t1"));
+	assert_eq!(
+		remapped.session.tests[1]
+			.prerequisite
+			.as_ref()
+			.unwrap()
+			.test
+			.id
+			.to_raw(),
+		target("t1")
+	);
+	assert_eq!(
+		raw(&remapped.tests[0].questions),
+		vec![target("q1"), target("q2")]
+	);
+	assert_eq!(
+		raw(&remapped.questions[0].options),
+		vec![target("q1a"), target("q1b")]
+	);
+	assert_eq!(remapped.options[4].id.id.to_raw(), target("q3a"));
+	assert_eq!(remapped.session.name, bundle.session.name);
+	assert!(bundle_errors(&remapped).is_empty());
+}
+
+#[test]
+fn test_rename_conflicts_should_suffix_conflicting_names() {
+	let mut bundle = sample_bundle();
+	let conflicts = vec![
+		SessionsBundleConflictDto {
+			resource: "app_sessions".into(),
+			source_id: "s1".into(),
+			name: "Tryout".into(),
+			existing_id: "other".into(),
+		},
+		SessionsBundleConflictDto {
+			resource: "app_tests".into(),
+			source_id: "t2".into(),
+			name: "Test t2".into(),
+			existing_id: "other".into(),
+		},
+	];
+	rename_conflicts(&mut bundle, &conflicts);
+	assert_eq!(bundle.session.name, "Tryout (imported)");
+	assert_eq!(bundle.tests[0].name, "Test t1");
+	assert_eq!(bundle.tests[1].name, "Test t2 (imported)");
+}
@@ -1,10 +1,13 @@
 use super::{
-	SessionsCreateRequestDto, SessionsDetailResponseDto, SessionsResponseDto,
-	SessionsService, SessionsUpdateRequestDto,
+	SessionsBroadcastDto, SessionsBroadcastRequestDto, SessionsBundleImportRequestDto,
+	SessionsBundleImportResponseDto, SessionsCreateRequestDto,
+	SessionsDetailResponseDto, SessionsResponseDto, SessionsService,
+	SessionsUpdateRequestDto, SessionsViewerDto,
 };
 use crate::{
-	permissions_guard, AppState, MessageResponseDto, MetaRequestDto, PermissionsEnum,
-	ResponseListSuccessDto, ResponseSuccessDto,
+	eligibility_guard, permissions_guard, AppState, EnrollmentsCandidateDto,
+	MessageResponseDto, MetaRequestDto, PermissionsEnum, ResponseListSuccessDto,
+	ResponseSuccessDto, UsersItemDtoRaw,
 };
 use axum::{
 	extract::{Path, Query},
@@ -33,6 +36,7 @@ use axum::{
 pub async fn get_session_list(
 	headers: axum::http::HeaderMap,
 	Extension(state): Extension<AppState>,
+	extensions: axum::http::Extensions,
 	Query(meta): Query<MetaRequestDto>,
 ) -> impl IntoResponse {
 	match permissions_guard(
@@ -42,7 +46,12 @@ pub async fn get_session_list(
 	)
 	.await
 	{
-		Ok(_) => SessionsService::get_session_list(&state, meta).await,
+		Ok(_) => {
+			let candidate = extensions
+				.get::<UsersItemDtoRaw>()
+				.map(EnrollmentsCandidateDto::from);
+			SessionsService::get_session_list(&state, meta, candidate).await
+		}
 		Err(response) => response,
 	}
 }
@@ -60,6 +69,7 @@ pub async fn get_session_list(
 pub async fn get_session_by_id(
 	headers: axum::http::HeaderMap,
 	Extension(state): Extension<AppState>,
+	extensions: axum::http::Extensions,
 	Path(id): Path<String>,
 ) -> impl IntoResponse {
 	match permissions_guard(
@@ -69,7 +79,14 @@ pub async fn get_session_by_id(
 	)
 	.await
 	{
-		Ok(_) => SessionsService::get_session_by_id(&state, id).await,
+		Ok(_) => {
+			let user = extensions.get::<UsersItemDtoRaw>();
+			if let Err(response) = eligibility_guard(&state, user, &id).await {
+				return response;
+			}
+			SessionsService::get_session_by_id(&state, id, SessionsViewerDto::from(user))
+				.await
+		}
 		Err(response) => response,
 	}
 }
@@ -154,3 +171,97 @@ pub async fn delete_session(
 		Err(response) => response,
 	}
 }
+
+#[utoipa::path(
+	post,
+	security(("Bearer" = [])),
+	path = "/v1/sessions/broadcast/{id}",
+	params(("id" = String, Path, description = "Session ID")),
+	request_body = SessionsBroadcastRequestDto,
+	responses(
+		(status = 200, description = "Push a message to every open exam channel of the session", body = ResponseSuccessDto<SessionsBroadcastDto>)
+	),
+	tag = "Sessions"
+)]
+pub async fn post_broadcast_session(
+	headers: axum::http::HeaderMap,
+	Extension(state): Extension<AppState>,
+	extensions: axum::http::Extensions,
+	Path(id): Path<String>,
+	Json(payload): Json<SessionsBroadcastRequestDto>,
+) -> impl IntoResponse {
+	match permissions_guard(
+		&headers,
+		state.clone(),
+		vec![PermissionsEnum::BroadcastSessions],
+	)
+	.await
+	{
+		Ok(_) => {
+			let sent_by = extensions
+				.get::<UsersItemDtoRaw>()
+				.map(|user| user.id.id.to_raw());
+			SessionsService::broadcast_session(&state, id, payload, sent_by).await
+		}
+		Err(response) => response,
+	}
+}
+
+#[utoipa::path(
+	get,
+	security(("Bearer" = [])),
+	path = "/v1/sessions/export/{id}",
+	params(
+		("id" = String, Path, description = "Session ID"),
+	),
+	responses(
+		(status = 200, description = "Download the session with its tests, questions and options as a versioned JSON bundle", content_type = "application/json", body = Object),
+		(status = 404, description = "Session not found", body = MessageResponseDto)
+	),
+	tag = "Sessions"
+)]
+pub async fn get_export_session(
+	headers: axum::http::HeaderMap,
+	Extension(state): Extension<AppState>,
+	Path(id): Path<String>,
+) -> impl IntoResponse {
+	match permissions_guard(
+		&headers,
+		state.clone(),
+		vec![PermissionsEnum::TransferSessions],
+	)
+	.await
+	{
+		Ok(_) => SessionsService::export_session(&state, id).await,
+		Err(response) => response,
+	}
+}
+
+#[utoipa::path(
+	post,
+	security(("Bearer" = [])),
+	path = "/v1/sessions/import",
+	request_body = SessionsBundleImportRequestDto,
+	responses(
+		(status = 200, description = "Preview or import a session bundle under new IDs", body = ResponseSuccessDto<SessionsBundleImportResponseDto>),
+		(status = 409, description = "A session or test with the same name exists", body = MessageResponseDto),
+		(status = 422, description = "The bundle references records it does not contain", body = MessageResponseDto)
+	),
+	tag = "Sessions"
+)]
+pub async fn post_import_session(
+	headers: axum::http::HeaderMap,
+	Extension(state): Extension<AppState>,
+	Json(payload): Json<SessionsBundleImportRequestDto>,
+) -> impl IntoResponse {
+	match permissions_guard(
+		&headers,
+		state.clone(),
+		vec![PermissionsEnum::TransferSessions],
+	)
+	.await
+	{
+		Ok(_) => SessionsService::import_session(&state, payload).await,
+		Err(response) => response,
+	}
+}
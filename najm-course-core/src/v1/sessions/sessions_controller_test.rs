@@ -2,18 +2,32 @@ use axum::{Extension, Router};
 use axum_test::TestServer;
 use najm_course_entities::AppState;
 use najm_course_utils::{authorized, create_mock_app_state};
+use serde_json::json;
 
 use crate::{
-	sessions::{SessionsRepository, SessionsUpdateRequestDto},
+	attempts::attempts_repository_test::build_user,
+	sessions::{
+		sessions_repository_test::seed_shuffled_session, SessionsRepository,
+		SessionsUpdateRequestDto,
+	},
 	PermissionsEnum,
 };
+use surrealdb::Uuid;
 
 use super::{SessionsCreateRequestDto, TestSessionsDto};
 
 fn create_test_app(state: AppState) -> TestServer {
+	let user = build_user(
+		&Uuid::new_v4().to_string(),
+		vec![
+			PermissionsEnum::ManageEnrollments,
+			PermissionsEnum::ReviewAnswerKeys,
+		],
+	);
 	let app = Router::new()
 		.nest("/v1/sessions", crate::v1::sessions::sessions_router())
-		.layer(Extension(state));
+		.layer(Extension(state))
+		.layer(Extension(user));
 	TestServer::new(app).unwrap()
 }
 
@@ -24,6 +38,10 @@ fn generate_payload(name: &str) -> SessionsCreateRequestDto {
 		is_active: true,
 		description: "Tryout Description".into(),
 		student_type: "SMA".into(),
+		passing_grade: None,
+		eligibility: None,
+		retake_policy: None,
+		visibility: None,
 		tests: vec![TestSessionsDto {
 			test_id: "mock_test_999".into(),
 			weight: 2.5,
@@ -31,6 +49,11 @@ fn generate_payload(name: &str) -> SessionsCreateRequestDto {
 			multiplier: 1.0,
 			start_date: "2025-01-01T00:00:00Z".into(),
 			end_date: "2025-01-10T00:00:00Z".into(),
+			shuffle_options: None,
+			duration_minutes: None,
+			scoring: None,
+			scoring_rule: None,
+			prerequisite: None,
 		}],
 	}
 }
@@ -107,6 +130,10 @@ async fn test_update_session_should_return_200() {
 		category: "Soshum".into(),
 		description: "Updated Description".into(),
 		student_type: "SMA".into(),
+		passing_grade: None,
+		eligibility: None,
+		retake_policy: None,
+		visibility: None,
 		tests: vec![TestSessionsDto {
 			test_id: "mock_test_update".into(),
 			weight: 2.5,
@@ -114,6 +141,11 @@ async fn test_update_session_should_return_200() {
 			multiplier: 1.25,
 			start_date: "2025-02-01T00:00:00Z".into(),
 			end_date: "2025-02-10T00:00:00Z".into(),
+			shuffle_options: None,
+			duration_minutes: None,
+			scoring: None,
+			scoring_rule: None,
+			prerequisite: None,
 		}],
 		is_active: true,
 	};
@@ -164,3 +196,97 @@ async fn test_get_session_detail_should_return_404_if_not_found() {
 	.await;
 	assert_eq!(res.status_code(), 404);
 }
+
+#[tokio::test]
+async fn test_import_session_bundle_should_preview_conflicts_then_rename() {
+	let state = create_mock_app_state().await;
+	let (session_id, _) = seed_shuffled_session(&state.surrealdb_ws).await.unwrap();
+	let server = create_test_app(state);
+	let permissions = [PermissionsEnum::TransferSessions.to_string()];
+	let permissions: Vec<&str> = permissions.iter().map(String::as_str).collect();
+
+	let exported = authorized::<()>(
+		&server,
+		"GET",
+		&format!("/v1/sessions/export/{}", session_id),
+		permissions.clone(),
+		None,
+	)
+	.await;
+	assert_eq!(exported.status_code(), 200);
+	assert_eq!(
+		exported.header("content-disposition"),
+		"attachment; filename=\"shuffle-session.json\""
+	);
+	let bundle = exported.json::<serde_json::Value>();
+	assert_eq!(bundle["version"], 1);
+
+	let preview = authorized(
+		&server,
+		"POST",
+		"/v1/sessions/import",
+		permissions.clone(),
+		Some(json!({ "bundle": bundle })),
+	)
+	.await;
+	assert_eq!(preview.status_code(), 200);
+	let body = preview.json::<serde_json::Value>();
+	assert_eq!(body["data"]["dry_run"], true);
+	assert_eq!(body["data"]["questions_count"], 8);
+	assert!(!body["data"]["conflicts"].as_array().unwrap().is_empty());
+
+	let rejected = authorized(
+		&server,
+		"POST",
+		"/v1/sessions/import",
+		permissions.clone(),
+		Some(json!({ "bundle": bundle, "dry_run": false })),
+	)
+	.await;
+	assert_eq!(rejected.status_code(), 409);
+
+	let imported = authorized(
+		&server,
+		"POST",
+		"/v1/sessions/import",
+		permissions.clone(),
+		Some(json!({ "bundle": bundle, "dry_run": false, "on_conflict": "rename" })),
+	)
+	.await;
+	assert_eq!(imported.status_code(), 200);
+	let body = imported.json::<serde_json::Value>();
+	assert_eq!(body["data"]["dry_run"], false);
+	let new_id = body["data"]["session_id"].as_str().unwrap().to_string();
+	assert_ne!(new_id, session_id);
+
+	let detail = authorized::<()>(
+		&server,
+		"GET",
+		&format!("/v1/sessions/detail/{}", new_id),
+		vec![&PermissionsEnum::ReadDetailSessions.to_string()],
+		None,
+	)
+	.await;
+	assert_eq!(detail.status_code(), 200);
+	let body = detail.json::<serde_json::Value>();
+	assert_eq!(body["data"]["name"], "Shuffle Session (imported)");
+}
+
+#[tokio::test]
+async fn test_import_session_bundle_should_reject_unknown_version() {
+	let state = create_mock_app_state().await;
+	let (session_id, _) = seed_shuffled_session(&state.surrealdb_ws).await.unwrap();
+	let repo = SessionsRepository::new(&state);
+	let mut bundle = repo.query_export_bundle(&session_id).await.unwrap();
+	bundle.version = 99;
+	let server = create_test_app(state);
+	let res = authorized(
+		&server,
+		"POST",
+		"/v1/sessions/import",
+		vec![&PermissionsEnum::TransferSessions.to_string()],
+		Some(json!({ "bundle": bundle, "dry_run": false })),
+	)
+	.await;
+	assert_eq!(res.status_code(), 400);
+}
@@ -1,6 +1,14 @@
-use super::{SessionsDetailSchema, SessionsSchema};
-use crate::{OptionsItemDto, QuestionsItemDto, TestsItemDto};
-use rand::seq::SliceRandom;
+use super::{
+	SessionsBroadcastSchema, SessionsBundleSchema, SessionsDetailSchema,
+	SessionsEligibilitySchema, SessionsRetakePolicySchema, SessionsSchema,
+	SessionsVisibilitySchema, TestSessionsPrerequisiteSchema,
+};
+use crate::{
+	AttemptsQuestionOrderSchema, OptionsItemDto, QuestionsItemDto, QuestionsTagsDto,
+	QuestionsTypeEnum, ScoringConfigDto, ScoringRuleDto, TestsItemDto,
+};
+use najm_course_libs::ResourceEnum;
+use najm_course_utils::{display_label, make_thing, seeded_shuffle, shuffle_seed};
 use serde::{Deserialize, Serialize};
 use utoipa::ToSchema;
 use validator::Validate;
@@ -19,11 +27,162 @@ pub struct TestSessionsDto {
 	#[schema(example = true)]
 	pub shuffle: bool,
 
+	#[schema(example = true)]
+	pub shuffle_options: Option<bool>,
+
 	#[schema(example = "2025-05-01T00:00:00Z")]
 	pub start_date: String,
 
 	#[schema(example = "2025-05-31T23:59:59Z")]
 	pub end_date: String,
+
+	#[schema(example = 90)]
+	pub duration_minutes: Option<u32>,
+
+	pub scoring: Option<ScoringConfigDto>,
+
+	pub scoring_rule: Option<ScoringRuleDto>,
+
+	pub prerequisite: Option<TestSessionsPrerequisiteDto>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, ToSchema)]
+pub struct TestSessionsPrerequisiteDto {
+	#[schema(example = "uuid")]
+	pub test_id: String,
+
+	#[schema(example = 10)]
+	pub min_gap_minutes: Option<u32>,
+
+	#[schema(example = 60)]
+	pub max_gap_minutes: Option<u32>,
+}
+
+impl From<TestSessionsPrerequisiteSchema> for TestSessionsPrerequisiteDto {
+	fn from(value: TestSessionsPrerequisiteSchema) -> Self {
+		Self {
+			test_id: value.test.id.to_raw(),
+			min_gap_minutes: value.min_gap_minutes,
+			max_gap_minutes: value.max_gap_minutes,
+		}
+	}
+}
+
+impl From<TestSessionsPrerequisiteDto> for TestSessionsPrerequisiteSchema {
+	fn from(value: TestSessionsPrerequisiteDto) -> Self {
+		Self {
+			test: make_thing(&ResourceEnum::Tests.to_string(), &value.test_id),
+			min_gap_minutes: value.min_gap_minutes,
+			max_gap_minutes: value.max_gap_minutes,
+		}
+	}
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, ToSchema)]
+pub struct TestSessionsLockDto {
+	#[schema(example = "locked")]
+	pub status: String,
+	pub reason: Option<String>,
+	pub available_at: Option<String>,
+	pub available_until: Option<String>,
+}
+
+#[derive(Clone, Debug, Default, Serialize, Deserialize, ToSchema)]
+pub struct SessionsEligibilityDto {
+	#[serde(default)]
+	pub roles: Vec<String>,
+
+	#[serde(default)]
+	pub groups: Vec<String>,
+
+	#[serde(default)]
+	#[schema(example = true)]
+	pub requires_enrollment: bool,
+
+	#[serde(default)]
+	#[schema(example = true)]
+	pub allow_self_enrollment: bool,
+}
+
+impl From<SessionsEligibilitySchema> for SessionsEligibilityDto {
+	fn from(value: SessionsEligibilitySchema) -> Self {
+		Self {
+			roles: value.roles,
+			groups: value.groups,
+			requires_enrollment: value.requires_enrollment,
+			allow_self_enrollment: value.allow_self_enrollment,
+		}
+	}
+}
+
+impl From<SessionsEligibilityDto> for SessionsEligibilitySchema {
+	fn from(value: SessionsEligibilityDto) -> Self {
+		Self {
+			roles: value.roles,
+			groups: value.groups,
+			requires_enrollment: value.requires_enrollment,
+			allow_self_enrollment: value.allow_self_enrollment,
+		}
+	}
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, ToSchema)]
+pub struct SessionsRetakePolicyDto {
+	#[schema(example = 3)]
+	pub max_attempts: u32,
+
+	#[schema(example = 60)]
+	pub cooldown_minutes: Option<u32>,
+
+	#[schema(example = "best")]
+	pub score_selection: String,
+}
+
+impl From<SessionsRetakePolicySchema> for SessionsRetakePolicyDto {
+	fn from(value: SessionsRetakePolicySchema) -> Self {
+		Self {
+			max_attempts: value.max_attempts,
+			cooldown_minutes: value.cooldown_minutes,
+			score_selection: value.score_selection,
+		}
+	}
+}
+
+impl From<SessionsRetakePolicyDto> for SessionsRetakePolicySchema {
+	fn from(value: SessionsRetakePolicyDto) -> Self {
+		Self {
+			max_attempts: value.max_attempts,
+			cooldown_minutes: value.cooldown_minutes,
+			score_selection: value.score_selection,
+		}
+	}
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, ToSchema)]
+pub struct SessionsVisibilityDto {
+	#[schema(example = "after_end")]
+	pub mode: String,
+
+	#[schema(example = "2025-02-01T00:00:00Z")]
+	pub release_at: Option<String>,
+}
+
+impl From<SessionsVisibilitySchema> for SessionsVisibilityDto {
+	fn from(value: SessionsVisibilitySchema) -> Self {
+		Self {
+			mode: value.mode,
+			release_at: value.release_at,
+		}
+	}
+}
+
+impl From<SessionsVisibilityDto> for SessionsVisibilitySchema {
+	fn from(value: SessionsVisibilityDto) -> Self {
+		Self {
+			mode: value.mode,
+			release_at: value.release_at,
+		}
+	}
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize, Validate, ToSchema)]
@@ -40,6 +199,19 @@ pub struct SessionsCreateRequestDto {
 	#[schema(example = "Akademik")]
 	pub student_type: String,
 
+	#[validate(range(min = 0.0))]
+	#[schema(example = 65.0)]
+	pub passing_grade: Option<f64>,
+
+	#[serde(default)]
+	pub eligibility: Option<SessionsEligibilityDto>,
+
+	#[serde(default)]
+	pub retake_policy: Option<SessionsRetakePolicyDto>,
+
+	#[serde(default)]
+	pub visibility: Option<SessionsVisibilityDto>,
+
 	#[schema(example = true)]
 	pub is_active: bool,
 
@@ -61,6 +233,19 @@ pub struct SessionsUpdateRequestDto {
 	#[schema(example = "SMA")]
 	pub student_type: String,
 
+	#[validate(range(min = 0.0))]
+	#[schema(example = 65.0)]
+	pub passing_grade: Option<f64>,
+
+	#[serde(default)]
+	pub eligibility: Option<SessionsEligibilityDto>,
+
+	#[serde(default)]
+	pub retake_policy: Option<SessionsRetakePolicyDto>,
+
+	#[serde(default)]
+	pub visibility: Option<SessionsVisibilityDto>,
+
 	#[schema(value_type = Vec<TestSessionsDto>)]
 	pub tests: Vec<TestSessionsDto>,
 
@@ -73,9 +258,15 @@ pub struct TestSessionsItemDto {
 	pub test: TestsItemDto,
 	pub weight: f32,
 	pub shuffle: bool,
+	pub shuffle_options: Option<bool>,
 	pub multiplier: f32,
 	pub start_date: String,
 	pub end_date: String,
+	pub duration_minutes: Option<u32>,
+	pub scoring: Option<ScoringConfigDto>,
+	pub scoring_rule: Option<ScoringRuleDto>,
+	pub prerequisite: Option<TestSessionsPrerequisiteDto>,
+	pub lock: Option<TestSessionsLockDto>,
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize, ToSchema)]
@@ -85,6 +276,10 @@ pub struct SessionsResponseDto {
 	pub category: String,
 	pub description: String,
 	pub student_type: String,
+	pub passing_grade: Option<f64>,
+	pub eligibility: Option<SessionsEligibilityDto>,
+	pub retake_policy: Option<SessionsRetakePolicyDto>,
+	pub visibility: Option<SessionsVisibilityDto>,
 	pub tests_count: u32,
 	pub is_active: bool,
 	pub created_at: String,
@@ -98,6 +293,10 @@ pub struct SessionsDetailResponseDto {
 	pub category: String,
 	pub description: String,
 	pub student_type: String,
+	pub passing_grade: Option<f64>,
+	pub eligibility: Option<SessionsEligibilityDto>,
+	pub retake_policy: Option<SessionsRetakePolicyDto>,
+	pub visibility: Option<SessionsVisibilityDto>,
 	pub tests: Vec<TestSessionsItemDto>,
 	pub is_active: bool,
 	pub created_at: String,
@@ -106,15 +305,13 @@ pub struct SessionsDetailResponseDto {
 
 impl From<SessionsDetailSchema> for SessionsDetailResponseDto {
 	fn from(value: SessionsDetailSchema) -> Self {
-		let mut rng = rand::rng();
-
 		let tests: Vec<TestSessionsItemDto> = value
 			.tests
 			.into_iter()
 			.map(|t| {
 				let test = t.test;
 
-				let mut questions: Vec<QuestionsItemDto> = test
+				let questions: Vec<QuestionsItemDto> = test
 					.questions
 					.into_iter()
 					.filter_map(|q_opt| {
@@ -122,16 +319,17 @@ impl From<SessionsDetailSchema> for SessionsDetailResponseDto {
 							let options = q
 								.options
 								.into_iter()
-								.filter_map(|o_opt| {
-									o_opt.map(|o| OptionsItemDto {
-										id: o.id.id.to_raw(),
-										label: o.label.unwrap_or("".into()),
-										is_correct: None,
-										points: None,
-										image_url: o.image_url,
-										created_at: o.created_at,
-										updated_at: o.updated_at,
-									})
+								.flatten()
+								.enumerate()
+								.map(|(index, o)| OptionsItemDto {
+									id: o.id.id.to_raw(),
+									label: o.label.unwrap_or("".into()),
+									display_label: Some(display_label(index)),
+									is_correct: None,
+									points: None,
+									image_url: o.image_url,
+									created_at: o.created_at,
+									updated_at: o.updated_at,
 								})
 								.collect();
 
@@ -142,6 +340,11 @@ impl From<SessionsDetailSchema> for SessionsDetailResponseDto {
 								question_image_url: q.question_image_url,
 								discussion_image_url: q.discussion_image_url,
 								options,
+								question_type: q
+									.question_type
+									.unwrap_or_else(|| QuestionsTypeEnum::default().to_string()),
+								answer_key: None,
+								tags: q.tags.map(QuestionsTagsDto::from),
 								created_at: q.created_at,
 								updated_at: q.updated_at,
 							}
@@ -149,10 +352,6 @@ impl From<SessionsDetailSchema> for SessionsDetailResponseDto {
 					})
 					.collect();
 
-				if t.shuffle {
-					questions.shuffle(&mut rng);
-				}
-
 				let test_item = TestsItemDto {
 					id: test.id.id.to_raw(),
 					name: test.name,
@@ -165,9 +364,15 @@ impl From<SessionsDetailSchema> for SessionsDetailResponseDto {
 					test: test_item,
 					weight: t.weight,
 					shuffle: t.shuffle,
+					shuffle_options: t.shuffle_options,
 					multiplier: t.multiplier,
 					start_date: t.start_date,
 					end_date: t.end_date,
+					duration_minutes: t.duration_minutes,
+					scoring: t.scoring.map(ScoringConfigDto::from),
+					scoring_rule: t.scoring_rule.map(ScoringRuleDto::from),
+					prerequisite: t.prerequisite.map(TestSessionsPrerequisiteDto::from),
+					lock: None,
 				}
 			})
 			.collect();
@@ -178,6 +383,10 @@ impl From<SessionsDetailSchema> for SessionsDetailResponseDto {
 			category: value.category,
 			description: value.description,
 			student_type: value.student_type,
+			passing_grade: value.passing_grade,
+			eligibility: value.eligibility.map(SessionsEligibilityDto::from),
+			retake_policy: value.retake_policy.map(SessionsRetakePolicyDto::from),
+			visibility: value.visibility.map(SessionsVisibilityDto::from),
 			tests,
 			is_active: value.is_active,
 			created_at: value.created_at,
@@ -186,6 +395,68 @@ impl From<SessionsDetailSchema> for SessionsDetailResponseDto {
 	}
 }
 
+impl TestSessionsItemDto {
+	pub fn arranged_for(mut self, user_id: &str, session_id: &str) -> Self {
+		let test_id = self.test.id.clone();
+		if self.shuffle {
+			seeded_shuffle(
+				&mut self.test.questions,
+				shuffle_seed(&[user_id, session_id, &test_id]),
+			);
+		}
+		if self.shuffle_options.unwrap_or(false) {
+			for question in self.test.questions.iter_mut() {
+				seeded_shuffle(
+					&mut question.options,
+					shuffle_seed(&[user_id, session_id, &test_id, &question.id]),
+				);
+			}
+		}
+		self.relabel()
+	}
+
+	pub fn arranged_by(mut self, order: &[AttemptsQuestionOrderSchema]) -> Self {
+		let position = |ids: &[String], id: &str| {
+			ids.iter().position(|i| i == id).unwrap_or(usize::MAX)
+		};
+		let question_ids: Vec<String> =
+			order.iter().map(|o| o.question.clone()).collect();
+		self
+			.test
+			.questions
+			.sort_by_key(|q| position(&question_ids, &q.id));
+		for question in self.test.questions.iter_mut() {
+			if let Some(entry) = order.iter().find(|o| o.question == question.id) {
+				question
+					.options
+					.sort_by_key(|o| position(&entry.options, &o.id));
+			}
+		}
+		self.relabel()
+	}
+
+	pub fn question_order(&self) -> Vec<AttemptsQuestionOrderSchema> {
+		self
+			.test
+			.questions
+			.iter()
+			.map(|q| AttemptsQuestionOrderSchema {
+				question: q.id.clone(),
+				options: q.options.iter().map(|o| o.id.clone()).collect(),
+			})
+			.collect()
+	}
+
+	fn relabel(mut self) -> Self {
+		for question in self.test.questions.iter_mut() {
+			for (index, option) in question.options.iter_mut().enumerate() {
+				option.display_label = Some(display_label(index));
+			}
+		}
+		self
+	}
+}
+
 impl From<SessionsSchema> for SessionsResponseDto {
 	fn from(value: SessionsSchema) -> Self {
 		Self {
@@ -194,6 +465,10 @@ impl From<SessionsSchema> for SessionsResponseDto {
 			category: value.category,
 			description: value.description,
 			student_type: value.student_type,
+			passing_grade: value.passing_grade,
+			eligibility: value.eligibility.map(SessionsEligibilityDto::from),
+			retake_policy: value.retake_policy.map(SessionsRetakePolicyDto::from),
+			visibility: value.visibility.map(SessionsVisibilityDto::from),
 			tests_count: value.tests.len() as u32,
 			is_active: value.is_active,
 			created_at: value.created_at,
@@ -201,3 +476,82 @@ impl From<SessionsSchema> for SessionsResponseDto {
 		}
 	}
 }
+
+#[derive(Clone, Debug, Serialize, Deserialize, Validate, ToSchema)]
+pub struct SessionsBroadcastRequestDto {
+	#[validate(length(min = 1, max = 500))]
+	#[schema(example = "Question 12 has been corrected")]
+	pub message: String,
+
+	#[schema(example = "uuid")]
+	pub test_id: Option<String>,
+
+	#[schema(example = "uuid")]
+	pub question_id: Option<String>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, ToSchema)]
+pub struct SessionsBroadcastDto {
+	pub id: String,
+	pub session_id: String,
+	pub test_id: Option<String>,
+	pub question_id: Option<String>,
+	#[schema(example = "Question 12 has been corrected")]
+	pub message: String,
+	pub created_at: String,
+}
+
+impl From<SessionsBroadcastSchema> for SessionsBroadcastDto {
+	fn from(value: SessionsBroadcastSchema) -> Self {
+		Self {
+			id: value.id.id.to_raw(),
+			session_id: value.session.id.to_raw(),
+			test_id: value.test.map(|t| t.id.to_raw()),
+			question_id: value.question.map(|q| q.id.to_raw()),
+			message: value.message,
+			created_at: value.created_at,
+		}
+	}
+}
+
+/// Body of a bundle import. `dry_run` defaults to true; `on_conflict` is
+/// `fail` (default) or `rename`.
+#[derive(Clone, Debug, Serialize, Deserialize, ToSchema)]
+pub struct SessionsBundleImportRequestDto {
+	#[schema(value_type = Object)]
+	pub bundle: SessionsBundleSchema,
+	pub dry_run: Option<bool>,
+	#[schema(example = "fail")]
+	pub on_conflict: Option<String>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, ToSchema, PartialEq)]
+pub struct SessionsBundleIdMapDto {
+	#[schema(example = "app_tests")]
+	pub resource: String,
+	pub source_id: String,
+	pub target_id: String,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, ToSchema, PartialEq)]
+pub struct SessionsBundleConflictDto {
+	#[schema(example = "app_sessions")]
+	pub resource: String,
+	pub source_id: String,
+	pub name: String,
+	pub existing_id: String,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, ToSchema)]
+pub struct SessionsBundleImportResponseDto {
+	pub session_id: String,
+	pub dry_run: bool,
+	pub version: u32,
+	pub tests_count: u32,
+	pub questions_count: u32,
+	pub options_count: u32,
+	pub media: Vec<String>,
+	pub id_map: Vec<SessionsBundleIdMapDto>,
+	pub conflicts: Vec<SessionsBundleConflictDto>,
+	pub errors: Vec<String>,
+}
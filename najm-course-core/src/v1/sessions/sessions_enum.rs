@@ -0,0 +1,116 @@
+use std::fmt;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TestSessionsLockStatusEnum {
+	Unlocked,
+	Locked,
+	Waiting,
+	Closed,
+}
+
+impl fmt::Display for TestSessionsLockStatusEnum {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		let status_str = match self {
+			TestSessionsLockStatusEnum::Unlocked => "unlocked",
+			TestSessionsLockStatusEnum::Locked => "locked",
+			TestSessionsLockStatusEnum::Waiting => "waiting",
+			TestSessionsLockStatusEnum::Closed => "closed",
+		};
+		write!(f, "{}", status_str)
+	}
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SessionsScoreSelectionEnum {
+	Best,
+	Last,
+	Average,
+}
+
+impl fmt::Display for SessionsScoreSelectionEnum {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		let selection_str = match self {
+			SessionsScoreSelectionEnum::Best => "best",
+			SessionsScoreSelectionEnum::Last => "last",
+			SessionsScoreSelectionEnum::Average => "average",
+		};
+		write!(f, "{}", selection_str)
+	}
+}
+
+impl std::str::FromStr for SessionsScoreSelectionEnum {
+	type Err = anyhow::Error;
+
+	fn from_str(value: &str) -> Result<Self, Self::Err> {
+		match value {
+			"best" => Ok(SessionsScoreSelectionEnum::Best),
+			"last" => Ok(SessionsScoreSelectionEnum::Last),
+			"average" => Ok(SessionsScoreSelectionEnum::Average),
+			_ => anyhow::bail!("Invalid score selection"),
+		}
+	}
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SessionsVisibilityModeEnum {
+	Never,
+	AfterSubmit,
+	AfterEnd,
+	Scheduled,
+}
+
+impl fmt::Display for SessionsVisibilityModeEnum {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		let mode_str = match self {
+			SessionsVisibilityModeEnum::Never => "never",
+			SessionsVisibilityModeEnum::AfterSubmit => "after_submit",
+			SessionsVisibilityModeEnum::AfterEnd => "after_end",
+			SessionsVisibilityModeEnum::Scheduled => "scheduled",
+		};
+		write!(f, "{}", mode_str)
+	}
+}
+
+impl std::str::FromStr for SessionsVisibilityModeEnum {
+	type Err = anyhow::Error;
+
+	fn from_str(value: &str) -> Result<Self, Self::Err> {
+		match value {
+			"never" => Ok(SessionsVisibilityModeEnum::Never),
+			"after_submit" => Ok(SessionsVisibilityModeEnum::AfterSubmit),
+			"after_end" => Ok(SessionsVisibilityModeEnum::AfterEnd),
+			"scheduled" => Ok(SessionsVisibilityModeEnum::Scheduled),
+			_ => anyhow::bail!("Invalid visibility mode"),
+		}
+	}
+}
+
+/// What a bundle import does when a session or test name is already taken.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum SessionsBundleConflictEnum {
+	#[default]
+	Fail,
+	Rename,
+}
+
+impl fmt::Display for SessionsBundleConflictEnum {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		let conflict_str = match self {
+			SessionsBundleConflictEnum::Fail => "fail",
+			SessionsBundleConflictEnum::Rename => "rename",
+		};
+		write!(f, "{}", conflict_str)
+	}
+}
+
+impl std::str::FromStr for SessionsBundleConflictEnum {
+	type Err = anyhow::Error;
+
+	fn from_str(value: &str) -> Result<Self, Self::Err> {
+		match value {
+			"fail" => Ok(SessionsBundleConflictEnum::Fail),
+			"rename" => Ok(SessionsBundleConflictEnum::Rename),
+			_ => anyhow::bail!("Invalid conflict mode"),
+		}
+	}
+}
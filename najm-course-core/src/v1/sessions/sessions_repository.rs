@@ -1,14 +1,72 @@
 use anyhow::{bail, Result};
+use chrono::{DateTime, Utc};
 use najm_course_entities::{AppState, MetaRequestDto, ResponseListSuccessDto};
 use najm_course_libs::ResourceEnum;
 use najm_course_utils::{get_id, get_iso_date, make_thing, query_list_with_meta};
+use serde::Deserialize;
+use std::collections::HashMap;
+use surrealdb::{method::QueryStream, sql::Thing, Notification};
 use validator::Validate;
 
+use crate::{
+	eligibility_conditions, is_revealed, session_ends_at, test_lock,
+	validate_prerequisites, AttemptsRepository, AttemptsStatusEnum,
+	EnrollmentsCandidateDto, EnrollmentsRepository, OptionsSchema, QuestionsSchema,
+	ScoringConfigSchema, TestsSchema, SCORING_REGISTRY,
+};
+
 use super::{
+	build_bundle, SessionsBroadcastDto, SessionsBroadcastRequestDto,
+	SessionsBroadcastSchema, SessionsBundleConflictDto, SessionsBundleSchema,
 	SessionsCreateRequestDto, SessionsDetailResponseDto, SessionsDetailSchema,
-	SessionsResponseDto, SessionsSchema, SessionsUpdateRequestDto, TestSessionsSchema,
+	SessionsEligibilitySchema, SessionsResponseDto, SessionsRetakePolicyDto,
+	SessionsRetakePolicySchema, SessionsSchema, SessionsScoreSelectionEnum,
+	SessionsUpdateRequestDto, SessionsVisibilityDto, SessionsVisibilityModeEnum,
+	SessionsVisibilitySchema, TestSessionsPrerequisiteSchema, TestSessionsSchema,
 };
 
+fn validate_retake_policy(policy: Option<&SessionsRetakePolicyDto>) -> Result<()> {
+	let Some(policy) = policy else {
+		return Ok(());
+	};
+	if policy.max_attempts == 0 {
+		bail!("Maximum attempts must be at least 1");
+	}
+	policy
+		.score_selection
+		.parse::<SessionsScoreSelectionEnum>()?;
+	Ok(())
+}
+
+fn validate_visibility(visibility: Option<&SessionsVisibilityDto>) -> Result<()> {
+	let Some(visibility) = visibility else {
+		return Ok(());
+	};
+	let mode: SessionsVisibilityModeEnum = visibility.mode.parse()?;
+	match visibility.release_at.as_deref() {
+		Some(release_at) if DateTime::parse_from_rfc3339(release_at).is_err() => {
+			bail!("Invalid date format")
+		}
+		None if mode == SessionsVisibilityModeEnum::Scheduled => {
+			bail!("Release time must be set for scheduled visibility")
+		}
+		_ => Ok(()),
+	}
+}
+
+#[derive(Deserialize)]
+struct SessionsNamedRow {
+	id: Thing,
+	name: String,
+}
+
+#[derive(Deserialize)]
+struct SessionsVisibilityRow {
+	visibility: Option<SessionsVisibilitySchema>,
+	#[serde(default)]
+	end_dates: Vec<Option<String>>,
+}
+
 pub struct SessionsRepository<'a> {
 	state: &'a AppState,
 }
@@ -28,11 +86,61 @@ impl<'a> SessionsRepository<'a> {
 		}
 	}
 
+	/// Reads only the visibility policy and end dates so sessions stored in
+	/// older shapes still resolve.
+	pub async fn query_is_revealed(
+		&self,
+		session_id: &str,
+		is_submitted: bool,
+	) -> Result<bool> {
+		let db = &self.state.surrealdb_ws;
+		let query = format!(
+			"SELECT visibility, tests.end_date AS end_dates FROM {}:⟨{}⟩ WHERE is_deleted = false",
+			ResourceEnum::Sessions,
+			session_id
+		);
+		let mut result = db.query(query).await?;
+		let row: Option<SessionsVisibilityRow> = result.take(0)?;
+		let Some(row) = row else {
+			bail!("Session not found");
+		};
+		let ends_at =
+			session_ends_at(row.end_dates.iter().flatten().map(String::as_str));
+		Ok(is_revealed(
+			&row.visibility.unwrap_or_default(),
+			is_submitted,
+			ends_at,
+			Utc::now(),
+		))
+	}
+
 	pub async fn query_session_list(
 		&self,
 		meta: MetaRequestDto,
+	) -> Result<ResponseListSuccessDto<Vec<SessionsResponseDto>>> {
+		self.query_session_list_with(meta, vec![]).await
+	}
+
+	pub async fn query_session_list_for_user(
+		&self,
+		meta: MetaRequestDto,
+		candidate: &EnrollmentsCandidateDto,
+	) -> Result<ResponseListSuccessDto<Vec<SessionsResponseDto>>> {
+		let enrollments = EnrollmentsRepository::new(self.state)
+			.query_enrollments_by_user(&candidate.user_id)
+			.await?;
+		self
+			.query_session_list_with(meta, eligibility_conditions(candidate, &enrollments))
+			.await
+	}
+
+	async fn query_session_list_with(
+		&self,
+		meta: MetaRequestDto,
+		eligibility: Vec<String>,
 	) -> Result<ResponseListSuccessDto<Vec<SessionsResponseDto>>> {
 		let mut conditions = vec!["is_deleted = false".into()];
+		conditions.extend(eligibility);
 		if let Some(_search) = &meta.search {
 			conditions
 				.push("string::contains(string::lowercase(name ?? ''), $search)".into());
@@ -77,6 +185,65 @@ impl<'a> SessionsRepository<'a> {
 		}
 	}
 
+	pub async fn query_session_by_id_for_user(
+		&self,
+		id: &str,
+		user_id: &str,
+		is_reviewer: bool,
+	) -> Result<SessionsDetailResponseDto> {
+		let mut session = self.query_session_by_id(id).await?;
+		let visibility = session
+			.visibility
+			.clone()
+			.map(SessionsVisibilitySchema::from)
+			.unwrap_or_default();
+		let ends_at = session_ends_at(session.tests.iter().map(|t| t.end_date.as_str()));
+		let attempt_repo = AttemptsRepository::new(self.state);
+		let mut histories = HashMap::with_capacity(session.tests.len());
+		for test in &session.tests {
+			let history = attempt_repo
+				.query_attempt_history(user_id, id, &test.test.id)
+				.await?;
+			histories.insert(test.test.id.clone(), history);
+		}
+		let now = Utc::now();
+		let mut tests = Vec::with_capacity(session.tests.len());
+		for mut test in session.tests {
+			let attempt = histories
+				.get(&test.test.id)
+				.and_then(|history| history.last())
+				.cloned();
+			let prerequisite = test
+				.prerequisite
+				.clone()
+				.map(TestSessionsPrerequisiteSchema::from);
+			let predecessor = prerequisite
+				.as_ref()
+				.and_then(|p| histories.get(&p.test.id.to_raw()))
+				.and_then(|history| history.iter().find(|a| a.submitted_at.is_some()));
+			test.lock = Some(test_lock(
+				prerequisite.as_ref(),
+				predecessor,
+				attempt.is_some(),
+				now,
+			));
+			let is_submitted = attempt
+				.as_ref()
+				.is_some_and(|a| a.status != AttemptsStatusEnum::InProgress.to_string());
+			if !is_reviewer && !is_revealed(&visibility, is_submitted, ends_at, now) {
+				test.test = test.test.without_answer_key();
+			}
+			tests.push(match attempt {
+				Some(a) if !a.question_order.is_empty() => {
+					test.arranged_by(&a.question_order)
+				}
+				_ => test.arranged_for(user_id, id),
+			});
+		}
+		session.tests = tests;
+		Ok(session)
+	}
+
 	pub async fn query_create_session(
 		&self,
 		payload: SessionsCreateRequestDto,
@@ -85,6 +252,12 @@ impl<'a> SessionsRepository<'a> {
 		if payload.tests.is_empty() {
 			bail!("Tests must not be empty");
 		}
+		for scoring in payload.tests.iter().filter_map(|t| t.scoring.as_ref()) {
+			SCORING_REGISTRY.validate(&scoring.strategy, &scoring.params)?;
+		}
+		validate_prerequisites(&payload.tests)?;
+		validate_retake_policy(payload.retake_policy.as_ref())?;
+		validate_visibility(payload.visibility.as_ref())?;
 		let db = &self.state.surrealdb_ws;
 		let session_id = surrealdb::Uuid::new_v4().to_string();
 		let now = get_iso_date();
@@ -96,8 +269,13 @@ impl<'a> SessionsRepository<'a> {
 				weight: t.weight,
 				multiplier: t.multiplier,
 				shuffle: t.shuffle,
+				shuffle_options: t.shuffle_options,
 				start_date: t.start_date,
 				end_date: t.end_date,
+				duration_minutes: t.duration_minutes,
+				scoring: t.scoring.map(ScoringConfigSchema::from),
+				scoring_rule: t.scoring_rule.map(Into::into),
+				prerequisite: t.prerequisite.map(Into::into),
 			})
 			.collect::<Vec<_>>();
 		let session = SessionsSchema {
@@ -106,6 +284,10 @@ impl<'a> SessionsRepository<'a> {
 			category: payload.category,
 			description: payload.description,
 			student_type: payload.student_type,
+			passing_grade: payload.passing_grade,
+			eligibility: payload.eligibility.map(SessionsEligibilitySchema::from),
+			retake_policy: payload.retake_policy.map(SessionsRetakePolicySchema::from),
+			visibility: payload.visibility.map(SessionsVisibilitySchema::from),
 			tests,
 			is_active: payload.is_active,
 			is_deleted: false,
@@ -128,6 +310,12 @@ impl<'a> SessionsRepository<'a> {
 		if data.tests.is_empty() {
 			bail!("Tests must not be empty");
 		}
+		for scoring in data.tests.iter().filter_map(|t| t.scoring.as_ref()) {
+			SCORING_REGISTRY.validate(&scoring.strategy, &scoring.params)?;
+		}
+		validate_prerequisites(&data.tests)?;
+		validate_retake_policy(data.retake_policy.as_ref())?;
+		validate_visibility(data.visibility.as_ref())?;
 		let db = &self.state.surrealdb_ws;
 		let existing = self.query_raw_session_by_id(&id).await?;
 		if existing.is_deleted {
@@ -140,9 +328,14 @@ impl<'a> SessionsRepository<'a> {
 				test: make_thing(&ResourceEnum::Tests.to_string(), &t.test_id),
 				weight: t.weight,
 				shuffle: t.shuffle,
+				shuffle_options: t.shuffle_options,
 				multiplier: t.multiplier,
 				start_date: t.start_date,
 				end_date: t.end_date,
+				duration_minutes: t.duration_minutes,
+				scoring: t.scoring.map(ScoringConfigSchema::from),
+				scoring_rule: t.scoring_rule.map(Into::into),
+				prerequisite: t.prerequisite.map(Into::into),
 			})
 			.collect::<Vec<_>>();
 		let updated = SessionsSchema {
@@ -151,6 +344,10 @@ impl<'a> SessionsRepository<'a> {
 			category: data.category,
 			description: data.description,
 			student_type: data.student_type,
+			passing_grade: data.passing_grade,
+			eligibility: data.eligibility.map(SessionsEligibilitySchema::from),
+			retake_policy: data.retake_policy.map(SessionsRetakePolicySchema::from),
+			visibility: data.visibility.map(SessionsVisibilitySchema::from),
 			tests,
 			is_active: data.is_active,
 			is_deleted: false,
@@ -184,4 +381,179 @@ impl<'a> SessionsRepository<'a> {
 			None => bail!("Failed to delete session"),
 		}
 	}
+
+	pub async fn query_create_broadcast(
+		&self,
+		session_id: &str,
+		payload: SessionsBroadcastRequestDto,
+		sent_by: Option<&str>,
+	) -> Result<SessionsBroadcastDto> {
+		payload.validate()?;
+		let session = self.query_raw_session_by_id(session_id).await?;
+		if let Some(test_id) = &payload.test_id {
+			if !session.tests.iter().any(|t| &t.test.id.to_raw() == test_id) {
+				bail!("Test not found in session");
+			}
+		}
+		let db = &self.state.surrealdb_ws;
+		let id = surrealdb::Uuid::new_v4().to_string();
+		let broadcast = SessionsBroadcastSchema {
+			id: make_thing(&ResourceEnum::SessionBroadcasts.to_string(), &id),
+			session: session.id,
+			test: payload
+				.test_id
+				.map(|t| make_thing(&ResourceEnum::Tests.to_string(), &t)),
+			question: payload
+				.question_id
+				.map(|q| make_thing(&ResourceEnum::Questions.to_string(), &q)),
+			message: payload.message,
+			sent_by: sent_by.map(|u| make_thing(&ResourceEnum::Users.to_string(), u)),
+			created_at: get_iso_date(),
+		};
+		let created: Option<SessionsBroadcastSchema> = db
+			.create((ResourceEnum::SessionBroadcasts.to_string(), id))
+			.content(broadcast)
+			.await?;
+		match created {
+			Some(b) => Ok(SessionsBroadcastDto::from(b)),
+			None => bail!("Failed to create broadcast"),
+		}
+	}
+
+	/// Live query on the broadcasts of one session. Dropping the stream
+	/// kills the live query.
+	pub async fn query_live_broadcasts(
+		&self,
+		session_id: &str,
+	) -> Result<QueryStream<Notification<SessionsBroadcastSchema>>> {
+		let db = &self.state.surrealdb_ws;
+		let mut response = db
+			.query(format!(
+				"LIVE SELECT * FROM {} WHERE session = {}:⟨{}⟩",
+				ResourceEnum::SessionBroadcasts,
+				ResourceEnum::Sessions,
+				session_id
+			))
+			.await?;
+		Ok(response.stream::<Notification<SessionsBroadcastSchema>>(0)?)
+	}
+
+	pub async fn query_export_bundle(&self, id: &str) -> Result<SessionsBundleSchema> {
+		let session = self.query_raw_session_by_id(id).await?;
+		let db = &self.state.surrealdb_ws;
+		let test_ids: Vec<Thing> =
+			session.tests.iter().map(|t| t.test.clone()).collect();
+		let mut result = db
+			.query(format!(
+				"SELECT * FROM {} WHERE id IN $ids AND is_deleted = false",
+				ResourceEnum::Tests
+			))
+			.bind(("ids", test_ids.clone()))
+			.await?;
+		let found: Vec<TestsSchema> = result.take(0)?;
+		let mut tests = vec![];
+		for id in &test_ids {
+			match found.iter().find(|t| &t.id == id) {
+				Some(test) if !tests.iter().any(|t: &TestsSchema| &t.id == id) => {
+					tests.push(test.clone())
+				}
+				Some(_) => {}
+				None => bail!("Test not found"),
+			}
+		}
+		let question_ids: Vec<Thing> =
+			tests.iter().flat_map(|t| t.questions.clone()).collect();
+		let mut result = db
+			.query(format!(
+				"SELECT * FROM {} WHERE id IN $ids AND is_deleted = false",
+				ResourceEnum::Questions
+			))
+			.bind(("ids", question_ids))
+			.await?;
+		let questions: Vec<QuestionsSchema> = result.take(0)?;
+		let option_ids: Vec<Thing> =
+			questions.iter().flat_map(|q| q.options.clone()).collect();
+		let mut result = db
+			.query(format!(
+				"SELECT * FROM {} WHERE id IN $ids AND is_deleted = false",
+				ResourceEnum::Options
+			))
+			.bind(("ids", option_ids))
+			.await?;
+		let options: Vec<OptionsSchema> = result.take(0)?;
+		Ok(build_bundle(session, tests, questions, options))
+	}
+
+	pub async fn query_bundle_conflicts(
+		&self,
+		bundle: &SessionsBundleSchema,
+	) -> Result<Vec<SessionsBundleConflictDto>> {
+		let db = &self.state.surrealdb_ws;
+		let test_names: Vec<String> =
+			bundle.tests.iter().map(|t| t.name.clone()).collect();
+		let mut result = db
+			.query(format!(
+				"SELECT id, name FROM {} WHERE name = $session_name AND is_deleted = false; SELECT id, name FROM {} WHERE name IN $test_names AND is_deleted = false;",
+				ResourceEnum::Sessions,
+				ResourceEnum::Tests
+			))
+			.bind(("session_name", bundle.session.name.clone()))
+			.bind(("test_names", test_names))
+			.await?;
+		let sessions: Vec<SessionsNamedRow> = result.take(0)?;
+		let tests: Vec<SessionsNamedRow> = result.take(1)?;
+		let conflict = |id: &Thing, name: &str, existing: &SessionsNamedRow| {
+			SessionsBundleConflictDto {
+				resource: id.tb.clone(),
+				source_id: id.id.to_raw(),
+				name: name.to_string(),
+				existing_id: existing.id.id.to_raw(),
+			}
+		};
+		let mut conflicts: Vec<SessionsBundleConflictDto> = sessions
+			.iter()
+			.take(1)
+			.map(|existing| conflict(&bundle.session.id, &bundle.session.name, existing))
+			.collect();
+		for test in &bundle.tests {
+			if let Some(existing) = tests.iter().find(|t| t.name == test.name) {
+				conflicts.push(conflict(&test.id, &test.name, existing));
+			}
+		}
+		Ok(conflicts)
+	}
+
+	/// Writes a remapped bundle in one transaction.
+	pub async fn query_import_bundle(
+		&self,
+		bundle: &SessionsBundleSchema,
+	) -> Result<()> {
+		let db = &self.state.surrealdb_ws;
+		let mut statements = vec!["BEGIN TRANSACTION;".to_string()];
+		if !bundle.options.is_empty() {
+			statements.push(format!("INSERT INTO {} $options;", ResourceEnum::Options));
+		}
+		if !bundle.questions.is_empty() {
+			statements.push(format!(
+				"INSERT INTO {} $questions;",
+				ResourceEnum::Questions
+			));
+		}
+		if !bundle.tests.is_empty() {
+			statements.push(format!("INSERT INTO {} $tests;", ResourceEnum::Tests));
+		}
+		statements.push(format!(
+			"CREATE {} CONTENT $session_record;",
+			bundle.session.id
+		));
+		statements.push("COMMIT TRANSACTION;".to_string());
+		db.query(statements.join(" "))
+			.bind(("options", bundle.options.clone()))
+			.bind(("questions", bundle.questions.clone()))
+			.bind(("tests", bundle.tests.clone()))
+			.bind(("session_record", bundle.session.clone()))
+			.await?
+			.check()?;
+		Ok(())
+	}
 }
@@ -1,6 +1,10 @@
 use super::SessionsRepository;
-use super::{SessionsCreateRequestDto, SessionsUpdateRequestDto, TestSessionsDto};
-use crate::TestsSchema;
+use super::{
+	bundle_errors, remap_bundle, SessionsBroadcastRequestDto,
+	SessionsCreateRequestDto, SessionsUpdateRequestDto, TestSessionsDto,
+	SESSIONS_BUNDLE_VERSION,
+};
+use crate::{ScoringConfigDto, ScoringRuleDto, TestsSchema};
 use anyhow::Result;
 use najm_course_utils::{create_mock_app_state, get_iso_date, make_thing};
 use surrealdb::Uuid;
@@ -33,6 +37,10 @@ async fn test_create_and_get_session() -> Result<()> {
 		description: "Mock tryout".to_string(),
 		is_active: true,
 		student_type: "SMA".to_string(),
+		passing_grade: None,
+		eligibility: None,
+		retake_policy: None,
+		visibility: None,
 		tests: vec![TestSessionsDto {
 			test_id: test_id.to_string(),
 			weight: 2.5,
@@ -40,6 +48,16 @@ async fn test_create_and_get_session() -> Result<()> {
 			multiplier: 1.2,
 			start_date: "2025-01-01T00:00:00Z".to_string(),
 			end_date: "2025-01-10T00:00:00Z".to_string(),
+			shuffle_options: None,
+			duration_minutes: None,
+			scoring: None,
+			scoring_rule: Some(ScoringRuleDto {
+				correct: 4.0,
+				wrong: -1.0,
+				blank: 0.0,
+				partial_credit: true,
+			}),
+			prerequisite: None,
 		}],
 	};
 	let session_id = repo.query_create_session(payload).await?;
@@ -47,6 +65,9 @@ async fn test_create_and_get_session() -> Result<()> {
 	assert_eq!(detail.name, "Tryout Test");
 	assert_eq!(detail.tests.len(), 1);
 	assert_eq!(detail.tests[0].test.name, session_name);
+	let rule = detail.tests[0].scoring_rule.clone().expect("Expected rule");
+	assert_eq!(rule.wrong, -1.0);
+	assert!(rule.partial_credit);
 	Ok(())
 }
 
@@ -61,6 +82,10 @@ async fn test_update_session() -> Result<()> {
 		is_active: true,
 
 		student_type: "SMA".to_string(),
+		passing_grade: None,
+		eligibility: None,
+		retake_policy: None,
+		visibility: None,
 		tests: vec![TestSessionsDto {
 			test_id: "mock_test_2".to_string(),
 			weight: 2.5,
@@ -68,6 +93,11 @@ async fn test_update_session() -> Result<()> {
 			multiplier: 1.0,
 			start_date: "2025-02-01T00:00:00Z".to_string(),
 			end_date: "2025-02-10T00:00:00Z".to_string(),
+			shuffle_options: None,
+			duration_minutes: None,
+			scoring: None,
+			scoring_rule: None,
+			prerequisite: None,
 		}],
 	};
 	let session_id = repo.query_create_session(payload).await?;
@@ -76,6 +106,10 @@ async fn test_update_session() -> Result<()> {
 		category: "Saintek".to_string(),
 		description: "Updated description".to_string(),
 		student_type: "SMA".to_string(),
+		passing_grade: None,
+		eligibility: None,
+		retake_policy: None,
+		visibility: None,
 		tests: vec![TestSessionsDto {
 			test_id: "mock_test_2".to_string(),
 			weight: 2.5,
@@ -83,6 +117,11 @@ async fn test_update_session() -> Result<()> {
 			multiplier: 1.5,
 			start_date: "2025-02-05T00:00:00Z".to_string(),
 			end_date: "2025-02-15T00:00:00Z".to_string(),
+			shuffle_options: None,
+			duration_minutes: None,
+			scoring: None,
+			scoring_rule: None,
+			prerequisite: None,
 		}],
 		is_active: true,
 	};
@@ -103,6 +142,10 @@ async fn test_delete_session() -> Result<()> {
 		is_active: true,
 		description: "For deletion test".to_string(),
 		student_type: "SMA".to_string(),
+		passing_grade: None,
+		eligibility: None,
+		retake_policy: None,
+		visibility: None,
 		tests: vec![TestSessionsDto {
 			test_id: "mock_test_3".to_string(),
 			weight: 2.5,
@@ -110,6 +153,11 @@ async fn test_delete_session() -> Result<()> {
 			shuffle: true,
 			start_date: "2025-03-01T00:00:00Z".to_string(),
 			end_date: "2025-03-10T00:00:00Z".to_string(),
+			shuffle_options: None,
+			duration_minutes: None,
+			scoring: None,
+			scoring_rule: None,
+			prerequisite: None,
 		}],
 	};
 	let session_id = repo.query_create_session(payload).await?;
@@ -128,6 +176,10 @@ async fn test_create_session_with_empty_tests_should_fail() {
 		description: "No tests".to_string(),
 		is_active: true,
 		student_type: "SMA".to_string(),
+		passing_grade: None,
+		eligibility: None,
+		retake_policy: None,
+		visibility: None,
 		tests: vec![], // ❌
 	};
 	let result = repo.query_create_session(payload).await;
@@ -144,6 +196,10 @@ async fn test_update_non_existing_session_should_fail() {
 		category: "Saintek".into(),
 		description: "Update should fail".into(),
 		student_type: "SMA".into(),
+		passing_grade: None,
+		eligibility: None,
+		retake_policy: None,
+		visibility: None,
 		tests: vec![TestSessionsDto {
 			test_id: "mock_test_x".into(),
 			weight: 2.7,
@@ -151,6 +207,11 @@ async fn test_update_non_existing_session_should_fail() {
 			multiplier: 1.0,
 			start_date: "2025-01-01T00:00:00Z".into(),
 			end_date: "2025-01-10T00:00:00Z".into(),
+			shuffle_options: None,
+			duration_minutes: None,
+			scoring: None,
+			scoring_rule: None,
+			prerequisite: None,
 		}],
 		is_active: true,
 	};
@@ -171,6 +232,10 @@ async fn test_create_session_with_invalid_test_ref_should_fail() {
 		is_active: true,
 		description: "Non-existing test ref".to_string(),
 		student_type: "SMA".to_string(),
+		passing_grade: None,
+		eligibility: None,
+		retake_policy: None,
+		visibility: None,
 		tests: vec![TestSessionsDto {
 			test_id: "non_existing_test_id".to_string(),
 			weight: 2.6,
@@ -178,6 +243,11 @@ async fn test_create_session_with_invalid_test_ref_should_fail() {
 			multiplier: 1.0,
 			start_date: "2025-01-01T00:00:00Z".to_string(),
 			end_date: "2025-01-10T00:00:00Z".to_string(),
+			shuffle_options: None,
+			duration_minutes: None,
+			scoring: None,
+			scoring_rule: None,
+			prerequisite: None,
 		}],
 	};
 	let session_id = repo.query_create_session(payload).await.unwrap();
@@ -208,6 +278,10 @@ async fn test_update_session_with_empty_tests_should_fail() {
 		is_active: true,
 		description: "To test update fail".to_string(),
 		student_type: "SMA".to_string(),
+		passing_grade: None,
+		eligibility: None,
+		retake_policy: None,
+		visibility: None,
 		tests: vec![TestSessionsDto {
 			test_id: "mock_test_update".to_string(),
 			shuffle: true,
@@ -215,6 +289,11 @@ async fn test_update_session_with_empty_tests_should_fail() {
 			multiplier: 1.0,
 			start_date: "2025-04-01T00:00:00Z".to_string(),
 			end_date: "2025-04-10T00:00:00Z".to_string(),
+			shuffle_options: None,
+			duration_minutes: None,
+			scoring: None,
+			scoring_rule: None,
+			prerequisite: None,
 		}],
 	};
 	let session_id = repo.query_create_session(payload).await.unwrap();
@@ -223,6 +302,10 @@ async fn test_update_session_with_empty_tests_should_fail() {
 		category: "X".to_string(),
 		description: "Should fail".to_string(),
 		student_type: "SMA".to_string(),
+		passing_grade: None,
+		eligibility: None,
+		retake_policy: None,
+		visibility: None,
 		tests: vec![], // ❌ kosong
 		is_active: true,
 	};
@@ -270,6 +353,10 @@ async fn test_delete_session_twice_should_fail() -> Result<()> {
 		description: "Double delete".to_string(),
 		is_active: true,
 		student_type: "SMA".to_string(),
+		passing_grade: None,
+		eligibility: None,
+		retake_policy: None,
+		visibility: None,
 		tests: vec![TestSessionsDto {
 			test_id: test_id.to_string(),
 			shuffle: true,
@@ -277,6 +364,11 @@ async fn test_delete_session_twice_should_fail() -> Result<()> {
 			multiplier: 1.0,
 			start_date: "2025-05-01T00:00:00Z".to_string(),
 			end_date: "2025-05-10T00:00:00Z".to_string(),
+			shuffle_options: None,
+			duration_minutes: None,
+			scoring: None,
+			scoring_rule: None,
+			prerequisite: None,
 		}],
 	};
 	let session_id = repo.query_create_session(payload).await?;
@@ -288,3 +380,207 @@ async fn test_delete_session_twice_should_fail() -> Result<()> {
 
 	Ok(())
 }
+
+#[tokio::test]
+async fn test_create_session_should_fail_with_unknown_scoring_strategy() {
+	let state = create_mock_app_state().await;
+	let repo = SessionsRepository::new(&state);
+	let payload = SessionsCreateRequestDto {
+		name: "Tryout Scoring".to_string(),
+		category: "Psikologi".to_string(),
+		description: "Mock tryout".to_string(),
+		is_active: true,
+		student_type: "SMA".to_string(),
+		passing_grade: None,
+		eligibility: None,
+		retake_policy: None,
+		visibility: None,
+		tests: vec![TestSessionsDto {
+			test_id: "mock_test_scoring".to_string(),
+			weight: 1.0,
+			shuffle: false,
+			multiplier: 1.0,
+			start_date: "2025-01-01T00:00:00Z".to_string(),
+			end_date: "2025-01-10T00:00:00Z".to_string(),
+			shuffle_options: None,
+			duration_minutes: None,
+			scoring: Some(ScoringConfigDto {
+				strategy: "unknown".to_string(),
+				params: serde_json::Value::Null,
+			}),
+			scoring_rule: None,
+			prerequisite: None,
+		}],
+	};
+	let result = repo.query_create_session(payload).await;
+	assert_eq!(
+		result.unwrap_err().to_string(),
+		"Scoring strategy not found"
+	);
+}
+
+pub async fn seed_shuffled_session(
+	db: &najm_course_entities::SurrealWsClient,
+) -> Result<(String, String)> {
+	let now = get_iso_date();
+	let session_id = Uuid::new_v4().to_string();
+	let test_id = Uuid::new_v4().to_string();
+	let mut question_refs = vec![];
+	for q in 0..8 {
+		let question_id = Uuid::new_v4().to_string();
+		let mut option_refs = vec![];
+		for o in 0..5 {
+			let option_id = Uuid::new_v4().to_string();
+			db.query(format!(
+				"CREATE app_options:⟨{option_id}⟩ SET label = 'Option {o}', is_correct = {}, is_deleted = false, created_at = '{now}', updated_at = '{now}'",
+				o == 0
+			))
+			.await?;
+			option_refs.push(format!("app_options:⟨{option_id}⟩"));
+		}
+		db.query(format!(
+			"CREATE app_questions:⟨{question_id}⟩ SET question = 'Question {q}', discussion = 'Discussion', options = [{}], is_deleted = false, created_at = '{now}', updated_at = '{now}'",
+			option_refs.join(", ")
+		))
+		.await?;
+		question_refs.push(format!("app_questions:⟨{question_id}⟩"));
+	}
+	db.query(format!(
+		"CREATE app_tests:⟨{test_id}⟩ SET name = 'Shuffle Test', questions = [{}], is_deleted = false, created_at = '{now}', updated_at = '{now}'",
+		question_refs.join(", ")
+	))
+	.await?;
+	db.query(format!(
+		"CREATE app_sessions:⟨{session_id}⟩ SET name = 'Shuffle Session', category = 'Akademik', description = 'Shuffle', student_type = 'SMA', tests = [{{ test: app_tests:⟨{test_id}⟩, shuffle: true, shuffle_options: true, weight: 1.0, multiplier: 1.0, start_date: '2025-01-01T00:00:00Z', end_date: '2099-01-01T00:00:00Z' }}], is_active = true, is_deleted = false, created_at = '{now}', updated_at = '{now}'"
+	))
+	.await?;
+	Ok((session_id, test_id))
+}
+
+#[tokio::test]
+async fn test_session_by_id_for_user_should_shuffle_deterministically() -> Result<()>
+{
+	let state = create_mock_app_state().await;
+	let (session_id, _) = seed_shuffled_session(&state.surrealdb_ws).await?;
+	let repo = SessionsRepository::new(&state);
+	let first = repo
+		.query_session_by_id_for_user(&session_id, "student_a", true)
+		.await?;
+	let second = repo
+		.query_session_by_id_for_user(&session_id, "student_a", true)
+		.await?;
+	let base = repo.query_session_by_id(&session_id).await?;
+	assert_eq!(
+		first.tests[0].question_order(),
+		second.tests[0].question_order()
+	);
+	let mut shuffled_ids = first.tests[0].question_order();
+	let mut base_ids = base.tests[0].question_order();
+	for entry in shuffled_ids.iter_mut().chain(base_ids.iter_mut()) {
+		entry.options.sort();
+	}
+	shuffled_ids.sort_by(|a, b| a.question.cmp(&b.question));
+	base_ids.sort_by(|a, b| a.question.cmp(&b.question));
+	assert_eq!(shuffled_ids, base_ids);
+	let labels: Vec<String> = first.tests[0].test.questions[0]
+		.options
+		.iter()
+		.filter_map(|o| o.display_label.clone())
+		.collect();
+	assert_eq!(labels, vec!["A", "B", "C", "D", "E"]);
+	Ok(())
+}
+
+#[tokio::test]
+async fn test_session_by_id_for_user_should_hide_discussions_before_submit(
+) -> Result<()> {
+	let state = create_mock_app_state().await;
+	let (session_id, _) = seed_shuffled_session(&state.surrealdb_ws).await?;
+	let repo = SessionsRepository::new(&state);
+	let student = repo
+		.query_session_by_id_for_user(&session_id, "student_a", false)
+		.await?;
+	let reviewer = repo
+		.query_session_by_id_for_user(&session_id, "student_a", true)
+		.await?;
+	let hidden = &student.tests[0].test.questions;
+	assert!(hidden.iter().all(|q| q.discussion.is_empty()));
+	assert!(hidden
+		.iter()
+		.flat_map(|q| &q.options)
+		.all(|o| o.is_correct.is_none()));
+	let shown = &reviewer.tests[0].test.questions;
+	assert!(shown.iter().all(|q| q.discussion == "Discussion"));
+	Ok(())
+}
+
+#[tokio::test]
+async fn test_create_broadcast_should_target_session_tests() -> Result<()> {
+	let state = create_mock_app_state().await;
+	let (session_id, test_id) = seed_shuffled_session(&state.surrealdb_ws).await?;
+	let repo = SessionsRepository::new(&state);
+	let broadcast = repo
+		.query_create_broadcast(
+			&session_id,
+			SessionsBroadcastRequestDto {
+				message: "Question 12 has been corrected".into(),
+				test_id: Some(test_id),
+				question_id: None,
+			},
+			Some("admin"),
+		)
+		.await?;
+	assert_eq!(broadcast.session_id, session_id);
+	let unknown = repo
+		.query_create_broadcast(
+			&session_id,
+			SessionsBroadcastRequestDto {
+				message: "Hello".into(),
+				test_id: Some("missing".into()),
+				question_id: None,
+			},
+			None,
+		)
+		.await;
+	assert_eq!(
+		unknown.unwrap_err().to_string(),
+		"Test not found in session"
+	);
+	Ok(())
+}
+
+#[tokio::test]
+async fn test_export_bundle_should_import_under_new_ids() -> Result<()> {
+	let state = create_mock_app_state().await;
+	let db = &state.surrealdb_ws;
+	let (session_id, test_id) = seed_shuffled_session(db).await?;
+	let repo = SessionsRepository::new(&state);
+
+	let bundle = repo.query_export_bundle(&session_id).await?;
+	assert_eq!(bundle.version, SESSIONS_BUNDLE_VERSION);
+	assert_eq!(bundle.tests.len(), 1);
+	assert_eq!(bundle.questions.len(), 8);
+	assert_eq!(bundle.options.len(), 40);
+	assert!(bundle_errors(&bundle).is_empty());
+
+	let conflicts = repo.query_bundle_conflicts(&bundle).await?;
+	assert!(conflicts
+		.iter()
+		.any(|c| c.resource == "app_sessions" && c.source_id == session_id));
+	assert!(conflicts
+		.iter()
+		.any(|c| c.resource == "app_tests" && c.source_id == test_id));
+
+	let (remapped, id_map) = remap_bundle(&bundle);
+	assert_eq!(id_map.len(), 1 + 1 + 8 + 40);
+	repo.query_import_bundle(&remapped).await?;
+	let imported = repo
+		.query_session_by_id(&remapped.session.id.id.to_raw())
+		.await?;
+	assert_eq!(imported.name, "Shuffle Session");
+	assert_eq!(imported.tests[0].test.id, remapped.tests[0].id.id.to_raw());
+	assert_eq!(imported.tests[0].test.questions.len(), 8);
+	let original = repo.query_raw_session_by_id(&session_id).await?;
+	assert_eq!(original.tests[0].test.id.to_raw(), test_id);
+	Ok(())
+}
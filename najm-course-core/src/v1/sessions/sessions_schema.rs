@@ -1,15 +1,31 @@
-use crate::TestsDetailSchema;
+use super::{SessionsScoreSelectionEnum, SessionsVisibilityModeEnum};
+use crate::{
+	OptionsSchema, QuestionsSchema, ScoringConfigSchema, ScoringRuleSchema,
+	TestsDetailSchema, TestsSchema,
+};
 use serde::{Deserialize, Serialize};
 use surrealdb::sql::Thing;
 
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct TestSessionsPrerequisiteSchema {
+	pub test: Thing,
+	pub min_gap_minutes: Option<u32>,
+	pub max_gap_minutes: Option<u32>,
+}
+
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct TestSessionsSchema {
 	pub test: Thing,
 	pub shuffle: bool,
+	pub shuffle_options: Option<bool>,
 	pub weight: f32,
 	pub multiplier: f32,
 	pub start_date: String,
 	pub end_date: String,
+	pub duration_minutes: Option<u32>,
+	pub scoring: Option<ScoringConfigSchema>,
+	pub scoring_rule: Option<ScoringRuleSchema>,
+	pub prerequisite: Option<TestSessionsPrerequisiteSchema>,
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
@@ -17,9 +33,58 @@ pub struct TestSessionsDetailSchema {
 	pub test: TestsDetailSchema,
 	pub weight: f32,
 	pub shuffle: bool,
+	pub shuffle_options: Option<bool>,
 	pub multiplier: f32,
 	pub start_date: String,
 	pub end_date: String,
+	pub duration_minutes: Option<u32>,
+	pub scoring: Option<ScoringConfigSchema>,
+	pub scoring_rule: Option<ScoringRuleSchema>,
+	pub prerequisite: Option<TestSessionsPrerequisiteSchema>,
+}
+
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct SessionsEligibilitySchema {
+	#[serde(default)]
+	pub roles: Vec<String>,
+	#[serde(default)]
+	pub groups: Vec<String>,
+	#[serde(default)]
+	pub requires_enrollment: bool,
+	#[serde(default)]
+	pub allow_self_enrollment: bool,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct SessionsRetakePolicySchema {
+	pub max_attempts: u32,
+	pub cooldown_minutes: Option<u32>,
+	pub score_selection: String,
+}
+
+impl Default for SessionsRetakePolicySchema {
+	fn default() -> Self {
+		Self {
+			max_attempts: 1,
+			cooldown_minutes: None,
+			score_selection: SessionsScoreSelectionEnum::Last.to_string(),
+		}
+	}
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct SessionsVisibilitySchema {
+	pub mode: String,
+	pub release_at: Option<String>,
+}
+
+impl Default for SessionsVisibilitySchema {
+	fn default() -> Self {
+		Self {
+			mode: SessionsVisibilityModeEnum::AfterSubmit.to_string(),
+			release_at: None,
+		}
+	}
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
@@ -30,6 +95,10 @@ pub struct SessionsSchema {
 	pub category: String,
 	pub description: String,
 	pub student_type: String,
+	pub passing_grade: Option<f64>,
+	pub eligibility: Option<SessionsEligibilitySchema>,
+	pub retake_policy: Option<SessionsRetakePolicySchema>,
+	pub visibility: Option<SessionsVisibilitySchema>,
 	pub is_active: bool,
 	pub is_deleted: bool,
 	pub created_at: String,
@@ -44,8 +113,36 @@ pub struct SessionsDetailSchema {
 	pub category: String,
 	pub description: String,
 	pub student_type: String,
+	pub passing_grade: Option<f64>,
+	pub eligibility: Option<SessionsEligibilitySchema>,
+	pub retake_policy: Option<SessionsRetakePolicySchema>,
+	pub visibility: Option<SessionsVisibilitySchema>,
 	pub is_active: bool,
 	pub is_deleted: bool,
 	pub created_at: String,
 	pub updated_at: String,
 }
+
+/// A session with everything it needs to be recreated on another database.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct SessionsBundleSchema {
+	pub version: u32,
+	pub exported_at: String,
+	pub session: SessionsSchema,
+	pub tests: Vec<TestsSchema>,
+	pub questions: Vec<QuestionsSchema>,
+	pub options: Vec<OptionsSchema>,
+	#[serde(default)]
+	pub media: Vec<String>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct SessionsBroadcastSchema {
+	pub id: Thing,
+	pub session: Thing,
+	pub test: Option<Thing>,
+	pub question: Option<Thing>,
+	pub message: String,
+	pub sent_by: Option<Thing>,
+	pub created_at: String,
+}
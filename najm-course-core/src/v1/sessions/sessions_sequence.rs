@@ -0,0 +1,124 @@
+use super::{
+	TestSessionsDto, TestSessionsLockDto, TestSessionsLockStatusEnum,
+	TestSessionsPrerequisiteSchema,
+};
+use crate::AttemptsSchema;
+use anyhow::{bail, Result};
+use chrono::{DateTime, Duration, Utc};
+use std::collections::HashMap;
+
+/// Rejects prerequisites that point outside the session, at the test itself,
+/// form a cycle or have an inverted gap window.
+pub fn validate_prerequisites(tests: &[TestSessionsDto]) -> Result<()> {
+	let predecessor: HashMap<&str, &str> = tests
+		.iter()
+		.filter_map(|t| {
+			t.prerequisite
+				.as_ref()
+				.map(|p| (t.test_id.as_str(), p.test_id.as_str()))
+		})
+		.collect();
+	for test in tests {
+		let Some(prerequisite) = &test.prerequisite else {
+			continue;
+		};
+		if prerequisite.test_id == test.test_id
+			|| !tests.iter().any(|t| t.test_id == prerequisite.test_id)
+		{
+			bail!("Prerequisite test must be another test in the session");
+		}
+		if let (Some(min), Some(max)) =
+			(prerequisite.min_gap_minutes, prerequisite.max_gap_minutes)
+		{
+			if min > max {
+				bail!("Prerequisite minimum gap must not exceed maximum gap");
+			}
+		}
+		let mut current = test.test_id.as_str();
+		for _ in 0..tests.len() {
+			match predecessor.get(current) {
+				Some(next) if *next == test.test_id => {
+					bail!("Prerequisite chain must not be circular")
+				}
+				Some(next) => current = next,
+				None => break,
+			}
+		}
+	}
+	Ok(())
+}
+
+fn lock(
+	status: TestSessionsLockStatusEnum,
+	reason: Option<&str>,
+	available_at: Option<DateTime<Utc>>,
+	available_until: Option<DateTime<Utc>>,
+) -> TestSessionsLockDto {
+	TestSessionsLockDto {
+		status: status.to_string(),
+		reason: reason.map(String::from),
+		available_at: available_at.map(|d| d.to_rfc3339()),
+		available_until: available_until.map(|d| d.to_rfc3339()),
+	}
+}
+
+/// Lock state of a test for one student, given the student's attempt on the
+/// prerequisite and whether the test itself was already started. A started
+/// test is never locked again.
+pub fn test_lock(
+	prerequisite: Option<&TestSessionsPrerequisiteSchema>,
+	predecessor: Option<&AttemptsSchema>,
+	is_started: bool,
+	now: DateTime<Utc>,
+) -> TestSessionsLockDto {
+	let Some(prerequisite) = prerequisite else {
+		return lock(TestSessionsLockStatusEnum::Unlocked, None, None, None);
+	};
+	let finished_at = predecessor
+		.and_then(|a| a.submitted_at.as_deref())
+		.and_then(|d| DateTime::parse_from_rfc3339(d).ok())
+		.map(|d| d.with_timezone(&Utc));
+	let Some(finished_at) = finished_at else {
+		return lock(
+			TestSessionsLockStatusEnum::Locked,
+			Some("Prerequisite test has not been submitted"),
+			None,
+			None,
+		);
+	};
+	let available_at = finished_at
+		+ Duration::minutes(prerequisite.min_gap_minutes.unwrap_or(0) as i64);
+	let available_until = prerequisite
+		.max_gap_minutes
+		.map(|max| finished_at + Duration::minutes(max as i64));
+	if is_started {
+		return lock(
+			TestSessionsLockStatusEnum::Unlocked,
+			None,
+			Some(available_at),
+			available_until,
+		);
+	}
+	if now < available_at {
+		return lock(
+			TestSessionsLockStatusEnum::Waiting,
+			Some("Prerequisite gap has not elapsed"),
+			Some(available_at),
+			available_until,
+		);
+	}
+	if available_until.is_some_and(|until| now > until) {
+		return lock(
+			TestSessionsLockStatusEnum::Closed,
+			Some("Prerequisite gap window has passed"),
+			Some(available_at),
+			available_until,
+		);
+	}
+	lock(
+		TestSessionsLockStatusEnum::Unlocked,
+		None,
+		Some(available_at),
+		available_until,
+	)
+}
@@ -0,0 +1,126 @@
+use super::*;
+use crate::AttemptsSchema;
+use chrono::{Duration, TimeZone, Utc};
+use najm_course_utils::make_thing;
+
+fn test(
+	id: &str,
+	prerequisite: Option<TestSessionsPrerequisiteDto>,
+) -> TestSessionsDto {
+	TestSessionsDto {
+		test_id: id.into(),
+		weight: 1.0,
+		shuffle: false,
+		shuffle_options: None,
+		multiplier: 1.0,
+		start_date: "2025-01-01T00:00:00Z".into(),
+		end_date: "2099-01-01T00:00:00Z".into(),
+		duration_minutes: None,
+		scoring: None,
+		scoring_rule: None,
+		prerequisite,
+	}
+}
+
+fn after(
+	id: &str,
+	min: Option<u32>,
+	max: Option<u32>,
+) -> TestSessionsPrerequisiteDto {
+	TestSessionsPrerequisiteDto {
+		test_id: id.into(),
+		min_gap_minutes: min,
+		max_gap_minutes: max,
+	}
+}
+
+fn submitted_attempt(submitted_at: Option<&str>) -> AttemptsSchema {
+	AttemptsSchema {
+		id: make_thing("app_attempts", "attempt"),
+		user: make_thing("app_users", "user"),
+		session: make_thing("app_sessions", "session"),
+		test: make_thing("app_tests", "first"),
+		status: "submitted".into(),
+		attempt_number: 1,
+		started_at: "2025-01-01T00:00:00Z".into(),
+		deadline_at: "2025-01-01T01:00:00Z".into(),
+		last_heartbeat_at: "2025-01-01T00:00:00Z".into(),
+		submitted_at: submitted_at.map(String::from),
+		question_order: vec![],
+		is_deleted: false,
+		created_at: "2025-01-01T00:00:00Z".into(),
+		updated_at: "2025-01-01T00:00:00Z".into(),
+	}
+}
+
+#[test]
+fn test_validate_prerequisites_should_accept_chain() {
+	let tests = vec![
+		test("first", None),
+		test("second", Some(after("first", Some(10), Some(60)))),
+		test("third", Some(after("second", None, None))),
+	];
+	assert!(validate_prerequisites(&tests).is_ok());
+}
+
+#[test]
+fn test_validate_prerequisites_should_reject_invalid_references() {
+	let missing = vec![test("first", Some(after("other", None, None)))];
+	assert_eq!(
+		validate_prerequisites(&missing).unwrap_err().to_string(),
+		"Prerequisite test must be another test in the session"
+	);
+	let own = vec![test("first", Some(after("first", None, None)))];
+	assert!(validate_prerequisites(&own).is_err());
+	let cycle = vec![
+		test("first", Some(after("second", None, None))),
+		test("second", Some(after("first", None, None))),
+	];
+	assert_eq!(
+		validate_prerequisites(&cycle).unwrap_err().to_string(),
+		"Prerequisite chain must not be circular"
+	);
+	let inverted = vec![
+		test("first", None),
+		test("second", Some(after("first", Some(30), Some(10)))),
+	];
+	assert_eq!(
+		validate_prerequisites(&inverted).unwrap_err().to_string(),
+		"Prerequisite minimum gap must not exceed maximum gap"
+	);
+}
+
+#[test]
+fn test_test_lock_should_follow_gap_window() {
+	let prerequisite =
+		TestSessionsPrerequisiteSchema::from(after("first", Some(10), Some(60)));
+	let submitted = Utc.with_ymd_and_hms(2025, 1, 1, 1, 0, 0).unwrap();
+	let attempt = submitted_attempt(Some("2025-01-01T01:00:00Z"));
+	let status = |predecessor: Option<&AttemptsSchema>, minutes: i64| {
+		test_lock(
+			Some(&prerequisite),
+			predecessor,
+			false,
+			submitted + Duration::minutes(minutes),
+		)
+		.status
+	};
+	assert_eq!(status(None, 0), "locked");
+	assert_eq!(status(Some(&submitted_attempt(None)), 0), "locked");
+	assert_eq!(status(Some(&attempt), 5), "waiting");
+	assert_eq!(status(Some(&attempt), 30), "unlocked");
+	assert_eq!(status(Some(&attempt), 61), "closed");
+	let started = test_lock(
+		Some(&prerequisite),
+		Some(&attempt),
+		true,
+		submitted + Duration::minutes(61),
+	);
+	assert_eq!(started.status, "unlocked");
+	let waiting = test_lock(Some(&prerequisite), Some(&attempt), false, submitted);
+	assert_eq!(
+		waiting.available_at.as_deref(),
+		Some("2025-01-01T01:10:00+00:00")
+	);
+	assert_eq!(test_lock(None, None, false, submitted).status, "unlocked");
+}
@@ -1,18 +1,31 @@
 use super::{
+	bundle_errors, bundle_media, remap_bundle, rename_conflicts,
+	SessionsBroadcastRequestDto, SessionsBundleConflictEnum,
+	SessionsBundleImportRequestDto, SessionsBundleImportResponseDto,
 	SessionsCreateRequestDto, SessionsRepository, SessionsUpdateRequestDto,
+	SessionsViewerDto, SESSIONS_BUNDLE_VERSION,
 };
 use crate::{
-	common_response, success_list_response, success_response, validate_request,
-	AppState, MetaRequestDto, ResponseListSuccessDto, ResponseSuccessDto,
+	attachment_response, common_response, slugify, success_list_response,
+	success_response, validate_request, AppState, EnrollmentsCandidateDto,
+	MetaRequestDto, ResponseListSuccessDto, ResponseSuccessDto,
 };
 use axum::{http::StatusCode, response::Response};
 
 pub struct SessionsService;
 
 impl SessionsService {
-	pub async fn get_session_list(state: &AppState, meta: MetaRequestDto) -> Response {
+	pub async fn get_session_list(
+		state: &AppState,
+		meta: MetaRequestDto,
+		candidate: Option<EnrollmentsCandidateDto>,
+	) -> Response {
 		let repo = SessionsRepository::new(state);
-		match repo.query_session_list(meta).await {
+		let result = match candidate {
+			Some(candidate) => repo.query_session_list_for_user(meta, &candidate).await,
+			None => repo.query_session_list(meta).await,
+		};
+		match result {
 			Ok(data) => {
 				let response = ResponseListSuccessDto {
 					data: data.data,
@@ -24,9 +37,21 @@ impl SessionsService {
 		}
 	}
 
-	pub async fn get_session_by_id(state: &AppState, id: String) -> Response {
+	pub async fn get_session_by_id(
+		state: &AppState,
+		id: String,
+		viewer: SessionsViewerDto,
+	) -> Response {
 		let repo = SessionsRepository::new(state);
-		match repo.query_session_by_id(&id).await {
+		let result = match &viewer.user_id {
+			Some(user_id) => {
+				repo
+					.query_session_by_id_for_user(&id, user_id, viewer.is_reviewer)
+					.await
+			}
+			None => repo.query_session_by_id(&id).await,
+		};
+		match result {
 			Ok(data) => success_response(ResponseSuccessDto { data }),
 			Err(e) => common_response(StatusCode::NOT_FOUND, &e.to_string()),
 		}
@@ -44,7 +69,15 @@ impl SessionsService {
 			Ok(id) => common_response(StatusCode::CREATED, &id),
 			Err(e) => {
 				let msg = e.to_string();
-				if msg.contains("must not be empty") {
+				if msg.contains("must not be empty")
+					|| msg.starts_with("Prerequisite")
+					|| msg.starts_with("Maximum attempts")
+					|| msg == "Invalid score selection"
+					|| msg == "Invalid visibility mode"
+					|| msg == "Invalid date format"
+					|| msg.starts_with("Release time")
+					|| msg == "Scoring strategy not found"
+				{
 					common_response(StatusCode::BAD_REQUEST, &msg)
 				} else {
 					common_response(StatusCode::INTERNAL_SERVER_ERROR, &msg)
@@ -68,6 +101,33 @@ impl SessionsService {
 		}
 	}
 
+	pub async fn broadcast_session(
+		state: &AppState,
+		id: String,
+		payload: SessionsBroadcastRequestDto,
+		sent_by: Option<String>,
+	) -> Response {
+		if let Err((status, message)) = validate_request(&payload) {
+			return common_response(status, &message);
+		}
+		let repo = SessionsRepository::new(state);
+		match repo
+			.query_create_broadcast(&id, payload, sent_by.as_deref())
+			.await
+		{
+			Ok(data) => success_response(ResponseSuccessDto { data }),
+			Err(e) => {
+				let msg = e.to_string();
+				let status = match msg.as_str() {
+					"Session not found" => StatusCode::NOT_FOUND,
+					"Test not found in session" => StatusCode::BAD_REQUEST,
+					_ => StatusCode::INTERNAL_SERVER_ERROR,
+				};
+				common_response(status, &msg)
+			}
+		}
+	}
+
 	pub async fn delete_session(state: &AppState, id: String) -> Response {
 		let repo = SessionsRepository::new(state);
 		match repo.query_raw_session_by_id(&id).await {
@@ -84,4 +144,96 @@ impl SessionsService {
 			Err(e) => common_response(StatusCode::BAD_REQUEST, &e.to_string()),
 		}
 	}
+
+	pub async fn export_session(state: &AppState, id: String) -> Response {
+		let repo = SessionsRepository::new(state);
+		match repo.query_export_bundle(&id).await {
+			Ok(bundle) => match serde_json::to_vec_pretty(&bundle) {
+				Ok(bytes) => attachment_response(
+					"application/json",
+					&format!("{}.json", slugify(&bundle.session.name, "session")),
+					bytes,
+				),
+				Err(e) => common_response(StatusCode::INTERNAL_SERVER_ERROR, &e.to_string()),
+			},
+			Err(e) => {
+				let msg = e.to_string();
+				let status = if msg.contains("not found") {
+					StatusCode::NOT_FOUND
+				} else {
+					StatusCode::INTERNAL_SERVER_ERROR
+				};
+				common_response(status, &msg)
+			}
+		}
+	}
+
+	/// Remaps the bundle to fresh IDs and reports conflicts and missing
+	/// references; writes it only when `dry_run` is false and nothing blocks.
+	pub async fn import_session(
+		state: &AppState,
+		payload: SessionsBundleImportRequestDto,
+	) -> Response {
+		let on_conflict = match payload
+			.on_conflict
+			.as_deref()
+			.map(str::parse::<SessionsBundleConflictEnum>)
+			.transpose()
+		{
+			Ok(mode) => mode.unwrap_or_default(),
+			Err(e) => return common_response(StatusCode::BAD_REQUEST, &e.to_string()),
+		};
+		let mut bundle = payload.bundle;
+		if bundle.version != SESSIONS_BUNDLE_VERSION {
+			return common_response(StatusCode::BAD_REQUEST, "Unsupported bundle version");
+		}
+		let repo = SessionsRepository::new(state);
+		let errors = bundle_errors(&bundle);
+		let conflicts = match repo.query_bundle_conflicts(&bundle).await {
+			Ok(conflicts) => conflicts,
+			Err(e) => {
+				return common_response(StatusCode::INTERNAL_SERVER_ERROR, &e.to_string())
+			}
+		};
+		if on_conflict == SessionsBundleConflictEnum::Rename {
+			rename_conflicts(&mut bundle, &conflicts);
+		}
+		let (remapped, id_map) = remap_bundle(&bundle);
+		let mut response = SessionsBundleImportResponseDto {
+			session_id: remapped.session.id.id.to_raw(),
+			dry_run: true,
+			version: remapped.version,
+			tests_count: remapped.tests.len() as u32,
+			questions_count: remapped.questions.len() as u32,
+			options_count: remapped.options.len() as u32,
+			media: bundle_media(&remapped.questions, &remapped.options),
+			id_map,
+			conflicts,
+			errors,
+		};
+		if payload.dry_run.unwrap_or(true) {
+			return success_response(ResponseSuccessDto { data: response });
+		}
+		if !response.errors.is_empty() {
+			return common_response(
+				StatusCode::UNPROCESSABLE_ENTITY,
+				"Bundle has missing references",
+			);
+		}
+		if !response.conflicts.is_empty()
+			&& on_conflict == SessionsBundleConflictEnum::Fail
+		{
+			return common_response(
+				StatusCode::CONFLICT,
+				"Bundle conflicts with existing records",
+			);
+		}
+		match repo.query_import_bundle(&remapped).await {
+			Ok(_) => {
+				response.dry_run = false;
+				success_response(ResponseSuccessDto { data: response })
+			}
+			Err(e) => common_response(StatusCode::INTERNAL_SERVER_ERROR, &e.to_string()),
+		}
+	}
 }
@@ -0,0 +1,67 @@
+use super::{SessionsVisibilityModeEnum, SessionsVisibilitySchema};
+use crate::{PermissionsEnum, UsersItemDtoRaw};
+use chrono::{DateTime, Utc};
+
+/// Who answer keys, discussions and scores are served to. Reviewers see
+/// everything; students and requests without a resolved user only what the
+/// session has released.
+#[derive(Clone, Debug)]
+pub struct SessionsViewerDto {
+	pub user_id: Option<String>,
+	pub is_reviewer: bool,
+}
+
+impl From<Option<&UsersItemDtoRaw>> for SessionsViewerDto {
+	fn from(value: Option<&UsersItemDtoRaw>) -> Self {
+		let review = PermissionsEnum::ReviewAnswerKeys.to_string();
+		match value {
+			Some(user) => Self {
+				user_id: Some(user.id.id.to_raw()),
+				is_reviewer: user.role.permissions.iter().any(|p| p.name == review),
+			},
+			None => Self {
+				user_id: None,
+				is_reviewer: false,
+			},
+		}
+	}
+}
+
+impl SessionsViewerDto {
+	pub fn can_view(&self, user_id: &str) -> bool {
+		self.is_reviewer || self.user_id.as_deref() == Some(user_id)
+	}
+}
+
+fn parse_date(value: &str) -> Option<DateTime<Utc>> {
+	DateTime::parse_from_rfc3339(value)
+		.ok()
+		.map(|date| date.with_timezone(&Utc))
+}
+
+/// The session ends when its last test closes.
+pub fn session_ends_at<'a>(
+	end_dates: impl IntoIterator<Item = &'a str>,
+) -> Option<DateTime<Utc>> {
+	end_dates.into_iter().filter_map(parse_date).max()
+}
+
+pub fn is_revealed(
+	policy: &SessionsVisibilitySchema,
+	is_submitted: bool,
+	ends_at: Option<DateTime<Utc>>,
+	now: DateTime<Utc>,
+) -> bool {
+	match policy.mode.parse() {
+		Ok(SessionsVisibilityModeEnum::Never) => false,
+		Ok(SessionsVisibilityModeEnum::AfterEnd) => {
+			ends_at.is_some_and(|end| now >= end)
+		}
+		Ok(SessionsVisibilityModeEnum::Scheduled) => policy
+			.release_at
+			.as_deref()
+			.and_then(parse_date)
+			.is_some_and(|release| now >= release),
+		Ok(SessionsVisibilityModeEnum::AfterSubmit) | Err(_) => is_submitted,
+	}
+}
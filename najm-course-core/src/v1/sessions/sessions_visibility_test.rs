@@ -0,0 +1,83 @@
+use super::*;
+use chrono::{Duration, Utc};
+
+fn policy(
+	mode: SessionsVisibilityModeEnum,
+	release_at: Option<&str>,
+) -> SessionsVisibilitySchema {
+	SessionsVisibilitySchema {
+		mode: mode.to_string(),
+		release_at: release_at.map(String::from),
+	}
+}
+
+#[test]
+fn test_can_view_should_allow_owner_and_reviewers() {
+	let student = SessionsViewerDto {
+		user_id: Some("u1".into()),
+		is_reviewer: false,
+	};
+	assert!(student.can_view("u1"));
+	assert!(!student.can_view("u2"));
+	let reviewer = SessionsViewerDto {
+		user_id: Some("u1".into()),
+		is_reviewer: true,
+	};
+	assert!(reviewer.can_view("u2"));
+}
+
+#[test]
+fn test_viewer_without_user_should_not_be_reviewer() {
+	let anonymous = SessionsViewerDto::from(None);
+	assert!(!anonymous.is_reviewer);
+	assert!(!anonymous.can_view("u1"));
+}
+
+#[test]
+fn test_is_revealed_after_submit_should_follow_submission() {
+	let now = Utc::now();
+	let policy = SessionsVisibilitySchema::default();
+	assert!(!is_revealed(&policy, false, None, now));
+	assert!(is_revealed(&policy, true, None, now));
+}
+
+#[test]
+fn test_is_revealed_never_should_stay_hidden() {
+	let now = Utc::now();
+	let policy = policy(SessionsVisibilityModeEnum::Never, None);
+	assert!(!is_revealed(
+		&policy,
+		true,
+		Some(now - Duration::days(1)),
+		now
+	));
+}
+
+#[test]
+fn test_is_revealed_after_end_should_wait_for_last_test() {
+	let now = Utc::now();
+	let policy = policy(SessionsVisibilityModeEnum::AfterEnd, None);
+	let ends_at = session_ends_at(["2025-01-01T00:00:00Z", "2025-02-01T00:00:00Z"]);
+	assert_eq!(
+		ends_at.map(|d| d.to_rfc3339()),
+		Some("2025-02-01T00:00:00+00:00".into())
+	);
+	assert!(is_revealed(&policy, false, ends_at, now));
+	assert!(!is_revealed(
+		&policy,
+		true,
+		Some(now + Duration::hours(1)),
+		now
+	));
+}
+
+#[test]
+fn test_is_revealed_scheduled_should_wait_for_release_time() {
+	let now = Utc::now();
+	let past = (now - Duration::minutes(1)).to_rfc3339();
+	let future = (now + Duration::minutes(1)).to_rfc3339();
+	let released = policy(SessionsVisibilityModeEnum::Scheduled, Some(&past));
+	let pending = policy(SessionsVisibilityModeEnum::Scheduled, Some(&future));
+	assert!(is_revealed(&released, false, None, now));
+	assert!(!is_revealed(&pending, true, None, now));
+}
@@ -3,14 +3,28 @@ use axum::{
 	Router,
 };
 
+pub mod tests_aiken;
 pub mod tests_controller;
 pub mod tests_dto;
+pub mod tests_enum;
+pub mod tests_exchange;
+pub mod tests_generator;
+pub mod tests_gift;
+pub mod tests_import;
+pub mod tests_qti;
 pub mod tests_repository;
 pub mod tests_schema;
 pub mod tests_service;
 
+pub use tests_aiken::*;
 pub use tests_controller::*;
 pub use tests_dto::*;
+pub use tests_enum::*;
+pub use tests_exchange::*;
+pub use tests_generator::*;
+pub use tests_gift::*;
+pub use tests_import::*;
+pub use tests_qti::*;
 pub use tests_repository::*;
 pub use tests_schema::*;
 pub use tests_service::*;
@@ -18,12 +32,21 @@ pub use tests_service::*;
 #[cfg(test)]
 pub mod tests_controller_test;
 #[cfg(test)]
+pub mod tests_exchange_test;
+#[cfg(test)]
+pub mod tests_generator_test;
+#[cfg(test)]
+pub mod tests_import_test;
+#[cfg(test)]
 pub mod tests_repository_test;
 
 pub fn tests_router() -> Router {
 	Router::new()
 		.route("/", get(get_test_list))
 		.route("/create", post(post_create_test))
+		.route("/generate", post(post_generate_test))
+		.route("/import", post(post_import_test))
+		.route("/export/{id}", get(get_export_test))
 		.route("/detail/{id}", get(get_test_by_id))
 		.route("/update/{id}", put(put_update_test))
 		.route("/delete/{id}", delete(delete_test))
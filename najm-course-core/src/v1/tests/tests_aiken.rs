@@ -0,0 +1,134 @@
+use super::{
+	exchange_preview, TestsExchangeItem, TestsExportFile, TestsImportPreviewDto,
+};
+use crate::{OptionsCreateRequestDto, QuestionsCreateRequestDto, QuestionsTypeEnum};
+
+const AIKEN_LETTERS: &str = "ABCDEFGHIJKLMNOPQRSTUVWXYZ";
+
+fn one_line(value: &str) -> String {
+	value.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+fn option_line(line: &str) -> Option<(char, &str)> {
+	let mut chars = line.chars();
+	let letter = chars.next().filter(|c| c.is_ascii_uppercase())?;
+	let rest = chars.as_str();
+	let label = rest
+		.strip_prefix(". ")
+		.or_else(|| rest.strip_prefix(") "))?;
+	Some((letter, label.trim()))
+}
+
+/// Aiken only holds single choice questions with plain text; images,
+/// discussions and points are dropped and other types are skipped.
+pub fn write_aiken(items: &[TestsExchangeItem]) -> TestsExportFile {
+	let mut out = String::new();
+	let mut skipped = 0;
+	for item in items {
+		let is_single = matches!(
+			item.question_type(),
+			QuestionsTypeEnum::SingleChoice | QuestionsTypeEnum::TrueFalse
+		);
+		if !is_single
+			|| item.text().trim().is_empty()
+			|| !(2..=AIKEN_LETTERS.len()).contains(&item.options.len())
+			|| item.correct_count() != 1
+		{
+			skipped += 1;
+			continue;
+		}
+		out.push_str(&one_line(item.text()));
+		out.push('\n');
+		let mut answer = 'A';
+		for (option, letter) in item.options.iter().zip(AIKEN_LETTERS.chars()) {
+			let label = one_line(option.label.as_deref().unwrap_or_default());
+			out.push_str(&format!("{}. {}\n", letter, label));
+			if option.is_correct {
+				answer = letter;
+			}
+		}
+		out.push_str(&format!("ANSWER: {}\n\n", answer));
+	}
+	TestsExportFile {
+		bytes: out.into_bytes(),
+		skipped,
+	}
+}
+
+fn finish_question(
+	question: &[String],
+	options: &[String],
+	answer: &str,
+) -> Result<QuestionsCreateRequestDto, Vec<String>> {
+	let mut messages = vec![];
+	if question.is_empty() {
+		messages.push("Question is required".to_string());
+	}
+	if options.len() < 2 {
+		messages.push("At least two options are required".to_string());
+	}
+	let index = AIKEN_LETTERS.find(answer.trim().to_uppercase().as_str());
+	let index = match index {
+		Some(index) if answer.trim().len() == 1 && index < options.len() => Some(index),
+		_ => {
+			messages.push(format!("Answer {} has no option", answer.trim()));
+			None
+		}
+	};
+	if !messages.is_empty() {
+		return Err(messages);
+	}
+	Ok(QuestionsCreateRequestDto {
+		question: Some(question.join(" ")),
+		discussion: None,
+		question_image_url: None,
+		discussion_image_url: None,
+		question_type: Some(QuestionsTypeEnum::SingleChoice.to_string()),
+		answer_key: None,
+		tags: None,
+		options: options
+			.iter()
+			.enumerate()
+			.map(|(i, label)| OptionsCreateRequestDto {
+				label: Some(label.clone()),
+				image_url: None,
+				is_correct: Some(i) == index,
+				points: None,
+			})
+			.collect(),
+	})
+}
+
+pub fn parse_aiken(text: &str) -> TestsImportPreviewDto {
+	let mut parsed = vec![];
+	let mut question: Vec<String> = vec![];
+	let mut options: Vec<String> = vec![];
+	for line in text.lines().map(str::trim).filter(|l| !l.is_empty()) {
+		if let Some(answer) = line
+			.get(..7)
+			.filter(|p| p.eq_ignore_ascii_case("ANSWER:"))
+			.map(|_| &line[7..])
+		{
+			parsed.push(finish_question(&question, &options, answer));
+			question.clear();
+			options.clear();
+			continue;
+		}
+		let expected = AIKEN_LETTERS.chars().nth(options.len());
+		match option_line(line) {
+			Some((letter, label)) if !question.is_empty() && Some(letter) == expected => {
+				options.push(label.to_string());
+			}
+			_ if !options.is_empty() => {
+				parsed.push(Err(vec!["Missing ANSWER line".to_string()]));
+				options.clear();
+				question = vec![line.to_string()];
+			}
+			_ => question.push(line.to_string()),
+		}
+	}
+	if !question.is_empty() {
+		parsed.push(Err(vec!["Missing ANSWER line".to_string()]));
+	}
+	exchange_preview(parsed)
+}
@@ -1,16 +1,17 @@
 use axum::{
-	extract::{Path, Query},
+	extract::{Multipart, Path, Query},
 	response::IntoResponse,
 	Extension, Json,
 };
 
 use super::{
-	TestsCreateRequestDto, TestsItemDto, TestsResponseListDto, TestsService,
-	TestsUpdateRequestDto,
+	TestsCreateRequestDto, TestsExportQueryDto, TestsGenerateRequestDto,
+	TestsGenerateResponseDto, TestsImportPreviewDto, TestsImportRequestDto,
+	TestsItemDto, TestsResponseListDto, TestsService, TestsUpdateRequestDto,
 };
 use crate::{
 	permissions_guard, AppState, MessageResponseDto, MetaRequestDto, PermissionsEnum,
-	ResponseListSuccessDto, ResponseSuccessDto,
+	ResponseListSuccessDto, ResponseSuccessDto, SessionsViewerDto, UsersItemDtoRaw,
 };
 
 #[utoipa::path(
@@ -61,6 +62,7 @@ pub async fn get_test_list(
 pub async fn get_test_by_id(
 	headers: axum::http::HeaderMap,
 	Extension(state): Extension<AppState>,
+	extensions: axum::http::Extensions,
 	Path(id): Path<String>,
 ) -> impl IntoResponse {
 	match permissions_guard(
@@ -70,7 +72,10 @@ pub async fn get_test_by_id(
 	)
 	.await
 	{
-		Ok(_) => TestsService::get_test_by_id(&state, id).await,
+		Ok(_) => {
+			let viewer = SessionsViewerDto::from(extensions.get::<UsersItemDtoRaw>());
+			TestsService::get_test_by_id(&state, id, viewer).await
+		}
 		Err(response) => response,
 	}
 }
@@ -102,6 +107,97 @@ pub async fn post_create_test(
 	}
 }
 
+#[utoipa::path(
+	post,
+	security(("Bearer" = [])),
+	path = "/v1/tests/generate",
+	request_body = TestsGenerateRequestDto,
+	responses(
+		(status = 200, description = "Generate test from blueprint", body = ResponseSuccessDto<TestsGenerateResponseDto>),
+		(status = 422, description = "Not enough questions in the bank", body = MessageResponseDto)
+	),
+	tag = "Tests"
+)]
+pub async fn post_generate_test(
+	headers: axum::http::HeaderMap,
+	Extension(state): Extension<AppState>,
+	Json(payload): Json<TestsGenerateRequestDto>,
+) -> impl IntoResponse {
+	match permissions_guard(
+		&headers,
+		state.clone(),
+		vec![PermissionsEnum::CreateTests],
+	)
+	.await
+	{
+		Ok(_) => TestsService::generate_test(&state, payload).await,
+		Err(response) => response,
+	}
+}
+
+#[utoipa::path(
+	post,
+	security(("Bearer" = [])),
+	path = "/v1/tests/import",
+	request_body(content = TestsImportRequestDto, content_type = "multipart/form-data"),
+	responses(
+		(status = 200, description = "Preview or import questions from a CSV, XLSX, QTI 2.1 package, GIFT or Aiken file", body = ResponseSuccessDto<TestsImportPreviewDto>),
+		(status = 422, description = "The file has invalid rows", body = MessageResponseDto)
+	),
+	tag = "Tests"
+)]
+pub async fn post_import_test(
+	headers: axum::http::HeaderMap,
+	Extension(state): Extension<AppState>,
+	multipart: Multipart,
+) -> impl IntoResponse {
+	match permissions_guard(
+		&headers,
+		state.clone(),
+		vec![PermissionsEnum::CreateTests],
+	)
+	.await
+	{
+		Ok(_) => TestsService::import_test(&state, multipart).await,
+		Err(response) => response,
+	}
+}
+
+#[utoipa::path(
+	get,
+	security(("Bearer" = [])),
+	path = "/v1/tests/export/{id}",
+	params(
+		("id" = String, Path, description = "Test ID"),
+		("format" = Option<String>, Query, description = "qti (default), gift or aiken"),
+	),
+	responses(
+		(status = 200, description = "Download the test questions; x-skipped-questions counts the questions the format cannot hold", content_type = "application/octet-stream", body = Vec<u8>),
+		(status = 404, description = "Test not found", body = MessageResponseDto)
+	),
+	tag = "Tests"
+)]
+pub async fn get_export_test(
+	headers: axum::http::HeaderMap,
+	Extension(state): Extension<AppState>,
+	Path(id): Path<String>,
+	Query(query): Query<TestsExportQueryDto>,
+) -> impl IntoResponse {
+	match permissions_guard(
+		&headers,
+		state.clone(),
+		vec![
+			PermissionsEnum::ReadDetailTests,
+			PermissionsEnum::ReviewAnswerKeys,
+		],
+	)
+	.await
+	{
+		Ok(_) => TestsService::export_test(&state, id, query).await,
+		Err(response) => response,
+	}
+}
+
 #[utoipa::path(
 	put,
 	security(("Bearer" = [])),
@@ -2,15 +2,22 @@ use crate::{
 	create_mock_app_state,
 	v1::{
 		options::OptionsCreateRequestDto,
-		questions::QuestionsCreateRequestDto,
-		tests::{tests_router, TestsCreateRequestDto, TestsRepository},
+		questions::{QuestionsCreateRequestDto, QuestionsTagsDto},
+		tests::{
+			tests_repository_test::seed_bank_questions, tests_router,
+			TestsBlueprintItemDto, TestsCreateRequestDto, TestsGenerateRequestDto,
+			TestsRepository,
+		},
 	},
 	AppState, PermissionsEnum, TestsResponseListDto,
 };
 use axum::{Extension, Router};
-use axum_test::TestServer;
+use axum_test::{
+	multipart::{MultipartForm, Part},
+	TestServer,
+};
 use najm_course_entities::ResponseListSuccessDto;
-use najm_course_utils::authorized;
+use najm_course_utils::{authorized, test_auth_token_with_permissions};
 use surrealdb::Uuid;
 
 fn create_test_app(state: AppState) -> TestServer {
@@ -35,6 +42,9 @@ fn generate_question_payload() -> QuestionsCreateRequestDto {
 		discussion: Some("Discuss here".into()),
 		question_image_url: None,
 		discussion_image_url: None,
+		question_type: None,
+		answer_key: None,
+		tags: None,
 		options: vec![generate_option("A", false), generate_option("B", true)],
 	}
 }
@@ -43,6 +53,7 @@ fn generate_test_payload(name: &str) -> TestsCreateRequestDto {
 	TestsCreateRequestDto {
 		name: name.to_string(),
 		questions: vec![generate_question_payload()],
+		question_ids: None,
 	}
 }
 
@@ -146,6 +157,7 @@ async fn test_create_test_should_fail_if_no_questions() {
 	let payload = TestsCreateRequestDto {
 		name: "Test Without Questions".into(),
 		questions: vec![],
+		question_ids: None,
 	};
 	let res = authorized(
 		&server,
@@ -363,6 +375,7 @@ async fn test_create_test_should_fail_if_question_has_no_option() {
 	let payload = TestsCreateRequestDto {
 		name: "No Option Question".into(),
 		questions: vec![question],
+		question_ids: None,
 	};
 	let res = authorized(
 		&server,
@@ -418,3 +431,188 @@ async fn test_update_test_should_fail_if_option_missing_id() {
 	.await;
 	assert_eq!(res.status_code(), 422);
 }
+
+#[tokio::test]
+async fn test_create_test_should_fail_if_bank_question_not_found() {
+	let state = create_mock_app_state().await;
+	let server = create_test_app(state);
+	let payload = TestsCreateRequestDto {
+		name: "Missing Bank Question".into(),
+		questions: vec![],
+		question_ids: Some(vec![Uuid::new_v4().to_string()]),
+	};
+	let res = authorized(
+		&server,
+		"POST",
+		"/v1/tests/create",
+		vec![&PermissionsEnum::CreateTests.to_string()],
+		Some(&payload),
+	)
+	.await;
+	assert_eq!(res.status_code(), 404);
+}
+
+#[tokio::test]
+async fn test_generate_test_should_return_422_if_bank_is_short() {
+	let state = create_mock_app_state().await;
+	let server = create_test_app(state);
+	let payload = TestsGenerateRequestDto {
+		name: "Generated Tryout".into(),
+		blueprint: vec![TestsBlueprintItemDto {
+			tags: QuestionsTagsDto {
+				subject: Some(format!("Subject {}", Uuid::new_v4())),
+				..Default::default()
+			},
+			count: 1,
+		}],
+		seed: None,
+		preview: Some(true),
+		cohort_student_type: None,
+		cohort_user_ids: None,
+	};
+	let res = authorized(
+		&server,
+		"POST",
+		"/v1/tests/generate",
+		vec![&PermissionsEnum::CreateTests.to_string()],
+		Some(&payload),
+	)
+	.await;
+	assert_eq!(res.status_code(), 422);
+}
+
+#[tokio::test]
+async fn test_generate_test_should_return_200() {
+	let state = create_mock_app_state().await;
+	let subject = format!("Subject {}", Uuid::new_v4());
+	seed_bank_questions(&state, &subject, "algebra", "easy", 2).await;
+	let server = create_test_app(state);
+	let payload = TestsGenerateRequestDto {
+		name: "Generated Tryout".into(),
+		blueprint: vec![TestsBlueprintItemDto {
+			tags: QuestionsTagsDto {
+				subject: Some(subject),
+				..Default::default()
+			},
+			count: 2,
+		}],
+		seed: Some("fixed".into()),
+		preview: None,
+		cohort_student_type: None,
+		cohort_user_ids: None,
+	};
+	let res = authorized(
+		&server,
+		"POST",
+		"/v1/tests/generate",
+		vec![&PermissionsEnum::CreateTests.to_string()],
+		Some(&payload),
+	)
+	.await;
+	assert_eq!(res.status_code(), 200);
+}
+
+fn import_form(csv: &str, dry_run: bool) -> MultipartForm {
+	MultipartForm::new()
+		.add_part(
+			"file",
+			Part::bytes(csv.as_bytes().to_vec()).file_name("questions.csv"),
+		)
+		.add_text("name", "Imported Tryout")
+		.add_text("dry_run", dry_run)
+}
+
+#[tokio::test]
+async fn test_import_test_should_preview_then_create() {
+	let state = create_mock_app_state().await;
+	let server = create_test_app(state);
+	let token =
+		test_auth_token_with_permissions(
+			vec![&PermissionsEnum::CreateTests.to_string()],
+		);
+	let csv = "question,option_a,option_b,correct_key,points\n1 + 1 = ?,1,2,B,4\n";
+
+	let preview = server
+		.post("/v1/tests/import")
+		.add_header("Authorization", &token)
+		.multipart(import_form(csv, true))
+		.await;
+	assert_eq!(preview.status_code(), 200);
+	let body = preview.json::<serde_json::Value>();
+	assert_eq!(body["data"]["dry_run"], true);
+	assert_eq!(body["data"]["valid_rows"], 1);
+	assert!(body["data"]["test_id"].is_null());
+
+	let imported = server
+		.post("/v1/tests/import")
+		.add_header("Authorization", &token)
+		.multipart(import_form(csv, false))
+		.await;
+	assert_eq!(imported.status_code(), 200);
+	let body = imported.json::<serde_json::Value>();
+	assert_eq!(body["data"]["dry_run"], false);
+	assert!(body["data"]["test_id"].is_string());
+}
+
+#[tokio::test]
+async fn test_import_test_should_return_422_if_rows_are_invalid() {
+	let state = create_mock_app_state().await;
+	let server = create_test_app(state);
+	let token =
+		test_auth_token_with_permissions(
+			vec![&PermissionsEnum::CreateTests.to_string()],
+		);
+	let csv = "question,option_a,option_b,correct_key\nMissing key,1,2,\n";
+	let res = server
+		.post("/v1/tests/import")
+		.add_header("Authorization", &token)
+		.multipart(import_form(csv, false))
+		.await;
+	assert_eq!(res.status_code(), 422);
+}
+
+#[tokio::test]
+async fn test_export_test_should_return_imported_gift_questions_as_aiken() {
+	let state = create_mock_app_state().await;
+	let server = create_test_app(state);
+	let token = test_auth_token_with_permissions(vec![
+		&PermissionsEnum::CreateTests.to_string(),
+		&PermissionsEnum::ReadDetailTests.to_string(),
+		&PermissionsEnum::ReviewAnswerKeys.to_string(),
+	]);
+	let gift = "::Q1:: 1 + 1 = ? {~1 =2}\n\n::Q2:: Explain addition. {}\n";
+	let form = MultipartForm::new()
+		.add_part(
+			"file",
+			Part::bytes(gift.as_bytes().to_vec()).file_name("questions.txt"),
+		)
+		.add_text("format", "gift")
+		.add_text("name", "Gift Tryout")
+		.add_text("dry_run", false);
+	let imported = server
+		.post("/v1/tests/import")
+		.add_header("Authorization", &token)
+		.multipart(form)
+		.await;
+	assert_eq!(imported.status_code(), 200);
+	let body = imported.json::<serde_json::Value>();
+	let test_id = body["data"]["test_id"].as_str().unwrap().to_string();
+
+	let res = server
+		.get(&format!("/v1/tests/export/{}?format=aiken", test_id))
+		.add_header("Authorization", &token)
+		.await;
+	assert_eq!(res.status_code(), 200);
+	assert_eq!(res.header("x-skipped-questions"), "1");
+	assert_eq!(
+		res.header("content-disposition"),
+		"attachment; filename=\"gift-tryout.txt\""
+	);
+	assert_eq!(res.text(), "1 + 1 = ?\nA. 1\nB. 2\nANSWER: B\n\n");
+
+	let res = server
+		.get(&format!("/v1/tests/export/{}?format=csv", test_id))
+		.add_header("Authorization", &token)
+		.await;
+	assert_eq!(res.status_code(), 400);
+}
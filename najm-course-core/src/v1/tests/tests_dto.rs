@@ -1,6 +1,7 @@
 use super::tests_schema::TestsSchema;
 use crate::{
-	questions::QuestionsItemDto, QuestionsCreateRequestDto, QuestionsUpdateRequestDto,
+	questions::QuestionsItemDto, QuestionsCreateRequestDto, QuestionsTagsDto,
+	QuestionsUpdateRequestDto,
 };
 use serde::{Deserialize, Serialize};
 use utoipa::ToSchema;
@@ -11,6 +12,8 @@ pub struct TestsCreateRequestDto {
 	#[validate(length(min = 1, message = "Name must not be empty"))]
 	pub name: String,
 	pub questions: Vec<QuestionsCreateRequestDto>,
+	#[serde(default)]
+	pub question_ids: Option<Vec<String>>,
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize, ToSchema, Validate)]
@@ -18,6 +21,84 @@ pub struct TestsUpdateRequestDto {
 	#[validate(length(min = 1, message = "Name must not be empty"))]
 	pub name: String,
 	pub questions: Vec<QuestionsUpdateRequestDto>,
+	#[serde(default)]
+	pub question_ids: Option<Vec<String>>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, ToSchema, Validate)]
+pub struct TestsBlueprintItemDto {
+	pub tags: QuestionsTagsDto,
+	#[validate(range(min = 1, message = "Count must be at least 1"))]
+	#[schema(example = 10)]
+	pub count: u32,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, ToSchema, Validate)]
+pub struct TestsGenerateRequestDto {
+	#[validate(length(min = 1, message = "Name must not be empty"))]
+	pub name: String,
+	#[validate(length(min = 1, message = "Blueprint must not be empty"))]
+	#[validate]
+	pub blueprint: Vec<TestsBlueprintItemDto>,
+	#[schema(example = "tryout-2025-05")]
+	pub seed: Option<String>,
+	pub preview: Option<bool>,
+	pub cohort_student_type: Option<String>,
+	pub cohort_user_ids: Option<Vec<String>>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, ToSchema)]
+pub struct TestsGenerateSectionDto {
+	pub tags: QuestionsTagsDto,
+	pub requested: u32,
+	pub available: u32,
+	pub question_ids: Vec<String>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, ToSchema)]
+pub struct TestsGenerateResponseDto {
+	pub test_id: Option<String>,
+	pub name: String,
+	pub seed: String,
+	pub preview: bool,
+	pub excluded_count: u32,
+	pub sections: Vec<TestsGenerateSectionDto>,
+}
+
+/// Multipart form of the question import. `dry_run` defaults to true; send
+/// `false` with either `name` or `test_id` to create the questions.
+/// `format` is taken from the file extension when missing.
+#[derive(Clone, Debug, Serialize, Deserialize, ToSchema)]
+pub struct TestsImportRequestDto {
+	#[schema(format = Binary, value_type = String)]
+	pub file: String,
+	#[schema(example = "qti")]
+	pub format: Option<String>,
+	pub name: Option<String>,
+	pub test_id: Option<String>,
+	pub dry_run: Option<bool>,
+}
+
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct TestsExportQueryDto {
+	pub format: Option<String>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, ToSchema)]
+pub struct TestsImportRowErrorDto {
+	#[schema(example = 3)]
+	pub row: u32,
+	pub messages: Vec<String>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, ToSchema)]
+pub struct TestsImportPreviewDto {
+	pub test_id: Option<String>,
+	pub dry_run: bool,
+	pub total_rows: u32,
+	pub valid_rows: u32,
+	pub errors: Vec<TestsImportRowErrorDto>,
+	pub questions: Vec<QuestionsCreateRequestDto>,
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize, ToSchema)]
@@ -71,4 +152,15 @@ impl TestsItemDto {
 			updated_at: value.updated_at,
 		}
 	}
+
+	pub fn without_answer_key(self) -> Self {
+		Self {
+			questions: self
+				.questions
+				.into_iter()
+				.map(QuestionsItemDto::without_answer_key)
+				.collect(),
+			..self
+		}
+	}
 }
@@ -0,0 +1,79 @@
+use std::fmt;
+
+/// File formats questions can be imported from or exported to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TestsExchangeFormatEnum {
+	Csv,
+	Xlsx,
+	Qti,
+	Gift,
+	Aiken,
+}
+
+impl TestsExchangeFormatEnum {
+	/// The explicit `format` wins; otherwise the file extension decides.
+	/// Plain `.txt` files may hold GIFT or Aiken, so they need the format.
+	pub fn resolve(format: Option<&str>, file_name: &str) -> anyhow::Result<Self> {
+		if let Some(format) = format {
+			return format.parse();
+		}
+		let file_name = file_name.to_lowercase();
+		match file_name.rsplit_once('.').map(|(_, ext)| ext) {
+			Some("csv") => Ok(Self::Csv),
+			Some("xlsx") => Ok(Self::Xlsx),
+			Some("zip") => Ok(Self::Qti),
+			Some("gift") => Ok(Self::Gift),
+			Some("txt") => anyhow::bail!("Format is required for .txt files"),
+			_ => anyhow::bail!("Unsupported file type"),
+		}
+	}
+
+	pub fn file_extension(&self) -> &'static str {
+		match self {
+			Self::Csv => "csv",
+			Self::Xlsx => "xlsx",
+			Self::Qti => "zip",
+			Self::Gift => "gift",
+			Self::Aiken => "txt",
+		}
+	}
+
+	pub fn content_type(&self) -> &'static str {
+		match self {
+			Self::Csv => "text/csv; charset=utf-8",
+			Self::Xlsx => {
+				"application/vnd.openxmlformats-officedocument.spreadsheetml.sheet"
+			}
+			Self::Qti => "application/zip",
+			Self::Gift | Self::Aiken => "text/plain; charset=utf-8",
+		}
+	}
+}
+
+impl fmt::Display for TestsExchangeFormatEnum {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		let format_str = match self {
+			Self::Csv => "csv",
+			Self::Xlsx => "xlsx",
+			Self::Qti => "qti",
+			Self::Gift => "gift",
+			Self::Aiken => "aiken",
+		};
+		write!(f, "{}", format_str)
+	}
+}
+
+impl std::str::FromStr for TestsExchangeFormatEnum {
+	type Err = anyhow::Error;
+
+	fn from_str(value: &str) -> Result<Self, Self::Err> {
+		match value.to_lowercase().as_str() {
+			"csv" => Ok(Self::Csv),
+			"xlsx" => Ok(Self::Xlsx),
+			"qti" => Ok(Self::Qti),
+			"gift" => Ok(Self::Gift),
+			"aiken" => Ok(Self::Aiken),
+			_ => anyhow::bail!("Unsupported format"),
+		}
+	}
+}
@@ -0,0 +1,144 @@
+use super::{
+	fetch_qti_images, write_aiken, write_gift, write_qti, TestsExchangeFormatEnum,
+	TestsImportPreviewDto, TestsImportRowErrorDto,
+};
+use crate::{
+	validate_question_shape, OptionsSchema, QuestionsCreateRequestDto,
+	QuestionsSchema, QuestionsTypeEnum,
+};
+use anyhow::{bail, Result};
+use najm_course_libs::Env;
+use najm_course_utils::slugify;
+use std::collections::HashMap;
+
+#[derive(Clone, Debug)]
+pub struct TestsExchangeItem {
+	pub question: QuestionsSchema,
+	pub options: Vec<OptionsSchema>,
+}
+
+impl TestsExchangeItem {
+	pub fn question_type(&self) -> QuestionsTypeEnum {
+		QuestionsTypeEnum::resolve(self.question.question_type.as_deref())
+			.unwrap_or_default()
+	}
+
+	pub fn text(&self) -> &str {
+		self.question.question.as_deref().unwrap_or_default()
+	}
+
+	pub fn discussion(&self) -> Option<&str> {
+		self
+			.question
+			.discussion
+			.as_deref()
+			.filter(|d| !d.trim().is_empty())
+	}
+
+	pub fn correct_count(&self) -> usize {
+		self.options.iter().filter(|o| o.is_correct).count()
+	}
+
+	pub fn image_urls(&self) -> Vec<&str> {
+		[
+			self.question.question_image_url.as_deref(),
+			self.question.discussion_image_url.as_deref(),
+		]
+		.into_iter()
+		.chain(self.options.iter().map(|o| o.image_url.as_deref()))
+		.flatten()
+		.collect()
+	}
+}
+
+#[derive(Clone, Debug)]
+pub struct TestsExchangeAsset {
+	pub path: String,
+	pub bytes: Vec<u8>,
+}
+
+#[derive(Clone, Debug)]
+pub struct TestsExportFile {
+	pub bytes: Vec<u8>,
+	pub skipped: u32,
+}
+
+pub fn exchange_text(bytes: &[u8]) -> Result<String> {
+	let Ok(text) = std::str::from_utf8(bytes) else {
+		bail!("File must be UTF-8 text");
+	};
+	Ok(text.trim_start_matches('\u{feff}').to_string())
+}
+
+pub fn exchange_preview(
+	parsed: Vec<Result<QuestionsCreateRequestDto, Vec<String>>>,
+) -> TestsImportPreviewDto {
+	let mut preview = TestsImportPreviewDto {
+		test_id: None,
+		dry_run: true,
+		total_rows: parsed.len() as u32,
+		valid_rows: 0,
+		errors: vec![],
+		questions: vec![],
+	};
+	for (index, result) in parsed.into_iter().enumerate() {
+		let result = result.and_then(|question| {
+			let correct: Vec<bool> =
+				question.options.iter().map(|o| o.is_correct).collect();
+			match validate_question_shape(
+				question.question_type.as_deref(),
+				&correct,
+				question.answer_key.as_ref(),
+			) {
+				Ok(_) => Ok(question),
+				Err(e) => Err(vec![e.to_string()]),
+			}
+		});
+		match result {
+			Ok(question) => preview.questions.push(question),
+			Err(messages) => preview.errors.push(TestsImportRowErrorDto {
+				row: index as u32 + 1,
+				messages,
+			}),
+		}
+	}
+	preview.valid_rows = preview.questions.len() as u32;
+	preview
+}
+
+pub async fn write_exchange(
+	format: TestsExchangeFormatEnum,
+	items: &[TestsExchangeItem],
+) -> Result<TestsExportFile> {
+	match format {
+		TestsExchangeFormatEnum::Qti => {
+			let storage_endpoint = Env::new().minio_endpoint;
+			write_qti(items, &fetch_qti_images(items, &storage_endpoint).await)
+		}
+		TestsExchangeFormatEnum::Gift => Ok(write_gift(items)),
+		TestsExchangeFormatEnum::Aiken => Ok(write_aiken(items)),
+		_ => bail!("Unsupported format"),
+	}
+}
+
+pub fn export_file_name(test_name: &str, format: TestsExchangeFormatEnum) -> String {
+	format!("{}.{}", slugify(test_name, "test"), format.file_extension())
+}
+
+pub fn replace_asset_urls(
+	questions: &mut [QuestionsCreateRequestDto],
+	urls: &HashMap<String, String>,
+) {
+	let replace = |value: &mut Option<String>| {
+		if let Some(url) = value.as_ref().and_then(|path| urls.get(path)) {
+			*value = Some(url.clone());
+		}
+	};
+	for question in questions {
+		replace(&mut question.question_image_url);
+		replace(&mut question.discussion_image_url);
+		for option in question.options.iter_mut() {
+			replace(&mut option.image_url);
+		}
+	}
+}
@@ -0,0 +1,303 @@
+use super::*;
+use crate::{
+	OptionsCreateRequestDto, QuestionsAnswerKeyDto, QuestionsCreateRequestDto,
+};
+use std::collections::HashMap;
+
+const IMAGE_URL: &str = "https://cdn.example.com/images/diagram.png";
+
+type OptionSummary = (Option<String>, bool, Option<i32>, Option<String>);
+
+fn option(
+	label: &str,
+	is_correct: bool,
+	points: Option<i32>,
+) -> OptionsCreateRequestDto {
+	OptionsCreateRequestDto {
+		label: Some(label.into()),
+		image_url: None,
+		is_correct,
+		points,
+	}
+}
+
+fn question(
+	text: &str,
+	question_type: &str,
+	options: Vec<OptionsCreateRequestDto>,
+) -> QuestionsCreateRequestDto {
+	QuestionsCreateRequestDto {
+		question: Some(text.into()),
+		discussion: None,
+		question_image_url: None,
+		discussion_image_url: None,
+		question_type: Some(question_type.into()),
+		answer_key: None,
+		tags: None,
+		options,
+	}
+}
+
+fn numeric_key(
+	answer: f64,
+	tolerance: Option<f64>,
+	points: Option<i32>,
+) -> QuestionsAnswerKeyDto {
+	QuestionsAnswerKeyDto {
+		numeric_answer: Some(answer),
+		numeric_tolerance: tolerance,
+		points,
+		scoring_rule: None,
+	}
+}
+
+fn sample_questions() -> Vec<QuestionsCreateRequestDto> {
+	let mut single = question(
+		"What is 2 + 3? Use {braces} & <tags> = fine",
+		"single_choice",
+		vec![
+			option("4", false, Some(0)),
+			option("5", true, Some(4)),
+			option("6 ~ 7", false, Some(0)),
+		],
+	);
+	single.discussion = Some("Add both numbers: 2 + 3 = 5.".into());
+	single.question_image_url = Some(IMAGE_URL.into());
+	let multiple = question(
+		"Which numbers are prime?",
+		"multiple_correct",
+		vec![
+			option("2", true, None),
+			option("4", false, None),
+			option("7", true, None),
+		],
+	);
+	let true_false = question(
+		"The sun rises in the east.",
+		"true_false",
+		vec![option("True", true, None), option("False", false, None)],
+	);
+	let mut numeric = question("What is half of five?", "numeric", vec![]);
+	numeric.answer_key = Some(numeric_key(2.5, Some(0.01), Some(2)));
+	let essay = question("Explain photosynthesis.", "essay", vec![]);
+	vec![single, multiple, true_false, numeric, essay]
+}
+
+fn exchange_items(
+	questions: &[QuestionsCreateRequestDto],
+) -> Vec<TestsExchangeItem> {
+	questions
+		.iter()
+		.map(|question| {
+			let (question, options) = question_records(question).unwrap();
+			TestsExchangeItem { question, options }
+		})
+		.collect()
+}
+
+fn options_summary(question: &QuestionsCreateRequestDto) -> Vec<OptionSummary> {
+	question
+		.options
+		.iter()
+		.map(|o| (o.label.clone(), o.is_correct, o.points, o.image_url.clone()))
+		.collect()
+}
+
+fn numeric_summary(
+	question: &QuestionsCreateRequestDto,
+) -> (Option<f64>, Option<f64>) {
+	let key = question.answer_key.as_ref();
+	(
+		key.and_then(|k| k.numeric_answer),
+		key.and_then(|k| k.numeric_tolerance),
+	)
+}
+
+#[test]
+fn test_qti_round_trip_should_keep_questions_options_and_images() {
+	let originals = sample_questions();
+	let images = HashMap::from([(IMAGE_URL.to_string(), b"png-bytes".to_vec())]);
+	let file = write_qti(&exchange_items(&originals), &images).unwrap();
+	assert_eq!(file.skipped, 0);
+
+	let (mut preview, assets) = parse_qti_package(&file.bytes).unwrap();
+	assert!(preview.errors.is_empty(), "{:?}", preview.errors);
+	assert_eq!(preview.total_rows, 5);
+	assert_eq!(assets.len(), 1);
+	assert_eq!(assets[0].path, "images/image-1.png");
+	assert_eq!(assets[0].bytes, b"png-bytes");
+	assert_eq!(
+		preview.questions[0].question_image_url.as_deref(),
+		Some("images/image-1.png")
+	);
+
+	let urls = HashMap::from([(assets[0].path.clone(), IMAGE_URL.to_string())]);
+	replace_asset_urls(&mut preview.questions, &urls);
+	for (original, parsed) in originals.iter().zip(&preview.questions) {
+		assert_eq!(parsed.question, original.question);
+		assert_eq!(parsed.discussion, original.discussion);
+		assert_eq!(parsed.question_image_url, original.question_image_url);
+		assert_eq!(parsed.question_type, original.question_type);
+		assert_eq!(options_summary(parsed), options_summary(original));
+		assert_eq!(numeric_summary(parsed), numeric_summary(original));
+	}
+	let points = preview.questions[3]
+		.answer_key
+		.as_ref()
+		.and_then(|k| k.points);
+	assert_eq!(points, Some(2));
+}
+
+#[test]
+fn test_gift_round_trip_should_keep_text_answers_and_discussions() {
+	let originals = sample_questions();
+	let file = write_gift(&exchange_items(&originals));
+	assert_eq!(file.skipped, 0);
+
+	let text = exchange_text(&file.bytes).unwrap();
+	let preview = parse_gift(&text);
+	assert!(preview.errors.is_empty(), "{:?}", preview.errors);
+	assert_eq!(preview.questions.len(), originals.len());
+	for (original, parsed) in originals.iter().zip(&preview.questions) {
+		assert_eq!(parsed.question, original.question);
+		assert_eq!(parsed.discussion, original.discussion);
+		assert_eq!(parsed.question_type, original.question_type);
+		let labels: Vec<(Option<String>, bool)> = parsed
+			.options
+			.iter()
+			.map(|o| (o.label.clone(), o.is_correct))
+			.collect();
+		let expected: Vec<(Option<String>, bool)> = original
+			.options
+			.iter()
+			.map(|o| (o.label.clone(), o.is_correct))
+			.collect();
+		assert_eq!(labels, expected);
+		assert_eq!(numeric_summary(parsed), numeric_summary(original));
+	}
+}
+
+#[test]
+fn test_aiken_round_trip_should_keep_single_answer_questions() {
+	let originals = sample_questions();
+	let file = write_aiken(&exchange_items(&originals));
+	assert_eq!(file.skipped, 3);
+
+	let text = exchange_text(&file.bytes).unwrap();
+	let preview = parse_aiken(&text);
+	assert!(preview.errors.is_empty(), "{:?}", preview.errors);
+	assert_eq!(preview.questions.len(), 2);
+	for (original, parsed) in [&originals[0], &originals[2]]
+		.into_iter()
+		.zip(&preview.questions)
+	{
+		assert_eq!(parsed.question, original.question);
+		assert_eq!(parsed.question_type.as_deref(), Some("single_choice"));
+		let labels: Vec<(Option<String>, bool)> = parsed
+			.options
+			.iter()
+			.map(|o| (o.label.clone(), o.is_correct))
+			.collect();
+		let expected: Vec<(Option<String>, bool)> = original
+			.options
+			.iter()
+			.map(|o| (o.label.clone(), o.is_correct))
+			.collect();
+		assert_eq!(labels, expected);
+	}
+}
+
+#[test]
+fn test_parsers_should_report_invalid_questions_by_position() {
+	let gift = parse_gift(
+		"// comment\n::Q1:: Capital of France? {=Paris ~London}\n\nNo answer block here\n\n::Q3:: Match {=a -> 1 =b -> 2}",
+	);
+	assert_eq!(gift.valid_rows, 1);
+	let rows: Vec<(u32, Vec<String>)> = gift
+		.errors
+		.into_iter()
+		.map(|e| (e.row, e.messages))
+		.collect();
+	assert_eq!(
+		rows,
+		vec![
+			(2, vec!["Missing answer block".to_string()]),
+			(3, vec!["Matching questions are not supported".to_string()]),
+		]
+	);
+
+	let aiken = parse_aiken("Q1?\nA. x\nB. y\nANSWER: C\n\nQ2?\nA. x\nB. y\n");
+	let rows: Vec<(u32, Vec<String>)> = aiken
+		.errors
+		.into_iter()
+		.map(|e| (e.row, e.messages))
+		.collect();
+	assert_eq!(
+		rows,
+		vec![
+			(1, vec!["Answer C has no option".to_string()]),
+			(2, vec!["Missing ANSWER line".to_string()]),
+		]
+	);
+
+	assert_eq!(
+		parse_qti_package(b"not a zip").unwrap_err().to_string(),
+		"Invalid QTI package"
+	);
+	assert_eq!(
+		exchange_text(&[0xff, 0xfe, 0x00]).unwrap_err().to_string(),
+		"File must be UTF-8 text"
+	);
+}
+
+#[test]
+fn test_export_file_name_should_slug_the_test_name() {
+	assert_eq!(
+		export_file_name("Try Out: UTBK 2025!", TestsExchangeFormatEnum::Qti),
+		"try-out-utbk-2025.zip"
+	);
+	assert_eq!(
+		export_file_name("???", TestsExchangeFormatEnum::Aiken),
+		"test.txt"
+	);
+}
+
+#[test]
+fn test_is_storage_url_should_only_allow_the_storage_host() {
+	let endpoint = "storage.example.com";
+	assert!(is_storage_url(
+		"https://storage.example.com/bucket/image.png",
+		endpoint
+	));
+	assert!(is_storage_url(
+		"https://storage.example.com/bucket/image.png",
+		"https://storage.example.com"
+	));
+	assert!(!is_storage_url(IMAGE_URL, endpoint));
+	assert!(!is_storage_url(
+		"http://169.254.169.254/latest/meta-data",
+		endpoint
+	));
+	assert!(!is_storage_url(
+		"https://storage.example.com:8443/image.png",
+		endpoint
+	));
+	assert!(!is_storage_url("file:///etc/passwd", endpoint));
+}
+
+#[test]
+fn test_parse_qti_package_should_reject_oversized_entries() {
+	use std::io::{Cursor, Write};
+	use zip::{write::SimpleFileOptions, CompressionMethod, ZipWriter};
+
+	let mut zip = ZipWriter::new(Cursor::new(Vec::new()));
+	let options =
+		SimpleFileOptions::default().compression_method(CompressionMethod::Deflated);
+	zip.start_file("item-1.xml", options).unwrap();
+	zip.write_all(&vec![b' '; 11 * 1024 * 1024]).unwrap();
+	let bytes = zip.finish().unwrap().into_inner();
+	assert_eq!(
+		parse_qti_package(&bytes).unwrap_err().to_string(),
+		"QTI package is too large"
+	);
+}
@@ -0,0 +1,22 @@
+use najm_course_utils::{seeded_shuffle, shuffle_seed};
+use std::collections::HashSet;
+
+pub fn pick_questions(
+	candidates: &[String],
+	excluded: &HashSet<String>,
+	count: usize,
+	seed: &str,
+	section: usize,
+) -> Vec<String> {
+	let mut pool: Vec<String> = candidates
+		.iter()
+		.filter(|id| !excluded.contains(*id))
+		.cloned()
+		.collect::<HashSet<_>>()
+		.into_iter()
+		.collect();
+	pool.sort();
+	seeded_shuffle(&mut pool, shuffle_seed(&[seed, &section.to_string()]));
+	pool.truncate(count);
+	pool
+}
@@ -0,0 +1,34 @@
+use super::*;
+use std::collections::HashSet;
+
+fn candidates(n: usize) -> Vec<String> {
+	(0..n).map(|i| format!("q{:02}", i)).collect()
+}
+
+#[test]
+fn test_pick_questions_should_be_reproducible_with_seed() {
+	let pool = candidates(20);
+	let mut reversed = pool.clone();
+	reversed.reverse();
+	let first = pick_questions(&pool, &HashSet::new(), 5, "seed", 0);
+	let second = pick_questions(&reversed, &HashSet::new(), 5, "seed", 0);
+	assert_eq!(first, second);
+	assert_eq!(first.len(), 5);
+}
+
+#[test]
+fn test_pick_questions_should_vary_by_seed() {
+	let pool = candidates(20);
+	let first = pick_questions(&pool, &HashSet::new(), 10, "seed-a", 0);
+	let second = pick_questions(&pool, &HashSet::new(), 10, "seed-b", 0);
+	assert_ne!(first, second);
+}
+
+#[test]
+fn test_pick_questions_should_skip_excluded() {
+	let pool = candidates(6);
+	let excluded: HashSet<String> = pool[..4].iter().cloned().collect();
+	let picked = pick_questions(&pool, &excluded, 5, "seed", 0);
+	assert_eq!(picked.len(), 2);
+	assert!(picked.iter().all(|id| !excluded.contains(id)));
+}
@@ -0,0 +1,342 @@
+use super::{
+	exchange_preview, TestsExchangeItem, TestsExportFile, TestsImportPreviewDto,
+};
+use crate::{
+	OptionsCreateRequestDto, QuestionsAnswerKeyDto, QuestionsCreateRequestDto,
+	QuestionsTypeEnum,
+};
+
+const GIFT_SPECIAL: &str = "~=#{}:\\";
+
+pub fn gift_escape(value: &str) -> String {
+	let mut out = String::with_capacity(value.len());
+	for ch in value.chars() {
+		match ch {
+			'\n' => out.push_str("\\n"),
+			'\r' => {}
+			ch if GIFT_SPECIAL.contains(ch) => {
+				out.push('\\');
+				out.push(ch);
+			}
+			ch => out.push(ch),
+		}
+	}
+	out
+}
+
+pub fn gift_unescape(value: &str) -> String {
+	let mut out = String::with_capacity(value.len());
+	let mut chars = value.chars();
+	while let Some(ch) = chars.next() {
+		if ch != '\\' {
+			out.push(ch);
+			continue;
+		}
+		match chars.next() {
+			Some('n') => out.push('\n'),
+			Some(next) => out.push(next),
+			None => out.push('\\'),
+		}
+	}
+	out.trim().to_string()
+}
+
+fn unescaped_positions(value: &str, targets: &[char]) -> Vec<(usize, char)> {
+	let mut positions = vec![];
+	let mut escaped = false;
+	for (index, ch) in value.char_indices() {
+		if escaped {
+			escaped = false;
+		} else if ch == '\\' {
+			escaped = true;
+		} else if targets.contains(&ch) {
+			positions.push((index, ch));
+		}
+	}
+	positions
+}
+
+fn find_unescaped(value: &str, pattern: &str) -> Option<usize> {
+	let first = pattern.chars().next()?;
+	unescaped_positions(value, &[first])
+		.into_iter()
+		.map(|(index, _)| index)
+		.find(|index| value[*index..].starts_with(pattern))
+}
+
+fn gift_weight(correct_count: usize) -> String {
+	let weight = 100.0 / correct_count as f64;
+	let weight = format!("{:.5}", weight);
+	weight
+		.trim_end_matches('0')
+		.trim_end_matches('.')
+		.to_string()
+}
+
+fn is_true_label(label: Option<&str>) -> bool {
+	matches!(
+		label.map(|l| l.trim().to_lowercase()).as_deref(),
+		Some("true" | "t" | "benar" | "b" | "ya")
+	)
+}
+
+/// GIFT keeps text, discussions, choices, true/false, numeric and essay
+/// questions. Images and points are not part of the format.
+pub fn write_gift(items: &[TestsExchangeItem]) -> TestsExportFile {
+	let mut out = String::new();
+	let mut skipped = 0;
+	for (index, item) in items.iter().enumerate() {
+		let answer = match item.question_type() {
+			QuestionsTypeEnum::SingleChoice if item.correct_count() == 1 => item
+				.options
+				.iter()
+				.map(|o| {
+					let mark = if o.is_correct { '=' } else { '~' };
+					format!(
+						"\n\t{}{}",
+						mark,
+						gift_escape(o.label.as_deref().unwrap_or_default())
+					)
+				})
+				.collect::<String>(),
+			QuestionsTypeEnum::MultipleCorrect if item.correct_count() > 0 => {
+				let weight = gift_weight(item.correct_count());
+				item
+					.options
+					.iter()
+					.map(|o| {
+						let weight = if o.is_correct {
+							weight.as_str()
+						} else {
+							"-100"
+						};
+						format!(
+							"\n\t~%{}%{}",
+							weight,
+							gift_escape(o.label.as_deref().unwrap_or_default())
+						)
+					})
+					.collect::<String>()
+			}
+			QuestionsTypeEnum::TrueFalse if item.correct_count() == 1 => {
+				let correct = item.options.iter().position(|o| o.is_correct);
+				let label = correct.and_then(|i| item.options[i].label.as_deref());
+				let is_true = match label {
+					Some(_) if is_true_label(label) => true,
+					Some(_) => false,
+					None => correct == Some(0),
+				};
+				if is_true { "TRUE" } else { "FALSE" }.to_string()
+			}
+			QuestionsTypeEnum::Numeric => {
+				let key = item.question.answer_key.as_ref();
+				match key.and_then(|k| k.numeric_answer) {
+					Some(answer) => match key.and_then(|k| k.numeric_tolerance) {
+						Some(tolerance) => format!("#{}:{}", answer, tolerance),
+						None => format!("#{}", answer),
+					},
+					None => {
+						skipped += 1;
+						continue;
+					}
+				}
+			}
+			QuestionsTypeEnum::Essay => String::new(),
+			_ => {
+				skipped += 1;
+				continue;
+			}
+		};
+		let discussion = item
+			.discussion()
+			.map(|d| format!("\n####{}", gift_escape(d)))
+			.unwrap_or_default();
+		let closing = if answer.starts_with('\n') || !discussion.is_empty() {
+			"\n"
+		} else {
+			""
+		};
+		out.push_str(&format!(
+			"::Q{}:: {} {{{}{}{}}}\n\n",
+			index + 1,
+			gift_escape(item.text()),
+			answer,
+			discussion,
+			closing
+		));
+	}
+	TestsExportFile {
+		bytes: out.into_bytes(),
+		skipped,
+	}
+}
+
+fn parse_choices(
+	body: &str,
+) -> Result<(QuestionsTypeEnum, Vec<OptionsCreateRequestDto>), Vec<String>> {
+	let marks = unescaped_positions(body, &['=', '~']);
+	if marks.is_empty() {
+		return Err(vec!["Unsupported answer format".to_string()]);
+	}
+	let mut options = vec![];
+	let mut is_weighted = false;
+	for (position, (start, mark)) in marks.iter().enumerate() {
+		let end = marks.get(position + 1).map_or(body.len(), |(end, _)| *end);
+		let mut content = body[start + 1..end].trim();
+		if content.contains("->") {
+			return Err(vec!["Matching questions are not supported".to_string()]);
+		}
+		if let Some(feedback) = find_unescaped(content, "#") {
+			content = content[..feedback].trim();
+		}
+		let mut weight = None;
+		if let Some(rest) = content.strip_prefix('%') {
+			if let Some((value, label)) = rest.split_once('%') {
+				weight = value.trim().parse::<f64>().ok();
+				content = label.trim();
+				is_weighted = true;
+			}
+		}
+		options.push(OptionsCreateRequestDto {
+			label: Some(gift_unescape(content)),
+			image_url: None,
+			is_correct: *mark == '=' || weight.is_some_and(|w| w > 0.0),
+			points: None,
+		});
+	}
+	let correct = options.iter().filter(|o| o.is_correct).count();
+	let question_type = if correct > 1 || (is_weighted && correct > 0) {
+		QuestionsTypeEnum::MultipleCorrect
+	} else {
+		QuestionsTypeEnum::SingleChoice
+	};
+	Ok((question_type, options))
+}
+
+fn parse_numeric(body: &str) -> Result<QuestionsAnswerKeyDto, Vec<String>> {
+	let body = body.trim_start_matches('#').trim();
+	let parse = |value: &str| value.trim().parse::<f64>().ok();
+	let parsed = if let Some((answer, tolerance)) = body.split_once(':') {
+		parse(answer).zip(parse(tolerance).map(Some))
+	} else if let Some((low, high)) = body.split_once("..") {
+		parse(low)
+			.zip(parse(high))
+			.map(|(low, high)| ((low + high) / 2.0, Some((high - low).abs() / 2.0)))
+	} else {
+		parse(body).map(|answer| (answer, None))
+	};
+	match parsed {
+		Some((answer, tolerance)) => Ok(QuestionsAnswerKeyDto {
+			numeric_answer: Some(answer),
+			numeric_tolerance: tolerance,
+			points: None,
+			scoring_rule: None,
+		}),
+		None => Err(vec!["Invalid numeric answer".to_string()]),
+	}
+}
+
+fn parse_gift_question(
+	block: &str,
+) -> Result<QuestionsCreateRequestDto, Vec<String>> {
+	let mut text = block.trim();
+	if let Some(rest) = text.strip_prefix("::") {
+		if let Some(end) = find_unescaped(rest, "::") {
+			text = rest[end + 2..].trim();
+		}
+	}
+	if text.starts_with('[') {
+		if let Some(end) = text.find(']') {
+			text = text[end + 1..].trim();
+		}
+	}
+	let braces = unescaped_positions(text, &['{', '}']);
+	let open = braces.iter().find(|(_, ch)| *ch == '{').map(|(i, _)| *i);
+	let close = braces
+		.iter()
+		.rev()
+		.find(|(_, ch)| *ch == '}')
+		.map(|(i, _)| *i);
+	let (Some(open), Some(close)) = (open, close.filter(|c| Some(*c) > open)) else {
+		return Err(vec!["Missing answer block".to_string()]);
+	};
+	let before = gift_unescape(&text[..open]);
+	let after = gift_unescape(&text[close + 1..]);
+	let question = if after.is_empty() {
+		before
+	} else {
+		format!("{} _____ {}", before, after)
+	};
+	let mut body = text[open + 1..close].trim();
+	let mut discussion = None;
+	if let Some(feedback) = find_unescaped(body, "####") {
+		discussion =
+			Some(gift_unescape(&body[feedback + 4..])).filter(|d| !d.is_empty());
+		body = body[..feedback].trim();
+	}
+
+	let mut answer_key = None;
+	let (question_type, options) = if body.is_empty() {
+		(QuestionsTypeEnum::Essay, vec![])
+	} else if body.starts_with('#') {
+		answer_key = Some(parse_numeric(body)?);
+		(QuestionsTypeEnum::Numeric, vec![])
+	} else {
+		let value = body
+			.split('#')
+			.next()
+			.unwrap_or_default()
+			.trim()
+			.to_uppercase();
+		match value.as_str() {
+			"T" | "TRUE" | "F" | "FALSE" => {
+				let is_true = value.starts_with('T');
+				let option = |label: &str, is_correct: bool| OptionsCreateRequestDto {
+					label: Some(label.into()),
+					image_url: None,
+					is_correct,
+					points: None,
+				};
+				(
+					QuestionsTypeEnum::TrueFalse,
+					vec![option("True", is_true), option("False", !is_true)],
+				)
+			}
+			_ => parse_choices(body)?,
+		}
+	};
+	if question.is_empty() {
+		return Err(vec!["Question is required".to_string()]);
+	}
+	Ok(QuestionsCreateRequestDto {
+		question: Some(question),
+		discussion,
+		question_image_url: None,
+		discussion_image_url: None,
+		question_type: Some(question_type.to_string()),
+		answer_key,
+		tags: None,
+		options,
+	})
+}
+
+pub fn parse_gift(text: &str) -> TestsImportPreviewDto {
+	let mut blocks: Vec<Vec<&str>> = vec![vec![]];
+	for line in text.lines() {
+		let trimmed = line.trim();
+		if trimmed.starts_with("//") || trimmed.starts_with("$CATEGORY:") {
+			continue;
+		}
+		if trimmed.is_empty() {
+			blocks.push(vec![]);
+		} else if let Some(block) = blocks.last_mut() {
+			block.push(line);
+		}
+	}
+	let parsed = blocks
+		.into_iter()
+		.filter(|block| !block.is_empty())
+		.map(|block| parse_gift_question(&block.join("\n")))
+		.collect();
+	exchange_preview(parsed)
+}
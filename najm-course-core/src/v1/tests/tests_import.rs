@@ -0,0 +1,290 @@
+use super::{
+	TestsExchangeFormatEnum, TestsImportPreviewDto, TestsImportRowErrorDto,
+};
+use crate::{
+	validate_question_shape, OptionsCreateRequestDto, QuestionsCreateRequestDto,
+	QuestionsTypeEnum,
+};
+use anyhow::{bail, Result};
+use axum::extract::Multipart;
+use calamine::{Data, Reader, Xlsx};
+use std::io::Cursor;
+
+/// Option columns in sheet order; the letter of each is its correct key.
+pub const IMPORT_OPTION_COLUMNS: [(char, &str); 5] = [
+	('A', "option_a"),
+	('B', "option_b"),
+	('C', "option_c"),
+	('D', "option_d"),
+	('E', "option_e"),
+];
+
+pub const IMPORT_REQUIRED_COLUMNS: [&str; 4] =
+	["question", "option_a", "option_b", "correct_key"];
+
+/// An uploaded question file together with where its questions should go.
+#[derive(Clone, Debug, Default)]
+pub struct TestsImportUpload {
+	pub file_name: String,
+	pub bytes: Vec<u8>,
+	pub format: Option<String>,
+	pub name: Option<String>,
+	pub test_id: Option<String>,
+	pub dry_run: bool,
+}
+
+/// Reads the `file`, `format`, `name`, `test_id` and `dry_run` fields of an
+/// import form. Blank text fields count as missing.
+pub async fn read_import_upload(
+	mut multipart: Multipart,
+) -> Result<TestsImportUpload> {
+	let mut upload = TestsImportUpload {
+		dry_run: true,
+		..Default::default()
+	};
+	let mut has_file = false;
+	loop {
+		let field = match multipart.next_field().await {
+			Ok(Some(field)) => field,
+			Ok(None) => break,
+			Err(_) => bail!("Invalid multipart data"),
+		};
+		let field_name = field.name().unwrap_or_default().to_string();
+		if field_name == "file" {
+			upload.file_name = field.file_name().unwrap_or_default().to_string();
+			let Ok(bytes) = field.bytes().await else {
+				bail!("Failed to read file data");
+			};
+			upload.bytes = bytes.to_vec();
+			has_file = true;
+			continue;
+		}
+		let Ok(text) = field.text().await else {
+			bail!("Invalid multipart data");
+		};
+		let text = Some(text.trim().to_string()).filter(|t| !t.is_empty());
+		match field_name.as_str() {
+			"format" => upload.format = text,
+			"name" => upload.name = text,
+			"test_id" => upload.test_id = text,
+			"dry_run" => {
+				upload.dry_run = !matches!(text.as_deref(), Some("false") | Some("0"))
+			}
+			_ => {}
+		}
+	}
+	if !has_file {
+		bail!("File is required");
+	}
+	Ok(upload)
+}
+
+fn cell_text(cell: &Data) -> String {
+	match cell {
+		Data::Empty => String::new(),
+		Data::Float(value) if value.fract() == 0.0 => format!("{}", *value as i64),
+		other => other.to_string(),
+	}
+}
+
+/// Reads every row of a CSV file or of the first XLSX sheet as text.
+pub fn read_spreadsheet(
+	format: TestsExchangeFormatEnum,
+	bytes: &[u8],
+) -> Result<Vec<Vec<String>>> {
+	if format == TestsExchangeFormatEnum::Csv {
+		let mut reader = csv::ReaderBuilder::new()
+			.has_headers(false)
+			.flexible(true)
+			.from_reader(bytes);
+		let mut rows = vec![];
+		for record in reader.records() {
+			let Ok(record) = record else {
+				bail!("Invalid CSV file");
+			};
+			// Blank lines are skipped by the reader but still count as rows;
+			// the reported position sits before them.
+			let line = record.position().map_or(0, |p| {
+				let skipped = bytes[p.byte() as usize..]
+					.iter()
+					.take_while(|b| matches!(b, b'\r' | b'\n'))
+					.filter(|b| **b == b'\n')
+					.count();
+				p.line() as usize + skipped
+			});
+			while rows.len() + 1 < line {
+				rows.push(vec![]);
+			}
+			rows.push(record.iter().map(String::from).collect());
+		}
+		return Ok(rows);
+	}
+	if format == TestsExchangeFormatEnum::Xlsx {
+		let Ok(mut workbook) = Xlsx::new(Cursor::new(bytes)) else {
+			bail!("Invalid XLSX file");
+		};
+		let Some(Ok(range)) = workbook.worksheet_range_at(0) else {
+			bail!("Invalid XLSX file");
+		};
+		return Ok(
+			range
+				.rows()
+				.map(|row| row.iter().map(cell_text).collect())
+				.collect(),
+		);
+	}
+	bail!("Unsupported file type")
+}
+
+fn column_name(header: &str) -> String {
+	header
+		.trim_start_matches('\u{feff}')
+		.trim()
+		.to_lowercase()
+		.replace([' ', '-'], "_")
+}
+
+fn is_image_url(value: &str) -> bool {
+	value.starts_with("http://") || value.starts_with("https://")
+}
+
+fn parse_row(
+	cell: impl Fn(&str) -> Option<String>,
+) -> Result<QuestionsCreateRequestDto, Vec<String>> {
+	let mut messages = vec![];
+	let question = cell("question");
+	let question_image_url = cell("question_image_url");
+	if question.is_none() && question_image_url.is_none() {
+		messages.push("Question is required".to_string());
+	}
+	let discussion_image_url = cell("discussion_image_url");
+	for url in [&question_image_url, &discussion_image_url]
+		.into_iter()
+		.flatten()
+	{
+		if !is_image_url(url) {
+			messages.push(format!("Invalid image URL: {}", url));
+		}
+	}
+
+	let labels: Vec<(char, Option<String>)> = IMPORT_OPTION_COLUMNS
+		.iter()
+		.map(|(letter, column)| (*letter, cell(column)))
+		.collect();
+	let filled = labels
+		.iter()
+		.take_while(|(_, label)| label.is_some())
+		.count();
+	if labels[filled..].iter().any(|(_, label)| label.is_some()) {
+		messages.push("Options must be filled from A without gaps".to_string());
+	}
+	if filled < 2 {
+		messages.push("At least two options are required".to_string());
+	}
+
+	let mut keys = vec![];
+	match cell("correct_key") {
+		None => messages.push("Correct key is required".to_string()),
+		Some(value) => {
+			for key in value.split([',', ';', ' ']).filter(|k| !k.is_empty()) {
+				let letter = key.to_uppercase();
+				match labels.iter().position(|(l, _)| l.to_string() == letter) {
+					Some(index) if index < filled => keys.push(index),
+					Some(_) => messages.push(format!("Correct key {} has no option", letter)),
+					None => messages.push(format!("Invalid correct key: {}", key)),
+				}
+			}
+		}
+	}
+
+	let points = match cell("points").map(|p| p.parse::<i32>()) {
+		None => None,
+		Some(Ok(points)) if points >= 0 => Some(points),
+		Some(_) => {
+			messages.push("Points must be a non-negative whole number".to_string());
+			None
+		}
+	};
+	if !messages.is_empty() {
+		return Err(messages);
+	}
+
+	let question_type = if keys.len() > 1 {
+		QuestionsTypeEnum::MultipleCorrect
+	} else {
+		QuestionsTypeEnum::SingleChoice
+	};
+	let correct: Vec<bool> = (0..filled).map(|index| keys.contains(&index)).collect();
+	if let Err(e) =
+		validate_question_shape(Some(&question_type.to_string()), &correct, None)
+	{
+		return Err(vec![e.to_string()]);
+	}
+	let options = labels
+		.into_iter()
+		.take(filled)
+		.zip(correct)
+		.map(|((_, label), is_correct)| OptionsCreateRequestDto {
+			label,
+			image_url: None,
+			is_correct,
+			points: points.map(|p| if is_correct { p } else { 0 }),
+		})
+		.collect();
+	Ok(QuestionsCreateRequestDto {
+		question,
+		discussion: cell("discussion"),
+		question_image_url,
+		discussion_image_url,
+		question_type: Some(question_type.to_string()),
+		answer_key: None,
+		tags: None,
+		options,
+	})
+}
+
+/// Validates every data row of a sheet whose first row holds the column
+/// names. Row numbers in the errors match the sheet, header included.
+pub fn parse_spreadsheet(rows: Vec<Vec<String>>) -> Result<TestsImportPreviewDto> {
+	let Some(header) = rows.first() else {
+		bail!("File has no rows");
+	};
+	let columns: Vec<String> = header.iter().map(|h| column_name(h)).collect();
+	for required in IMPORT_REQUIRED_COLUMNS {
+		if !columns.iter().any(|c| c == required) {
+			bail!("Missing column: {}", required);
+		}
+	}
+	let mut preview = TestsImportPreviewDto {
+		test_id: None,
+		dry_run: true,
+		total_rows: 0,
+		valid_rows: 0,
+		errors: vec![],
+		questions: vec![],
+	};
+	for (index, row) in rows.iter().enumerate().skip(1) {
+		if row.iter().all(|value| value.trim().is_empty()) {
+			continue;
+		}
+		preview.total_rows += 1;
+		let cell = |name: &str| {
+			columns
+				.iter()
+				.position(|c| c == name)
+				.and_then(|i| row.get(i))
+				.map(|value| value.trim())
+				.filter(|value| !value.is_empty())
+				.map(String::from)
+		};
+		match parse_row(cell) {
+			Ok(question) => preview.questions.push(question),
+			Err(messages) => preview.errors.push(TestsImportRowErrorDto {
+				row: index as u32 + 1,
+				messages,
+			}),
+		}
+	}
+	preview.valid_rows = preview.questions.len() as u32;
+	Ok(preview)
+}
@@ -0,0 +1,95 @@
+use super::*;
+
+const HEADER: &str = "Question,Discussion,Question Image URL,Discussion Image URL,Option A,Option B,Option C,Option D,Option E,Correct Key,Points";
+
+fn sheet(rows: &[&str]) -> Vec<Vec<String>> {
+	let csv = std::iter::once(HEADER)
+		.chain(rows.iter().copied())
+		.collect::<Vec<_>>()
+		.join("\n");
+	read_spreadsheet(TestsExchangeFormatEnum::Csv, csv.as_bytes()).unwrap()
+}
+
+#[test]
+fn test_parse_spreadsheet_should_build_questions_from_valid_rows() {
+	let preview = parse_spreadsheet(sheet(&[
+		"2 + 2 = ?,Add both numbers,,,3,4,5,,,B,5",
+		"Pick primes,,https://cdn.example.com/q.png,,2,4,5,9,,\"A, C\",",
+	]))
+	.unwrap();
+	assert_eq!(preview.total_rows, 2);
+	assert_eq!(preview.valid_rows, 2);
+	assert!(preview.errors.is_empty());
+
+	let first = &preview.questions[0];
+	assert_eq!(first.question_type.as_deref(), Some("single_choice"));
+	assert_eq!(first.options.len(), 3);
+	assert!(first.options[1].is_correct);
+	assert_eq!(first.options[1].points, Some(5));
+	assert_eq!(first.options[0].points, Some(0));
+
+	let second = &preview.questions[1];
+	assert_eq!(second.question_type.as_deref(), Some("multiple_correct"));
+	assert_eq!(
+		second
+			.options
+			.iter()
+			.map(|o| o.is_correct)
+			.collect::<Vec<_>>(),
+		vec![true, false, true, false]
+	);
+	assert_eq!(second.options[0].points, None);
+}
+
+#[test]
+fn test_parse_spreadsheet_should_report_row_level_errors() {
+	let preview = parse_spreadsheet(sheet(&[
+		",,,,1,2,,,,A,",
+		"",
+		"Gap,,,,1,,3,,,F,two",
+		"Key out of range,,ftp://x,,1,2,,,,D,",
+		"Fine,,,,1,2,,,,a,",
+	]))
+	.unwrap();
+	assert_eq!(preview.total_rows, 4);
+	assert_eq!(preview.valid_rows, 1);
+	let rows: Vec<u32> = preview.errors.iter().map(|e| e.row).collect();
+	assert_eq!(rows, vec![2, 4, 5]);
+	assert_eq!(preview.errors[0].messages, vec!["Question is required"]);
+	assert_eq!(
+		preview.errors[1].messages,
+		vec![
+			"Options must be filled from A without gaps",
+			"At least two options are required",
+			"Invalid correct key: F",
+			"Points must be a non-negative whole number",
+		]
+	);
+	assert_eq!(
+		preview.errors[2].messages,
+		vec!["Invalid image URL: ftp://x", "Correct key D has no option"]
+	);
+}
+
+#[test]
+fn test_parse_spreadsheet_should_reject_missing_columns() {
+	let rows =
+		read_spreadsheet(TestsExchangeFormatEnum::Csv, b"Question,Option A\nQ,1")
+			.unwrap();
+	assert_eq!(
+		parse_spreadsheet(rows).unwrap_err().to_string(),
+		"Missing column: option_b"
+	);
+	assert_eq!(
+		TestsExchangeFormatEnum::resolve(None, "questions.pdf")
+			.unwrap_err()
+			.to_string(),
+		"Unsupported file type"
+	);
+	assert_eq!(
+		TestsExchangeFormatEnum::resolve(None, "questions.txt")
+			.unwrap_err()
+			.to_string(),
+		"Format is required for .txt files"
+	);
+}
@@ -0,0 +1,723 @@
+use super::{
+	exchange_preview, TestsExchangeAsset, TestsExchangeItem, TestsExportFile,
+	TestsImportPreviewDto,
+};
+use crate::{
+	OptionsCreateRequestDto, QuestionsAnswerKeyDto, QuestionsCreateRequestDto,
+	QuestionsTypeEnum,
+};
+use anyhow::{bail, Result};
+use quick_xml::{
+	encoding::Decoder,
+	escape::{escape, unescape},
+	events::{BytesStart, Event},
+	Reader,
+};
+use reqwest::Url;
+use std::{
+	collections::HashMap,
+	io::{Cursor, Read, Write},
+	time::Duration,
+};
+use zip::{write::SimpleFileOptions, CompressionMethod, ZipArchive, ZipWriter};
+
+const QTI_NAMESPACE: &str = "http://www.imsglobal.org/xsd/imsqti_v2p1";
+const QTI_MANIFEST: &str = "imsmanifest.xml";
+const QTI_ITEM_TYPE: &str = "imsqti_item_xmlv2p1";
+const QTI_TRUE_FALSE_CLASS: &str = "true_false";
+const QTI_IMAGE_MAX_BYTES: usize = 5 * 1024 * 1024;
+const QTI_ENTRY_MAX_BYTES: u64 = 10 * 1024 * 1024;
+const QTI_PACKAGE_MAX_BYTES: u64 = 50 * 1024 * 1024;
+const QTI_INTERACTIONS: [&str; 3] = [
+	"choiceInteraction",
+	"textEntryInteraction",
+	"extendedTextInteraction",
+];
+
+#[derive(Debug, Default)]
+struct XmlNode {
+	name: String,
+	attrs: Vec<(String, String)>,
+	children: Vec<XmlChild>,
+}
+
+#[derive(Debug)]
+enum XmlChild {
+	Node(XmlNode),
+	Text(String),
+}
+
+impl XmlNode {
+	fn attr(&self, name: &str) -> Option<&str> {
+		self
+			.attrs
+			.iter()
+			.find(|(key, _)| key == name)
+			.map(|(_, value)| value.as_str())
+	}
+
+	fn nodes(&self) -> impl Iterator<Item = &XmlNode> {
+		self.children.iter().filter_map(|child| match child {
+			XmlChild::Node(node) => Some(node),
+			XmlChild::Text(_) => None,
+		})
+	}
+
+	fn child(&self, name: &str) -> Option<&XmlNode> {
+		self.nodes().find(|node| node.name == name)
+	}
+
+	fn find_all<'a>(
+		&'a self,
+		name: &str,
+		skip: &[&str],
+		found: &mut Vec<&'a XmlNode>,
+	) {
+		for node in self.nodes() {
+			if node.name == name {
+				found.push(node);
+			} else if !skip.contains(&node.name.as_str()) {
+				node.find_all(name, skip, found);
+			}
+		}
+	}
+
+	fn descendants(&self, name: &str) -> Vec<&XmlNode> {
+		let mut found = vec![];
+		self.find_all(name, &[], &mut found);
+		found
+	}
+
+	fn text(&self) -> String {
+		let mut out = String::new();
+		for child in &self.children {
+			match child {
+				XmlChild::Text(text) => out.push_str(text),
+				XmlChild::Node(node) => out.push_str(&node.text()),
+			}
+		}
+		out
+	}
+}
+
+fn element(start: &BytesStart, decoder: Decoder) -> Result<XmlNode> {
+	let mut node = XmlNode {
+		name: String::from_utf8_lossy(start.local_name().as_ref()).into_owned(),
+		..Default::default()
+	};
+	for attr in start.attributes() {
+		let attr = attr?;
+		node.attrs.push((
+			String::from_utf8_lossy(attr.key.local_name().as_ref()).into_owned(),
+			attr.decode_and_unescape_value(decoder)?.into_owned(),
+		));
+	}
+	Ok(node)
+}
+
+fn parse_xml(bytes: &[u8]) -> Result<XmlNode> {
+	let mut reader = Reader::from_reader(bytes);
+	let mut buf = vec![];
+	let mut stack = vec![XmlNode::default()];
+	loop {
+		let event = reader.read_event_into(&mut buf)?;
+		match event {
+			Event::Start(start) => stack.push(element(&start, reader.decoder())?),
+			Event::Empty(start) => {
+				let node = element(&start, reader.decoder())?;
+				if let Some(parent) = stack.last_mut() {
+					parent.children.push(XmlChild::Node(node));
+				}
+			}
+			Event::End(_) => {
+				let Some(node) = stack.pop().filter(|_| !stack.is_empty()) else {
+					bail!("Invalid XML");
+				};
+				if let Some(parent) = stack.last_mut() {
+					parent.children.push(XmlChild::Node(node));
+				}
+			}
+			Event::Text(text) => {
+				if let Some(parent) = stack.last_mut() {
+					let text = unescape(&reader.decoder().decode(&text)?)?.into_owned();
+					parent.children.push(XmlChild::Text(text));
+				}
+			}
+			Event::CData(data) => {
+				if let Some(parent) = stack.last_mut() {
+					let text = String::from_utf8_lossy(&data.into_inner()).into_owned();
+					parent.children.push(XmlChild::Text(text));
+				}
+			}
+			Event::Eof => break,
+			_ => {}
+		}
+		buf.clear();
+	}
+	let root = stack.pop().filter(|_| stack.is_empty());
+	match root.and_then(|root| {
+		root.children.into_iter().find_map(|child| match child {
+			XmlChild::Node(node) => Some(node),
+			XmlChild::Text(_) => None,
+		})
+	}) {
+		Some(node) => Ok(node),
+		None => bail!("Invalid XML"),
+	}
+}
+
+fn image_extension(url: &str) -> &str {
+	let path = url.split(['?', '#']).next().unwrap_or_default();
+	path
+		.rsplit_once('.')
+		.map(|(_, ext)| ext)
+		.filter(|ext| {
+			(1..=5).contains(&ext.len()) && ext.chars().all(char::is_alphanumeric)
+		})
+		.unwrap_or("bin")
+}
+
+fn img_tag(url: Option<&str>, packaged: &HashMap<String, String>) -> String {
+	match url {
+		Some(url) => {
+			let src = packaged.get(url).map(String::as_str).unwrap_or(url);
+			format!("<img src=\"{}\" alt=\"\"/>", escape(src))
+		}
+		None => String::new(),
+	}
+}
+
+fn number(value: f64) -> String {
+	format!("{}", value)
+}
+
+fn qti_item_xml(
+	item: &TestsExchangeItem,
+	identifier: &str,
+	packaged: &HashMap<String, String>,
+) -> String {
+	let question_type = item.question_type();
+	let key = item.question.answer_key.as_ref();
+	let letters = |index: usize| format!("CHOICE_{}", index + 1);
+	let mut declarations = String::new();
+	let mut interaction = String::new();
+	let mut processing = String::new();
+	match question_type {
+		QuestionsTypeEnum::Numeric => {
+			let answer = key.and_then(|k| k.numeric_answer).unwrap_or_default();
+			declarations.push_str(&format!(
+				"<responseDeclaration identifier=\"RESPONSE\" cardinality=\"single\" baseType=\"float\"><correctResponse><value>{}</value></correctResponse></responseDeclaration>",
+				number(answer)
+			));
+			let tolerance = key.and_then(|k| k.numeric_tolerance).unwrap_or_default();
+			processing = format!(
+				"<responseProcessing><responseCondition><responseIf><equal toleranceMode=\"absolute\" tolerance=\"{0} {0}\"><variable identifier=\"RESPONSE\"/><correct identifier=\"RESPONSE\"/></equal><setOutcomeValue identifier=\"SCORE\"><variable identifier=\"MAXSCORE\"/></setOutcomeValue></responseIf></responseCondition></responseProcessing>",
+				number(tolerance)
+			);
+			interaction.push_str(
+				"<textEntryInteraction responseIdentifier=\"RESPONSE\" expectedLength=\"15\"/>",
+			);
+		}
+		QuestionsTypeEnum::Essay => {
+			declarations.push_str(
+				"<responseDeclaration identifier=\"RESPONSE\" cardinality=\"single\" baseType=\"string\"/>",
+			);
+			interaction
+				.push_str("<extendedTextInteraction responseIdentifier=\"RESPONSE\"/>");
+		}
+		_ => {
+			let is_multiple = question_type == QuestionsTypeEnum::MultipleCorrect;
+			let correct: String = item
+				.options
+				.iter()
+				.enumerate()
+				.filter(|(_, o)| o.is_correct)
+				.map(|(i, _)| format!("<value>{}</value>", letters(i)))
+				.collect();
+			let entries: String = item
+				.options
+				.iter()
+				.enumerate()
+				.filter_map(|(i, o)| {
+					o.points.map(|points| {
+						format!(
+							"<mapEntry mapKey=\"{}\" mappedValue=\"{}\"/>",
+							letters(i),
+							points
+						)
+					})
+				})
+				.collect();
+			let mapping = if entries.is_empty() {
+				String::new()
+			} else {
+				format!("<mapping defaultValue=\"0\">{}</mapping>", entries)
+			};
+			declarations.push_str(&format!(
+				"<responseDeclaration identifier=\"RESPONSE\" cardinality=\"{}\" baseType=\"identifier\"><correctResponse>{}</correctResponse>{}</responseDeclaration>",
+				if is_multiple { "multiple" } else { "single" },
+				correct,
+				mapping
+			));
+			let class = if question_type == QuestionsTypeEnum::TrueFalse {
+				format!(" class=\"{}\"", QTI_TRUE_FALSE_CLASS)
+			} else {
+				String::new()
+			};
+			let choices: String = item
+				.options
+				.iter()
+				.enumerate()
+				.map(|(i, o)| {
+					format!(
+						"<simpleChoice identifier=\"{}\">{}{}</simpleChoice>",
+						letters(i),
+						escape(o.label.as_deref().unwrap_or_default()),
+						img_tag(o.image_url.as_deref(), packaged)
+					)
+				})
+				.collect();
+			interaction.push_str(&format!(
+				"<choiceInteraction responseIdentifier=\"RESPONSE\" shuffle=\"false\" maxChoices=\"{}\"{}>{}</choiceInteraction>",
+				if is_multiple { 0 } else { 1 },
+				class,
+				choices
+			));
+			processing = if is_multiple || !mapping.is_empty() {
+				"<responseProcessing template=\"http://www.imsglobal.org/question/qti_v2p1/rptemplates/map_response\"/>".into()
+			} else {
+				"<responseProcessing template=\"http://www.imsglobal.org/question/qti_v2p1/rptemplates/match_correct\"/>".into()
+			};
+		}
+	}
+	let max_score = key
+		.and_then(|k| k.points)
+		.map(|points| {
+			format!(
+				"<outcomeDeclaration identifier=\"MAXSCORE\" cardinality=\"single\" baseType=\"float\"><defaultValue><value>{}</value></defaultValue></outcomeDeclaration>",
+				points
+			)
+		})
+		.unwrap_or_default();
+	let feedback = if item.discussion().is_some()
+		|| item.question.discussion_image_url.is_some()
+	{
+		format!(
+			"<modalFeedback outcomeIdentifier=\"FEEDBACK\" identifier=\"DISCUSSION\" showHide=\"show\"><div>{}</div>{}</modalFeedback>",
+			escape(item.discussion().unwrap_or_default()),
+			img_tag(item.question.discussion_image_url.as_deref(), packaged)
+		)
+	} else {
+		String::new()
+	};
+	format!(
+		"<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n<assessmentItem xmlns=\"{ns}\" identifier=\"{id}\" title=\"{id}\" adaptive=\"false\" timeDependent=\"false\">{declarations}<outcomeDeclaration identifier=\"SCORE\" cardinality=\"single\" baseType=\"float\"/>{max_score}<outcomeDeclaration identifier=\"FEEDBACK\" cardinality=\"single\" baseType=\"identifier\"/><itemBody><div>{text}</div>{image}{interaction}</itemBody>{processing}{feedback}</assessmentItem>\n",
+		ns = QTI_NAMESPACE,
+		id = identifier,
+		text = escape(item.text()),
+		image = img_tag(item.question.question_image_url.as_deref(), packaged),
+	)
+}
+
+/// Only images on the storage host are downloaded; any other URL stays a link.
+pub async fn fetch_qti_images(
+	items: &[TestsExchangeItem],
+	storage_endpoint: &str,
+) -> HashMap<String, Vec<u8>> {
+	let mut images = HashMap::new();
+	let Ok(client) = reqwest::Client::builder()
+		.timeout(Duration::from_secs(10))
+		.redirect(reqwest::redirect::Policy::none())
+		.build()
+	else {
+		return images;
+	};
+	for url in items.iter().flat_map(|item| item.image_urls()) {
+		if images.contains_key(url) || !is_storage_url(url, storage_endpoint) {
+			continue;
+		}
+		if let Some(bytes) = fetch_image(&client, url).await {
+			images.insert(url.to_string(), bytes);
+		}
+	}
+	images
+}
+
+pub fn is_storage_url(url: &str, storage_endpoint: &str) -> bool {
+	let endpoint = if storage_endpoint.contains("://") {
+		storage_endpoint.to_string()
+	} else {
+		format!("https://{}", storage_endpoint)
+	};
+	let (Ok(url), Ok(endpoint)) = (Url::parse(url), Url::parse(&endpoint)) else {
+		return false;
+	};
+	matches!(url.scheme(), "http" | "https")
+		&& url.host_str().is_some()
+		&& url.host_str() == endpoint.host_str()
+		&& url.port() == endpoint.port()
+}
+
+async fn fetch_image(client: &reqwest::Client, url: &str) -> Option<Vec<u8>> {
+	let mut response = match client.get(url).send().await {
+		Ok(response) if response.status().is_success() => response,
+		_ => return None,
+	};
+	if response
+		.content_length()
+		.is_some_and(|length| length > QTI_IMAGE_MAX_BYTES as u64)
+	{
+		return None;
+	}
+	let mut bytes = vec![];
+	while let Some(chunk) = response.chunk().await.ok()? {
+		if bytes.len() + chunk.len() > QTI_IMAGE_MAX_BYTES {
+			return None;
+		}
+		bytes.extend_from_slice(&chunk);
+	}
+	Some(bytes)
+}
+
+pub fn write_qti(
+	items: &[TestsExchangeItem],
+	images: &HashMap<String, Vec<u8>>,
+) -> Result<TestsExportFile> {
+	let mut zip = ZipWriter::new(Cursor::new(Vec::new()));
+	let options =
+		SimpleFileOptions::default().compression_method(CompressionMethod::Deflated);
+	let mut packaged: HashMap<String, String> = HashMap::new();
+	for url in items.iter().flat_map(|i| i.image_urls()) {
+		let Some(bytes) = images.get(url) else {
+			continue;
+		};
+		if packaged.contains_key(url) {
+			continue;
+		}
+		let path = format!(
+			"images/image-{}.{}",
+			packaged.len() + 1,
+			image_extension(url)
+		);
+		zip.start_file(path.as_str(), options)?;
+		zip.write_all(bytes)?;
+		packaged.insert(url.to_string(), path);
+	}
+	let mut resources = String::new();
+	for (index, item) in items.iter().enumerate() {
+		let identifier = format!("item-{}", index + 1);
+		let href = format!("{}.xml", identifier);
+		zip.start_file(href.as_str(), options)?;
+		zip.write_all(qti_item_xml(item, &identifier, &packaged).as_bytes())?;
+		let files: String = item
+			.image_urls()
+			.into_iter()
+			.filter_map(|url| packaged.get(url))
+			.map(|path| format!("<file href=\"{}\"/>", path))
+			.collect();
+		resources.push_str(&format!(
+			"<resource identifier=\"{id}\" type=\"{ty}\" href=\"{href}\"><file href=\"{href}\"/>{files}</resource>",
+			id = identifier,
+			ty = QTI_ITEM_TYPE,
+			href = href,
+			files = files
+		));
+	}
+	zip.start_file(QTI_MANIFEST, options)?;
+	zip.write_all(
+		format!(
+			"<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n<manifest xmlns=\"http://www.imsglobal.org/xsd/imscp_v1p1\" identifier=\"MANIFEST\"><metadata><schema>QTIv2.1 Package</schema><schemaversion>1.0.0</schemaversion></metadata><organizations/><resources>{}</resources></manifest>\n",
+			resources
+		)
+		.as_bytes(),
+	)?;
+	Ok(TestsExportFile {
+		bytes: zip.finish()?.into_inner(),
+		skipped: 0,
+	})
+}
+
+fn package_path(item_href: &str, src: &str) -> String {
+	let mut parts: Vec<&str> = item_href.split('/').collect();
+	parts.pop();
+	for part in src.split('/') {
+		match part {
+			"." | "" => {}
+			".." => {
+				parts.pop();
+			}
+			part => parts.push(part),
+		}
+	}
+	parts.join("/")
+}
+
+struct QtiPackage {
+	files: HashMap<String, Vec<u8>>,
+	assets: HashMap<String, Vec<u8>>,
+}
+
+impl QtiPackage {
+	/// Package paths become the image URLs until the assets are uploaded;
+	/// absolute URLs are kept as they are.
+	fn image(
+		&mut self,
+		node: Option<&XmlNode>,
+		item_href: &str,
+	) -> Result<Option<String>, String> {
+		let Some(src) = node.and_then(|n| n.attr("src")) else {
+			return Ok(None);
+		};
+		if src.starts_with("http://") || src.starts_with("https://") {
+			return Ok(Some(src.to_string()));
+		}
+		let path = package_path(item_href, src);
+		match self.files.get(&path) {
+			Some(bytes) => {
+				self.assets.insert(path.clone(), bytes.clone());
+				Ok(Some(path))
+			}
+			None => Err(format!("Missing image: {}", src)),
+		}
+	}
+
+	fn item(&mut self, href: &str) -> Result<QuestionsCreateRequestDto, Vec<String>> {
+		let Some(bytes) = self.files.get(href) else {
+			return Err(vec![format!("Missing item file: {}", href)]);
+		};
+		let root = match parse_xml(bytes) {
+			Ok(root) if root.name == "assessmentItem" => root,
+			_ => return Err(vec!["Not a QTI assessment item".to_string()]),
+		};
+		let Some(body) = root.child("itemBody") else {
+			return Err(vec!["Missing item body".to_string()]);
+		};
+		let interaction = QTI_INTERACTIONS
+			.iter()
+			.find_map(|name| body.descendants(name).into_iter().next());
+		let Some(interaction) = interaction else {
+			return Err(vec!["Unsupported interaction".to_string()]);
+		};
+		let response_id = interaction.attr("responseIdentifier").unwrap_or("RESPONSE");
+		let declaration = root.nodes().find(|n| {
+			n.name == "responseDeclaration" && n.attr("identifier") == Some(response_id)
+		});
+		let correct: Vec<String> = declaration
+			.and_then(|d| d.child("correctResponse"))
+			.map(|c| c.nodes().map(|v| v.text().trim().to_string()).collect())
+			.unwrap_or_default();
+		let mapping: HashMap<String, i32> = declaration
+			.and_then(|d| d.child("mapping"))
+			.map(|m| {
+				m.nodes()
+					.filter_map(|entry| {
+						let key = entry.attr("mapKey")?;
+						let value = entry.attr("mappedValue")?.parse::<f64>().ok()?;
+						Some((key.to_string(), value.round() as i32))
+					})
+					.collect()
+			})
+			.unwrap_or_default();
+		let max_score = root
+			.nodes()
+			.find(|n| {
+				n.name == "outcomeDeclaration" && n.attr("identifier") == Some("MAXSCORE")
+			})
+			.and_then(|n| n.child("defaultValue"))
+			.and_then(|d| d.child("value"))
+			.and_then(|v| v.text().trim().parse::<f64>().ok())
+			.map(|v| v.round() as i32);
+
+		let mut messages = vec![];
+		let mut text_parts = vec![];
+		let mut question_image = None;
+		for node in body.nodes() {
+			if QTI_INTERACTIONS.contains(&node.name.as_str()) {
+				continue;
+			}
+			if node.name == "img" {
+				question_image = question_image.or(Some(node));
+				continue;
+			}
+			let mut images = vec![];
+			node.find_all("img", &QTI_INTERACTIONS, &mut images);
+			question_image = question_image.or(images.first().copied());
+			let mut interactions = vec![];
+			for name in QTI_INTERACTIONS {
+				node.find_all(name, &[], &mut interactions);
+			}
+			if interactions.is_empty() {
+				let text = node.text().trim().to_string();
+				if !text.is_empty() {
+					text_parts.push(text);
+				}
+			}
+		}
+		if text_parts.is_empty() {
+			if let Some(prompt) = interaction.child("prompt") {
+				text_parts.push(prompt.text().trim().to_string());
+			}
+		}
+		let question_image_url = self.image(question_image, href).unwrap_or_else(|e| {
+			messages.push(e);
+			None
+		});
+
+		let feedback = root.child("modalFeedback");
+		let discussion = feedback
+			.map(|f| f.text().trim().to_string())
+			.filter(|d| !d.is_empty());
+		let discussion_image =
+			feedback.and_then(|f| f.descendants("img").into_iter().next());
+		let discussion_image_url =
+			self.image(discussion_image, href).unwrap_or_else(|e| {
+				messages.push(e);
+				None
+			});
+
+		let mut options = vec![];
+		let mut answer_key = None;
+		let question_type = match interaction.name.as_str() {
+			"choiceInteraction" => {
+				for choice in interaction.descendants("simpleChoice") {
+					let identifier = choice.attr("identifier").unwrap_or_default();
+					let image = choice.descendants("img").into_iter().next();
+					let image_url = self.image(image, href).unwrap_or_else(|e| {
+						messages.push(e);
+						None
+					});
+					let label = choice.text().trim().to_string();
+					options.push(OptionsCreateRequestDto {
+						label: Some(label).filter(|l| !l.is_empty()),
+						image_url,
+						is_correct: correct.iter().any(|c| c == identifier),
+						points: mapping.get(identifier).copied(),
+					});
+				}
+				let is_multiple = declaration.and_then(|d| d.attr("cardinality"))
+					== Some("multiple")
+					|| interaction.attr("maxChoices").is_some_and(|m| m != "1");
+				if interaction
+					.attr("class")
+					.is_some_and(|c| c.split_whitespace().any(|c| c == QTI_TRUE_FALSE_CLASS))
+				{
+					QuestionsTypeEnum::TrueFalse
+				} else if is_multiple {
+					QuestionsTypeEnum::MultipleCorrect
+				} else {
+					QuestionsTypeEnum::SingleChoice
+				}
+			}
+			"textEntryInteraction" => {
+				let is_numeric = matches!(
+					declaration.and_then(|d| d.attr("baseType")),
+					Some("float" | "integer")
+				);
+				let answer = correct.first().and_then(|c| c.parse::<f64>().ok());
+				if !is_numeric || answer.is_none() {
+					messages.push("Only numeric text entry is supported".to_string());
+				}
+				let tolerance = root
+					.descendants("equal")
+					.into_iter()
+					.find_map(|e| e.attr("tolerance"))
+					.and_then(|t| t.split_whitespace().next())
+					.and_then(|t| t.parse::<f64>().ok())
+					.filter(|t| *t > 0.0);
+				answer_key = Some(QuestionsAnswerKeyDto {
+					numeric_answer: answer,
+					numeric_tolerance: tolerance,
+					points: max_score,
+					scoring_rule: None,
+				});
+				QuestionsTypeEnum::Numeric
+			}
+			_ => {
+				answer_key = max_score.map(|points| QuestionsAnswerKeyDto {
+					numeric_answer: None,
+					numeric_tolerance: None,
+					points: Some(points),
+					scoring_rule: None,
+				});
+				QuestionsTypeEnum::Essay
+			}
+		};
+		if text_parts.is_empty() && question_image_url.is_none() {
+			messages.push("Question is required".to_string());
+		}
+		if !messages.is_empty() {
+			return Err(messages);
+		}
+		Ok(QuestionsCreateRequestDto {
+			question: Some(text_parts.join("\n")).filter(|t| !t.is_empty()),
+			discussion,
+			question_image_url,
+			discussion_image_url,
+			question_type: Some(question_type.to_string()),
+			answer_key,
+			tags: None,
+			options,
+		})
+	}
+}
+
+pub fn parse_qti_package(
+	bytes: &[u8],
+) -> Result<(TestsImportPreviewDto, Vec<TestsExchangeAsset>)> {
+	let Ok(mut archive) = ZipArchive::new(Cursor::new(bytes)) else {
+		bail!("Invalid QTI package");
+	};
+	let mut files = HashMap::new();
+	let mut budget = QTI_PACKAGE_MAX_BYTES;
+	for index in 0..archive.len() {
+		let file = archive.by_index(index)?;
+		if file.is_dir() {
+			continue;
+		}
+		let limit = QTI_ENTRY_MAX_BYTES.min(budget);
+		if file.size() > limit {
+			bail!("QTI package is too large");
+		}
+		let name = file.name().to_string();
+		let mut content = vec![];
+		file.take(limit + 1).read_to_end(&mut content)?;
+		if content.len() as u64 > limit {
+			bail!("QTI package is too large");
+		}
+		budget -= content.len() as u64;
+		files.insert(name.trim_start_matches("./").to_string(), content);
+	}
+	let hrefs: Vec<String> = match files.get(QTI_MANIFEST).map(|m| parse_xml(m)) {
+		Some(Ok(manifest)) => manifest
+			.descendants("resource")
+			.into_iter()
+			.filter(|r| r.attr("type").is_some_and(|t| t.starts_with("imsqti_item")))
+			.filter_map(|r| r.attr("href").map(String::from))
+			.collect(),
+		Some(Err(_)) => bail!("Invalid QTI manifest"),
+		None => {
+			let mut hrefs: Vec<String> = files
+				.keys()
+				.filter(|name| name.ends_with(".xml"))
+				.cloned()
+				.collect();
+			hrefs.sort();
+			hrefs
+		}
+	};
+	if hrefs.is_empty() {
+		bail!("QTI package has no items");
+	}
+	let mut package = QtiPackage {
+		files,
+		assets: HashMap::new(),
+	};
+	let parsed = hrefs.iter().map(|href| package.item(href)).collect();
+	let mut assets: Vec<TestsExchangeAsset> = package
+		.assets
+		.into_iter()
+		.map(|(path, bytes)| TestsExchangeAsset { path, bytes })
+		.collect();
+	assets.sort_by(|a, b| a.path.cmp(&b.path));
+	Ok((exchange_preview(parsed), assets))
+}
@@ -1,15 +1,21 @@
 use super::{
-	TestsCreateRequestDto, TestsDetailSchema, TestsItemDto, TestsResponseListDto,
-	TestsSchema, TestsUpdateRequestDto,
+	pick_questions, TestsCreateRequestDto, TestsDetailSchema, TestsExchangeItem,
+	TestsGenerateRequestDto, TestsGenerateResponseDto, TestsGenerateSectionDto,
+	TestsItemDto, TestsResponseListDto, TestsSchema, TestsSeenAttemptSchema,
+	TestsUpdateRequestDto,
 };
 use crate::{
-	get_id, make_thing, query_list_with_meta, AppState, MetaRequestDto,
-	OptionsItemDto, OptionsSchema, QuestionsDetailSchema, QuestionsItemDto,
-	QuestionsSchema, ResourceEnum, ResponseListSuccessDto,
+	get_id, make_thing, query_list_with_meta, validate_question_shape, AppState,
+	MetaRequestDto, OptionsItemDto, OptionsSchema, QuestionsAnswerKeyDto,
+	QuestionsCreateRequestDto, QuestionsDetailSchema, QuestionsItemDto,
+	QuestionsRepository, QuestionsSchema, QuestionsTagsDto, QuestionsTypeEnum,
+	ResourceEnum, ResponseListSuccessDto,
 };
 use anyhow::{bail, Result};
 use najm_course_utils::get_iso_date;
-use surrealdb::Uuid;
+use std::collections::HashSet;
+use surrealdb::{sql::Thing, Uuid};
+use validator::Validate;
 
 pub struct TestsRepository<'a> {
 	pub state: &'a AppState,
@@ -92,6 +98,9 @@ impl<'a> TestsRepository<'a> {
 				question_image_url,
 				discussion_image_url,
 				options,
+				question_type,
+				answer_key,
+				tags,
 				is_deleted: _,
 				created_at,
 				updated_at,
@@ -105,6 +114,7 @@ impl<'a> TestsRepository<'a> {
 				option_items.push(OptionsItemDto {
 					id,
 					label: opt.label.unwrap_or("".into()),
+					display_label: None,
 					image_url: opt.image_url,
 					is_correct: Some(opt.is_correct),
 					points: opt.points,
@@ -123,6 +133,10 @@ impl<'a> TestsRepository<'a> {
 				question_image_url,
 				discussion_image_url,
 				options: option_items,
+				question_type: question_type
+					.unwrap_or_else(|| QuestionsTypeEnum::default().to_string()),
+				answer_key: answer_key.map(QuestionsAnswerKeyDto::from),
+				tags: tags.map(QuestionsTagsDto::from),
 				created_at,
 				updated_at,
 			});
@@ -167,6 +181,9 @@ impl<'a> TestsRepository<'a> {
 				question_image_url,
 				discussion_image_url,
 				options,
+				question_type,
+				answer_key: _,
+				tags,
 				is_deleted: _,
 				created_at,
 				updated_at,
@@ -180,6 +197,7 @@ impl<'a> TestsRepository<'a> {
 				option_items.push(OptionsItemDto {
 					id,
 					label: opt.label.unwrap_or("".into()),
+					display_label: None,
 					image_url: opt.image_url,
 					is_correct: None,
 					points: None,
@@ -198,6 +216,10 @@ impl<'a> TestsRepository<'a> {
 				question_image_url,
 				discussion_image_url,
 				options: option_items,
+				question_type: question_type
+					.unwrap_or_else(|| QuestionsTypeEnum::default().to_string()),
+				answer_key: None,
+				tags: tags.map(QuestionsTagsDto::from),
 				created_at,
 				updated_at,
 			});
@@ -218,59 +240,30 @@ impl<'a> TestsRepository<'a> {
 		&self,
 		payload: TestsCreateRequestDto,
 	) -> Result<String> {
-		if payload.questions.is_empty() {
+		let question_ids = payload.question_ids.clone().unwrap_or_default();
+		if payload.questions.is_empty() && question_ids.is_empty() {
 			bail!("Test must contain at least one question");
 		}
 		let db = &self.state.surrealdb_ws;
 		let test_id = Uuid::new_v4().to_string();
+		let bank_things = self.query_bank_question_things(&question_ids).await?;
 		let mut question_things = Vec::new();
 		for question in &payload.questions {
-			if question.options.is_empty() {
-				bail!("Each question must have at least one option");
-			}
-			let question_id = Uuid::new_v4().to_string();
-			let question_thing =
-				make_thing(&ResourceEnum::Questions.to_string(), &question_id);
-
-			let mut option_things = Vec::new();
-			for option in &question.options {
-				let option_id = Uuid::new_v4().to_string();
-				let option_thing =
-					make_thing(&ResourceEnum::Options.to_string(), &option_id);
-				let option_schema = OptionsSchema {
-					id: option_thing.clone(),
-					label: option.label.clone(),
-					image_url: option.image_url.clone(),
-					is_correct: option.is_correct,
-					points: option.points,
-					is_deleted: false,
-					created_at: get_iso_date(),
-					updated_at: get_iso_date(),
-				};
+			let (question_schema, options) = question_records(question)?;
+			for option_schema in options {
 				let _: Option<OptionsSchema> = db
-					.create((&ResourceEnum::Options.to_string(), option_id))
+					.create(get_id(&option_schema.id)?)
 					.content(option_schema)
 					.await?;
-				option_things.push(option_thing);
 			}
-
-			let question_schema = QuestionsSchema {
-				id: question_thing.clone(),
-				question: question.question.clone(),
-				discussion: question.discussion.clone(),
-				question_image_url: question.question_image_url.clone(),
-				discussion_image_url: question.discussion_image_url.clone(),
-				options: option_things,
-				is_deleted: false,
-				created_at: get_iso_date(),
-				updated_at: get_iso_date(),
-			};
+			let question_thing = question_schema.id.clone();
 			let _: Option<QuestionsSchema> = db
-				.create((&ResourceEnum::Questions.to_string(), question_id))
+				.create(get_id(&question_thing)?)
 				.content(question_schema)
 				.await?;
 			question_things.push(question_thing);
 		}
+		question_things.extend(bank_things);
 		let test_thing = make_thing(&ResourceEnum::Tests.to_string(), &test_id);
 		let test = TestsSchema {
 			id: test_thing.clone(),
@@ -288,6 +281,115 @@ impl<'a> TestsRepository<'a> {
 		Ok(test_id)
 	}
 
+	/// Creates the imported questions and their options in one transaction,
+	/// appended to `test_id` or to a new test called `name`.
+	pub async fn query_import_questions(
+		&self,
+		name: Option<String>,
+		test_id: Option<&str>,
+		questions: &[QuestionsCreateRequestDto],
+	) -> Result<String> {
+		if questions.is_empty() {
+			bail!("File has no questions");
+		}
+		let existing = match test_id {
+			Some(id) => Some(self.query_raw_test_by_id(id).await?),
+			None => None,
+		};
+		let is_new = existing.is_none();
+		let mut question_schemas = vec![];
+		let mut option_schemas = vec![];
+		for question in questions {
+			let (question_schema, options) = question_records(question)?;
+			question_schemas.push(question_schema);
+			option_schemas.extend(options);
+		}
+		let question_things: Vec<Thing> =
+			question_schemas.iter().map(|q| q.id.clone()).collect();
+		let test = match existing {
+			Some(test) => test,
+			None => {
+				let Some(name) = name.filter(|n| !n.trim().is_empty()) else {
+					bail!("Name must not be empty");
+				};
+				TestsSchema {
+					id: make_thing(
+						&ResourceEnum::Tests.to_string(),
+						&Uuid::new_v4().to_string(),
+					),
+					name,
+					questions: vec![],
+					is_deleted: false,
+					created_at: get_iso_date(),
+					updated_at: get_iso_date(),
+				}
+			}
+		};
+		let test_id = test.id.id.to_raw();
+		let create_statement = if is_new {
+			format!("CREATE {} CONTENT $test;", test.id)
+		} else {
+			String::new()
+		};
+		let db = &self.state.surrealdb_ws;
+		db.query(format!(
+			"BEGIN TRANSACTION; INSERT INTO {} $options; INSERT INTO {} $questions; {} UPDATE {} SET questions = array::concat(questions, $question_ids), updated_at = $now; COMMIT TRANSACTION;",
+			ResourceEnum::Options,
+			ResourceEnum::Questions,
+			create_statement,
+			test.id
+		))
+		.bind(("test", test))
+		.bind(("options", option_schemas))
+		.bind(("questions", question_schemas))
+		.bind(("question_ids", question_things))
+		.bind(("now", get_iso_date()))
+		.await?
+		.check()?;
+		Ok(test_id)
+	}
+
+	/// The test and its live questions with their options, in test order.
+	pub async fn query_export_items(
+		&self,
+		id: &str,
+	) -> Result<(TestsSchema, Vec<TestsExchangeItem>)> {
+		let test = self.query_raw_test_by_id(id).await?;
+		let db = &self.state.surrealdb_ws;
+		let mut result = db
+			.query(format!(
+				"SELECT * FROM {} WHERE id IN $ids AND is_deleted = false",
+				ResourceEnum::Questions
+			))
+			.bind(("ids", test.questions.clone()))
+			.await?;
+		let questions: Vec<QuestionsSchema> = result.take(0)?;
+		let option_ids: Vec<Thing> =
+			questions.iter().flat_map(|q| q.options.clone()).collect();
+		let mut result = db
+			.query(format!(
+				"SELECT * FROM {} WHERE id IN $ids AND is_deleted = false",
+				ResourceEnum::Options
+			))
+			.bind(("ids", option_ids))
+			.await?;
+		let options: Vec<OptionsSchema> = result.take(0)?;
+		let items = test
+			.questions
+			.iter()
+			.filter_map(|id| questions.iter().find(|q| &q.id == id))
+			.map(|question| TestsExchangeItem {
+				question: question.clone(),
+				options: question
+					.options
+					.iter()
+					.filter_map(|id| options.iter().find(|o| &o.id == id).cloned())
+					.collect(),
+			})
+			.collect();
+		Ok((test, items))
+	}
+
 	pub async fn query_update_test(
 		&self,
 		id: String,
@@ -299,10 +401,25 @@ impl<'a> TestsRepository<'a> {
 		if existing.questions.is_empty() {
 			bail!("Test has no questions");
 		}
+		let bank_things = self
+			.query_bank_question_things(&payload.question_ids.clone().unwrap_or_default())
+			.await?;
 
 		let mut question_things = Vec::new();
 
 		for question in &payload.questions {
+			let question_type = validate_question_shape(
+				question.question_type.as_deref(),
+				&question
+					.options
+					.iter()
+					.map(|option| option.is_correct)
+					.collect::<Vec<_>>(),
+				question.answer_key.as_ref(),
+			)?;
+			if let Some(tags) = &question.tags {
+				tags.validate_difficulty()?;
+			}
 			let question_id = question.id.clone();
 			let question_thing =
 				make_thing(&ResourceEnum::Questions.to_string(), &question_id);
@@ -345,6 +462,9 @@ impl<'a> TestsRepository<'a> {
 				option_things.push(option_thing);
 			}
 
+			let existing_question: Option<QuestionsSchema> =
+				db.select(get_id(&question_thing)?).await.ok().flatten();
+
 			let question_schema = QuestionsSchema {
 				id: question_thing.clone(),
 				question: question.question.clone(),
@@ -352,14 +472,19 @@ impl<'a> TestsRepository<'a> {
 				question_image_url: question.question_image_url.clone(),
 				discussion_image_url: question.discussion_image_url.clone(),
 				options: option_things,
+				question_type: Some(question_type.to_string()),
+				answer_key: question.answer_key.clone().map(Into::into),
+				tags: question
+					.tags
+					.clone()
+					.map(Into::into)
+					.or(existing_question.as_ref().and_then(|q| q.tags.clone())),
+				irt: existing_question.as_ref().and_then(|q| q.irt.clone()),
 				is_deleted: false,
 				created_at: get_iso_date(),
 				updated_at: get_iso_date(),
 			};
 
-			let existing_question: Option<QuestionsSchema> =
-				db.select(get_id(&question_thing)?).await.ok().flatten();
-
 			if existing_question.is_some() {
 				let _: Option<QuestionsSchema> = db
 					.update(get_id(&question_thing)?)
@@ -377,6 +502,11 @@ impl<'a> TestsRepository<'a> {
 
 			question_things.push(question_thing);
 		}
+		for thing in bank_things {
+			if !question_things.contains(&thing) {
+				question_things.push(thing);
+			}
+		}
 
 		let updated_test = TestsSchema {
 			id: test_thing_id.clone(),
@@ -395,6 +525,160 @@ impl<'a> TestsRepository<'a> {
 		Ok("Success update test".into())
 	}
 
+	pub async fn query_generate_test(
+		&self,
+		payload: TestsGenerateRequestDto,
+	) -> Result<TestsGenerateResponseDto> {
+		payload.validate()?;
+		let db = &self.state.surrealdb_ws;
+		let seed = payload
+			.seed
+			.clone()
+			.filter(|seed| !seed.is_empty())
+			.unwrap_or_else(|| Uuid::new_v4().to_string());
+		let preview = payload.preview.unwrap_or(false);
+		let mut excluded = self
+			.query_seen_question_ids(
+				payload.cohort_student_type.as_deref(),
+				&payload.cohort_user_ids.clone().unwrap_or_default(),
+			)
+			.await?;
+		let excluded_count = excluded.len() as u32;
+		let mut sections = Vec::new();
+		for (index, item) in payload.blueprint.iter().enumerate() {
+			item.tags.validate_difficulty()?;
+			let mut conditions = vec!["is_deleted = false".to_string()];
+			let (tag_conditions, bindings) = item.tags.conditions();
+			conditions.extend(tag_conditions);
+			let mut query = db.query(format!(
+				"SELECT VALUE id FROM {} WHERE {}",
+				ResourceEnum::Questions,
+				conditions.join(" AND ")
+			));
+			for binding in bindings {
+				query = query.bind(binding);
+			}
+			let candidates: Vec<Thing> = query.await?.take(0)?;
+			let candidates: Vec<String> =
+				candidates.iter().map(|thing| thing.id.to_raw()).collect();
+			let available = candidates
+				.iter()
+				.filter(|id| !excluded.contains(*id))
+				.count() as u32;
+			if available < item.count {
+				bail!(
+					"Not enough questions for blueprint item {}: requested {}, available {}",
+					index + 1,
+					item.count,
+					available
+				);
+			}
+			let question_ids =
+				pick_questions(&candidates, &excluded, item.count as usize, &seed, index);
+			excluded.extend(question_ids.iter().cloned());
+			sections.push(TestsGenerateSectionDto {
+				tags: item.tags.clone(),
+				requested: item.count,
+				available,
+				question_ids,
+			});
+		}
+		let test_id = if preview {
+			None
+		} else {
+			let test_id = Uuid::new_v4().to_string();
+			let test = TestsSchema {
+				id: make_thing(&ResourceEnum::Tests.to_string(), &test_id),
+				name: payload.name.clone(),
+				questions: sections
+					.iter()
+					.flat_map(|section| &section.question_ids)
+					.map(|id| make_thing(&ResourceEnum::Questions.to_string(), id))
+					.collect(),
+				is_deleted: false,
+				created_at: get_iso_date(),
+				updated_at: get_iso_date(),
+			};
+			let _: Option<TestsSchema> = db
+				.create((&ResourceEnum::Tests.to_string(), &test_id))
+				.content(test)
+				.await?;
+			Some(test_id)
+		};
+		Ok(TestsGenerateResponseDto {
+			test_id,
+			name: payload.name,
+			seed,
+			preview,
+			excluded_count,
+			sections,
+		})
+	}
+
+	async fn query_seen_question_ids(
+		&self,
+		student_type: Option<&str>,
+		user_ids: &[String],
+	) -> Result<HashSet<String>> {
+		let mut cohort = Vec::new();
+		if !user_ids.is_empty() {
+			let users = user_ids
+				.iter()
+				.map(|id| format!("{}:⟨{}⟩", ResourceEnum::Users, id))
+				.collect::<Vec<_>>()
+				.join(", ");
+			cohort.push(format!("user INSIDE [{}]", users));
+		}
+		if student_type.is_some() {
+			cohort.push("user.student_type = $student_type".to_string());
+		}
+		if cohort.is_empty() {
+			return Ok(HashSet::new());
+		}
+		let cohort = cohort.join(" OR ");
+		let mut result = self
+			.state
+			.surrealdb_ws
+			.query(format!(
+				"SELECT VALUE question FROM {} WHERE is_deleted = false AND ({})",
+				ResourceEnum::Answers,
+				cohort
+			))
+			.query(format!(
+				"SELECT question_order FROM {} WHERE is_deleted = false AND ({})",
+				ResourceEnum::Attempts,
+				cohort
+			))
+			.bind(("student_type", student_type.unwrap_or_default().to_string()))
+			.await?;
+		let answered: Vec<Thing> = result.take(0)?;
+		let attempts: Vec<TestsSeenAttemptSchema> = result.take(1)?;
+		let mut seen: HashSet<String> =
+			answered.iter().map(|thing| thing.id.to_raw()).collect();
+		seen.extend(
+			attempts
+				.into_iter()
+				.flat_map(|attempt| attempt.question_order)
+				.map(|order| order.question),
+		);
+		Ok(seen)
+	}
+
+	async fn query_bank_question_things(
+		&self,
+		question_ids: &[String],
+	) -> Result<Vec<Thing>> {
+		let questions = QuestionsRepository::new(self.state);
+		let mut things = Vec::new();
+		for question_id in question_ids {
+			let question = questions.query_raw_question_by_id(question_id).await?;
+			if !things.contains(&question.id) {
+				things.push(question.id);
+			}
+		}
+		Ok(things)
+	}
+
 	pub async fn query_delete_test(&self, id: String) -> Result<String> {
 		let db = &self.state.surrealdb_ws;
 		let test_id = make_thing(&ResourceEnum::Tests.to_string(), &id);
@@ -413,3 +697,58 @@ impl<'a> TestsRepository<'a> {
 		}
 	}
 }
+
+/// The question record and its option records for a new question, after
+/// checking that the options fit the question type.
+pub fn question_records(
+	question: &QuestionsCreateRequestDto,
+) -> Result<(QuestionsSchema, Vec<OptionsSchema>)> {
+	let question_type = validate_question_shape(
+		question.question_type.as_deref(),
+		&question
+			.options
+			.iter()
+			.map(|option| option.is_correct)
+			.collect::<Vec<_>>(),
+		question.answer_key.as_ref(),
+	)?;
+	if let Some(tags) = &question.tags {
+		tags.validate_difficulty()?;
+	}
+	let options: Vec<OptionsSchema> = question
+		.options
+		.iter()
+		.map(|option| OptionsSchema {
+			id: make_thing(
+				&ResourceEnum::Options.to_string(),
+				&Uuid::new_v4().to_string(),
+			),
+			label: option.label.clone(),
+			image_url: option.image_url.clone(),
+			is_correct: option.is_correct,
+			points: option.points,
+			is_deleted: false,
+			created_at: get_iso_date(),
+			updated_at: get_iso_date(),
+		})
+		.collect();
+	let question_schema = QuestionsSchema {
+		id: make_thing(
+			&ResourceEnum::Questions.to_string(),
+			&Uuid::new_v4().to_string(),
+		),
+		question: question.question.clone(),
+		discussion: question.discussion.clone(),
+		question_image_url: question.question_image_url.clone(),
+		discussion_image_url: question.discussion_image_url.clone(),
+		options: options.iter().map(|option| option.id.clone()).collect(),
+		question_type: Some(question_type.to_string()),
+		answer_key: question.answer_key.clone().map(Into::into),
+		tags: question.tags.clone().map(Into::into),
+		irt: None,
+		is_deleted: false,
+		created_at: get_iso_date(),
+		updated_at: get_iso_date(),
+	};
+	Ok((question_schema, options))
+}
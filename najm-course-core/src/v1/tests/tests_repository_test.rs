@@ -2,9 +2,13 @@ use crate::{
 	create_mock_app_state,
 	v1::{
 		options::OptionsCreateRequestDto,
-		questions::QuestionsCreateRequestDto,
-		tests::{TestsCreateRequestDto, TestsRepository},
+		questions::{QuestionsCreateRequestDto, QuestionsRepository, QuestionsTagsDto},
+		tests::{
+			TestsBlueprintItemDto, TestsCreateRequestDto, TestsGenerateRequestDto,
+			TestsRepository,
+		},
 	},
+	AppState,
 };
 use surrealdb::Uuid;
 
@@ -23,6 +27,9 @@ fn generate_question_payload() -> QuestionsCreateRequestDto {
 		discussion: Some("Discussion".into()),
 		question_image_url: None,
 		discussion_image_url: None,
+		question_type: None,
+		answer_key: None,
+		tags: None,
 		options: vec![generate_option("A", false), generate_option("B", true)],
 	}
 }
@@ -31,6 +38,7 @@ fn generate_test_payload(name: &str) -> TestsCreateRequestDto {
 	TestsCreateRequestDto {
 		name: name.to_string(),
 		questions: vec![generate_question_payload()],
+		question_ids: None,
 	}
 }
 
@@ -50,6 +58,7 @@ async fn test_query_create_test_should_fail_if_no_questions() {
 	let payload = TestsCreateRequestDto {
 		name: "Empty".into(),
 		questions: vec![],
+		question_ids: None,
 	};
 	let res = repo.query_create_test(payload).await;
 	assert!(res.is_err());
@@ -97,6 +106,7 @@ async fn test_query_create_test_should_fail_if_question_has_no_options() {
 	let payload = TestsCreateRequestDto {
 		name: "No Options".into(),
 		questions: vec![question],
+		question_ids: None,
 	};
 	let res = repo.query_create_test(payload).await;
 	assert!(res.is_err());
@@ -147,3 +157,200 @@ async fn test_query_delete_test_should_fail_if_already_deleted() {
 		"Expected error when deleting already deleted test"
 	);
 }
+
+#[tokio::test]
+async fn test_query_create_test_should_reference_bank_questions() {
+	let state = create_mock_app_state().await;
+	let bank_question_id = QuestionsRepository::new(&state)
+		.query_create_question(generate_question_payload())
+		.await
+		.unwrap();
+	let repo = TestsRepository::new(&state);
+	let mut test_ids = vec![];
+	for name in ["Bank Test A", "Bank Test B"] {
+		let payload = TestsCreateRequestDto {
+			name: name.into(),
+			questions: vec![],
+			question_ids: Some(vec![bank_question_id.clone()]),
+		};
+		test_ids.push(repo.query_create_test(payload).await.unwrap());
+	}
+	for test_id in test_ids {
+		let test = repo.query_test_by_id(&test_id).await.unwrap();
+		assert_eq!(test.questions.len(), 1);
+		assert_eq!(test.questions[0].id, bank_question_id);
+	}
+}
+
+#[tokio::test]
+async fn test_query_create_test_should_fail_if_bank_question_not_found() {
+	let state = create_mock_app_state().await;
+	let repo = TestsRepository::new(&state);
+	let payload = TestsCreateRequestDto {
+		name: "Missing Bank Question".into(),
+		questions: vec![],
+		question_ids: Some(vec![Uuid::new_v4().to_string()]),
+	};
+	let res = repo.query_create_test(payload).await;
+	assert_eq!(res.unwrap_err().to_string(), "Question not found");
+}
+
+fn blueprint_tags(subject: &str, topic: &str, difficulty: &str) -> QuestionsTagsDto {
+	QuestionsTagsDto {
+		subject: Some(subject.into()),
+		topic: Some(topic.into()),
+		difficulty: Some(difficulty.into()),
+		..Default::default()
+	}
+}
+
+pub async fn seed_bank_questions(
+	state: &AppState,
+	subject: &str,
+	topic: &str,
+	difficulty: &str,
+	count: usize,
+) -> Vec<String> {
+	let repo = QuestionsRepository::new(state);
+	let mut ids = vec![];
+	for _ in 0..count {
+		let mut payload = generate_question_payload();
+		payload.tags = Some(blueprint_tags(subject, topic, difficulty));
+		ids.push(repo.query_create_question(payload).await.unwrap());
+	}
+	ids
+}
+
+fn generate_blueprint_payload(subject: &str, seed: &str) -> TestsGenerateRequestDto {
+	TestsGenerateRequestDto {
+		name: "Generated Tryout".into(),
+		blueprint: vec![
+			TestsBlueprintItemDto {
+				tags: blueprint_tags(subject, "algebra", "medium"),
+				count: 3,
+			},
+			TestsBlueprintItemDto {
+				tags: blueprint_tags(subject, "geometry", "hard"),
+				count: 2,
+			},
+		],
+		seed: Some(seed.into()),
+		preview: Some(true),
+		cohort_student_type: None,
+		cohort_user_ids: None,
+	}
+}
+
+#[tokio::test]
+async fn test_query_generate_test_preview_should_be_reproducible() {
+	let state = create_mock_app_state().await;
+	let subject = format!("Subject {}", Uuid::new_v4());
+	seed_bank_questions(&state, &subject, "algebra", "medium", 6).await;
+	seed_bank_questions(&state, &subject, "geometry", "hard", 3).await;
+	let repo = TestsRepository::new(&state);
+	let first = repo
+		.query_generate_test(generate_blueprint_payload(&subject, "fixed"))
+		.await
+		.unwrap();
+	let second = repo
+		.query_generate_test(generate_blueprint_payload(&subject, "fixed"))
+		.await
+		.unwrap();
+	assert!(first.test_id.is_none());
+	assert_eq!(first.sections[0].available, 6);
+	assert_eq!(first.sections[0].question_ids.len(), 3);
+	assert_eq!(first.sections[1].question_ids.len(), 2);
+	let ids = |res: &crate::TestsGenerateResponseDto| {
+		res
+			.sections
+			.iter()
+			.flat_map(|s| s.question_ids.clone())
+			.collect::<Vec<_>>()
+	};
+	assert_eq!(ids(&first), ids(&second));
+}
+
+#[tokio::test]
+async fn test_query_generate_test_should_create_test() {
+	let state = create_mock_app_state().await;
+	let subject = format!("Subject {}", Uuid::new_v4());
+	seed_bank_questions(&state, &subject, "algebra", "medium", 3).await;
+	seed_bank_questions(&state, &subject, "geometry", "hard", 2).await;
+	let repo = TestsRepository::new(&state);
+	let mut payload = generate_blueprint_payload(&subject, "fixed");
+	payload.preview = None;
+	let res = repo.query_generate_test(payload).await.unwrap();
+	let test = repo
+		.query_test_by_id(&res.test_id.expect("Expected a created test"))
+		.await
+		.unwrap();
+	assert_eq!(test.questions.len(), 5);
+}
+
+#[tokio::test]
+async fn test_query_generate_test_should_exclude_questions_seen_by_cohort() {
+	let state = create_mock_app_state().await;
+	let subject = format!("Subject {}", Uuid::new_v4());
+	let algebra = seed_bank_questions(&state, &subject, "algebra", "medium", 5).await;
+	seed_bank_questions(&state, &subject, "geometry", "hard", 2).await;
+	let student_type = format!("cohort_{}", Uuid::new_v4());
+	let user_id = Uuid::new_v4().to_string();
+	let now = najm_course_utils::get_iso_date();
+	let mut statements = vec![format!(
+		"CREATE app_users:⟨{user_id}⟩ SET fullname = 'Cohort Student', student_type = '{student_type}', is_deleted = false, created_at = '{now}', updated_at = '{now}';"
+	)];
+	for question_id in &algebra[..3] {
+		statements.push(format!(
+			"CREATE app_answers SET user = app_users:⟨{user_id}⟩, question = app_questions:⟨{question_id}⟩, is_correct = true, is_deleted = false, created_at = '{now}', updated_at = '{now}';"
+		));
+	}
+	state
+		.surrealdb_ws
+		.query(statements.join("\n"))
+		.await
+		.unwrap();
+	let repo = TestsRepository::new(&state);
+	let mut payload = generate_blueprint_payload(&subject, "fixed");
+	payload.cohort_student_type = Some(student_type);
+	let res = repo.query_generate_test(payload.clone()).await;
+	assert_eq!(
+		res.unwrap_err().to_string(),
+		"Not enough questions for blueprint item 1: requested 3, available 2"
+	);
+	payload.blueprint[0].count = 2;
+	let res = repo.query_generate_test(payload).await.unwrap();
+	assert_eq!(res.excluded_count, 3);
+	let mut picked = res.sections[0].question_ids.clone();
+	picked.sort();
+	let mut unseen = algebra[3..].to_vec();
+	unseen.sort();
+	assert_eq!(picked, unseen);
+}
+
+#[tokio::test]
+async fn test_query_import_questions_should_create_or_extend_test() {
+	let state = create_mock_app_state().await;
+	let repo = TestsRepository::new(&state);
+	let questions = vec![generate_question_payload(), generate_question_payload()];
+	let test_id = repo
+		.query_import_questions(Some("Imported".into()), None, &questions)
+		.await
+		.unwrap();
+	let test = repo.query_test_by_id(&test_id).await.unwrap();
+	assert_eq!(test.name, "Imported");
+	assert_eq!(test.questions.len(), 2);
+	assert_eq!(test.questions[0].options.len(), 2);
+
+	let extended = repo
+		.query_import_questions(None, Some(&test_id), &[generate_question_payload()])
+		.await
+		.unwrap();
+	assert_eq!(extended, test_id);
+	let test = repo.query_test_by_id(&test_id).await.unwrap();
+	assert_eq!(test.questions.len(), 3);
+
+	let missing = repo
+		.query_import_questions(None, Some("missing"), &questions)
+		.await;
+	assert_eq!(missing.unwrap_err().to_string(), "Test not found");
+}
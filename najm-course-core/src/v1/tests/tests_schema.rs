@@ -1,7 +1,7 @@
 use serde::{Deserialize, Serialize};
 use surrealdb::sql::Thing;
 
-use crate::QuestionsDetailSchema;
+use crate::{AttemptsQuestionOrderSchema, QuestionsDetailSchema};
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct TestsSchema {
@@ -22,3 +22,9 @@ pub struct TestsDetailSchema {
 	pub created_at: String,
 	pub updated_at: String,
 }
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct TestsSeenAttemptSchema {
+	#[serde(default)]
+	pub question_order: Vec<AttemptsQuestionOrderSchema>,
+}
@@ -1,9 +1,22 @@
-use super::{TestsCreateRequestDto, TestsRepository, TestsUpdateRequestDto};
+use super::{
+	exchange_text, export_file_name, parse_aiken, parse_gift, parse_qti_package,
+	parse_spreadsheet, read_import_upload, read_spreadsheet, replace_asset_urls,
+	write_exchange, TestsCreateRequestDto, TestsExchangeAsset,
+	TestsExchangeFormatEnum, TestsExportQueryDto, TestsGenerateRequestDto,
+	TestsImportPreviewDto, TestsImportUpload, TestsRepository, TestsUpdateRequestDto,
+};
 use crate::{
-	common_response, success_list_response, success_response, validate_request,
-	AppState, MetaRequestDto, ResponseListSuccessDto, ResponseSuccessDto,
+	attachment_response, common_response, storage_state, success_list_response,
+	success_response, validate_request, AppState, MetaRequestDto,
+	ResponseListSuccessDto, ResponseSuccessDto, SessionsViewerDto,
+};
+use anyhow::Result;
+use axum::{
+	extract::Multipart,
+	http::{HeaderName, HeaderValue, StatusCode},
+	response::Response,
 };
-use axum::{http::StatusCode, response::Response};
+use std::collections::HashMap;
 use validator::Validate;
 
 pub struct TestsService;
@@ -23,9 +36,16 @@ impl TestsService {
 		}
 	}
 
-	pub async fn get_test_by_id(state: &AppState, id: String) -> Response {
+	pub async fn get_test_by_id(
+		state: &AppState,
+		id: String,
+		viewer: SessionsViewerDto,
+	) -> Response {
 		let repo = TestsRepository::new(state);
 		match repo.query_test_by_id(&id).await {
+			Ok(test) if !viewer.is_reviewer => success_response(ResponseSuccessDto {
+				data: test.without_answer_key(),
+			}),
 			Ok(test) => success_response(ResponseSuccessDto { data: test }),
 			Err(e) => {
 				let status = if e.to_string().contains("not found") {
@@ -38,6 +58,30 @@ impl TestsService {
 		}
 	}
 
+	pub async fn generate_test(
+		state: &AppState,
+		payload: TestsGenerateRequestDto,
+	) -> Response {
+		if let Err((status, message)) = validate_request(&payload) {
+			return common_response(status, &message);
+		}
+		let repo = TestsRepository::new(state);
+		match repo.query_generate_test(payload).await {
+			Ok(data) => success_response(ResponseSuccessDto { data }),
+			Err(e) => {
+				let msg = e.to_string();
+				let status = if msg.starts_with("Not enough questions") {
+					StatusCode::UNPROCESSABLE_ENTITY
+				} else if msg == "Invalid difficulty" {
+					StatusCode::BAD_REQUEST
+				} else {
+					StatusCode::INTERNAL_SERVER_ERROR
+				};
+				common_response(status, &msg)
+			}
+		}
+	}
+
 	pub async fn create_test(
 		state: &AppState,
 		payload: TestsCreateRequestDto,
@@ -45,7 +89,12 @@ impl TestsService {
 		if let Err((status, message)) = validate_request(&payload) {
 			return common_response(status, &message);
 		}
-		if payload.questions.is_empty() {
+		if payload.questions.is_empty()
+			&& payload
+				.question_ids
+				.as_ref()
+				.is_none_or(|ids| ids.is_empty())
+		{
 			return common_response(
 				StatusCode::BAD_REQUEST,
 				"At least one question is required",
@@ -64,7 +113,185 @@ impl TestsService {
 		let repo = TestsRepository::new(state);
 		match repo.query_create_test(payload).await {
 			Ok(msg) => common_response(StatusCode::CREATED, &msg),
-			Err(e) => common_response(StatusCode::INTERNAL_SERVER_ERROR, &e.to_string()),
+			Err(e) => {
+				let msg = e.to_string();
+				let status = if msg.contains("not found") {
+					StatusCode::NOT_FOUND
+				} else if msg.contains("Invalid") || msg.contains("must") {
+					StatusCode::BAD_REQUEST
+				} else {
+					StatusCode::INTERNAL_SERVER_ERROR
+				};
+				common_response(status, &msg)
+			}
+		}
+	}
+
+	pub async fn import_test(state: &AppState, multipart: Multipart) -> Response {
+		let upload = match read_import_upload(multipart).await {
+			Ok(upload) => upload,
+			Err(e) => return common_response(StatusCode::BAD_REQUEST, &e.to_string()),
+		};
+		let format = match TestsExchangeFormatEnum::resolve(
+			upload.format.as_deref(),
+			&upload.file_name,
+		) {
+			Ok(format) => format,
+			Err(e) => return common_response(StatusCode::BAD_REQUEST, &e.to_string()),
+		};
+		let parsed = match format {
+			TestsExchangeFormatEnum::Csv | TestsExchangeFormatEnum::Xlsx => {
+				read_spreadsheet(format, &upload.bytes)
+					.and_then(parse_spreadsheet)
+					.map(|preview| (preview, vec![]))
+			}
+			TestsExchangeFormatEnum::Qti => parse_qti_package(&upload.bytes),
+			TestsExchangeFormatEnum::Gift => {
+				exchange_text(&upload.bytes).map(|text| (parse_gift(&text), vec![]))
+			}
+			TestsExchangeFormatEnum::Aiken => {
+				exchange_text(&upload.bytes).map(|text| (parse_aiken(&text), vec![]))
+			}
+		};
+		let (preview, assets) = match parsed {
+			Ok(parsed) => parsed,
+			Err(e) => return common_response(StatusCode::BAD_REQUEST, &e.to_string()),
+		};
+		Self::confirm_import(state, upload, preview, assets).await
+	}
+
+	/// Stores packaged images and maps each package path to its new URL.
+	async fn upload_assets(
+		assets: Vec<TestsExchangeAsset>,
+	) -> Result<HashMap<String, String>> {
+		let mut urls = HashMap::new();
+		if assets.is_empty() {
+			return Ok(urls);
+		}
+		let storage = storage_state()
+			.await
+			.map_err(|e| anyhow::anyhow!("{}", e))?;
+		let minio = storage.minio.lock().await;
+		for asset in assets {
+			let file_name = asset.path.rsplit('/').next().unwrap_or_default();
+			let url = minio
+				.upload_file(file_name, asset.bytes)
+				.await
+				.map_err(|e| anyhow::anyhow!("{}: {}", asset.path, e))?;
+			urls.insert(asset.path, url);
+		}
+		Ok(urls)
+	}
+
+	pub async fn export_test(
+		state: &AppState,
+		id: String,
+		query: TestsExportQueryDto,
+	) -> Response {
+		let format = match query
+			.format
+			.as_deref()
+			.unwrap_or("qti")
+			.parse::<TestsExchangeFormatEnum>()
+		{
+			Ok(format) => format,
+			Err(e) => return common_response(StatusCode::BAD_REQUEST, &e.to_string()),
+		};
+		let repo = TestsRepository::new(state);
+		let (test, items) = match repo.query_export_items(&id).await {
+			Ok(data) => data,
+			Err(e) => {
+				let msg = e.to_string();
+				let status = if msg.contains("not found") {
+					StatusCode::NOT_FOUND
+				} else if msg.contains("deleted") {
+					StatusCode::BAD_REQUEST
+				} else {
+					StatusCode::INTERNAL_SERVER_ERROR
+				};
+				return common_response(status, &msg);
+			}
+		};
+		match write_exchange(format, &items).await {
+			Ok(file) => {
+				let mut response = attachment_response(
+					format.content_type(),
+					&export_file_name(&test.name, format),
+					file.bytes,
+				);
+				response.headers_mut().insert(
+					HeaderName::from_static("x-skipped-questions"),
+					HeaderValue::from(file.skipped),
+				);
+				response
+			}
+			Err(e) => {
+				let msg = e.to_string();
+				let status = if msg == "Unsupported format" {
+					StatusCode::BAD_REQUEST
+				} else {
+					StatusCode::INTERNAL_SERVER_ERROR
+				};
+				common_response(status, &msg)
+			}
+		}
+	}
+
+	/// Answers a dry run with the preview, otherwise creates the previewed
+	/// questions when every row is valid.
+	async fn confirm_import(
+		state: &AppState,
+		upload: TestsImportUpload,
+		mut preview: TestsImportPreviewDto,
+		assets: Vec<TestsExchangeAsset>,
+	) -> Response {
+		if upload.dry_run {
+			return success_response(ResponseSuccessDto { data: preview });
+		}
+		if upload.name.is_some() && upload.test_id.is_some() {
+			return common_response(
+				StatusCode::BAD_REQUEST,
+				"Provide either name or test_id, not both",
+			);
+		}
+		if !preview.errors.is_empty() {
+			return common_response(
+				StatusCode::UNPROCESSABLE_ENTITY,
+				"Import contains invalid rows",
+			);
+		}
+		match Self::upload_assets(assets).await {
+			Ok(urls) => replace_asset_urls(&mut preview.questions, &urls),
+			Err(e) => return common_response(StatusCode::BAD_REQUEST, &e.to_string()),
+		}
+		let repo = TestsRepository::new(state);
+		match repo
+			.query_import_questions(
+				upload.name,
+				upload.test_id.as_deref(),
+				&preview.questions,
+			)
+			.await
+		{
+			Ok(test_id) => {
+				preview.test_id = Some(test_id);
+				preview.dry_run = false;
+				success_response(ResponseSuccessDto { data: preview })
+			}
+			Err(e) => {
+				let msg = e.to_string();
+				let status = if msg.contains("not found") {
+					StatusCode::NOT_FOUND
+				} else if msg.contains("deleted")
+					|| msg.contains("must")
+					|| msg == "File has no questions"
+				{
+					StatusCode::BAD_REQUEST
+				} else {
+					StatusCode::INTERNAL_SERVER_ERROR
+				};
+				common_response(status, &msg)
+			}
 		}
 	}
 
@@ -27,7 +27,12 @@ where
 	let listener = TcpListener::bind(&addr).await.unwrap();
 	info!("Listening on http://{}", addr);
 
-	match serve(listener, router).await {
+	match serve(
+		listener,
+		router.into_make_service_with_connect_info::<SocketAddr>(),
+	)
+	.await
+	{
 		Ok(_) => info!("Server stopped gracefully."),
 		Err(err) => error!("Server encountered an error: {}", err),
 	}
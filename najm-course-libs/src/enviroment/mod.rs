@@ -20,6 +20,7 @@ pub struct Env {
 	pub minio_bucket_name: String,
 	pub minio_access_key: String,
 	pub minio_secret_key: String,
+	pub allow_legacy_answers: bool,
 }
 
 impl Env {
@@ -62,6 +63,9 @@ impl Env {
 				.unwrap_or_else(|_| "minio_access".to_string()),
 			minio_secret_key: env::var("MINIO_SECRET_KEY")
 				.unwrap_or_else(|_| "minio_secret".to_string()),
+			allow_legacy_answers: env::var("ALLOW_LEGACY_ANSWERS")
+				.map(|value| value == "true")
+				.unwrap_or(false),
 		}
 	}
 }
@@ -13,6 +13,11 @@ pub enum ResourceEnum {
 	Flags,
 	Answers,
 	Permissions,
+	Attempts,
+	SessionResults,
+	Enrollments,
+	ProctoringEvents,
+	SessionBroadcasts,
 }
 
 impl fmt::Display for ResourceEnum {
@@ -29,6 +34,11 @@ impl fmt::Display for ResourceEnum {
 			ResourceEnum::Questions => "app_questions",
 			ResourceEnum::Tests => "app_tests",
 			ResourceEnum::Sessions => "app_sessions",
+			ResourceEnum::Attempts => "app_attempts",
+			ResourceEnum::SessionResults => "app_session_results",
+			ResourceEnum::Enrollments => "app_enrollments",
+			ResourceEnum::ProctoringEvents => "app_proctoring_events",
+			ResourceEnum::SessionBroadcasts => "app_session_broadcasts",
 		};
 		write!(f, "{}", str)
 	}
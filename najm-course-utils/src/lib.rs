@@ -4,9 +4,12 @@ pub mod generate_date;
 pub mod generate_otp;
 pub mod get_id;
 pub mod make_thing;
+pub mod mask_name;
 pub mod mock_test;
 pub mod query_list;
 pub mod response_format;
+pub mod seeded_shuffle;
+pub mod slugify;
 pub mod validator;
 
 pub use bind_filter::*;
@@ -15,7 +18,10 @@ pub use generate_date::*;
 pub use generate_otp::*;
 pub use get_id::*;
 pub use make_thing::*;
+pub use mask_name::*;
 pub use mock_test::*;
 pub use query_list::*;
 pub use response_format::*;
+pub use seeded_shuffle::*;
+pub use slugify::*;
 pub use validator::*;
@@ -0,0 +1,28 @@
+pub fn mask_name(name: &str) -> String {
+	name
+		.split_whitespace()
+		.map(|word| {
+			let mut chars = word.chars();
+			match chars.next() {
+				Some(first) => std::iter::once(first).chain(chars.map(|_| '*')).collect(),
+				None => String::new(),
+			}
+		})
+		.collect::<Vec<String>>()
+		.join(" ")
+}
+
+#[cfg(test)]
+mod mask_name_test {
+	use super::*;
+
+	#[test]
+	fn test_mask_name_should_keep_initials() {
+		assert_eq!(mask_name("Budi Santoso"), "B*** S******");
+	}
+
+	#[test]
+	fn test_mask_name_should_handle_empty_name() {
+		assert_eq!(mask_name("  "), "");
+	}
+}
@@ -13,6 +13,21 @@ pub async fn query_list_with_meta<T>(
 	conditions: Vec<String>,
 	custom_select: Option<String>,
 ) -> Result<ResponseListSuccessDto<Vec<T>>>
+where
+	T: DeserializeOwned + Serialize,
+{
+	query_list_with_bindings(db, table, meta, conditions, custom_select, vec![])
+		.await
+}
+
+pub async fn query_list_with_bindings<T>(
+	db: &Surreal<Client>,
+	table: &str,
+	meta: &MetaRequestDto,
+	conditions: Vec<String>,
+	custom_select: Option<String>,
+	bindings: Vec<(String, String)>,
+) -> Result<ResponseListSuccessDto<Vec<T>>>
 where
 	T: DeserializeOwned + Serialize,
 {
@@ -53,6 +68,9 @@ where
 	if let Some(filter_val) = meta.filter.clone() {
 		query_exec = bind_filter_value(query_exec, filter_val);
 	}
+	for binding in bindings.clone() {
+		query_exec = query_exec.bind(binding);
+	}
 	query_exec = query_exec
 		.bind(("per_page", per_page))
 		.bind(("start", start));
@@ -72,6 +90,9 @@ where
 	if let Some(filter_val) = meta.filter.clone() {
 		count_query = bind_filter_value(count_query, filter_val);
 	}
+	for binding in bindings {
+		count_query = count_query.bind(binding);
+	}
 	let count_result: Vec<CountResult> = count_query.await?.take(0)?;
 	let total = count_result.first().map(|c| c.count);
 	let meta = MetaResponseDto {
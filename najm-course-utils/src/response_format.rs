@@ -1,6 +1,6 @@
 use axum::{
 	Json,
-	http::StatusCode,
+	http::{StatusCode, header},
 	response::{IntoResponse, Response},
 };
 use najm_course_entities::{ResponseListSuccessDto, ResponseSuccessDto};
@@ -32,6 +32,25 @@ pub fn success_list_response<T: Serialize>(
 		.into_response()
 }
 
+pub fn attachment_response(
+	content_type: &str,
+	file_name: &str,
+	bytes: Vec<u8>,
+) -> Response {
+	(
+		StatusCode::OK,
+		[
+			(header::CONTENT_TYPE, content_type.to_string()),
+			(
+				header::CONTENT_DISPOSITION,
+				format!("attachment; filename=\"{}\"", file_name),
+			),
+		],
+		bytes,
+	)
+		.into_response()
+}
+
 pub fn common_response(status: StatusCode, message: &str) -> Response {
 	(
 		status,
@@ -0,0 +1,74 @@
+use rand::{SeedableRng, seq::SliceRandom};
+use rand_chacha::ChaCha8Rng;
+
+const FNV_OFFSET_BASIS: u64 = 0xcbf2_9ce4_8422_2325;
+const FNV_PRIME: u64 = 0x0100_0000_01b3;
+
+pub fn shuffle_seed(parts: &[&str]) -> u64 {
+	parts.iter().fold(FNV_OFFSET_BASIS, |hash, part| {
+		part
+			.bytes()
+			.chain(std::iter::once(0xff))
+			.fold(hash, |hash, byte| {
+				(hash ^ byte as u64).wrapping_mul(FNV_PRIME)
+			})
+	})
+}
+
+/// Uses ChaCha8 rather than `StdRng`, whose algorithm may change between
+/// releases and reorder every stored arrangement.
+pub fn seeded_shuffle<T>(items: &mut [T], seed: u64) {
+	let mut rng = ChaCha8Rng::seed_from_u64(seed);
+	items.shuffle(&mut rng);
+}
+
+pub fn display_label(index: usize) -> String {
+	match u8::try_from(index) {
+		Ok(i) if i < 26 => ((b'A' + i) as char).to_string(),
+		_ => (index + 1).to_string(),
+	}
+}
+
+#[cfg(test)]
+mod seeded_shuffle_test {
+	use super::*;
+
+	#[test]
+	fn test_shuffle_seed_should_be_stable() {
+		let a = shuffle_seed(&["user", "session", "test"]);
+		let b = shuffle_seed(&["user", "session", "test"]);
+		assert_eq!(a, b);
+	}
+
+	#[test]
+	fn test_shuffle_seed_should_separate_parts() {
+		let a = shuffle_seed(&["ab", "c"]);
+		let b = shuffle_seed(&["a", "bc"]);
+		assert_ne!(a, b);
+	}
+
+	#[test]
+	fn test_seeded_shuffle_should_be_deterministic() {
+		let seed = shuffle_seed(&["user", "session", "test"]);
+		let mut first: Vec<u32> = (0..20).collect();
+		let mut second: Vec<u32> = (0..20).collect();
+		seeded_shuffle(&mut first, seed);
+		seeded_shuffle(&mut second, seed);
+		assert_eq!(first, second);
+		assert_ne!(first, (0..20).collect::<Vec<u32>>());
+	}
+
+	#[test]
+	fn test_seeded_shuffle_should_match_pinned_permutation() {
+		let mut items: Vec<u32> = (0..10).collect();
+		seeded_shuffle(&mut items, 42);
+		assert_eq!(items, vec![7, 1, 5, 4, 6, 9, 3, 8, 2, 0]);
+	}
+
+	#[test]
+	fn test_display_label_should_return_letters() {
+		assert_eq!(display_label(0), "A");
+		assert_eq!(display_label(4), "E");
+		assert_eq!(display_label(26), "27");
+	}
+}
@@ -0,0 +1,29 @@
+/// Lowercase ASCII letters and digits joined by dashes, for download names.
+pub fn slugify(name: &str, fallback: &str) -> String {
+	let slug = name
+		.to_lowercase()
+		.split(|c: char| !c.is_ascii_alphanumeric())
+		.filter(|part| !part.is_empty())
+		.collect::<Vec<_>>()
+		.join("-");
+	if slug.is_empty() {
+		fallback.to_string()
+	} else {
+		slug
+	}
+}
+
+#[cfg(test)]
+mod slugify_test {
+	use super::*;
+
+	#[test]
+	fn test_slugify_should_join_words_with_dashes() {
+		assert_eq!(slugify("Try Out: UTBK 2025!", "test"), "try-out-utbk-2025");
+	}
+
+	#[test]
+	fn test_slugify_should_use_fallback_without_letters() {
+		assert_eq!(slugify("???", "session"), "session");
+	}
+}